@@ -1,2 +1,2 @@
-cargo build --release --no-default-features --features=embedded
+cargo build --release
 cp ./target/release/monee-cli ~/.local/bin/monee
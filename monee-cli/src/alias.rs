@@ -1,4 +1,4 @@
-use monee::prelude::AppContext;
+use monee::{prelude::AppContext, shared::domain::slug::Slug};
 use std::{fmt::Display, str::FromStr};
 
 use crate::error::PanicError;
@@ -10,6 +10,13 @@ pub trait AliasedId: Sized + FromStr + Clone {
         ctx: &AppContext,
         alias: Self::Alias,
     ) -> Result<Option<Self>, monee::shared::infrastructure::errors::InfrastructureError>;
+
+    async fn get_all(
+        ctx: &AppContext,
+    ) -> Result<
+        Vec<(Self, Option<Self::Alias>)>,
+        monee::shared::infrastructure::errors::InfrastructureError,
+    >;
 }
 
 #[derive(Clone)]
@@ -32,14 +39,63 @@ where
         let alias_str = alias.to_string();
         match I::resolve(ctx, alias).await {
             Ok(Some(id)) => Ok(id),
-            Ok(None) => Err({
-                miette::miette!(code = "NotFound", "Could not resolve alias `{}`", alias_str)
-            }),
+            Ok(None) => Err(not_found::<I>(ctx, &alias_str).await),
             Err(err) => Err(PanicError::new(err).into_final_report(ctx)),
         }
     }
 }
 
+async fn not_found<I: AliasedId>(ctx: &AppContext, alias: &str) -> miette::Error {
+    let known = match I::get_all(ctx).await {
+        Ok(entries) => entries
+            .into_iter()
+            .filter_map(|(_, alias)| alias.map(|alias| alias.to_string()))
+            .collect(),
+        Err(err) => return PanicError::new(err).into_final_report(ctx),
+    };
+
+    let suggestions = suggest(alias, known);
+    if suggestions.is_empty() {
+        return miette::miette!(code = "NotFound", "Could not resolve alias `{}`", alias);
+    }
+
+    let suggestions = suggestions
+        .iter()
+        .map(|alias| format!("`{}`", alias))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    miette::miette!(
+        code = "NotFound",
+        help = format!("did you mean {}?", suggestions),
+        "Could not resolve alias `{}`",
+        alias
+    )
+}
+
+const SUGGESTION_THRESHOLD: f64 = 0.8;
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Known aliases that look like `alias`, ignoring case and accents, closest first.
+fn suggest(alias: &str, known: Vec<String>) -> Vec<String> {
+    let slug = Slug::new(alias);
+    let mut scored: Vec<_> = known
+        .into_iter()
+        .map(|candidate| {
+            let score = strsim::jaro_winkler(slug.as_ref(), Slug::new(&candidate).as_ref());
+            (score, candidate)
+        })
+        .filter(|(score, _)| *score >= SUGGESTION_THRESHOLD)
+        .collect();
+
+    scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError<I>
 where
@@ -77,12 +133,12 @@ mod impl_trait {
     use monee::{
         backoffice::{
             actors::domain::actor_alias::ActorAlias,
-            currencies::domain::currency_code::CurrencyCode,
+            currencies::domain::currency_code::CurrencyCode, goals::domain::goal_name::GoalName,
             item_tags::domain::item_name::ItemName, wallets::domain::wallet_name::WalletName,
         },
         prelude::AppContext,
     };
-    use monee_core::{ActorId, CurrencyId, ItemTagId, WalletId};
+    use monee_core::{ActorId, CurrencyId, GoalId, ItemTagId, WalletId};
 
     use super::AliasedId;
 
@@ -98,6 +154,22 @@ mod impl_trait {
                 .provide::<monee::backoffice::currencies::application::code_resolve::CodeResolve>();
             service.run(&alias).await
         }
+
+        async fn get_all(
+            ctx: &AppContext,
+        ) -> Result<
+            Vec<(Self, Option<Self::Alias>)>,
+            monee::shared::infrastructure::errors::InfrastructureError,
+        > {
+            let service =
+                ctx.provide::<monee::backoffice::currencies::application::get_all::GetAll>();
+            let currencies = service.run().await?;
+
+            Ok(currencies
+                .into_iter()
+                .map(|(id, currency)| (id, Some(currency.code)))
+                .collect())
+        }
     }
 
     impl AliasedId for WalletId {
@@ -112,6 +184,21 @@ mod impl_trait {
                 ctx.provide::<monee::backoffice::wallets::application::name_resolve::NameResolve>();
             service.run(&alias).await
         }
+
+        async fn get_all(
+            ctx: &AppContext,
+        ) -> Result<
+            Vec<(Self, Option<Self::Alias>)>,
+            monee::shared::infrastructure::errors::InfrastructureError,
+        > {
+            let service = ctx.provide::<monee::backoffice::wallets::application::get_all::GetAll>();
+            let wallets = service.run().await?;
+
+            Ok(wallets
+                .into_iter()
+                .map(|(id, wallet)| (id, Some(wallet.name)))
+                .collect())
+        }
     }
 
     impl AliasedId for ActorId {
@@ -125,6 +212,21 @@ mod impl_trait {
                 .provide::<monee::backoffice::actors::application::alias_resolve::AliasResolve>();
             service.run(&alias).await
         }
+
+        async fn get_all(
+            ctx: &AppContext,
+        ) -> Result<
+            Vec<(Self, Option<Self::Alias>)>,
+            monee::shared::infrastructure::errors::InfrastructureError,
+        > {
+            let service = ctx.provide::<monee::backoffice::actors::application::get_all::GetAll>();
+            let actors = service.run().await?;
+
+            Ok(actors
+                .into_iter()
+                .map(|(id, actor)| (id, actor.alias))
+                .collect())
+        }
     }
 
     impl AliasedId for ItemTagId {
@@ -138,5 +240,75 @@ mod impl_trait {
                 .provide::<monee::backoffice::item_tags::application::name_resolve::NameResolve>();
             service.run(&alias).await
         }
+
+        async fn get_all(
+            ctx: &AppContext,
+        ) -> Result<
+            Vec<(Self, Option<Self::Alias>)>,
+            monee::shared::infrastructure::errors::InfrastructureError,
+        > {
+            let service =
+                ctx.provide::<monee::backoffice::item_tags::application::get_all::GetAll>();
+            let items = service.run().await?;
+
+            Ok(items
+                .into_iter()
+                .map(|node| (node.id, Some(node.tag.name)))
+                .collect())
+        }
+    }
+
+    impl AliasedId for GoalId {
+        type Alias = GoalName;
+
+        async fn resolve(
+            ctx: &AppContext,
+            alias: Self::Alias,
+        ) -> Result<Option<Self>, monee::shared::infrastructure::errors::InfrastructureError>
+        {
+            let service =
+                ctx.provide::<monee::backoffice::goals::application::name_resolve::NameResolve>();
+            service.run(&alias).await
+        }
+
+        async fn get_all(
+            ctx: &AppContext,
+        ) -> Result<
+            Vec<(Self, Option<Self::Alias>)>,
+            monee::shared::infrastructure::errors::InfrastructureError,
+        > {
+            let service = ctx.provide::<monee::backoffice::goals::application::get_all::GetAll>();
+            let goals = service.run().await?;
+
+            Ok(goals
+                .into_iter()
+                .map(|(id, goal)| (id, Some(goal.name)))
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::suggest;
+
+    #[test]
+    fn suggests_close_aliases_first() {
+        let known = vec![
+            "ahorros_niño".to_owned(),
+            "ahorro".to_owned(),
+            "tarjeta".to_owned(),
+        ];
+
+        assert_eq!(
+            suggest("ahoros_nino", known),
+            vec!["ahorros_niño".to_owned(), "ahorro".to_owned()]
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_aliases() {
+        let known = vec!["tarjeta".to_owned(), "efectivo".to_owned()];
+        assert!(suggest("café", known).is_empty());
     }
 }
@@ -1,17 +1,45 @@
 pub mod wallet {
     use cream::context::Context;
     use monee::{
-        backoffice::wallets::domain::wallet_name::WalletName, prelude::AppContext,
-        shared::domain::errors::UniqueSaveError,
+        backoffice::wallets::{
+            application::change_kind::Error as ChangeKindError,
+            domain::{repository::UpdateError, wallet_name::WalletName},
+        },
+        prelude::AppContext,
+        shared::domain::errors::{DeleteError, UniqueSaveError},
     };
-    use monee_core::CurrencyId;
+    use monee_core::{Amount, CurrencyId, WalletId, WalletKind};
+
+    use crate::{
+        alias::MaybeAlias,
+        completion::completer,
+        error::PanicError,
+        prelude::{LogAndErr, MapAppErr},
+    };
+
+    #[derive(clap::ValueEnum, Clone, Copy)]
+    pub enum KindArg {
+        Debit,
+        Overdraft,
+        CreditCard,
+    }
 
-    use crate::{alias::MaybeAlias, prelude::MapAppErr};
+    fn wallet_kind(kind: KindArg, credit_limit: Option<Amount>) -> miette::Result<WalletKind> {
+        match kind {
+            KindArg::Debit if credit_limit.is_some() => Err(miette::diagnostic! {
+                "Debit wallets cannot have a credit limit"
+            }
+            .into()),
+            KindArg::Debit => Ok(WalletKind::Debit),
+            KindArg::Overdraft => Ok(WalletKind::Overdraft { credit_limit }),
+            KindArg::CreditCard => Ok(WalletKind::CreditCard { credit_limit }),
+        }
+    }
 
     #[derive(clap::Subcommand)]
     pub enum WalletCommand {
         Create {
-            #[arg(short, long)]
+            #[arg(short, long, add = completer::<CurrencyId>())]
             currency: MaybeAlias<CurrencyId>,
 
             #[arg(short, long)]
@@ -19,6 +47,38 @@ pub mod wallet {
 
             #[arg(short, long, default_value = "")]
             description: String,
+
+            /// Overdraft and credit card wallets may go below zero
+            #[arg(short, long, value_enum, default_value = "debit")]
+            kind: KindArg,
+
+            /// How far below zero the wallet may go, unlimited if not set
+            #[arg(short = 'l', long)]
+            credit_limit: Option<Amount>,
+        },
+
+        Update {
+            #[arg(add = completer::<WalletId>())]
+            wallet: MaybeAlias<WalletId>,
+
+            #[arg(short, long)]
+            name: Option<WalletName>,
+
+            #[arg(short, long)]
+            description: Option<String>,
+
+            #[arg(short, long, value_enum)]
+            kind: Option<KindArg>,
+
+            /// How far below zero the wallet may go, unlimited if not set
+            #[arg(short = 'l', long, requires = "kind")]
+            credit_limit: Option<Amount>,
+        },
+
+        #[command(alias = "rm")]
+        Delete {
+            #[arg(add = completer::<WalletId>())]
+            wallet: MaybeAlias<WalletId>,
         },
     }
 
@@ -28,15 +88,19 @@ pub mod wallet {
                 currency,
                 name,
                 description,
+                kind,
+                credit_limit,
             } => {
                 let service =
                     ctx.provide::<monee::backoffice::wallets::application::create_one::CreateOne>();
 
+                let kind = wallet_kind(kind, credit_limit)?;
                 let currency_id = currency.resolve(ctx).await?;
                 let wallet = monee::backoffice::wallets::domain::wallet::Wallet {
                     description,
                     name,
                     currency_id,
+                    kind,
                 };
 
                 service.run(wallet).await.map_app_err(ctx, |e| match e {
@@ -46,6 +110,75 @@ pub mod wallet {
                     .into(),
                 })
             }
+
+            WalletCommand::Update {
+                wallet,
+                name,
+                description,
+                kind,
+                credit_limit,
+            } => {
+                let kind = kind
+                    .map(|kind| wallet_kind(kind, credit_limit))
+                    .transpose()?;
+                let wallet_id = wallet.resolve(ctx).await?;
+
+                let get_service =
+                    ctx.provide::<monee::backoffice::wallets::application::get_one::GetOne>();
+                let Some(current) = get_service.run(wallet_id).await.log_err(ctx)? else {
+                    return Err(miette::diagnostic! { "Wallet not found" }.into());
+                };
+
+                let service =
+                    ctx.provide::<monee::backoffice::wallets::application::update_one::UpdateOne>();
+                let name = name.unwrap_or(current.name);
+                let description = description.unwrap_or(current.description);
+
+                service
+                    .run(wallet_id, Some(name), description)
+                    .await
+                    .map_err(|e| match e {
+                        UpdateError::NotFound => miette::diagnostic! { "Wallet not found" }.into(),
+                        UpdateError::AlreadyExists => miette::diagnostic! {
+                            "Wallet with this name already exists"
+                        }
+                        .into(),
+                        UpdateError::Unspecified(e) => PanicError::new(e).into_final_report(ctx),
+                    })?;
+
+                let Some(kind) = kind else {
+                    return Ok(());
+                };
+
+                let service = ctx
+                    .provide::<monee::backoffice::wallets::application::change_kind::ChangeKind>();
+                service
+                    .run(wallet_id, kind)
+                    .await
+                    .map_app_err(ctx, |e| match e {
+                        ChangeKindError::NotFound => {
+                            miette::diagnostic! { "Wallet not found" }.into()
+                        }
+                        ChangeKindError::BalanceOutOfRange => miette::diagnostic! {
+                            "The wallet balance does not fit the new kind or credit limit"
+                        }
+                        .into(),
+                    })
+            }
+
+            WalletCommand::Delete { wallet } => {
+                let service =
+                    ctx.provide::<monee::backoffice::wallets::application::delete_one::DeleteOne>();
+
+                let wallet_id = wallet.resolve(ctx).await?;
+                service.run(wallet_id).await.map_app_err(ctx, |e| match e {
+                    DeleteError::NotFound => miette::diagnostic! { "Wallet not found" }.into(),
+                    DeleteError::Referenced => miette::diagnostic! {
+                        "Wallet is still used by some events"
+                    }
+                    .into(),
+                })
+            }
         }
     }
 }
@@ -56,13 +189,39 @@ pub mod events {
 
     use crate::prelude::MapAppErr;
     use monee::{
-        backoffice::events::domain::event::{Purchase, Event, RegisterBalance},
+        backoffice::{
+            debts::{
+                application::accrue_interest::AccrueInterest,
+                domain::{
+                    debt_terms::{DebtTerms, InstallmentPlan, Interest, InterestKind},
+                    period::Period,
+                },
+            },
+            events::{
+                application::{
+                    add,
+                    reconcile_balance::{self, Reconcile},
+                    refund_purchase::RefundPurchase,
+                    split_purchase::{self, PurchaseSplit, SplitError, SplitPurchase},
+                },
+                domain::{
+                    event::{
+                        DebtRegister, Event, Fee, MoveValue, Purchase, Refund, RegisterBalance,
+                    },
+                    quantity::{Quantity, Unit},
+                    reconcile::{self, LARGE_DISCREPANCY_PERCENT},
+                },
+            },
+        },
         prelude::AppContext,
+        shared::domain::date::Date,
+    };
+    use monee_core::{
+        ActorId, Amount, Balance, CurrencyId, DebtId, EventId, ItemTagId, Percent, Shares, WalletId,
     };
-    use monee_core::{ActorId, Amount, ItemTagId, WalletId};
     use tokio::{task::JoinSet, try_join};
 
-    use crate::alias::MaybeAlias;
+    use crate::{alias::MaybeAlias, completion::completer};
 
     #[derive(clap::Subcommand)]
     pub enum EventCommand {
@@ -70,29 +229,173 @@ pub mod events {
             #[command(subcommand)]
             command: AddEventCommand,
         },
+
+        /// Adds the interest debts and loans accrued since it was last added
+        AccrueInterest {
+            /// Accrue up to this date instead of today
+            #[arg(short, long)]
+            until: Option<Date>,
+        },
+    }
+
+    #[derive(clap::Args)]
+    pub struct DebtArgs {
+        #[arg(long, add = completer::<ActorId>())]
+        actor: MaybeAlias<ActorId>,
+
+        #[arg(short, long, add = completer::<CurrencyId>())]
+        currency: MaybeAlias<CurrencyId>,
+
+        #[arg(short, long)]
+        amount: Amount,
+
+        /// Wallet the borrowed money goes into, or the lent money leaves from
+        #[arg(short, long, add = completer::<WalletId>())]
+        wallet: Option<MaybeAlias<WalletId>>,
+
+        /// Interest charged every period, e.g. 1.5 for 1.5%
+        #[arg(long)]
+        interest: Option<Percent>,
+
+        /// Charge interest on the accrued interest too
+        #[arg(long, requires = "interest")]
+        compound: bool,
+
+        /// How often interest is charged
+        #[arg(long, default_value_t = Period::Monthly)]
+        interest_period: Period,
+
+        /// Pay it back in this many installments of equal principal
+        #[arg(long, requires = "first_due", value_parser = clap::value_parser!(u32).range(1..))]
+        installments: Option<u32>,
+
+        /// Date the first installment is due
+        #[arg(long, requires = "installments")]
+        first_due: Option<Date>,
+
+        /// How often installments are due
+        #[arg(long, default_value_t = Period::Monthly)]
+        every: Period,
     }
 
     #[derive(clap::Subcommand)]
     pub enum AddEventCommand {
         RegisterBalance {
-            #[arg(short, long)]
+            #[arg(short, long, add = completer::<WalletId>())]
             wallet: MaybeAlias<WalletId>,
             #[arg(short, long)]
             amount: Amount,
         },
 
+        /// Sets the wallet to the balance seen elsewhere, e.g. a bank statement,
+        /// recording the difference as an adjustment
+        ReconcileBalance {
+            #[arg(short, long, add = completer::<WalletId>())]
+            wallet: MaybeAlias<WalletId>,
+
+            /// Balance the wallet actually has, may be negative for credit wallets
+            #[arg(short, long, allow_negative_numbers = true)]
+            observed: Balance,
+        },
+
         Purchase {
-            #[arg(short, long)]
+            #[arg(short, long, add = completer::<ItemTagId>())]
             item: MaybeAlias<ItemTagId>,
 
-            #[arg(short = 's', long = "seller")]
+            #[arg(short = 's', long = "seller", add = completer::<ActorId>())]
             actors: Vec<MaybeAlias<ActorId>>,
 
+            #[arg(short, long, add = completer::<WalletId>())]
+            wallet: MaybeAlias<WalletId>,
+
+            #[arg(short, long)]
+            amount: Amount,
+
+            /// How much was bought, to track its unit price
+            #[arg(short, long)]
+            quantity: Option<Amount>,
+
+            /// What the quantity is measured in: kg, l or unit
+            #[arg(long, requires = "quantity", default_value_t = Unit::Unit)]
+            unit: Unit,
+        },
+
+        /// Money I borrowed from an actor, optionally with interest and installments
+        RegisterDebt(DebtArgs),
+
+        /// Money I lent to an actor, optionally with interest and installments
+        RegisterLoan(DebtArgs),
+
+        /// Moves money between wallets of the same currency
+        MoveValue {
+            #[arg(short, long, add = completer::<WalletId>())]
+            from: MaybeAlias<WalletId>,
+
+            #[arg(short, long, add = completer::<WalletId>())]
+            to: MaybeAlias<WalletId>,
+
+            /// What gets to the destination wallet
             #[arg(short, long)]
+            amount: Amount,
+
+            /// Charged on top of the amount to the source wallet
+            #[arg(long, requires = "fee_item")]
+            fee: Option<Amount>,
+
+            /// Item the fee is spent on
+            #[arg(long, requires = "fee", add = completer::<ItemTagId>())]
+            fee_item: Option<MaybeAlias<ItemTagId>>,
+
+            /// Actor charging the fee
+            #[arg(long, requires = "fee", add = completer::<ActorId>())]
+            fee_seller: Vec<MaybeAlias<ActorId>>,
+        },
+
+        /// Money given back for a purchase, either a given one or just for an item
+        Refund {
+            /// Purchase event refunded, everything left of it is refunded when no amount is given
+            #[arg(short, long, conflicts_with_all = ["item", "actors"])]
+            purchase: Option<EventId>,
+
+            #[arg(short, long, add = completer::<ItemTagId>(), required_unless_present = "purchase")]
+            item: Option<MaybeAlias<ItemTagId>>,
+
+            #[arg(short = 's', long = "seller", add = completer::<ActorId>())]
+            actors: Vec<MaybeAlias<ActorId>>,
+
+            /// Wallet the money goes back into, the purchase's one by default
+            #[arg(short, long, add = completer::<WalletId>(), required_unless_present = "purchase")]
+            wallet: Option<MaybeAlias<WalletId>>,
+
+            #[arg(short, long, required_unless_present = "purchase")]
+            amount: Option<Amount>,
+        },
+
+        /// Purchase shared with other actors, my share is a purchase and theirs are loans
+        SplitPurchase {
+            #[arg(short, long, add = completer::<ItemTagId>())]
+            item: MaybeAlias<ItemTagId>,
+
+            #[arg(short = 's', long = "seller", add = completer::<ActorId>())]
+            actors: Vec<MaybeAlias<ActorId>>,
+
+            #[arg(short, long, add = completer::<WalletId>())]
             wallet: MaybeAlias<WalletId>,
 
             #[arg(short, long)]
             amount: Amount,
+
+            /// Actor sharing the purchase
+            #[arg(long = "with", required = true, add = completer::<ActorId>())]
+            participants: Vec<MaybeAlias<ActorId>>,
+
+            /// Shares by weight, mine first, e.g. 2,1,1. Equal shares when omitted
+            #[arg(long, value_delimiter = ',', conflicts_with = "percentages")]
+            weights: Option<Vec<u64>>,
+
+            /// Shares by percentage, mine first, e.g. 50,25,25
+            #[arg(long, value_delimiter = ',')]
+            percentages: Option<Vec<Percent>>,
         },
     }
 
@@ -118,11 +421,18 @@ pub mod events {
                 Event::RegisterBalance(RegisterBalance { amount, wallet_id })
             }
 
+            AddEventCommand::ReconcileBalance { wallet, observed } => {
+                let wallet_id = wallet.resolve(ctx).await?;
+                return reconcile_balance(ctx, wallet_id, observed).await;
+            }
+
             AddEventCommand::Purchase {
                 item,
                 actors,
                 wallet,
                 amount,
+                quantity,
+                unit,
             } => {
                 let wallet_id = wallet.resolve(ctx);
                 let item_id = item.resolve(ctx);
@@ -138,11 +448,196 @@ pub mod events {
                     amount,
                     item: item_id,
                     wallet_id,
+                    quantity: quantity.map(|value| Quantity { value, unit }),
+                })
+            }
+
+            AddEventCommand::RegisterDebt(args) => {
+                Event::RegisterDebt(debt_register(ctx, args).await?)
+            }
+
+            AddEventCommand::RegisterLoan(args) => {
+                Event::RegisterLoan(debt_register(ctx, args).await?)
+            }
+
+            AddEventCommand::MoveValue {
+                from,
+                to,
+                amount,
+                fee,
+                fee_item,
+                fee_seller,
+            } => {
+                let (from, to) = try_join!(from.resolve(ctx), to.resolve(ctx))?;
+
+                let fee = match (fee, fee_item) {
+                    (Some(amount), Some(item)) => {
+                        let item = item.resolve(ctx);
+                        let actors = try_join_collect(fee_seller.into_iter().map(|actor| {
+                            let ctx = ctx.clone();
+                            async move { actor.resolve(&ctx).await }
+                        }));
+                        let (item, actors) = try_join!(item, actors)?;
+
+                        Some(Fee {
+                            amount,
+                            item,
+                            actors: actors.into(),
+                        })
+                    }
+                    _ => None,
+                };
+
+                Event::MoveValue(MoveValue {
+                    from,
+                    to,
+                    amount,
+                    fee,
+                })
+            }
+
+            AddEventCommand::Refund {
+                purchase: Some(purchase_id),
+                wallet,
+                amount,
+                ..
+            } => {
+                let wallet_id = match wallet {
+                    Some(wallet) => Some(wallet.resolve(ctx).await?),
+                    None => None,
+                };
+
+                return refund_purchase(ctx, purchase_id, wallet_id, amount).await;
+            }
+
+            AddEventCommand::Refund {
+                purchase: None,
+                item,
+                actors,
+                wallet,
+                amount,
+            } => {
+                let (Some(item), Some(wallet), Some(amount)) = (item, wallet, amount) else {
+                    unreachable!("clap requires them without a purchase");
+                };
+
+                let wallet_id = wallet.resolve(ctx);
+                let item_id = item.resolve(ctx);
+                let actors = try_join_collect(actors.into_iter().map(|actor| {
+                    let ctx = ctx.clone();
+                    async move { actor.resolve(&ctx).await }
+                }));
+
+                let (wallet_id, item, actors) = try_join!(wallet_id, item_id, actors)?;
+
+                Event::Refund(Refund {
+                    purchase_id: None,
+                    item,
+                    actors: actors.into(),
+                    wallet_id,
+                    amount,
                 })
             }
+
+            AddEventCommand::SplitPurchase {
+                item,
+                actors,
+                wallet,
+                amount,
+                participants,
+                weights,
+                percentages,
+            } => {
+                let wallet_id = wallet.resolve(ctx);
+                let item_id = item.resolve(ctx);
+                let sellers = try_join_collect(actors.into_iter().map(|actor| {
+                    let ctx = ctx.clone();
+                    async move { actor.resolve(&ctx).await }
+                }));
+                let participants = try_join_collect(participants.into_iter().map(|actor| {
+                    let ctx = ctx.clone();
+                    async move { actor.resolve(&ctx).await }
+                }));
+
+                let (wallet_id, item, sellers, participants) =
+                    try_join!(wallet_id, item_id, sellers, participants)?;
+
+                let shares = match (weights, percentages) {
+                    (Some(weights), _) => Shares::Weights(weights),
+                    (_, Some(percentages)) => Shares::Percentages(percentages),
+                    (None, None) => Shares::Equal(participants.len() + 1),
+                };
+
+                let split = PurchaseSplit {
+                    item,
+                    sellers: sellers.into(),
+                    wallet_id,
+                    amount,
+                    participants: participants.into(),
+                    shares,
+                };
+
+                return split_purchase(ctx, split).await;
+            }
+        };
+
+        service.run(event).await.map_app_err(ctx, add_error)
+    }
+
+    async fn debt_register(ctx: &AppContext, args: DebtArgs) -> miette::Result<DebtRegister> {
+        let (actor_id, currency_id) =
+            try_join!(args.actor.resolve(ctx), args.currency.resolve(ctx))?;
+        let wallet_id = match args.wallet {
+            Some(wallet) => Some(wallet.resolve(ctx).await?),
+            None => None,
         };
 
-        service.run(event).await.map_app_err(ctx, |err| match err {
+        let interest = args.interest.map(|rate| Interest {
+            rate,
+            kind: if args.compound {
+                InterestKind::Compound
+            } else {
+                InterestKind::Simple
+            },
+            period: args.interest_period,
+        });
+
+        let installments = match (args.installments, args.first_due) {
+            (Some(count), Some(first_due)) => Some(InstallmentPlan {
+                count,
+                period: args.every,
+                first_due,
+            }),
+            _ => None,
+        };
+
+        let terms = (interest.is_some() || installments.is_some()).then_some(DebtTerms {
+            interest,
+            installments,
+        });
+
+        Ok(DebtRegister {
+            amount: args.amount,
+            currency_id,
+            actor_id,
+            payment_promise: None,
+            wallet_id,
+            debt_id: Some(DebtId::new()),
+            terms,
+        })
+    }
+
+    pub async fn accrue_interest(ctx: &AppContext, until: Option<Date>) -> miette::Result<()> {
+        let service = ctx.provide::<AccrueInterest>();
+
+        let accrued = service.run(until).await.map_app_err(ctx, add_error)?;
+        println!("Accrued interest on {} debts and loans", accrued);
+
+        Ok(())
+    }
+
+    fn add_error(err: add::Error) -> miette::Error {
+        match err {
             monee::backoffice::events::application::add::Error::Apply(e) => miette::diagnostic! {
                 "Failed to apply event {}", e
             }
@@ -157,6 +652,116 @@ pub mod events {
                     }
                 }.into()
             }
+
+            monee::backoffice::events::application::add::Error::Precision(units) => {
+                miette::diagnostic! {
+                    "The currency only allows {} decimals", units
+                }
+                .into()
+            }
+
+            monee::backoffice::events::application::add::Error::Refund(e) => match e {
+                add::RefundError::PurchaseNotFound => miette::diagnostic! {
+                    "No purchase found to refund"
+                }
+                .into(),
+                add::RefundError::ItemMismatch => miette::diagnostic! {
+                    "The refund is for another item than the purchase"
+                }
+                .into(),
+                add::RefundError::CurrenciesNonEqual => miette::diagnostic! {
+                    "The wallet uses another currency than the purchase"
+                }
+                .into(),
+                add::RefundError::ExceedsPurchase { refundable } => miette::diagnostic! {
+                    "Refunds can not exceed the purchase, only {} is left to refund", refundable
+                }
+                .into(),
+            },
+        }
+    }
+
+    async fn refund_purchase(
+        ctx: &AppContext,
+        purchase_id: EventId,
+        wallet_id: Option<WalletId>,
+        amount: Option<Amount>,
+    ) -> miette::Result<()> {
+        let service = ctx.provide::<RefundPurchase>();
+
+        let refunded = service
+            .run(purchase_id, wallet_id, amount)
+            .await
+            .map_app_err(ctx, add_error)?;
+        println!("Refunded {}", refunded);
+
+        Ok(())
+    }
+
+    async fn reconcile_balance(
+        ctx: &AppContext,
+        wallet_id: WalletId,
+        observed: Balance,
+    ) -> miette::Result<()> {
+        let service = ctx.provide::<Reconcile>();
+
+        let reconciliation = service
+            .run(wallet_id, observed)
+            .await
+            .map_app_err(ctx, |err| match err {
+                reconcile_balance::Error::Reconcile(reconcile::Error::WalletNotFound(_)) => {
+                    miette::diagnostic! { "Wallet not found" }.into()
+                }
+                reconcile_balance::Error::Reconcile(reconcile::Error::TooBig) => {
+                    miette::diagnostic! { "The difference is too big" }.into()
+                }
+                reconcile_balance::Error::Add(e) => add_error(e),
+            })?;
+
+        println!(
+            "Expected {}, adjusted by {}",
+            reconciliation.expected(),
+            reconciliation.adjustment
+        );
+        if reconciliation.is_large_discrepancy() {
+            println!(
+                "Warning: the adjustment is over {}% of the expected balance",
+                LARGE_DISCREPANCY_PERCENT
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn split_purchase(ctx: &AppContext, split: PurchaseSplit) -> miette::Result<()> {
+        let service = ctx.provide::<SplitPurchase>();
+
+        service.run(split).await.map_app_err(ctx, |err| match err {
+            split_purchase::Error::WalletNotFound => miette::diagnostic! {
+                "Wallet not found"
+            }
+            .into(),
+
+            split_purchase::Error::Split(SplitError::SharesMismatch) => miette::diagnostic! {
+                help = "give my share first, then one per --with actor",
+                "Wrong number of shares"
+            }
+            .into(),
+
+            split_purchase::Error::Split(SplitError::Allocation(e)) => miette::diagnostic! {
+                "Failed to split purchase: {}", e
+            }
+            .into(),
+
+            split_purchase::Error::Apply(add::Error::Apply(e)) => miette::diagnostic! {
+                "Failed to apply event {}", e
+            }
+            .into(),
+
+            split_purchase::Error::Apply(_) => miette::diagnostic! {
+                "Failed to apply event"
+            }
+            .into(),
         })
     }
 }
@@ -169,9 +774,13 @@ pub mod currency {
             currency_symbol::CurrencySymbol,
         },
         prelude::AppContext,
+        shared::domain::errors::{DeleteError, UpdateError},
     };
+    use monee_core::{CurrencyId, MinorUnits};
 
-    use crate::{error::LogAndErr, formatted, prelude::MapAppErr};
+    use crate::{
+        alias::MaybeAlias, completion::completer, error::LogAndErr, formatted, prelude::MapAppErr,
+    };
 
     #[derive(clap::Subcommand)]
     pub enum CurrencyCommand {
@@ -184,19 +793,57 @@ pub mod currency {
 
             #[arg(short, long)]
             symbol: CurrencySymbol,
+
+            /// Decimals used by the currency, e.g. 0 for JPY or 8 for BTC
+            #[arg(short, long, default_value_t)]
+            minor_units: MinorUnits,
         },
 
         #[command(alias = "ls")]
         List,
+
+        Update {
+            #[arg(add = completer::<CurrencyId>())]
+            currency: MaybeAlias<CurrencyId>,
+
+            #[arg(short, long)]
+            name: Option<CurrencyName>,
+
+            #[arg(short, long)]
+            code: Option<CurrencyCode>,
+
+            #[arg(short, long)]
+            symbol: Option<CurrencySymbol>,
+
+            /// Decimals used by the currency, e.g. 0 for JPY or 8 for BTC
+            #[arg(short, long)]
+            minor_units: Option<MinorUnits>,
+        },
+
+        #[command(alias = "rm")]
+        Delete {
+            #[arg(add = completer::<CurrencyId>())]
+            currency: MaybeAlias<CurrencyId>,
+        },
     }
 
     pub async fn run(ctx: &AppContext, command: CurrencyCommand) -> miette::Result<()> {
         match command {
-            CurrencyCommand::Create { name, code, symbol } => {
+            CurrencyCommand::Create {
+                name,
+                code,
+                symbol,
+                minor_units,
+            } => {
                 let service =
                     ctx.provide::<monee::backoffice::currencies::application::save_one::SaveOne>();
 
-                let currency = Currency { code, name, symbol };
+                let currency = Currency {
+                    code,
+                    name,
+                    symbol,
+                    minor_units,
+                };
                 service.run(currency).await.map_app_err(ctx, |_| {
                     miette::diagnostic! {
                         "Duplicated currency code",
@@ -212,29 +859,373 @@ pub mod currency {
 
                 crate::output::print_data(currencies.iter().map(|(_, currency)| {
                     formatted!(
-                        "{} \"{}\" {}",
+                        "{} \"{}\" {} ({} decimals)",
                         currency.code,
                         currency.name,
-                        currency.symbol
+                        currency.symbol,
+                        currency.minor_units
                     )
                 }));
 
                 Ok(())
             }
-        }
-    }
-}
 
-pub mod actor {
-    use cream::context::Context;
+            CurrencyCommand::Update {
+                currency,
+                name,
+                code,
+                symbol,
+                minor_units,
+            } => {
+                let currency_id = currency.resolve(ctx).await?;
+
+                let get_service =
+                    ctx.provide::<monee::backoffice::currencies::application::get_one::GetOne>();
+                let Some(current) = get_service.run(currency_id).await.log_err(ctx)? else {
+                    return Err(miette::diagnostic! { "Currency not found" }.into());
+                };
+
+                let service = ctx
+                    .provide::<monee::backoffice::currencies::application::update_one::UpdateOne>();
+                let currency = Currency {
+                    name: name.unwrap_or(current.name),
+                    code: code.unwrap_or(current.code),
+                    symbol: symbol.unwrap_or(current.symbol),
+                    minor_units: minor_units.unwrap_or(current.minor_units),
+                };
+
+                service
+                    .run(currency_id, currency)
+                    .await
+                    .map_app_err(ctx, |e| match e {
+                        UpdateError::NotFound => miette::diagnostic! { "Currency not found" }.into(),
+                        UpdateError::AlreadyExists(_) => miette::diagnostic! {
+                            "Duplicated currency code",
+                        }
+                        .into(),
+                        UpdateError::PrecisionLoss => miette::diagnostic! {
+                            "Some amounts in this currency have more decimals than --minor-units",
+                        }
+                        .into(),
+                    })
+            }
+
+            CurrencyCommand::Delete { currency } => {
+                let service = ctx
+                    .provide::<monee::backoffice::currencies::application::delete_one::DeleteOne>();
+
+                let currency_id = currency.resolve(ctx).await?;
+                service.run(currency_id).await.map_app_err(ctx, |e| match e {
+                    DeleteError::NotFound => miette::diagnostic! { "Currency not found" }.into(),
+                    DeleteError::Referenced => miette::diagnostic! {
+                        "Currency is still used by some wallets, debts or events"
+                    }
+                    .into(),
+                })
+            }
+        }
+    }
+}
+
+pub mod rate {
+    use cream::context::Context;
+    use monee::{
+        backoffice::{
+            currencies::application::get_all::GetAll as GetAllCurrencies,
+            exchange_rates::{
+                application::{delete_one::DeleteOne, get_all::GetAll, set_one::SetOne},
+                domain::{exchange_rate::ExchangeRate, rate::Rate},
+            },
+        },
+        prelude::AppContext,
+        shared::domain::date::{Date, Timezone},
+    };
+    use monee_core::CurrencyId;
+
+    use crate::{
+        alias::MaybeAlias, completion::completer, error::LogAndErr, formatted, prelude::MapAppErr,
+    };
+
+    #[derive(clap::Subcommand)]
+    pub enum RateCommand {
+        /// How many `to` one `from` is worth from a date on, e.g. `rate set USD PEN 3.75`
+        Set {
+            #[arg(add = completer::<CurrencyId>())]
+            from: MaybeAlias<CurrencyId>,
+
+            #[arg(add = completer::<CurrencyId>())]
+            to: MaybeAlias<CurrencyId>,
+
+            rate: Rate,
+
+            /// Date the rate is in effect from, today by default
+            #[arg(short, long)]
+            date: Option<Date>,
+        },
+
+        #[command(alias = "ls")]
+        List,
+
+        #[command(alias = "rm")]
+        Delete {
+            #[arg(add = completer::<CurrencyId>())]
+            from: MaybeAlias<CurrencyId>,
+
+            #[arg(add = completer::<CurrencyId>())]
+            to: MaybeAlias<CurrencyId>,
+
+            #[arg(short, long)]
+            date: Date,
+        },
+    }
+
+    pub async fn run(ctx: &AppContext, command: RateCommand) -> miette::Result<()> {
+        match command {
+            RateCommand::Set {
+                from,
+                to,
+                rate,
+                date,
+            } => {
+                let (from, to) = tokio::try_join!(from.resolve(ctx), to.resolve(ctx))?;
+
+                let service = ctx.provide::<SetOne>();
+                let rate = ExchangeRate {
+                    from,
+                    to,
+                    date: date.unwrap_or_else(|| Timezone::now().date_naive()),
+                    rate,
+                };
+
+                service.run(rate).await.map_app_err(ctx, |_| {
+                    miette::diagnostic! {
+                        "A currency can not be exchanged with itself"
+                    }
+                    .into()
+                })
+            }
+
+            RateCommand::List => {
+                let rates_service = ctx.provide::<GetAll>();
+                let currencies_service = ctx.provide::<GetAllCurrencies>();
+                let (rates, currencies) =
+                    tokio::join!(rates_service.run(), currencies_service.run());
+                let rates = rates.log_err(ctx)?;
+                let currencies = currencies.log_err(ctx)?;
+
+                let code = |id: CurrencyId| {
+                    currencies
+                        .iter()
+                        .find(|(currency_id, _)| *currency_id == id)
+                        .map(|(_, currency)| currency.code.to_string())
+                        .unwrap_or_else(|| id.to_string())
+                };
+
+                crate::output::print_data(rates.iter().map(|rate| {
+                    formatted!(
+                        "{} 1 {} = {} {}",
+                        rate.date,
+                        code(rate.from),
+                        rate.rate,
+                        code(rate.to)
+                    )
+                }));
+
+                Ok(())
+            }
+
+            RateCommand::Delete { from, to, date } => {
+                let (from, to) = tokio::try_join!(from.resolve(ctx), to.resolve(ctx))?;
+
+                let service = ctx.provide::<DeleteOne>();
+                // Nothing references exchange rates, not found is the only way to fail
+                service.run(from, to, date).await.map_app_err(ctx, |_| {
+                    miette::diagnostic! { "Exchange rate not found" }.into()
+                })
+            }
+        }
+    }
+}
+
+pub mod goal {
+    use std::str::FromStr;
+
+    use cream::context::Context;
+    use monee::{
+        backoffice::{
+            currencies::application::get_all::GetAll as GetAllCurrencies,
+            goals::{
+                application::{
+                    create_one::{CreateOne, Error as CreateError},
+                    delete_one::DeleteOne,
+                    get_all::GetAll,
+                },
+                domain::{goal::Goal, goal_name::GoalName},
+            },
+        },
+        prelude::AppContext,
+        shared::domain::{date::Date, errors::DeleteError},
+    };
+    use monee_core::{Amount, CurrencyId, GoalId, WalletId};
+
+    use crate::{
+        alias::MaybeAlias, completion::completer, error::LogAndErr, formatted, prelude::MapAppErr,
+    };
+
+    /// A date, or a `YYYY-MM` month meaning its last day
+    #[derive(Clone)]
+    pub struct Deadline(Date);
+
+    impl FromStr for Deadline {
+        type Err = chrono::ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            if let Ok(date) = Date::from_str(s) {
+                return Ok(Self(date));
+            }
+
+            let first = Date::from_str(&format!("{}-01", s))?;
+            let last = first
+                .checked_add_months(chrono::Months::new(1))
+                .and_then(|next| next.pred_opt())
+                .unwrap_or(first);
+
+            Ok(Self(last))
+        }
+    }
+
+    #[derive(clap::Subcommand)]
+    pub enum GoalCommand {
+        /// Money to gather by a deadline, e.g. `goal create vacation -c USD -t 3000 -d 2027-03 -w savings`
+        Create {
+            name: GoalName,
+
+            #[arg(short, long, add = completer::<CurrencyId>())]
+            currency: MaybeAlias<CurrencyId>,
+
+            #[arg(short, long)]
+            target: Amount,
+
+            /// Date to reach the target by, a `YYYY-MM` month means its last day
+            #[arg(short, long)]
+            deadline: Deadline,
+
+            /// Wallets of the goal currency that hold the savings
+            #[arg(short, long = "wallet", required = true, add = completer::<WalletId>())]
+            wallets: Vec<MaybeAlias<WalletId>>,
+        },
+
+        #[command(alias = "ls")]
+        List,
+
+        #[command(alias = "rm")]
+        Delete {
+            #[arg(add = completer::<GoalId>())]
+            goal: MaybeAlias<GoalId>,
+        },
+    }
+
+    pub async fn run(ctx: &AppContext, command: GoalCommand) -> miette::Result<()> {
+        match command {
+            GoalCommand::Create {
+                name,
+                currency,
+                target,
+                deadline,
+                wallets,
+            } => {
+                let currency_id = currency.resolve(ctx).await?;
+                let mut wallet_ids = Vec::with_capacity(wallets.len());
+                for wallet in wallets {
+                    wallet_ids.push(wallet.resolve(ctx).await?);
+                }
+
+                let service = ctx.provide::<CreateOne>();
+                let goal = Goal {
+                    name,
+                    currency_id,
+                    target,
+                    deadline: deadline.0,
+                    wallets: wallet_ids,
+                };
+
+                service.run(goal).await.map_app_err(ctx, |e| match e {
+                    CreateError::NoWallets => {
+                        miette::diagnostic! { "Goal needs at least one wallet" }.into()
+                    }
+                    CreateError::WalletNotFound(_) => {
+                        miette::diagnostic! { "Wallet not found" }.into()
+                    }
+                    CreateError::CurrenciesNonEqual(_) => miette::diagnostic! {
+                        "Goal wallets must hold the goal currency"
+                    }
+                    .into(),
+                    CreateError::AlreadyExists => miette::diagnostic! {
+                        "Goal with this name already exists"
+                    }
+                    .into(),
+                })
+            }
+
+            GoalCommand::List => {
+                let goals_service = ctx.provide::<GetAll>();
+                let currencies_service = ctx.provide::<GetAllCurrencies>();
+                let (goals, currencies) =
+                    tokio::join!(goals_service.run(), currencies_service.run());
+                let goals = goals.log_err(ctx)?;
+                let currencies = currencies.log_err(ctx)?;
+
+                crate::output::print_data(goals.iter().map(|(id, goal)| {
+                    let target = currencies
+                        .iter()
+                        .find(|(currency_id, _)| *currency_id == goal.currency_id)
+                        .map(|(_, currency)| {
+                            format!(
+                                "{} {}{}",
+                                currency.code,
+                                currency.symbol,
+                                goal.target.with_precision(currency.minor_units)
+                            )
+                        })
+                        .unwrap_or_else(|| goal.target.to_string());
+
+                    formatted!("{} {} by {} ({})", goal.name, target, goal.deadline, id)
+                }));
+
+                Ok(())
+            }
+
+            GoalCommand::Delete { goal } => {
+                let goal_id = goal.resolve(ctx).await?;
+
+                let service = ctx.provide::<DeleteOne>();
+                // Nothing references goals, not found is the only way to fail
+                service.run(goal_id).await.map_app_err(ctx, |e| match e {
+                    DeleteError::NotFound | DeleteError::Referenced => {
+                        miette::diagnostic! { "Goal not found" }.into()
+                    }
+                })
+            }
+        }
+    }
+}
+
+pub mod actor {
+    use cream::context::Context;
     use monee::{
         backoffice::actors::domain::{
-            actor_alias::ActorAlias, actor_name::ActorName, actor_type::ActorType,
+            actor::Actor, actor_alias::ActorAlias, actor_name::ActorName, actor_type::ActorType,
         },
         prelude::AppContext,
+        shared::domain::errors::{DeleteError, MergeError, UpdateError},
     };
+    use monee_core::ActorId;
+    use tokio::try_join;
 
-    use crate::{error::LogAndErr, output::formatted, prelude::MapAppErr};
+    use crate::{
+        alias::MaybeAlias, completion::completer, error::LogAndErr, output::formatted,
+        prelude::MapAppErr,
+    };
 
     #[derive(clap::Subcommand)]
     pub enum ActorCommand {
@@ -251,6 +1242,38 @@ pub mod actor {
 
         #[command(alias = "ls")]
         List,
+
+        Update {
+            #[arg(add = completer::<ActorId>())]
+            actor: MaybeAlias<ActorId>,
+
+            #[arg(short, long)]
+            name: Option<ActorName>,
+
+            #[arg(short, long)]
+            r#type: Option<ActorType>,
+
+            #[arg(short, long, conflicts_with = "remove_alias")]
+            alias: Option<ActorAlias>,
+
+            #[arg(long)]
+            remove_alias: bool,
+        },
+
+        #[command(alias = "rm")]
+        Delete {
+            #[arg(add = completer::<ActorId>())]
+            actor: MaybeAlias<ActorId>,
+        },
+
+        /// Move every debt, loan and event of an actor to another one, then delete it
+        Merge {
+            #[arg(add = completer::<ActorId>())]
+            source: MaybeAlias<ActorId>,
+
+            #[arg(long = "into", add = completer::<ActorId>())]
+            target: MaybeAlias<ActorId>,
+        },
     }
 
     pub async fn run(ctx: &AppContext, command: ActorCommand) -> miette::Result<()> {
@@ -299,6 +1322,71 @@ pub mod actor {
 
                 Ok(())
             }
+
+            ActorCommand::Update {
+                actor,
+                name,
+                r#type,
+                alias,
+                remove_alias,
+            } => {
+                let actor_id = actor.resolve(ctx).await?;
+
+                let get_service =
+                    ctx.provide::<monee::backoffice::actors::application::get_one::GetOne>();
+                let Some(current) = get_service.run(actor_id).await.log_err(ctx)? else {
+                    return Err(miette::diagnostic! { "Actor not found" }.into());
+                };
+
+                let service =
+                    ctx.provide::<monee::backoffice::actors::application::update_one::UpdateOne>();
+                let actor = Actor {
+                    name: name.unwrap_or(current.name),
+                    actor_type: r#type.unwrap_or(current.actor_type),
+                    alias: if remove_alias {
+                        None
+                    } else {
+                        alias.or(current.alias)
+                    },
+                };
+
+                service.run(actor_id, actor).await.map_app_err(ctx, |e| match e {
+                    UpdateError::NotFound => miette::diagnostic! { "Actor not found" }.into(),
+                    UpdateError::AlreadyExists(_) => miette::diagnostic! {
+                        "Duplicated actor alias",
+                    }
+                    .into(),
+                    UpdateError::PrecisionLoss => unreachable!("actors hold no amounts"),
+                })
+            }
+
+            ActorCommand::Delete { actor } => {
+                let service =
+                    ctx.provide::<monee::backoffice::actors::application::delete_one::DeleteOne>();
+
+                let actor_id = actor.resolve(ctx).await?;
+                service.run(actor_id).await.map_app_err(ctx, |e| match e {
+                    DeleteError::NotFound => miette::diagnostic! { "Actor not found" }.into(),
+                    DeleteError::Referenced => miette::diagnostic! {
+                        help = "use `monee actor merge` to move its records to another actor",
+                        "Actor is still used by some debts, loans or events"
+                    }
+                    .into(),
+                })
+            }
+
+            ActorCommand::Merge { source, target } => {
+                let service = ctx.provide::<monee::backoffice::actors::application::merge::Merge>();
+
+                let (source, target) = try_join!(source.resolve(ctx), target.resolve(ctx))?;
+                service.run(source, target).await.map_app_err(ctx, |e| match e {
+                    MergeError::NotFound => miette::diagnostic! { "Actor not found" }.into(),
+                    MergeError::SameEntity => miette::diagnostic! {
+                        "Cannot merge an actor into itself"
+                    }
+                    .into(),
+                })
+            }
         }
     }
 }
@@ -307,28 +1395,48 @@ pub mod show {
     use cream::context::Context;
     use std::fmt::Display;
 
-    use monee::{reports::snapshot::domain::snapshot::Money, shared::domain::context::AppContext};
+    use monee::{
+        reports::snapshot::{
+            application::{
+                snapshot_at::{Error as SnapshotAtError, SnapshotAt},
+                snapshot_report::SnapshotReport,
+            },
+            domain::snapshot::{Money, Wallet, WalletMoney},
+        },
+        shared::domain::{context::AppContext, date::Date},
+    };
+    use monee_core::ToPrecision;
 
-    use crate::{formatted, output::Listter, prelude::LogAndErr};
+    use crate::{
+        formatted,
+        output::Listter,
+        prelude::{LogAndErr, MapAppErr},
+    };
 
     #[derive(clap::Args)]
-    pub struct Args;
+    pub struct Args {
+        /// Balances at the end of this date instead of now
+        #[arg(long)]
+        at: Option<Date>,
+    }
 
-    struct MoneyCli(Money);
+    struct MoneyCli<A>(Money<A>);
 
-    impl Display for MoneyCli {
+    impl<A: ToPrecision> Display for MoneyCli<A> {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             let MoneyCli(money) = self;
             write!(
                 f,
                 "{} {}{}",
-                money.currency.code, money.currency.symbol, money.amount
+                money.currency.code,
+                money.currency.symbol,
+                money.amount.with_precision(money.currency.minor_units)
             )
         }
     }
 
-    fn print_entity<T, D: Display>(
-        entities: impl ExactSizeIterator<Item = (T, Money)>,
+    fn print_entity<T, D: Display, A: ToPrecision>(
+        entities: impl ExactSizeIterator<Item = (T, Money<A>)>,
         entity_display: impl Fn(T) -> D,
     ) {
         let iter = entities.map(|(entity, money)| {
@@ -339,13 +1447,48 @@ pub mod show {
         crate::output::print_iter(Listter::new(iter).map(|item| formatted!("\t{}", item)))
     }
 
-    pub async fn run(ctx: &AppContext, _: Args) -> miette::Result<()> {
-        let service =
-            ctx.provide::<monee::reports::snapshot::application::snapshot_report::SnapshotReport>();
-        let snapshot = service.run().await.log_err(ctx)?;
+    /// Wallet name followed by its credit usage, if it is a credit one
+    fn wallet_display(wallet: &Wallet, money: &WalletMoney) -> String {
+        let Some(usage) = wallet.credit_usage(money) else {
+            return wallet.name.to_string();
+        };
+
+        let units = money.currency.minor_units;
+        match (usage.available(), usage.utilization()) {
+            (Some(available), Some(utilization)) => format!(
+                "{} (credit: {} available, {:.0}% used)",
+                wallet.name,
+                available.with_precision(units),
+                utilization
+            ),
+            _ => format!(
+                "{} (credit: {} used, no limit)",
+                wallet.name,
+                usage.used.with_precision(units)
+            ),
+        }
+    }
+
+    pub async fn run(ctx: &AppContext, Args { at }: Args) -> miette::Result<()> {
+        let snapshot = match at {
+            Some(date) => {
+                let service = ctx.provide::<SnapshotAt>();
+                service.run(date).await.map_app_err(ctx, |e| match e {
+                    SnapshotAtError::Replay(_) => miette::diagnostic! {
+                        "Could not rebuild the balances at that date from past events"
+                    }
+                    .into(),
+                })?
+            }
+            None => ctx.provide::<SnapshotReport>().run().await.log_err(ctx)?,
+        };
 
         println!("Wallets:");
-        print_entity(snapshot.wallets.into_values(), |wallet| wallet.name);
+        let wallets = snapshot
+            .wallets
+            .into_values()
+            .map(|(wallet, money)| (wallet_display(&wallet, &money), money));
+        print_entity(wallets, |name| name);
 
         println!("Debts:");
         print_entity(snapshot.debts.into_values(), |debt| {
@@ -361,62 +1504,769 @@ pub mod show {
     }
 }
 
-pub mod item_tags {
-    use crate::{
-        alias::MaybeAlias,
-        formatted,
-        output::{print_data, IterDisplayExt},
-        prelude::*,
-    };
+pub mod report {
     use cream::context::Context;
     use monee::{
-        backoffice::item_tags::{
-            application::link_one::Status,
-            domain::{item_name::ItemName, item_tag::ItemTag},
+        backoffice::{
+            actors::domain::actor::Actor,
+            currencies::{
+                application::get_all::GetAll as GetAllCurrencies, domain::currency::Currency,
+            },
+            events::domain::reconcile::LARGE_DISCREPANCY_PERCENT,
+        },
+        reports::{
+            net_worth::{
+                application::net_worth_report::{Error as NetWorthError, NetWorthReport},
+                domain::consolidate::NetWorth,
+            },
+            snapshot::{
+                application::actor_balances::ActorBalances,
+                domain::{
+                    actor_balance::{CurrencyBalances, Error as BalancesError},
+                    settle_up::Party,
+                },
+            },
+            goals::{
+                application::goals_report::{Error as GoalsError, GoalsReport},
+                domain::goal_report::GoalReport,
+            },
+            installments::{
+                application::installments_report::InstallmentsReport,
+                domain::debt_schedule::DebtSchedule,
+            },
+            events::{
+                application::get_events::GetEvents,
+                domain::event::{DebtRegister, Event},
+            },
+            prices::{application::prices_report::PricesReport, domain::unit_price::PriceEntry},
+            spending::{
+                application::spending_report::SpendingReport,
+                domain::item_spending::SpendingEntry,
+            },
+        },
+        shared::domain::{
+            context::AppContext,
+            date::{Date, Timezone},
         },
-        prelude::*,
     };
-    use monee_core::ItemTagId;
-    use tokio::try_join;
+    use monee_core::{ActorId, Balance, CurrencyId, ItemTagId};
+    use tokio::task::JoinSet;
+
+    use crate::{alias::MaybeAlias, completion::completer, formatted, output::Listter, prelude::*};
 
     #[derive(clap::Subcommand)]
-    pub enum ItemTagCommand {
-        #[command(alias = "c")]
-        Create {
-            #[arg(short, long)]
-            name: ItemName,
+    pub enum ReportCommand {
+        /// Who owes whom overall, and the fewest payments to settle up
+        Balances {
+            /// Only take these actors into account
+            #[arg(short, long = "actor", add = completer::<ActorId>())]
+            actors: Vec<MaybeAlias<ActorId>>,
         },
 
-        #[command(alias = "ls")]
-        List,
+        /// Spending per item, net of refunds
+        Spending,
 
-        #[command(alias = "ln")]
-        Link {
-            #[arg(short, long)]
-            parent: MaybeAlias<ItemTagId>,
+        /// Unit price history per item and seller, cheapest seller first
+        Prices {
+            #[arg(short, long, add = completer::<ItemTagId>())]
+            item: Option<MaybeAlias<ItemTagId>>,
+
+            #[arg(short, long, add = completer::<ActorId>())]
+            seller: Option<MaybeAlias<ActorId>>,
+        },
+
+        /// Progress of every savings goal, with the monthly deposits needed to make it
+        Goals,
 
+        /// Next installment of every debt and loan with an installment plan,
+        /// followed by their amortization tables
+        Installments,
+
+        /// Wallets plus loans minus debts, converted to a single currency
+        NetWorth {
+            /// Currency every holding is converted to
+            #[arg(short, long, add = completer::<CurrencyId>())]
+            base: MaybeAlias<CurrencyId>,
+
+            /// Net worth at the end of this date instead of now
+            #[arg(short, long, conflicts_with = "months")]
+            date: Option<Date>,
+
+            /// Net worth at the end of each of the last N months
             #[arg(short, long)]
-            child: MaybeAlias<ItemTagId>,
+            months: Option<u32>,
         },
+
+        /// Every event, oldest first, flagging reconciliations with a large discrepancy
+        Events,
     }
 
-    pub async fn run(ctx: &AppContext, command: ItemTagCommand) -> miette::Result<()> {
+    pub async fn run(ctx: &AppContext, command: ReportCommand) -> miette::Result<()> {
         match command {
-            ItemTagCommand::Create { name } => {
-                let service = ctx
-                    .provide::<monee::backoffice::item_tags::application::create_one::CreateOne>();
-                service.run(ItemTag { name }).await.map_app_err(ctx, |_| {
-                    miette::diagnostic! { "Duplicated item tag name" }.into()
+            ReportCommand::Balances { actors } => {
+                let mut set: JoinSet<_> = actors
+                    .into_iter()
+                    .map(|actor| {
+                        let ctx = ctx.clone();
+                        async move { actor.resolve(&ctx).await }
+                    })
+                    .collect();
+
+                let mut group = Vec::new();
+                while let Some(next) = set.join_next().await {
+                    group.push(next.expect("to join")?);
+                }
+
+                let service = ctx.provide::<ActorBalances>();
+                let balances = service.run(&group).await.map_app_err(ctx, |e| match e {
+                    BalancesError::Overflow => miette::diagnostic! {
+                        "The balances with the actors are too large to add up"
+                    }
+                    .into(),
                 })?;
 
+                let iter = balances.iter().map(|balances| formatted!("{}", CurrencyCli(balances)));
+                crate::output::print_iter(Listter::new(iter));
+
                 Ok(())
             }
 
-            ItemTagCommand::List => {
-                let service =
-                    ctx.provide::<monee::backoffice::item_tags::application::get_all::GetAll>();
+            ReportCommand::Spending => {
+                let service = ctx.provide::<SpendingReport>();
+                let entries = service.run().await.log_err(ctx)?;
 
-                let item_tags = service.run().await.log_err(ctx)?;
+                let iter = entries.iter().map(|entry| formatted!("{}", SpendingCli(entry)));
+                crate::output::print_iter(Listter::new(iter));
+
+                Ok(())
+            }
+
+            ReportCommand::Prices { item, seller } => {
+                let item = match item {
+                    Some(item) => Some(item.resolve(ctx).await?),
+                    None => None,
+                };
+                let seller = match seller {
+                    Some(seller) => Some(seller.resolve(ctx).await?),
+                    None => None,
+                };
+
+                let service = ctx.provide::<PricesReport>();
+                let entries = service.run(item, seller).await.log_err(ctx)?;
+
+                let iter = entries.iter().map(|entry| formatted!("{}", PriceCli(entry)));
+                crate::output::print_iter(Listter::new(iter));
+
+                Ok(())
+            }
+
+            ReportCommand::Goals => {
+                let service = ctx.provide::<GoalsReport>();
+                let reports = service.run().await.map_app_err(ctx, |e| match e {
+                    GoalsError::Replay(_) => miette::diagnostic! {
+                        "Could not rebuild the past balances from events"
+                    }
+                    .into(),
+                    GoalsError::Overflow => miette::diagnostic! {
+                        "The wallets of a goal hold too much to add up"
+                    }
+                    .into(),
+                })?;
+
+                let iter = reports.iter().map(|report| formatted!("{}", GoalCli(report)));
+                crate::output::print_iter(Listter::new(iter));
+
+                Ok(())
+            }
+
+            ReportCommand::Installments => {
+                let service = ctx.provide::<InstallmentsReport>();
+                let schedules = service.run().await.log_err(ctx)?;
+
+                let today = Timezone::now().date_naive();
+                let mut upcoming: Vec<_> = schedules
+                    .iter()
+                    .filter_map(|schedule| Some((schedule, schedule.upcoming(today).next()?)))
+                    .collect();
+                upcoming.sort_by_key(|(_, installment)| installment.due);
+
+                println!("Upcoming:");
+                let iter = upcoming.iter().map(|(schedule, installment)| {
+                    formatted!(
+                        "\t{} {} (installment {} of {})",
+                        installment.due,
+                        ScheduleCli(schedule).heading(installment.payment),
+                        installment.number,
+                        schedule.installments.len()
+                    )
+                });
+                crate::output::print_iter(Listter::new(iter));
+
+                let iter = schedules
+                    .iter()
+                    .map(|schedule| formatted!("{}", ScheduleCli(schedule)));
+                crate::output::print_iter(iter);
+
+                Ok(())
+            }
+
+            ReportCommand::NetWorth { base, date, months } => {
+                let base = base.resolve(ctx).await?;
+
+                let service = ctx.provide::<NetWorthReport>();
+                let history = match months {
+                    Some(months) => service.history(base, months).await,
+                    None => service.run(base, date).await.map(|net_worth| vec![net_worth]),
+                }
+                .map_app_err(ctx, |e| match e {
+                    NetWorthError::CurrencyNotFound => {
+                        miette::diagnostic! { "Currency not found" }.into()
+                    }
+                    NetWorthError::Replay(_) => miette::diagnostic! {
+                        "Could not rebuild the balances at that date from past events"
+                    }
+                    .into(),
+                })?;
+
+                let currencies = ctx.provide::<GetAllCurrencies>().run().await.log_err(ctx)?;
+                let currencies = currencies.as_slice();
+                let iter = history
+                    .iter()
+                    .map(|net_worth| formatted!("{}", NetWorthCli(net_worth, currencies)));
+                crate::output::print_iter(Listter::new(iter));
+
+                Ok(())
+            }
+
+            ReportCommand::Events => {
+                let service = ctx.provide::<GetEvents>();
+                let events = service.run().await.log_err(ctx)?;
+
+                let iter = events.iter().map(|event| formatted!("{}", EventCli(event)));
+                crate::output::print_iter(Listter::new(iter));
+
+                Ok(())
+            }
+        }
+    }
+
+    struct EventCli<'a>(&'a Event);
+
+    impl std::fmt::Display for EventCli<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let EventCli(event) = self;
+            let names = |actors: &[Actor]| {
+                actors
+                    .iter()
+                    .map(|actor| format!("'{}'", actor.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            match event {
+                Event::Purchase {
+                    item,
+                    actors,
+                    wallet,
+                    amount,
+                    quantity,
+                } => {
+                    write!(f, "Purchase of {} from '{}': {}", item, wallet, amount)?;
+                    if let Some(quantity) = quantity {
+                        write!(f, " for {}", quantity)?;
+                    }
+                    if !actors.is_empty() {
+                        write!(f, " at {}", names(actors))?;
+                    }
+                }
+                Event::Refund {
+                    item,
+                    actors,
+                    wallet,
+                    amount,
+                } => {
+                    write!(f, "Refund of {} into '{}': {}", item, wallet, amount)?;
+                    if !actors.is_empty() {
+                        write!(f, " from {}", names(actors))?;
+                    }
+                }
+                Event::MoveValue {
+                    from,
+                    to,
+                    amount,
+                    fee,
+                } => {
+                    write!(f, "Move from '{}' to '{}': {}", from, to, amount)?;
+                    if let Some(fee) = fee {
+                        write!(f, " (fee {})", fee)?;
+                    }
+                }
+                Event::RegisterBalance { wallet, amount } => {
+                    write!(f, "Balance of '{}': {}", wallet, amount)?;
+                }
+                Event::ReconcileBalance {
+                    wallet,
+                    observed,
+                    adjustment,
+                } => {
+                    write!(
+                        f,
+                        "Reconciliation of '{}' to {}, adjusted by {}",
+                        wallet, observed, adjustment
+                    )?;
+                    if event.is_large_discrepancy() {
+                        write!(
+                            f,
+                            " (over {}% of the expected balance)",
+                            LARGE_DISCREPANCY_PERCENT
+                        )?;
+                    }
+                }
+                Event::RegisterDebt(debt) => write!(f, "Debt with {}", DebtCli(debt))?,
+                Event::RegisterLoan(loan) => write!(f, "Loan to {}", DebtCli(loan))?,
+                Event::AccrueDebtInterest { amount, until } => {
+                    write!(f, "Debt interest until {}: {}", until, amount)?;
+                }
+                Event::AccrueLoanInterest { amount, until } => {
+                    write!(f, "Loan interest until {}: {}", until, amount)?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    struct DebtCli<'a>(&'a DebtRegister);
+
+    impl std::fmt::Display for DebtCli<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let DebtCli(debt) = self;
+            let currency = &debt.currency;
+
+            write!(
+                f,
+                "'{}': {} {}{}",
+                debt.actor.name,
+                currency.code,
+                currency.symbol,
+                debt.amount.with_precision(currency.minor_units)
+            )?;
+            if let Some(promise) = debt.payment_promise {
+                write!(f, ", promised for {}", promise.date_naive())?;
+            }
+
+            Ok(())
+        }
+    }
+
+    struct SpendingCli<'a>(&'a SpendingEntry);
+
+    impl std::fmt::Display for SpendingCli<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let SpendingCli(entry) = self;
+            let currency = &entry.currency;
+            let units = currency.minor_units;
+
+            write!(
+                f,
+                "{} => {} {}{}",
+                entry.item,
+                currency.code,
+                currency.symbol,
+                entry.spending.net().with_precision(units)
+            )?;
+
+            if entry.spending.refunded != monee_core::Amount::default() {
+                write!(
+                    f,
+                    " ({} spent, {} refunded)",
+                    entry.spending.spent.with_precision(units),
+                    entry.spending.refunded.with_precision(units)
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+
+    struct PriceCli<'a>(&'a PriceEntry);
+
+    impl std::fmt::Display for PriceCli<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let PriceCli(entry) = self;
+            let currency = &entry.currency;
+
+            write!(f, "{} per {}", entry.item, entry.history.unit)?;
+            match &entry.seller {
+                Some(seller) => write!(f, " at '{}'", seller.name)?,
+                None => write!(f, " without seller")?,
+            }
+            write!(f, ":")?;
+
+            for point in entry.history.prices.iter() {
+                write!(
+                    f,
+                    "\n\t{} {} {}{}",
+                    point.date,
+                    currency.code,
+                    currency.symbol,
+                    point.price.with_precision(currency.minor_units)
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+
+    struct GoalCli<'a>(&'a GoalReport);
+
+    impl GoalCli<'_> {
+        fn money(&self, amount: monee_core::Amount) -> String {
+            let currency = &self.0.currency;
+            format!(
+                "{} {}{}",
+                currency.code,
+                currency.symbol,
+                amount.with_precision(currency.minor_units)
+            )
+        }
+    }
+
+    impl std::fmt::Display for GoalCli<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let GoalCli(report) = self;
+            let progress = &report.progress;
+
+            writeln!(
+                f,
+                "{}: {} of {} by {}",
+                report.goal.name,
+                self.money(progress.current),
+                self.money(report.goal.target),
+                report.goal.deadline
+            )?;
+
+            match progress.monthly_required {
+                Some(required) if required == monee_core::Amount::default() => {
+                    writeln!(f, "\tReached")?
+                }
+                Some(required) => writeln!(
+                    f,
+                    "\tPut aside {} a month for {} months",
+                    self.money(required),
+                    progress.months_left
+                )?,
+                None => writeln!(
+                    f,
+                    "\tDeadline passed, {} short",
+                    self.money(progress.remaining)
+                )?,
+            }
+
+            write!(
+                f,
+                "\tDeposits: {} a month",
+                self.money(progress.monthly_deposits)
+            )?;
+            if progress.remaining == monee_core::Amount::default() {
+                return Ok(());
+            }
+            match progress.projected {
+                Some(date) => write!(f, ", reached by {}", date),
+                None => write!(f, ", not growing"),
+            }
+        }
+    }
+
+    struct ScheduleCli<'a>(&'a DebtSchedule);
+
+    impl ScheduleCli<'_> {
+        fn money(&self, amount: monee_core::Amount) -> String {
+            let currency = &self.0.currency;
+            format!(
+                "{} {}{}",
+                currency.code,
+                currency.symbol,
+                amount.with_precision(currency.minor_units)
+            )
+        }
+
+        /// Who pays whom `amount`
+        fn heading(&self, amount: monee_core::Amount) -> String {
+            let ScheduleCli(schedule) = self;
+            if schedule.is_loan {
+                format!("'{}' pays you {}", schedule.actor.name, self.money(amount))
+            } else {
+                format!("You pay '{}' {}", schedule.actor.name, self.money(amount))
+            }
+        }
+    }
+
+    impl std::fmt::Display for ScheduleCli<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let ScheduleCli(schedule) = self;
+            let units = schedule.currency.minor_units;
+            let principal = schedule
+                .installments
+                .iter()
+                .fold(monee_core::Amount::default(), |total, installment| {
+                    total.checked_add(installment.principal).unwrap_or(total)
+                });
+
+            let kind = if schedule.is_loan { "Loan to" } else { "Debt with" };
+            writeln!(
+                f,
+                "{} '{}' of {} ({}):",
+                kind,
+                schedule.actor.name,
+                self.money(principal),
+                schedule.debt_id
+            )?;
+            write!(f, "\t#\tDue\t\tPrincipal\tInterest\tPayment\tRemaining")?;
+
+            for installment in schedule.installments.iter() {
+                write!(
+                    f,
+                    "\n\t{}\t{}\t{}\t\t{}\t\t{}\t{}",
+                    installment.number,
+                    installment.due,
+                    installment.principal.with_precision(units),
+                    installment.interest.with_precision(units),
+                    installment.payment.with_precision(units),
+                    installment.remaining.with_precision(units)
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+
+    struct NetWorthCli<'a>(&'a NetWorth, &'a [(CurrencyId, Currency)]);
+
+    impl NetWorthCli<'_> {
+        fn currency(&self, id: CurrencyId) -> Option<&Currency> {
+            self.1
+                .iter()
+                .find(|(currency_id, _)| *currency_id == id)
+                .map(|(_, currency)| currency)
+        }
+
+        fn money(&self, id: CurrencyId, balance: Balance) -> String {
+            match self.currency(id) {
+                Some(currency) => format!(
+                    "{} {}{}",
+                    currency.code,
+                    currency.symbol,
+                    balance.with_precision(currency.minor_units)
+                ),
+                None => format!("{} {}", id, balance),
+            }
+        }
+
+        fn code(&self, id: CurrencyId) -> String {
+            self.currency(id)
+                .map(|currency| currency.code.to_string())
+                .unwrap_or_else(|| id.to_string())
+        }
+    }
+
+    impl std::fmt::Display for NetWorthCli<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let NetWorthCli(net_worth, _) = self;
+
+            writeln!(f, "{} in {}:", net_worth.date, self.code(net_worth.base))?;
+            for holding in net_worth.holdings.iter() {
+                let converted = match holding.converted {
+                    Some(converted) => self.money(net_worth.base, converted),
+                    None => "no exchange rate".to_owned(),
+                };
+                writeln!(
+                    f,
+                    "\t{} => {}",
+                    self.money(holding.currency_id, holding.balance),
+                    converted
+                )?;
+            }
+
+            write!(f, "\tTotal: {}", self.money(net_worth.base, net_worth.total))?;
+
+            let missing: Vec<_> = net_worth
+                .missing_rates()
+                .map(|currency_id| self.code(currency_id))
+                .collect();
+            if !missing.is_empty() {
+                write!(
+                    f,
+                    " (incomplete, missing rates for {})",
+                    missing.join(", ")
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+
+    struct CurrencyCli<'a>(&'a CurrencyBalances);
+
+    impl CurrencyCli<'_> {
+        fn party(&self, party: Party) -> String {
+            match party {
+                Party::Me => "you".to_owned(),
+                Party::Actor(id) => self
+                    .0
+                    .actors
+                    .iter()
+                    .find(|actor| actor.actor_id == id)
+                    .map(|actor| actor.actor.name.to_string())
+                    .unwrap_or_else(|| id.to_string()),
+            }
+        }
+    }
+
+    impl std::fmt::Display for CurrencyCli<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let CurrencyCli(balances) = self;
+            let currency = &balances.currency;
+            let money = |amount: monee_core::Amount| {
+                formatted!(
+                    "{} {}{}",
+                    currency.code,
+                    currency.symbol,
+                    amount.with_precision(currency.minor_units)
+                )
+            };
+
+            writeln!(f, "{}:", currency.code)?;
+            for actor in balances.actors.iter() {
+                if actor.balance.is_negative() {
+                    let owed = money(actor.balance.negative_part());
+                    writeln!(f, "\tYou owe '{}' {}", actor.actor.name, owed)?;
+                } else {
+                    let owed = money(actor.balance.positive_part());
+                    writeln!(f, "\t'{}' owes you {}", actor.actor.name, owed)?;
+                }
+            }
+
+            write!(f, "\tSettle up:")?;
+            for payment in balances.settlement.iter() {
+                write!(
+                    f,
+                    "\n\t\t{} pays {} {}",
+                    self.party(payment.from),
+                    self.party(payment.to),
+                    money(payment.amount)
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+pub mod item_tags {
+    use crate::{
+        alias::MaybeAlias,
+        completion::completer,
+        formatted,
+        output::{print_data, IterDisplayExt},
+        prelude::*,
+    };
+    use cream::context::Context;
+    use monee::{
+        backoffice::item_tags::{
+            application::{
+                link_one::Status, merge::Status as MergeStatus, move_one::Status as MoveStatus,
+            },
+            domain::{item_name::ItemName, item_tag::ItemTag, item_tag_node::ItemTagNode},
+        },
+        prelude::*,
+        shared::domain::errors::{DeleteError, UpdateError},
+    };
+    use monee_core::ItemTagId;
+    use std::{collections::HashMap, fmt::Write};
+    use tokio::try_join;
+
+    #[derive(clap::Subcommand)]
+    pub enum ItemTagCommand {
+        #[command(alias = "c")]
+        Create {
+            #[arg(short, long)]
+            name: ItemName,
+        },
+
+        #[command(alias = "ls")]
+        List,
+
+        #[command(alias = "ln")]
+        Link {
+            #[arg(short, long, add = completer::<ItemTagId>())]
+            parent: MaybeAlias<ItemTagId>,
+
+            #[arg(short, long, add = completer::<ItemTagId>())]
+            child: MaybeAlias<ItemTagId>,
+        },
+
+        Unlink {
+            #[arg(short, long, add = completer::<ItemTagId>())]
+            parent: MaybeAlias<ItemTagId>,
+
+            #[arg(short, long, add = completer::<ItemTagId>())]
+            child: MaybeAlias<ItemTagId>,
+        },
+
+        Rename {
+            #[arg(add = completer::<ItemTagId>())]
+            item: MaybeAlias<ItemTagId>,
+
+            name: ItemName,
+        },
+
+        /// Detach a tag from all its parents and place it under another one
+        #[command(alias = "mv")]
+        Move {
+            #[arg(add = completer::<ItemTagId>())]
+            item: MaybeAlias<ItemTagId>,
+
+            #[arg(long, add = completer::<ItemTagId>())]
+            to: MaybeAlias<ItemTagId>,
+        },
+
+        /// Print every tag nested under its parents, tags with several parents are marked with `*`
+        Tree,
+
+        #[command(alias = "rm")]
+        Delete {
+            #[arg(add = completer::<ItemTagId>())]
+            item: MaybeAlias<ItemTagId>,
+        },
+
+        /// Move every event and relation of a tag to another one, then delete it
+        Merge {
+            #[arg(add = completer::<ItemTagId>())]
+            source: MaybeAlias<ItemTagId>,
+
+            #[arg(long = "into", add = completer::<ItemTagId>())]
+            target: MaybeAlias<ItemTagId>,
+        },
+    }
+
+    pub async fn run(ctx: &AppContext, command: ItemTagCommand) -> miette::Result<()> {
+        match command {
+            ItemTagCommand::Create { name } => {
+                let service = ctx
+                    .provide::<monee::backoffice::item_tags::application::create_one::CreateOne>();
+                service.run(ItemTag { name }).await.map_app_err(ctx, |_| {
+                    miette::diagnostic! { "Duplicated item tag name" }.into()
+                })?;
+
+                Ok(())
+            }
+
+            ItemTagCommand::List => {
+                let service =
+                    ctx.provide::<monee::backoffice::item_tags::application::get_all::GetAll>();
+
+                let item_tags = service.run().await.log_err(ctx)?;
                 print_data(item_tags.iter().map(|node| {
                     let parents = if node.parents_name.is_empty() {
                         Either::Left(formatted!(""))
@@ -458,6 +2308,536 @@ pub mod item_tags {
 
                 Ok(())
             }
+
+            ItemTagCommand::Unlink { parent, child } => {
+                let service =
+                    ctx.provide::<monee::backoffice::item_tags::application::unlink::Unlink>();
+
+                let (parent, child) = try_join!(parent.resolve(ctx), child.resolve(ctx))?;
+                service.run(parent, child).await.log_err(ctx)?;
+
+                println!("Unlinked");
+                Ok(())
+            }
+
+            ItemTagCommand::Rename { item, name } => {
+                let service = ctx
+                    .provide::<monee::backoffice::item_tags::application::update_one::UpdateOne>();
+
+                let item_id = item.resolve(ctx).await?;
+                service
+                    .run(item_id, ItemTag { name })
+                    .await
+                    .map_app_err(ctx, |e| match e {
+                        UpdateError::NotFound => miette::diagnostic! { "Item tag not found" }.into(),
+                        UpdateError::AlreadyExists(_) => {
+                            miette::diagnostic! { "Duplicated item tag name" }.into()
+                        }
+                        UpdateError::PrecisionLoss => unreachable!("item tags hold no amounts"),
+                    })
+            }
+
+            ItemTagCommand::Move { item, to } => {
+                let service =
+                    ctx.provide::<monee::backoffice::item_tags::application::move_one::MoveOne>();
+
+                let (item, to) = try_join!(item.resolve(ctx), to.resolve(ctx))?;
+                let status = service.run(item, to).await.log_err(ctx)?;
+
+                match status {
+                    MoveStatus::Moved => {
+                        println!("Moved")
+                    }
+                    MoveStatus::CyclicRelation => {
+                        println!("Cyclic relation")
+                    }
+                    MoveStatus::NotFound(tag) => {
+                        println!("{} not found", tag)
+                    }
+                }
+
+                Ok(())
+            }
+
+            ItemTagCommand::Tree => {
+                let service =
+                    ctx.provide::<monee::backoffice::item_tags::application::get_all::GetAll>();
+
+                let item_tags = service.run().await.log_err(ctx)?;
+                if item_tags.is_empty() {
+                    println!("<None>");
+                } else {
+                    print!("{}", render_tree(&item_tags));
+                }
+
+                Ok(())
+            }
+
+            ItemTagCommand::Delete { item } => {
+                let service = ctx
+                    .provide::<monee::backoffice::item_tags::application::delete_one::DeleteOne>();
+
+                let item_id = item.resolve(ctx).await?;
+                service.run(item_id).await.map_app_err(ctx, |e| match e {
+                    DeleteError::NotFound => miette::diagnostic! { "Item tag not found" }.into(),
+                    DeleteError::Referenced => miette::diagnostic! {
+                        help = "use `monee item merge` to move its events and children to another tag",
+                        "Item tag is still used by some events or contains other tags"
+                    }
+                    .into(),
+                })
+            }
+
+            ItemTagCommand::Merge { source, target } => {
+                let service =
+                    ctx.provide::<monee::backoffice::item_tags::application::merge::Merge>();
+
+                let (source, target) = try_join!(source.resolve(ctx), target.resolve(ctx))?;
+                let status = service.run(source, target).await.log_err(ctx)?;
+
+                match status {
+                    MergeStatus::Merged => {
+                        println!("Merged")
+                    }
+                    MergeStatus::SameTag => {
+                        println!("Cannot merge a tag into itself")
+                    }
+                    MergeStatus::Related => {
+                        println!("Cannot merge a tag into its ancestor or descendant")
+                    }
+                    MergeStatus::NotFound(tag) => {
+                        println!("{} not found", tag)
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn render_tree(nodes: &[ItemTagNode]) -> String {
+        let mut children: HashMap<String, Vec<&ItemTagNode>> = HashMap::new();
+        let mut roots = Vec::new();
+
+        for node in nodes {
+            if node.parents_name.is_empty() {
+                roots.push(node);
+            }
+
+            for parent in node.parents_name.iter() {
+                children.entry(parent.to_string()).or_default().push(node);
+            }
+        }
+
+        let by_name = |a: &&ItemTagNode, b: &&ItemTagNode| {
+            a.tag.name.to_string().cmp(&b.tag.name.to_string())
+        };
+        roots.sort_by(by_name);
+        children.values_mut().for_each(|nodes| nodes.sort_by(by_name));
+
+        let mut out = String::new();
+        for root in roots {
+            write_tree_node(&mut out, &children, root, "", None);
+        }
+
+        out
+    }
+
+    fn write_tree_node(
+        out: &mut String,
+        children: &HashMap<String, Vec<&ItemTagNode>>,
+        node: &ItemTagNode,
+        prefix: &str,
+        is_last: Option<bool>,
+    ) {
+        let (branch, child_prefix) = match is_last {
+            None => ("", prefix.to_owned()),
+            Some(true) => ("└── ", format!("{prefix}    ")),
+            Some(false) => ("├── ", format!("{prefix}│   ")),
+        };
+        let mark = if node.parents_name.len() > 1 { " *" } else { "" };
+        let _ = writeln!(out, "{prefix}{branch}{}{mark}", node.tag.name);
+
+        let Some(node_children) = children.get(&node.tag.name.to_string()) else {
+            return;
+        };
+
+        for (i, child) in node_children.iter().enumerate() {
+            let is_last = i + 1 == node_children.len();
+            write_tree_node(out, children, child, &child_prefix, Some(is_last));
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn node(name: &str, parents: &[&str]) -> ItemTagNode {
+            ItemTagNode {
+                id: ItemTagId::new(),
+                tag: ItemTag {
+                    name: name.parse().unwrap(),
+                },
+                parents_name: parents.iter().map(|p| p.parse().unwrap()).collect(),
+            }
+        }
+
+        #[test]
+        fn renders_shared_children_under_every_parent() {
+            let nodes = vec![
+                node("food", &[]),
+                node("drinks", &[]),
+                node("coffee", &["food", "drinks"]),
+                node("bread", &["food"]),
+            ];
+
+            let expected = "\
+drinks
+└── coffee *
+food
+├── bread
+└── coffee *
+";
+            assert_eq!(render_tree(&nodes), expected);
+        }
+    }
+}
+
+pub mod migrate {
+    use cream::context::Context;
+    use monee::{migrations::application::status::Status, prelude::AppContext};
+
+    #[allow(unused_imports)]
+    use crate::{
+        formatted,
+        prelude::{LogAndErr, MapAppErr},
+    };
+
+    #[derive(clap::Subcommand)]
+    pub enum MigrateCommand {
+        /// Applied and pending database migrations
+        Status,
+        /// Copy the current database into a new SQLite file, use it with `--db sqlite://<path>`
+        #[cfg(feature = "sqlite")]
+        ToSqlite { path: std::path::PathBuf },
+    }
+
+    pub async fn run(ctx: &AppContext, command: MigrateCommand) -> miette::Result<()> {
+        match command {
+            MigrateCommand::Status => {
+                let service = ctx.provide::<Status>();
+                let migrations = service.run().await.log_err(ctx)?;
+
+                crate::output::print_data(migrations.iter().map(|migration| {
+                    let status = match migration.applied_at {
+                        Some(applied_at) => {
+                            format!("applied {}", applied_at.format("%Y-%m-%d %H:%M"))
+                        }
+                        None => "pending".to_owned(),
+                    };
+                    formatted!("{} {} ({})", migration.version, migration.name, status)
+                }));
+
+                Ok(())
+            }
+            #[cfg(feature = "sqlite")]
+            MigrateCommand::ToSqlite { path } => {
+                use monee::migrations::application::export_sqlite::{Error, ExportSqlite};
+
+                let service = ctx.provide::<ExportSqlite>();
+                service.run(&path).await.map_app_err(ctx, |e| match e {
+                    Error::TargetExists => miette::diagnostic! {
+                        "{} already exists, choose a new path", path.display()
+                    }
+                    .into(),
+                    Error::AlreadyExists(table) => miette::diagnostic! {
+                        "Could not copy duplicated {} entries", table
+                    }
+                    .into(),
+                })?;
+
+                println!("Copied into {}", path.display());
+                Ok(())
+            }
+        }
+    }
+}
+
+pub mod ledger {
+    use monee::{
+        ledgers::{
+            application::ledgers::{Error, Ledgers},
+            domain::ledger_name::LedgerName,
+        },
+        prelude::AppError,
+    };
+
+    #[derive(clap::Subcommand)]
+    pub enum LedgerCommand {
+        /// Add a ledger, kept in its own database
+        Create { name: LedgerName },
+        /// Every ledger, the current one marked with *
+        List,
+        /// Use another ledger when `--ledger` is not given
+        Switch { name: LedgerName },
+        /// Delete a ledger and everything in it
+        Delete { name: LedgerName },
+    }
+
+    /// Ledgers are handled before any context is built, so errors can not be logged through it
+    pub async fn run(ledgers: &Ledgers, command: LedgerCommand) -> miette::Result<()> {
+        match command {
+            LedgerCommand::Create { name } => ledgers.create(name).map_err(into_report),
+            LedgerCommand::List => {
+                let list = ledgers.list().map_err(|e| miette::miette!("{}", e))?;
+                crate::output::print_data(list.ledgers.into_iter().map(|ledger| {
+                    let current = if ledger == list.current { " *" } else { "" };
+                    formatted!("{}{}", ledger, current)
+                }));
+
+                Ok(())
+            }
+            LedgerCommand::Switch { name } => ledgers.switch(name).map_err(into_report),
+            LedgerCommand::Delete { name } => ledgers.delete(name).await.map_err(into_report),
+        }
+    }
+
+    pub fn into_report(error: AppError<Error>) -> miette::Report {
+        match error {
+            AppError::App(Error::NotFound(name)) => miette::diagnostic! {
+                help = "see `monee ledger list`",
+                "Ledger {} not found", name
+            }
+            .into(),
+            AppError::App(e) => miette::diagnostic! { "{}", e }.into(),
+            AppError::Infrastructure(e) => miette::miette!("{}", e),
+        }
+    }
+}
+
+pub mod vault {
+    use std::path::PathBuf;
+
+    use monee::{
+        prelude::AppError,
+        vault::application::vault::{Error, Vault},
+    };
+
+    #[derive(clap::Subcommand)]
+    pub enum VaultCommand {
+        /// Encrypt the database with a passphrase, nothing can be read until it is unlocked
+        Lock,
+        /// Decrypt a locked database
+        Unlock,
+        /// Change the passphrase of a locked database
+        Passphrase,
+        /// Write an encrypted copy of the database to a file
+        Backup { file: PathBuf },
+        /// Bring back a backup or a locked database, there must be no database yet
+        Restore { file: PathBuf },
+    }
+
+    /// Handled before any context is built, a locked database can not be opened
+    pub async fn run(vault: &Vault, command: VaultCommand) -> miette::Result<()> {
+        match command {
+            VaultCommand::Lock => {
+                let passphrase = new_passphrase("MONEE_PASSPHRASE")?;
+                vault.lock(&passphrase).await.map_err(into_report)
+            }
+            VaultCommand::Unlock => {
+                let passphrase = passphrase("MONEE_PASSPHRASE", "Passphrase: ")?;
+                vault.unlock(&passphrase).await.map_err(into_report)
+            }
+            VaultCommand::Passphrase => {
+                let current = passphrase("MONEE_PASSPHRASE", "Current passphrase: ")?;
+                let new = new_passphrase("MONEE_NEW_PASSPHRASE")?;
+                vault.change_passphrase(&current, &new).map_err(into_report)
+            }
+            VaultCommand::Backup { file } => {
+                let passphrase = new_passphrase("MONEE_PASSPHRASE")?;
+                vault.backup(&file, &passphrase).await.map_err(into_report)
+            }
+            VaultCommand::Restore { file } => {
+                let passphrase = passphrase("MONEE_PASSPHRASE", "Passphrase: ")?;
+                vault.restore(&file, &passphrase).await.map_err(into_report)
+            }
+        }
+    }
+
+    fn prompt(prompt: &str) -> miette::Result<String> {
+        rpassword::prompt_password(prompt)
+            .map_err(|e| miette::miette!("Could not read the passphrase: {}", e))
+    }
+
+    /// Taken from `var` when set, so scripts do not need a terminal
+    fn passphrase(var: &str, message: &str) -> miette::Result<String> {
+        match std::env::var(var) {
+            Ok(passphrase) => Ok(passphrase),
+            Err(_) => prompt(message),
+        }
+    }
+
+    fn new_passphrase(var: &str) -> miette::Result<String> {
+        let passphrase = match std::env::var(var) {
+            Ok(passphrase) => passphrase,
+            Err(_) => {
+                let passphrase = prompt("New passphrase: ")?;
+                if prompt("Repeat the passphrase: ")? != passphrase {
+                    return Err(miette::miette!("The passphrases do not match"));
+                }
+
+                passphrase
+            }
+        };
+
+        if passphrase.is_empty() {
+            return Err(miette::miette!("The passphrase can not be empty"));
+        }
+
+        Ok(passphrase)
+    }
+
+    pub fn locked() -> miette::Report {
+        miette::diagnostic! {
+            help = "unlock it with `monee vault unlock`",
+            "The database is locked"
+        }
+        .into()
+    }
+
+    fn into_report(error: AppError<Error>) -> miette::Report {
+        match error {
+            AppError::App(Error::Locked) => locked(),
+            AppError::App(e) => miette::diagnostic! { "{}", e }.into(),
+            AppError::Infrastructure(e) => miette::miette!("{}", e),
+        }
+    }
+}
+
+pub mod doctor {
+    use cream::context::Context;
+    use monee::{
+        doctor::{
+            application::doctor::Doctor,
+            domain::finding::{Finding, Reference, Severity},
+        },
+        prelude::AppContext,
+    };
+
+    use crate::{formatted, prelude::LogAndErr};
+
+    #[derive(clap::Args)]
+    pub struct Args {
+        /// Fix what can be fixed without losing data
+        #[arg(long)]
+        fix: bool,
+    }
+
+    pub async fn run(ctx: &AppContext, Args { fix }: Args) -> miette::Result<()> {
+        let service = ctx.provide::<Doctor>();
+        let findings = if fix {
+            service.fix().await.log_err(ctx)?
+        } else {
+            let findings = service.check().await.log_err(ctx)?;
+            findings.into_iter().map(|finding| (finding, false)).collect()
+        };
+
+        let iter = findings.iter().map(|(finding, fixed)| {
+            let status = match (*fixed, finding.fix().is_some()) {
+                (true, _) => " (fixed)",
+                (false, true) => " (fixable with --fix)",
+                (false, false) => "",
+            };
+            formatted!("{}{}", FindingCli(finding), status)
+        });
+        crate::output::print_data(iter);
+
+        Ok(())
+    }
+
+    struct FindingCli<'a>(&'a Finding);
+
+    impl std::fmt::Display for FindingCli<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let FindingCli(finding) = self;
+            let severity = match finding.severity() {
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            };
+            write!(f, "[{}] ", severity)?;
+
+            let debt = |loan: &bool| if *loan { "Loan" } else { "Debt" };
+            match finding {
+                Finding::SnapshotWalletWithoutRow { wallet_id, balance } => write!(
+                    f,
+                    "Wallet {} holds {} in the snapshot but does not exist",
+                    wallet_id, balance
+                ),
+                Finding::WalletWithoutSnapshot { wallet_id, .. } => {
+                    write!(f, "Wallet {} is missing from the snapshot", wallet_id)
+                }
+                Finding::CurrencyMismatch {
+                    wallet_id,
+                    row,
+                    snapshot,
+                } => write!(
+                    f,
+                    "Wallet {} is in currency {} but its snapshot money is in {}",
+                    wallet_id, row, snapshot
+                ),
+                Finding::DebtWithoutActor {
+                    debt_id,
+                    actor_id,
+                    loan,
+                } => write!(
+                    f,
+                    "{} {} is held with actor {} which does not exist",
+                    debt(loan),
+                    debt_id,
+                    actor_id
+                ),
+                Finding::MissingReference {
+                    event_id,
+                    reference,
+                } => {
+                    let (kind, id) = match reference {
+                        Reference::Wallet(id) => ("wallet", id.to_string()),
+                        Reference::Item(id) => ("item", id.to_string()),
+                        Reference::Actor(id) => ("actor", id.to_string()),
+                        Reference::Currency(id) => ("currency", id.to_string()),
+                    };
+                    write!(f, "Event {} points to missing {} {}", event_id, kind, id)
+                }
+                Finding::ReplayFailed { event_id } => write!(
+                    f,
+                    "Events can not be replayed past {}, balances were not compared",
+                    event_id
+                ),
+                Finding::WalletDivergence {
+                    wallet_id,
+                    stored,
+                    replayed,
+                } => write!(
+                    f,
+                    "Wallet {} holds {} but its events add up to {}",
+                    wallet_id, stored, replayed
+                ),
+                Finding::DebtDivergence {
+                    actor_id,
+                    currency_id,
+                    loan,
+                    stored,
+                    replayed,
+                } => write!(
+                    f,
+                    "{} with actor {} in currency {} is {} but its events add up to {}",
+                    debt(loan),
+                    actor_id,
+                    currency_id,
+                    stored,
+                    replayed
+                ),
+            }
         }
     }
 }
@@ -0,0 +1,131 @@
+use std::{ffi::OsStr, fmt::Display};
+
+use clap::CommandFactory;
+use clap_complete::{
+    engine::{ArgValueCompleter, CompletionCandidate},
+    env::Shells,
+};
+use monee::{
+    ledgers::{application::ledgers::Ledgers, domain::ledger_name::LedgerName},
+    shared::{domain::slug::Slug, infrastructure::filesystem},
+    vault::application::vault::Vault,
+};
+
+use crate::alias::AliasedId;
+
+const ID_PREFIX: &str = "id:";
+
+pub fn completer<I>() -> ArgValueCompleter
+where
+    I: AliasedId + Display + 'static,
+{
+    ArgValueCompleter::new(complete::<I>)
+}
+
+/// Completes a `MaybeAlias<I>` argument with the entries stored in the database.
+///
+/// Aliases are offered by default, entries without an alias are offered in their `id:` form.
+/// Once the current value starts with `id:`, every entry is offered in its `id:` form.
+/// Aliases with accents or uppercase letters are offered as their slug, which resolves to
+/// the same entry and is easier to type.
+pub fn complete<I>(current: &OsStr) -> Vec<CompletionCandidate>
+where
+    I: AliasedId + Display,
+{
+    let Some(current) = current.to_str() else {
+        return vec![];
+    };
+
+    let entries = load_entries::<I>().unwrap_or_default();
+    let complete_ids = current.starts_with(ID_PREFIX);
+    let current_slug = Slug::new(current);
+
+    entries
+        .into_iter()
+        .filter_map(|(id, alias)| match alias {
+            Some(alias) if !complete_ids => {
+                let alias = alias.to_string();
+                let slug = Slug::new(&alias);
+
+                if alias.starts_with(current) {
+                    Some(
+                        CompletionCandidate::new(alias)
+                            .help(Some(format!("{}{}", ID_PREFIX, id).into())),
+                    )
+                } else if slug.as_ref().starts_with(current_slug.as_ref()) {
+                    Some(
+                        CompletionCandidate::new(slug.to_string())
+                            .help(Some(format!("{} ({}{})", alias, ID_PREFIX, id).into())),
+                    )
+                } else {
+                    None
+                }
+            }
+            alias => {
+                let value = format!("{}{}", ID_PREFIX, id);
+                value.starts_with(current).then(|| {
+                    CompletionCandidate::new(value)
+                        .help(alias.map(|alias| alias.to_string().into()))
+                })
+            }
+        })
+        .collect()
+}
+
+fn load_entries<I: AliasedId>() -> Option<Vec<(I, Option<I::Alias>)>> {
+    // Completions run before the main runtime is started
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .ok()?;
+
+    let (db_url, ledger) = global_args();
+    let database = crate::load_database(db_url).ok()?;
+    // Unlocking asks for the passphrase, which a completion cannot do
+    if Vault::new(database.clone()).is_locked() {
+        return None;
+    }
+
+    let ledgers = Ledgers::new(filesystem::ledgers_path(), database);
+    runtime.block_on(async {
+        let ctx = crate::open_context(&ledgers, ledger.as_ref(), false)
+            .await
+            .ok()?;
+        I::get_all(&ctx).await.ok()
+    })
+}
+
+/// `--db` and `--ledger` of the command line being completed, which follows `--`
+fn global_args() -> (Option<String>, Option<LedgerName>) {
+    let args = std::env::args_os().skip_while(|arg| arg != "--").skip(1);
+    let Ok(matches) = crate::CliParser::command()
+        .ignore_errors(true)
+        .try_get_matches_from(args)
+    else {
+        return (None, None);
+    };
+
+    (
+        matches.get_one::<String>("db").cloned(),
+        matches.get_one::<LedgerName>("ledger").cloned(),
+    )
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Unsupported shell `{0}`")]
+pub struct UnsupportedShell(String);
+
+/// Writes the script that registers `monee` dynamic completions for `shell`.
+pub fn write_registration(shell: &str) -> Result<(), UnsupportedShell> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(shell)
+        .ok_or_else(|| UnsupportedShell(shell.to_owned()))?;
+
+    let bin = std::env::args().next().unwrap_or_else(|| "monee".to_owned());
+    completer
+        .write_registration("COMPLETE", "monee", &bin, &bin, &mut std::io::stdout())
+        .expect("to write completions registration");
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 mod alias;
+mod completion;
 mod date;
 mod output;
 
@@ -30,8 +31,14 @@ mod error {
             let service = ctx.provide::<monee::shared::application::logging::LogService>();
             service.error(self.0);
 
+            let help = format!(
+                "details were logged to {}",
+                monee::shared::infrastructure::filesystem::logs_dir().display()
+            );
+
             miette::diagnostic! {
-                "Unhandable error, logging error"
+                help = help,
+                "Unhandable error"
             }
             .into()
         }
@@ -72,14 +79,36 @@ mod error {
     }
 }
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use cream::{context::Context, tasks::Shutdown};
-use monee::shared::domain::context::{AppContext, AppContextBuilder};
+use monee::{
+    ledgers::{application::ledgers::Ledgers, domain::ledger_name::LedgerName},
+    shared::{
+        domain::context::{AppContext, AppContextBuilder},
+        infrastructure::{
+            database::config::DatabaseConfig,
+            errors::InfrastructureError,
+            filesystem,
+            logging::{self, LogOptions},
+        },
+    },
+    vault::application::vault::Vault,
+};
 
 mod commands;
 
 #[derive(clap::Parser)]
 struct CliParser {
+    /// Database to use instead of the default one, its scheme picks the engine:
+    /// `file://<path>`, `ws://<host>:<port>`, `mem://` or `sqlite://<path>`
+    #[arg(long, global = true)]
+    pub db: Option<String>,
+
+    /// Ledger to use instead of the current one, see `monee ledger`
+    #[arg(long, global = true)]
+    pub ledger: Option<LedgerName>,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -108,21 +137,104 @@ enum Command {
         command: commands::actor::ActorCommand,
     },
 
+    /// Exchange rates between currencies, used to consolidate reports
+    Rate {
+        #[command(subcommand)]
+        command: commands::rate::RateCommand,
+    },
+
     Item {
         #[command(subcommand)]
         command: commands::item_tags::ItemTagCommand,
     },
+
+    /// Savings goals backed by wallets, see `report goals` for their progress
+    Goal {
+        #[command(subcommand)]
+        command: commands::goal::GoalCommand,
+    },
+
+    Report {
+        #[command(subcommand)]
+        command: commands::report::ReportCommand,
+    },
+
+    /// Database schema migrations, pending ones are applied by any other command
+    Migrate {
+        #[command(subcommand)]
+        command: commands::migrate::MigrateCommand,
+    },
+
+    /// Separate sets of books, e.g. personal and household finances
+    Ledger {
+        #[command(subcommand)]
+        command: commands::ledger::LedgerCommand,
+    },
+
+    /// Encryption of the embedded store while it is not in use, and encrypted backups
+    Vault {
+        #[command(subcommand)]
+        command: commands::vault::VaultCommand,
+    },
+
+    /// Look for inconsistencies between the catalog, the events and the snapshot
+    Doctor(commands::doctor::Args),
+
+    /// Print the script that registers shell completions (bash, zsh, fish, ...)
+    Completions { shell: String },
 }
 
-#[tokio::main]
-async fn main() -> miette::Result<()> {
-    let ctx = AppContextBuilder::default()
-        .build()
-        .await
-        .expect("To build context")
-        .setup();
+fn main() -> miette::Result<()> {
+    CompleteEnv::with_factory(CliParser::command).complete();
 
     let cli = CliParser::parse();
+    if let Command::Completions { shell } = &cli.command {
+        return completion::write_registration(shell).map_err(|e| miette::miette!("{}", e));
+    }
+
+    // stdout is for command output, and a failing log sink should not stop the command
+    let _log_guard = logging::init(LogOptions {
+        dir: Some(filesystem::logs_dir()),
+        stdout: false,
+        json: false,
+    })
+    .ok();
+
+    tokio::runtime::Runtime::new()
+        .expect("To build runtime")
+        .block_on(start(cli))
+}
+
+async fn start(cli: CliParser) -> miette::Result<()> {
+    let database = load_database(cli.db.clone())?;
+
+    let vault = Vault::new(database.clone());
+    let cli = match cli {
+        CliParser {
+            command: Command::Vault { command },
+            ..
+        } => return commands::vault::run(&vault, command).await,
+        cli => cli,
+    };
+
+    if vault.is_locked() {
+        vault.settle().map_err(|e| miette::miette!("{}", e))?;
+        return Err(commands::vault::locked());
+    }
+
+    let ledgers = Ledgers::new(filesystem::ledgers_path(), database);
+    let cli = match cli {
+        CliParser {
+            command: Command::Ledger { command },
+            ..
+        } => return commands::ledger::run(&ledgers, command).await,
+        cli => cli,
+    };
+
+    // Reporting migrations must not apply them first
+    let migrate = !matches!(cli.command, Command::Migrate { .. });
+    let ctx = open_context(&ledgers, cli.ledger.as_ref(), migrate).await?;
+
     run(&ctx, cli).await?;
 
     let shutdown: Shutdown = ctx.provide();
@@ -131,6 +243,53 @@ async fn main() -> miette::Result<()> {
     Ok(())
 }
 
+/// The configured database, or `db_url` when given
+fn load_database(db_url: Option<String>) -> miette::Result<DatabaseConfig> {
+    let mut database = DatabaseConfig::load().map_err(|e| miette::miette!("{}", e))?;
+    if let Some(db_url) = db_url {
+        database.url = db_url;
+    }
+
+    Ok(database)
+}
+
+/// Opens the database of `ledger`, or of the current ledger
+async fn open_context(
+    ledgers: &Ledgers,
+    ledger: Option<&LedgerName>,
+    migrate: bool,
+) -> miette::Result<AppContext> {
+    let database = ledgers
+        .database(ledger)
+        .map_err(commands::ledger::into_report)?;
+
+    let url = database.url.clone();
+    let builder = AppContextBuilder { database, migrate };
+
+    match builder.build().await {
+        Ok(setup) => Ok(setup.setup()),
+        Err(InfrastructureError::Auth) => Err(auth_failed(&url)),
+        Err(e) => Err(miette::miette!(
+            "Could not open the database {}: {}",
+            url,
+            e
+        )),
+    }
+}
+
+fn auth_failed(url: &str) -> miette::Report {
+    let help = format!(
+        "check the [database.credentials] of {} or MONEE_DB_USER and MONEE_DB_PASSWORD",
+        filesystem::config_path().display()
+    );
+
+    miette::diagnostic! {
+        help = help,
+        "Could not sign in to the database at {}", url
+    }
+    .into()
+}
+
 async fn run(ctx: &AppContext, cli: CliParser) -> miette::Result<()> {
     match cli.command {
         Command::Wallet { command } => commands::wallet::run(ctx, command).await,
@@ -139,12 +298,30 @@ async fn run(ctx: &AppContext, cli: CliParser) -> miette::Result<()> {
             command: commands::events::EventCommand::Add { command },
         } => commands::events::run(ctx, command).await,
 
+        Command::Events {
+            command: commands::events::EventCommand::AccrueInterest { until },
+        } => commands::events::accrue_interest(ctx, until).await,
+
         Command::Currency { command } => commands::currency::run(ctx, command).await,
 
         Command::Actor { command } => commands::actor::run(ctx, command).await,
 
+        Command::Rate { command } => commands::rate::run(ctx, command).await,
+
         Command::Show(args) => commands::show::run(ctx, args).await,
 
         Command::Item { command } => commands::item_tags::run(ctx, command).await,
+
+        Command::Goal { command } => commands::goal::run(ctx, command).await,
+
+        Command::Migrate { command } => commands::migrate::run(ctx, command).await,
+
+        Command::Report { command } => commands::report::run(ctx, command).await,
+
+        Command::Doctor(args) => commands::doctor::run(ctx, args).await,
+
+        Command::Completions { .. } => unreachable!("completions are handled before startup"),
+        Command::Ledger { .. } => unreachable!("ledgers are handled before startup"),
+        Command::Vault { .. } => unreachable!("the vault is handled before startup"),
     }
 }
@@ -87,7 +87,6 @@ impl<'a> HostCon<'a> {
     }
 
     pub async fn sync_to_host(&self, data: &NodeChanges) -> Result<(), AppError<ConnectError>> {
-        println!("CALLING API");
         self.http
             .patch(format!("{}/sync", self.info.dir))
             .header("X-Node-Id", self.info.node_app_id.to_string())
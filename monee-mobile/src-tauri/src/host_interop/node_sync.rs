@@ -2,6 +2,7 @@ use std::future::Future;
 
 use cream::{context::FromContext, events::dispatcher::Dispatcher};
 use monee::{
+    host::sync::domain::catalog_removals::Removal,
     nodes::hosts::{
         application::save_host_dir::SetHostBinding,
         domain::{host::host_binding::HostBinding, sync::changes_record::ChangesRecord},
@@ -11,6 +12,7 @@ use monee::{
 use monee_core::{ActorId, CurrencyId, ItemTagId, WalletId};
 use tauri::{async_runtime::Sender, AppHandle, Emitter};
 use tokio::sync::{mpsc, watch};
+use tracing::Instrument;
 
 use crate::{prelude::*, CatchInfra};
 
@@ -35,6 +37,19 @@ impl NodeSyncContext {
         dispatcher.add::<handlers::OnEventAdded>();
         dispatcher.add::<handlers::OnItemCreated>();
 
+        dispatcher.add::<handlers::OnWalletUpdated>();
+        dispatcher.add::<handlers::OnWalletKindChanged>();
+        dispatcher.add::<handlers::OnCurrencyUpdated>();
+        dispatcher.add::<handlers::OnActorUpdated>();
+        dispatcher.add::<handlers::OnItemUpdated>();
+
+        dispatcher.add::<handlers::OnWalletDeleted>();
+        dispatcher.add::<handlers::OnCurrencyDeleted>();
+        dispatcher.add::<handlers::OnActorDeleted>();
+        dispatcher.add::<handlers::OnActorsMerged>();
+        dispatcher.add::<handlers::OnItemDeleted>();
+        dispatcher.add::<handlers::OnItemsMerged>();
+
         (ctx, dispatcher)
     }
 }
@@ -52,12 +67,27 @@ mod handlers {
         context::FromContext,
         events::{Error, Handler},
     };
-    use monee::backoffice::{
-        actors::domain::actor_created::ActorCreated,
-        currencies::domain::currency_created::CurrencyCreated,
-        events::domain::event_added::EventAdded,
-        item_tags::domain::item_tag_created::ItemTagCreated,
-        wallets::domain::wallet_created::WalletCreated,
+    use monee::{
+        backoffice::{
+            actors::domain::{
+                actor_created::ActorCreated, actor_deleted::ActorDeleted,
+                actor_updated::ActorUpdated, actors_merged::ActorsMerged,
+            },
+            currencies::domain::{
+                currency_created::CurrencyCreated, currency_deleted::CurrencyDeleted,
+                currency_updated::CurrencyUpdated,
+            },
+            events::domain::event_added::EventAdded,
+            item_tags::domain::{
+                item_tag_created::ItemTagCreated, item_tag_deleted::ItemTagDeleted,
+                item_tag_updated::ItemTagUpdated, item_tags_merged::ItemTagsMerged,
+            },
+            wallets::domain::{
+                wallet_created::WalletCreated, wallet_deleted::WalletDeleted,
+                wallet_kind_changed::WalletKindChanged, wallet_updated::WalletUpdated,
+            },
+        },
+        host::sync::domain::catalog_removals::Removal,
     };
 
     use super::{DataChangedPort, NodeSyncContext};
@@ -129,6 +159,180 @@ mod handlers {
             Ok(())
         }
     }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnWalletUpdated {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnWalletUpdated {
+        type Event = WalletUpdated;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port.send(super::DataChanged::Wallet(event.id)).await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnWalletKindChanged {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnWalletKindChanged {
+        type Event = WalletKindChanged;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port.send(super::DataChanged::Wallet(event.id)).await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnCurrencyUpdated {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnCurrencyUpdated {
+        type Event = CurrencyUpdated;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port.send(super::DataChanged::Currency(event.id)).await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnActorUpdated {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnActorUpdated {
+        type Event = ActorUpdated;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port.send(super::DataChanged::Actor(event.id)).await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnItemUpdated {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnItemUpdated {
+        type Event = ItemTagUpdated;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port.send(super::DataChanged::Item(event.id)).await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnWalletDeleted {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnWalletDeleted {
+        type Event = WalletDeleted;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port.send(super::DataChanged::WalletRemoved(event.id)).await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnCurrencyDeleted {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnCurrencyDeleted {
+        type Event = CurrencyDeleted;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port.send(super::DataChanged::CurrencyRemoved(event.id)).await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnActorDeleted {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnActorDeleted {
+        type Event = ActorDeleted;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port
+                .send(super::DataChanged::ActorRemoved(Removal {
+                    id: event.id,
+                    merged_into: None,
+                }))
+                .await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnActorsMerged {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnActorsMerged {
+        type Event = ActorsMerged;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port
+                .send(super::DataChanged::ActorRemoved(Removal {
+                    id: event.source,
+                    merged_into: Some(event.target),
+                }))
+                .await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnItemDeleted {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnItemDeleted {
+        type Event = ItemTagDeleted;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port
+                .send(super::DataChanged::ItemRemoved(Removal {
+                    id: event.id,
+                    merged_into: None,
+                }))
+                .await;
+            Ok(())
+        }
+    }
+
+    #[derive(FromContext)]
+    #[context(NodeSyncContext)]
+    pub struct OnItemsMerged {
+        port: DataChangedPort,
+    }
+
+    impl Handler for OnItemsMerged {
+        type Event = ItemTagsMerged;
+        async fn handle(self, event: Self::Event) -> Result<(), Error> {
+            self.port
+                .send(super::DataChanged::ItemRemoved(Removal {
+                    id: event.source,
+                    merged_into: Some(event.target),
+                }))
+                .await;
+            Ok(())
+        }
+    }
 }
 
 pub enum DataChanged {
@@ -136,6 +340,10 @@ pub enum DataChanged {
     Actor(ActorId),
     Wallet(WalletId),
     Item(ItemTagId),
+    CurrencyRemoved(CurrencyId),
+    ActorRemoved(Removal<ActorId>),
+    WalletRemoved(WalletId),
+    ItemRemoved(Removal<ItemTagId>),
     Event,
 }
 
@@ -189,6 +397,22 @@ async fn listen(
                         DataChanged::Actor(id) => changes.actors.push(id),
                         DataChanged::Wallet(id) => changes.wallets.push(id),
                         DataChanged::Item(id) => changes.items.push(id),
+                        DataChanged::CurrencyRemoved(id) => {
+                            changes.currencies.retain(|c| *c != id);
+                            changes.removals.currencies.push(id);
+                        }
+                        DataChanged::ActorRemoved(removal) => {
+                            changes.actors.retain(|a| *a != removal.id);
+                            changes.removals.actors.push(removal);
+                        }
+                        DataChanged::WalletRemoved(id) => {
+                            changes.wallets.retain(|w| *w != id);
+                            changes.removals.wallets.push(id);
+                        }
+                        DataChanged::ItemRemoved(removal) => {
+                            changes.items.retain(|i| *i != removal.id);
+                            changes.removals.items.push(removal);
+                        }
                         // Should save too?
                         DataChanged::Event => {}
                     }
@@ -218,17 +442,17 @@ async fn listen(
         };
 
         let Some(result) = result else {
-            eprintln!("WARNING: sync channel closed");
+            tracing::warn!("sync channel closed");
             break;
         };
 
         let Ok(result) = result else {
-            eprint!("WARNING: skipping sync: {}", file!());
+            tracing::warn!("host binding removed, skipping sync");
             continue;
         };
 
         let Some(host_con) = &host_con else {
-            eprintln!("WARNING: host binding not set");
+            tracing::warn!("host binding not set");
             continue;
         };
 
@@ -245,13 +469,15 @@ async fn listen(
                 ctx.provide();
             let node_changes = get_service.run(sync_guide, &changes).await?;
 
-            dbg!("SYNC TO HOST");
             service.sync_to_host(&node_changes).await?;
-            println!("SYNCED TO HOST");
+            tracing::info!(events = node_changes.events.len(), "synced to host");
 
             sync_from_host(&ctx, &host_context, &host_con).await
         };
-        let order = do_sync(&ctx, confirmer_tx.clone(), sync, &tauri_app).await;
+        let span = tracing::info_span!("sync", node_id = %host_con.node_app_id);
+        let order = do_sync(&ctx, confirmer_tx.clone(), sync, &tauri_app)
+            .instrument(span)
+            .await;
 
         if let ChangesOrder::Clear = order {
             changes = ChangesRecord::default();
@@ -259,7 +485,7 @@ async fn listen(
 
         let changes_saver: monee::nodes::changes::application::save::Save = ctx.provide();
         if let Err(e) = changes_saver.run(&changes).await {
-            eprintln!("WARNING: failed to save changes: {}", e);
+            tracing::warn!(error = %e, "failed to save changes");
         };
     }
 }
@@ -284,7 +510,7 @@ async fn do_sync(
     let (result, order, host_status) = match sync.await.catch_infra(ctx) {
         Ok(Ok(())) => (Ok(()), ChangesOrder::Clear, HostStatus::Online),
         Ok(Err(_)) => {
-            eprintln!("WARNING: Failed to connect host, skipping sync");
+            tracing::warn!("failed to connect host, skipping sync");
             (Ok(()), ChangesOrder::Preserve, HostStatus::Offline)
         }
         Err(e) => (Err(e), ChangesOrder::Preserve, HostStatus::Offline),
@@ -311,7 +537,7 @@ async fn sync_from_host(
         Err(AppError::App(e)) => Err(e),
     };
     if let Err(e) = result {
-        eprintln!("WARNING: Failed to overwrite system: error: {e:?}");
+        tracing::warn!(error = ?e, "failed to overwrite system");
     }
 
     Ok(())
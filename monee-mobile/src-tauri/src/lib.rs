@@ -1,6 +1,16 @@
 use cream::events::multi_dispatch_listener::{MultiDispatchListener, MultiDispatchers};
 use host_interop::{host_context::HostContext, node_sync::NodeSyncContext};
-use monee::shared::domain::context::AppContextBuilder;
+use monee::{
+    ledgers::application::ledgers::Ledgers,
+    shared::{
+        domain::context::AppContextBuilder,
+        infrastructure::{
+            database::{self, config::DatabaseConfig},
+            logging::{self, LogOptions},
+        },
+    },
+    vault::application::vault::Vault,
+};
 use tauri::Manager;
 
 use prelude::*;
@@ -12,8 +22,37 @@ mod prelude;
 
 async fn setup(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let base_dir = app.path().app_data_dir().expect("AppData not found");
-    dbg!(&base_dir);
-    let setup = AppContextBuilder { base_dir }.build().await?;
+    let log_guard = logging::init(LogOptions {
+        dir: Some(base_dir.join("logs")),
+        stdout: true,
+        json: false,
+    })?;
+    app.manage(log_guard);
+    tracing::info!(data_dir = %base_dir.display(), "starting");
+
+    let vault = Vault::new(DatabaseConfig::from_url(database::file_url(&base_dir)));
+    if vault.is_locked() {
+        // Only the vault commands work until it is unlocked and the app restarts
+        vault.settle()?;
+        app.manage(vault);
+        return Ok(());
+    }
+
+    let ledgers = Ledgers::new(
+        base_dir.join("ledgers.toml"),
+        DatabaseConfig::from_url(database::file_url(&base_dir)),
+    );
+    let database = match ledgers.database(None) {
+        Ok(database) => database,
+        Err(AppError::App(e)) => return Err(e.into()),
+        Err(AppError::Infrastructure(e)) => return Err(e.into()),
+    };
+    let setup = AppContextBuilder {
+        database,
+        migrate: true,
+    }
+    .build()
+    .await?;
 
     let host_ctx = HostContext::default();
 
@@ -41,6 +80,8 @@ async fn setup(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     app.manage(host_sync);
     app.manage(ctx);
     app.manage(host_ctx);
+    app.manage(ledgers);
+    app.manage(vault);
 
     Ok(())
 }
@@ -60,7 +101,15 @@ pub fn run() {
             monee_commands::get_all_wallets,
             monee_commands::get_all_actors,
             monee_commands::create_item,
-            monee_commands::create_actor
+            monee_commands::create_actor,
+            monee_commands::list_ledgers,
+            monee_commands::create_ledger,
+            monee_commands::switch_ledger,
+            monee_commands::delete_ledger,
+            monee_commands::is_locked,
+            monee_commands::lock,
+            monee_commands::unlock,
+            monee_commands::change_passphrase
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -38,9 +38,12 @@ use monee::backoffice::{actors::domain::actor::Actor, events::application::add a
 use monee::backoffice::item_tags::application::get_all as get_all_items;
 use monee::backoffice::item_tags::domain::item_tag_node::ItemTagNode;
 
+use monee::ledgers::application::ledgers::{self, Ledgers};
+use monee::ledgers::domain::{ledger_list::LedgerList, ledger_name::LedgerName};
 use monee::nodes::hosts::domain::host::host_dir::HostDir;
 use monee::reports::wallets::application::get_all as get_all_wallets;
 use monee::shared::domain::errors::UniqueSaveError;
+use monee::vault::application::vault::{self, Vault};
 use monee_core::{ActorId, ItemTagId, WalletId};
 
 use monee::backoffice::actors::application::get_all as get_all_actors;
@@ -54,7 +57,7 @@ use monee::backoffice::item_tags::application::create_one as create_item;
 
 write_command!(add_event::Add : add_event( event: add_event::Event ) -> (), MoneeError<add_event::Error>);
 read_command!(get_all_items::GetAll : get_all_items() -> Vec<ItemTagNode>, InternalError);
-read_command!(get_all_wallets::GetAll : get_all_wallets() -> Vec<(WalletId, (get_all_wallets::Wallet, get_all_wallets::Money))>, InternalError);
+read_command!(get_all_wallets::GetAll : get_all_wallets() -> Vec<(WalletId, (get_all_wallets::Wallet, get_all_wallets::WalletMoney))>, InternalError);
 read_command!(get_all_actors::GetAll : get_all_actors() -> Vec<(ActorId, Actor)>, InternalError);
 write_command!(create_item::CreateOne : create_item(item: ItemTag) -> ItemTagId, MoneeError<UniqueSaveError>);
 write_command!(create_actor::CreateOne : create_actor(actor: Actor) -> ActorId, MoneeError<UniqueSaveError>);
@@ -104,3 +107,98 @@ pub async fn is_synced(
 
     Ok(true)
 }
+
+#[tauri::command]
+pub fn list_ledgers(
+    ledgers: tauri::State<'_, Ledgers>,
+    ctx: tauri::State<'_, AppContext>,
+) -> Result<LedgerList, InternalError> {
+    ledgers.list().catch_infra(&ctx)
+}
+
+#[tauri::command]
+pub fn create_ledger(
+    name: LedgerName,
+    ledgers: tauri::State<'_, Ledgers>,
+    ctx: tauri::State<'_, AppContext>,
+) -> Result<(), MoneeError<ledgers::Error>> {
+    ledgers
+        .create(name)
+        .catch_infra(&ctx)?
+        .map_err(MoneeError::App)
+}
+
+/// The context is bound to a single database, so the app restarts to open the new ledger
+#[tauri::command]
+pub fn switch_ledger(
+    name: LedgerName,
+    ledgers: tauri::State<'_, Ledgers>,
+    ctx: tauri::State<'_, AppContext>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), MoneeError<ledgers::Error>> {
+    ledgers
+        .switch(name)
+        .catch_infra(&ctx)?
+        .map_err(MoneeError::App)?;
+    app_handle.restart();
+}
+
+/// The embedded store is already open, so the ledger is dropped through that connection
+#[tauri::command]
+pub async fn delete_ledger(
+    name: LedgerName,
+    ledgers: tauri::State<'_, Ledgers>,
+    ctx: tauri::State<'_, AppContext>,
+) -> Result<(), MoneeError<ledgers::Error>> {
+    let result = match ctx.surreal_connection() {
+        Some(connection) => ledgers.delete_open(name, &connection).await,
+        None => ledgers.delete(name).await,
+    };
+
+    result.catch_infra(&ctx)?.map_err(MoneeError::App)
+}
+
+#[tauri::command]
+pub fn is_locked(vault: tauri::State<'_, Vault>) -> bool {
+    vault.is_locked()
+}
+
+/// The store stays open until the app restarts, which removes the plaintext copy
+#[tauri::command]
+pub async fn lock(
+    passphrase: String,
+    vault: tauri::State<'_, Vault>,
+    ctx: tauri::State<'_, AppContext>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), MoneeError<vault::Error>> {
+    let Some(connection) = ctx.surreal_connection() else {
+        return Err(MoneeError::App(vault::Error::Unsupported));
+    };
+
+    vault
+        .lock_open(&connection, &passphrase)
+        .await
+        .catch_infra(&ctx)?
+        .map_err(MoneeError::App)?;
+    app_handle.restart();
+}
+
+/// There is no context while locked, errors are not logged
+#[tauri::command]
+pub async fn unlock(
+    passphrase: String,
+    vault: tauri::State<'_, Vault>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), MoneeError<vault::Error>> {
+    vault.unlock(&passphrase).await?;
+    app_handle.restart();
+}
+
+#[tauri::command]
+pub fn change_passphrase(
+    current: String,
+    new: String,
+    vault: tauri::State<'_, Vault>,
+) -> Result<(), MoneeError<vault::Error>> {
+    Ok(vault.change_passphrase(&current, &new)?)
+}
@@ -76,6 +76,7 @@ pub fn App() -> impl IntoView {
                     <Route path=path!("/") view=pages::startup::StartUp />
                     <ParentRoute path=path!("/*") view=AppLayout>
                         <Route path=path!("/home") view=pages::home::Home />
+                        <Route path=path!("/lock") view=pages::vault::Lock />
                         <Route path=path!("/events/purchase") view=move || view! { <EventPageForm<PurchaseForm> /> } />
                         <Route path=path!("/events/move-value") view=move || view! { <EventPageForm<MoveValueForm> /> } />
                     </ParentRoute>
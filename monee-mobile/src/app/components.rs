@@ -77,9 +77,9 @@ pub mod fields {
 
         use super::options::create_options;
         use crate::{bind_command, prelude::InternalError};
-        use monee_types::reports::snapshot::snapshot::{Money, Wallet};
+        use monee_types::reports::snapshot::snapshot::{Wallet, WalletMoney};
 
-        bind_command!(get_all_wallets() -> Vec<(WalletId, (Wallet, Money))>, InternalError);
+        bind_command!(get_all_wallets() -> Vec<(WalletId, (Wallet, WalletMoney))>, InternalError);
 
         #[derive(Default, Clone, Copy)]
         pub struct WalletSelectRef(NodeRef<Select>);
@@ -96,7 +96,7 @@ pub mod fields {
         pub fn WalletSelect(#[prop(optional)] node_ref: WalletSelectRef) -> impl IntoView {
             let wallets = LocalResource::new(get_all_wallets);
             let wallets_options = create_options(wallets, |(id, (wallet, money))| {
-                view! { <option value={id.to_string()}>{format!("{}: {} {}{}", wallet.name, money.currency.code, money.currency.symbol, money.amount)}</option> }
+                view! { <option value={id.to_string()}>{format!("{}: {} {}{}", wallet.name, money.currency.code, money.currency.symbol, money.amount.with_precision(money.currency.minor_units))}</option> }
             });
 
             view! {
@@ -1,3 +1,4 @@
 pub mod home;
 pub mod startup;
 pub mod event;
+pub mod vault;
@@ -124,6 +124,7 @@ pub mod purchase {
                     actors: actor_ids.into(),
                     wallet_id,
                     amount,
+                    quantity: None,
                 });
                 Some(event)
             } else {
@@ -221,7 +222,12 @@ pub mod move_value {
             if let (Some(from), Some(to), Some(amount)) =
                 (from_ref.get(), to_ref.get(), amount_input.get())
             {
-                let event = Event::MoveValue(MoveValue { from, to, amount });
+                let event = Event::MoveValue(MoveValue {
+                    from,
+                    to,
+                    amount,
+                    fee: None,
+                });
                 Some(event)
             } else {
                 None
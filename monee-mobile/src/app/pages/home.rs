@@ -1,8 +1,13 @@
-use crate::{prelude::*, tauri_interop::bind_command};
+use crate::{leptos_util::local::action::local_action, prelude::*, tauri_interop::bind_command};
 use leptos::prelude::*;
-use monee_types::reports::snapshot::snapshot::Snapshot;
+use monee_types::{
+    ledgers::{errors::LedgerError, ledger_list::LedgerList, ledger_name::LedgerName},
+    reports::snapshot::snapshot::Snapshot,
+};
 
 bind_command!(get_stats() -> Snapshot, InternalError);
+bind_command!(list_ledgers() -> LedgerList, InternalError);
+bind_command!(switch_ledger(name: LedgerName) -> (), MoneeError<LedgerError>);
 
 struct EventButton {
     name: &'static str,
@@ -29,11 +34,15 @@ pub fn Home() -> impl IntoView {
         <div class="flex flex-col justify-center h-full gap-20">
             <h1 class="text-5xl text-center">"Monee"</h1>
 
+            <LedgerSelect />
+
             <LoadStats />
 
             <ul class="flex flex-wrap gap-4 justify-center">
                 {EVENT_BUTTONS.iter().map(|event| view! { <li><EventButton name=event.name color=event.color href=event.href /></li> } ).collect::<Vec<_>>()}
             </ul>
+
+            <a href="/lock" class="text-center text-slate-300 underline">"Lock"</a>
         </div>
     }
 }
@@ -60,19 +69,19 @@ fn LoadStats() -> impl IntoView {
         let wallets = snapshot
     .wallets
     .iter()
-    .map(|(_, (wallet, money))| view! { <li>{format!("{}: {} {}{}", wallet.name, money.currency.code, money.currency.symbol, money.amount)}</li> })
+    .map(|(_, (wallet, money))| view! { <li>{format!("{}: {} {}{}", wallet.name, money.currency.code, money.currency.symbol, money.amount.with_precision(money.currency.minor_units))}</li> })
     .collect_view();
 
         let debts = snapshot
     .debts
     .iter()
-    .map(|(_, (debt, money))| view! { <li>{format!("{}: {} {}{}", debt.actor.name, money.currency.code, money.currency.symbol, money.amount)}</li> })
+    .map(|(_, (debt, money))| view! { <li>{format!("{}: {} {}{}", debt.actor.name, money.currency.code, money.currency.symbol, money.amount.with_precision(money.currency.minor_units))}</li> })
     .collect_view();
 
         let loan = snapshot
     .loans
     .iter()
-    .map(|(_, (loan, money))| view! { <li>{format!("{}: {} {}{}", loan.actor.name, money.currency.code, money.currency.symbol, money.amount)}</li> })
+    .map(|(_, (loan, money))| view! { <li>{format!("{}: {} {}{}", loan.actor.name, money.currency.code, money.currency.symbol, money.amount.with_precision(money.currency.minor_units))}</li> })
     .collect_view();
 
         view! {
@@ -113,6 +122,48 @@ fn LoadStats() -> impl IntoView {
     }
 }
 
+/// The app restarts on the chosen ledger, so there is nothing to update here
+#[component]
+fn LedgerSelect() -> impl IntoView {
+    let ledgers = LocalResource::new(list_ledgers);
+    let (_, dispatch) =
+        local_action(move |name: LedgerName| async move { switch_ledger(name).await });
+
+    let options = move || {
+        ledgers.with(|state| {
+            state.as_ref().map(|result| match result.as_ref() {
+                Ok(list) => list
+                    .ledgers
+                    .iter()
+                    .map(|ledger| {
+                        view! {
+                            <option value=ledger.to_string() selected=*ledger == list.current>
+                                {ledger.to_string()}
+                            </option>
+                        }
+                    })
+                    .collect_view()
+                    .into_any(),
+                Err(_) => view! { <option>"Error"</option> }.into_any(),
+            })
+        })
+    };
+
+    view! {
+        <select
+            class="bg-slate-800 p-2 self-center"
+            name="ledger"
+            on:change=move |ev| {
+                if let Ok(name) = event_target_value(&ev).parse() {
+                    dispatch.dispatch(name);
+                }
+            }
+        >
+            {options}
+        </select>
+    }
+}
+
 #[component]
 fn EventButton(name: &'static str, color: &'static str, href: &'static str) -> impl IntoView {
     view! {
@@ -2,7 +2,7 @@ use leptos::{ev::SubmitEvent, prelude::*};
 use leptos_router::hooks::use_navigate;
 
 use crate::{
-    app::components::pending::PendingPulse,
+    app::{components::pending::PendingPulse, pages::vault::Unlock},
     app_state::use_host_status,
     bind_command,
     leptos_util::{local::action::local_action, signal::AppWithErr},
@@ -11,9 +11,28 @@ use crate::{
 
 bind_command!(set_host(host_dir: String) -> (), InternalError);
 bind_command!(is_synced() -> bool, InternalError);
+bind_command!(is_locked() -> bool);
 
+/// Nothing but the vault can be reached until the store is unlocked
 #[component]
 pub fn StartUp() -> impl IntoView {
+    let is_locked = LocalResource::new(is_locked);
+
+    move || {
+        is_locked.with(|state| {
+            state.as_ref().map(|locked| {
+                if **locked {
+                    view! { <Unlock /> }.into_any()
+                } else {
+                    view! { <HostCheck /> }.into_any()
+                }
+            })
+        })
+    }
+}
+
+#[component]
+fn HostCheck() -> impl IntoView {
     let host_status = use_host_status();
 
     let navigate = use_navigate();
@@ -0,0 +1,122 @@
+use leptos::{ev::SubmitEvent, prelude::*};
+use monee_types::vault::errors::VaultError;
+
+use crate::{
+    bind_command,
+    leptos_util::{local::action::local_action, signal::AppGetErr},
+    prelude::MoneeError,
+};
+
+bind_command!(lock(passphrase: String) -> (), MoneeError<VaultError>);
+bind_command!(unlock(passphrase: String) -> (), MoneeError<VaultError>);
+
+fn error_message(error: &MoneeError<VaultError>) -> String {
+    match error {
+        MoneeError::App(e) => e.to_string(),
+        MoneeError::Internal(_) => "Something went wrong".to_owned(),
+    }
+}
+
+/// The app restarts with the store unlocked, so there is nothing to navigate to
+#[component]
+pub fn Unlock() -> impl IntoView {
+    let (passphrase, set_passphrase) = signal(String::default());
+    let (unlocked, dispatch) =
+        local_action(move |passphrase: String| async move { unlock(passphrase).await });
+
+    let on_submit = move |e: SubmitEvent| {
+        e.prevent_default();
+        dispatch.dispatch(passphrase.get());
+    };
+
+    let error = move || {
+        unlocked
+            .error()
+            .map(|e| view! { <p class="text-red-600">{error_message(&e)}</p> })
+    };
+
+    view! {
+        <div class="grid place-content-center auto-rows-auto h-full gap-16">
+            <h1 class="text-5xl text-center">"Monee"</h1>
+
+            <form on:submit=on_submit class="flex flex-col gap-2">
+                <label for="passphrase">"The database is locked"</label>
+                <input
+                    id="passphrase"
+                    type="password"
+                    required
+                    class="bg-slate-800 px-2 py-1"
+                    on:input:target=move |e| set_passphrase.set(e.target().value())
+                />
+
+                <button class="bg-blue-500 px-2 py-1">"Unlock"</button>
+            </form>
+
+            {error}
+
+            <Show when=move || unlocked.pending()>
+                <p class="text-slate-300">"Unlocking..."</p>
+            </Show>
+        </div>
+    }
+}
+
+/// Encrypts the store with a new passphrase, the app restarts locked
+#[component]
+pub fn Lock() -> impl IntoView {
+    let (passphrase, set_passphrase) = signal(String::default());
+    let (repeated, set_repeated) = signal(String::default());
+    let (locked, dispatch) =
+        local_action(move |passphrase: String| async move { lock(passphrase).await });
+
+    let on_submit = move |e: SubmitEvent| {
+        e.prevent_default();
+        if passphrase.get() == repeated.get() {
+            dispatch.dispatch(passphrase.get());
+        }
+    };
+
+    let error = move || {
+        locked
+            .error()
+            .map(|e| view! { <p class="text-red-600">{error_message(&e)}</p> })
+    };
+
+    view! {
+        <div class="flex flex-col justify-center h-full gap-8">
+            <h1 class="text-3xl text-center">"Lock"</h1>
+
+            <form on:submit=on_submit class="flex flex-col gap-2">
+                <label for="passphrase">"New passphrase"</label>
+                <input
+                    id="passphrase"
+                    type="password"
+                    required
+                    class="bg-slate-800 px-2 py-1"
+                    on:input:target=move |e| set_passphrase.set(e.target().value())
+                />
+
+                <label for="repeated">"Repeat it"</label>
+                <input
+                    id="repeated"
+                    type="password"
+                    required
+                    class="bg-slate-800 px-2 py-1"
+                    on:input:target=move |e| set_repeated.set(e.target().value())
+                />
+
+                <Show when=move || passphrase.get() != repeated.get()>
+                    <p class="text-red-600">"The passphrases do not match"</p>
+                </Show>
+
+                <button class="bg-blue-500 px-2 py-1">"Lock"</button>
+            </form>
+
+            {error}
+
+            <Show when=move || locked.pending()>
+                <p class="text-slate-300">"Locking..."</p>
+            </Show>
+        </div>
+    }
+}
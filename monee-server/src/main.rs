@@ -3,7 +3,17 @@ use axum::{
     routing::{get, patch, post},
     Router,
 };
-use monee::shared::domain::context::AppContextBuilder;
+use monee::{
+    shared::{
+        domain::context::AppContextBuilder,
+        infrastructure::{
+            database::config::DatabaseConfig,
+            filesystem,
+            logging::{self, LogOptions},
+        },
+    },
+    vault::application::vault::Vault,
+};
 
 mod prelude;
 
@@ -11,6 +21,12 @@ mod prelude;
 struct Args {
     #[clap(short, long)]
     port: Option<u16>,
+    /// Database to serve instead of the default one, e.g. `file://<path>` or `ws://<host>:<port>`
+    #[clap(long)]
+    db: Option<String>,
+    /// Write logs as one JSON object per line, for log collectors
+    #[clap(long)]
+    log_json: bool,
 }
 
 fn main() {
@@ -22,11 +38,32 @@ fn main() {
 }
 
 async fn serve(args: Args) {
-    let ctx = AppContextBuilder::default()
-        .build()
-        .await
-        .expect("To build context")
-        .setup();
+    let _log_guard = logging::init(LogOptions {
+        dir: Some(filesystem::logs_dir()),
+        stdout: true,
+        json: args.log_json,
+    })
+    .expect("To set up logging");
+
+    let mut database = DatabaseConfig::load().expect("To read config");
+    if let Some(db_url) = args.db.clone() {
+        database.url = db_url;
+    }
+
+    let vault = Vault::new(database.clone());
+    if vault.is_locked() {
+        vault.settle().expect("To remove the plaintext store");
+        panic!("The database is locked, unlock it with `monee vault unlock`");
+    }
+
+    let ctx = AppContextBuilder {
+        database,
+        migrate: true,
+    }
+    .build()
+    .await
+    .expect("To build context")
+    .setup();
 
     let app = Router::new()
         .route("/nodes", post(clients::register))
@@ -39,7 +76,7 @@ async fn serve(args: Args) {
     let host = "0.0.0.0";
     let port = args.port.unwrap_or(3000);
 
-    println!("listening on {}:{}", host, port);
+    tracing::info!(host, port, "listening");
 
     // run our app with hyper, listening globally on port 3000
     let listener = tokio::net::TcpListener::bind((host, port))
@@ -66,19 +103,21 @@ mod clients {
     }
 
     #[axum::debug_handler]
+    #[tracing::instrument(skip_all)]
     pub async fn register(
         State(ctx): State<AppContext>,
         payload: Option<Json<ClientReq>>,
     ) -> Result<Json<AppId>, StatusCode> {
-        println!("register");
         let service: host::nodes::application::register_one::RegisterOne = ctx.provide();
         let name = payload.and_then(|p| p.0.name);
 
-        service
+        let id = service
             .run(host::nodes::domain::app_manifest::AppManifest { name })
             .await
-            .catch_infra(&ctx)
-            .map(Json)
+            .catch_infra(&ctx)?;
+        tracing::info!(node_id = %id, "node registered");
+
+        Ok(Json(id))
     }
 }
 
@@ -95,33 +134,36 @@ mod sync {
 
     use crate::prelude::*;
 
+    #[tracing::instrument(skip_all)]
     pub async fn get_sync_guide(
         State(ctx): State<AppContext>,
     ) -> Result<Json<SyncGuide>, StatusCode> {
-        println!("get_sync_guide");
         let service: monee::host::sync::application::get_sync_guide::GetSyncGuide = ctx.provide();
         service.run().await.catch_infra(&ctx).map(Json)
     }
 
+    /// Also records the id in the `node_id` field of the current span
     fn get_client_id(headers: &HeaderMap) -> Result<AppId, StatusCode> {
-        dbg!(headers.get("X-Node-Id"));
-
-        headers
+        let id: AppId = headers
             .get("X-Node-Id")
-            .ok_or(StatusCode::UNAUTHORIZED)?
-            .to_str()
-            .map_err(|_| StatusCode::UNAUTHORIZED)?
-            .parse()
-            .map_err(|_| StatusCode::UNAUTHORIZED)
+            .and_then(|id| id.to_str().ok())
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| {
+                tracing::warn!("missing or invalid X-Node-Id");
+                StatusCode::UNAUTHORIZED
+            })?;
+
+        tracing::Span::current().record("node_id", tracing::field::display(id));
+        Ok(id)
     }
 
     #[axum::debug_handler]
+    #[tracing::instrument(skip_all, fields(node_id))]
     pub async fn do_sync(
         State(ctx): State<AppContext>,
         headers: HeaderMap,
         Json(payload): Json<NodeChanges>,
     ) -> Result<(), StatusCode> {
-        println!("do_sync");
         let id = get_client_id(&headers)?;
         let exists_service: monee::host::nodes::application::exists::Exists = ctx.provide();
         if !exists_service.run(id).await.catch_infra(&ctx)? {
@@ -141,11 +183,11 @@ mod sync {
     }
 
     #[axum::debug_handler]
+    #[tracing::instrument(skip_all, fields(node_id))]
     pub async fn get_host_state(
         State(ctx): State<AppContext>,
         headers: HeaderMap,
     ) -> Result<Json<HostState>, StatusCode> {
-        println!("get_host_state");
         let _ = get_client_id(&headers)?;
         let service: monee::host::sync::application::get_host_state::GetHostState = ctx.provide();
         service.run().await.catch_infra(&ctx).map(Json)
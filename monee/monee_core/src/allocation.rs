@@ -0,0 +1,279 @@
+use crate::{Amount, MinorUnits, RoundingMode};
+
+/// How an amount is divided between its parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shares {
+    /// Same share for every part
+    Equal(usize),
+    /// Parts proportional to their weight
+    Weights(Vec<u64>),
+    /// Parts by percentage, they must add up to 100%
+    Percentages(Vec<Percent>),
+}
+
+impl Shares {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Equal(parts) => *parts,
+            Self::Weights(weights) => weights.len(),
+            Self::Percentages(percentages) => percentages.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn weights(&self) -> Result<Vec<u64>, AllocationError> {
+        let weights = match self {
+            Self::Equal(parts) => vec![1; *parts],
+            Self::Weights(weights) => weights.clone(),
+            Self::Percentages(percentages) => {
+                let total: u64 = percentages.iter().map(|p| p.0 as u64).sum();
+                if total != Percent::HUNDRED.0 as u64 {
+                    return Err(AllocationError::PercentagesNotHundred);
+                }
+
+                percentages.iter().map(|p| p.0 as u64).collect()
+            }
+        };
+
+        if weights.is_empty() {
+            return Err(AllocationError::NoParts);
+        }
+
+        if weights.iter().all(|w| *w == 0) {
+            return Err(AllocationError::ZeroWeights);
+        }
+
+        Ok(weights)
+    }
+}
+
+/// A percentage with up to two decimals, e.g. `33.33`, kept in hundredths of a percent
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub struct Percent(u32);
+
+impl Percent {
+    pub const HUNDRED: Percent = Percent(10_000);
+
+    /// This percentage of `amount`, rounded to `units` decimals
+    pub fn of(self, amount: Amount, units: MinorUnits, mode: RoundingMode) -> Option<Amount> {
+        amount.checked_mul_div(self.0 as u64, Self::HUNDRED.0 as u64, units, mode)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidPercent;
+
+impl std::fmt::Display for InvalidPercent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Must be a percentage between 0 and 100 with up to 2 decimals")
+    }
+}
+
+impl std::error::Error for InvalidPercent {}
+
+impl std::str::FromStr for Percent {
+    type Err = InvalidPercent;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_suffix('%').unwrap_or(s);
+        let (int, decimals) = s.split_once(['.', ',']).unwrap_or((s, ""));
+
+        if decimals.len() > 2 || int.is_empty() && decimals.is_empty() {
+            return Err(InvalidPercent);
+        }
+
+        let int: u32 = match int {
+            "" => 0,
+            int => int.parse().map_err(|_| InvalidPercent)?,
+        };
+        let decimals: u32 = match decimals {
+            "" => 0,
+            decimals => decimals.parse().map_err(|_| InvalidPercent)?,
+        } * 10_u32.pow(2 - decimals.len() as u32);
+
+        let value = int
+            .checked_mul(100)
+            .and_then(|int| int.checked_add(decimals))
+            .filter(|value| *value <= Self::HUNDRED.0)
+            .ok_or(InvalidPercent)?;
+
+        Ok(Self(value))
+    }
+}
+
+impl std::fmt::Display for Percent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0 / 100)?;
+        match self.0 % 100 {
+            0 => Ok(()),
+            decimals if decimals % 10 == 0 => write!(f, ".{}", decimals / 10),
+            decimals => write!(f, ".{:02}", decimals),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AllocationError {
+    NoParts,
+    /// Every weight is zero, there is nothing to be proportional to
+    ZeroWeights,
+    PercentagesNotHundred,
+    /// The amount has more decimals than the currency uses
+    Precision,
+}
+
+impl std::fmt::Display for AllocationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoParts => write!(f, "There are no parts to allocate to"),
+            Self::ZeroWeights => write!(f, "At least one weight must be over zero"),
+            Self::PercentagesNotHundred => write!(f, "Percentages must add up to 100"),
+            Self::Precision => write!(f, "Has more decimals than allowed"),
+        }
+    }
+}
+
+impl std::error::Error for AllocationError {}
+
+/// Splits `amount` by `shares` in steps of the currency's minor unit.
+/// The parts always add up to `amount`: the units left by rounding down go, one each,
+/// to the parts with the largest remainder, earlier parts first on ties
+pub fn allocate(
+    amount: Amount,
+    shares: &Shares,
+    units: MinorUnits,
+) -> Result<Vec<Amount>, AllocationError> {
+    if !amount.fits(units) {
+        return Err(AllocationError::Precision);
+    }
+
+    let weights = shares.weights()?;
+    let step = units.step() as u128;
+    let steps = amount.0 as u128 / step;
+    let total: u128 = weights.iter().map(|w| *w as u128).sum();
+
+    let mut parts: Vec<(u128, u128)> = weights
+        .iter()
+        .map(|w| {
+            let exact = steps * *w as u128;
+            (exact / total, exact % total)
+        })
+        .collect();
+
+    let allocated: u128 = parts.iter().map(|(part, _)| part).sum();
+    let left = (steps - allocated) as usize;
+
+    let mut by_remainder: Vec<usize> = (0..parts.len()).collect();
+    // Stable sort keeps earlier parts first among equal remainders
+    by_remainder.sort_by(|a, b| parts[*b].1.cmp(&parts[*a].1));
+    for i in by_remainder.into_iter().take(left) {
+        parts[i].0 += 1;
+    }
+
+    Ok(parts
+        .into_iter()
+        .map(|(part, _)| Amount((part * step) as u64))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn units(n: u8) -> MinorUnits {
+        MinorUnits::try_from(n).unwrap()
+    }
+
+    fn amounts(values: &[&str]) -> Vec<Amount> {
+        values.iter().map(|v| v.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn splits_equal_giving_remainder_to_first_parts() {
+        let parts = allocate("100".parse().unwrap(), &Shares::Equal(3), units(2));
+        assert_eq!(parts, Ok(amounts(&["33.34", "33.33", "33.33"])));
+    }
+
+    #[test]
+    fn splits_by_weights() {
+        let parts = allocate(
+            "10".parse().unwrap(),
+            &Shares::Weights(vec![1, 2, 0]),
+            units(0),
+        );
+        assert_eq!(parts, Ok(amounts(&["3", "7", "0"])));
+    }
+
+    #[test]
+    fn splits_by_percentages() {
+        let percentages = ["50", "25.5", "24.5"]
+            .iter()
+            .map(|p| p.parse().unwrap())
+            .collect();
+        let parts = allocate(
+            "0.99".parse().unwrap(),
+            &Shares::Percentages(percentages),
+            units(2),
+        );
+        assert_eq!(parts, Ok(amounts(&["0.5", "0.25", "0.24"])));
+    }
+
+    #[test]
+    fn never_loses_minor_units() {
+        let amount: Amount = "1234.57".parse().unwrap();
+        let parts = allocate(amount, &Shares::Weights(vec![3, 5, 7, 11]), units(2)).unwrap();
+
+        let mut total = Amount::default();
+        for part in parts {
+            total += part;
+        }
+        assert_eq!(total, amount);
+    }
+
+    #[test]
+    fn rejects_invalid_shares() {
+        let amount: Amount = "1".parse().unwrap();
+        assert_eq!(
+            allocate(amount, &Shares::Equal(0), units(2)),
+            Err(AllocationError::NoParts)
+        );
+        assert_eq!(
+            allocate(amount, &Shares::Weights(vec![0, 0]), units(2)),
+            Err(AllocationError::ZeroWeights)
+        );
+        assert_eq!(
+            allocate(
+                amount,
+                &Shares::Percentages(vec!["50".parse().unwrap()]),
+                units(2)
+            ),
+            Err(AllocationError::PercentagesNotHundred)
+        );
+        assert_eq!(
+            allocate("1.005".parse().unwrap(), &Shares::Equal(2), units(2)),
+            Err(AllocationError::Precision)
+        );
+    }
+
+    #[test]
+    fn parses_percent() {
+        assert_eq!("33.33".parse::<Percent>().unwrap().to_string(), "33.33");
+        assert_eq!("12.5%".parse::<Percent>().unwrap().to_string(), "12.5");
+        assert_eq!("100".parse(), Ok(Percent::HUNDRED));
+        assert_eq!("100.01".parse::<Percent>(), Err(InvalidPercent));
+        assert_eq!("1.234".parse::<Percent>(), Err(InvalidPercent));
+    }
+
+    #[test]
+    fn takes_percentage_of_amount() {
+        let percent: Percent = "1.5".parse().unwrap();
+        let amount = percent.of("1000.5".parse().unwrap(), units(2), RoundingMode::HalfEven);
+
+        assert_eq!(amount, "15.01".parse().ok());
+    }
+}
@@ -1,15 +1,108 @@
 use std::ops::{AddAssign, Sub, SubAssign};
 
-#[derive(Default, Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
-pub struct Amount(u64);
+use crate::precision::{MinorUnits, RoundingMode, WithPrecision};
+
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct Amount(pub(crate) u64);
 
 impl Amount {
+    /// Decimals kept internally, every currency precision fits in them
+    pub const DECIMALS: u32 = DECIMALS;
+
+    pub const fn checked_add(self, rhs: Amount) -> Option<Amount> {
+        match self.0.checked_add(rhs.0) {
+            Some(amount) => Some(Amount(amount)),
+            None => None,
+        }
+    }
+
     pub const fn checked_sub(self, rhs: Amount) -> Option<Amount> {
         match self.0.checked_sub(rhs.0) {
             Some(amount) => Some(Amount(amount)),
             None => None,
         }
     }
+
+    /// Whether the amount has no more decimals than `units` allows
+    pub const fn fits(self, units: MinorUnits) -> bool {
+        self.0.is_multiple_of(units.step())
+    }
+
+    /// Rounds the amount to `units` decimals, `None` on overflow
+    pub fn checked_round(self, units: MinorUnits, mode: RoundingMode) -> Option<Amount> {
+        self.checked_mul_div(1, 1, units, mode)
+    }
+
+    /// Computes `self * mul / div` rounded to `units` decimals, used by conversions and splits.
+    /// `None` on overflow or when `div` is zero
+    pub fn checked_mul_div(
+        self,
+        mul: u64,
+        div: u64,
+        units: MinorUnits,
+        mode: RoundingMode,
+    ) -> Option<Amount> {
+        let step = units.step() as u128;
+        let numerator = self.0 as u128 * mul as u128;
+        let denominator = (div as u128).checked_mul(step).filter(|d| *d != 0)?;
+
+        let steps = mode.divide(numerator, denominator);
+        let raw = steps.checked_mul(step)?;
+
+        u64::try_from(raw).ok().map(Amount)
+    }
+
+    /// Converts with an exchange `rate`, i.e. `self * rate`, rounded to `units` decimals
+    pub fn checked_mul_rate(
+        self,
+        rate: Amount,
+        units: MinorUnits,
+        mode: RoundingMode,
+    ) -> Option<Amount> {
+        self.checked_mul_div(rate.0, MULTIPLIER, units, mode)
+    }
+
+    /// Converts with the inverse of an exchange `rate`, i.e. `self / rate`
+    pub fn checked_div_rate(
+        self,
+        rate: Amount,
+        units: MinorUnits,
+        mode: RoundingMode,
+    ) -> Option<Amount> {
+        self.checked_mul_div(MULTIPLIER, rate.0, units, mode)
+    }
+
+    /// Reads an amount stored with `decimals` decimals, as done before precision was raised
+    pub fn checked_rescale(self, decimals: u32) -> Option<Amount> {
+        let factor = 10_u64.checked_pow(DECIMALS.checked_sub(decimals)?)?;
+        self.0.checked_mul(factor).map(Amount)
+    }
+
+    /// Displays exactly `units` decimals, or more if the amount does not fit them
+    pub const fn with_precision(self, units: MinorUnits) -> WithPrecision {
+        WithPrecision::new(self.0, false, units)
+    }
+
+    /// Parses an amount rejecting more decimals than `units` allows
+    pub fn parse_with(s: &str, units: MinorUnits) -> Result<Amount, from_str::Error> {
+        let amount: Amount = s.parse()?;
+        if !amount.fits(units) {
+            return Err(from_str::Error::MaxDecimal);
+        }
+
+        Ok(amount)
+    }
 }
 
 impl SubAssign for Amount {
@@ -54,8 +147,10 @@ impl std::fmt::Display for Amount {
     }
 }
 
-const DECIMALS: u32 = 4;
-const MULTIPLIER: u64 = 10_u64.pow(DECIMALS);
+/// Decimals kept internally, the highest precision a currency may use. It bounds amounts to
+/// `u64::MAX / 10^8`, about 184 billion, and balances to `i64::MAX / 10^8`, about 92 billion
+pub(crate) const DECIMALS: u32 = 8;
+pub(crate) const MULTIPLIER: u64 = 10_u64.pow(DECIMALS);
 
 pub mod from_str {
     use std::str::FromStr;
@@ -64,7 +159,7 @@ pub mod from_str {
 
     #[derive(Debug, PartialEq)]
     pub enum Error {
-        /// Has more than 4 decimals
+        /// Has more decimals than allowed
         MaxDecimal,
         /// Contains too many commas or dots
         InvalidDecimal,
@@ -77,7 +172,7 @@ pub mod from_str {
     impl std::fmt::Display for Error {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
-                Self::MaxDecimal => write!(f, "Has more than 4 decimals"),
+                Self::MaxDecimal => write!(f, "Has more decimals than allowed"),
                 Self::InvalidDecimal => write!(f, "Contains too many commas or dots"),
                 Self::TooBig => write!(f, "Is over u64::MAX / MULTIPLIER"),
                 Self::InvalidNumber => write!(f, "Is not a number"),
@@ -133,7 +228,7 @@ mod tests {
 
     #[test]
     fn display_normal() {
-        assert_eq!(Amount(1234567).to_string(), "123.4567");
+        assert_eq!(Amount(123_4567_0000).to_string(), "123.4567");
     }
 
     #[test]
@@ -143,26 +238,112 @@ mod tests {
 
     #[test]
     fn donot_display_decimal() {
-        assert_eq!(Amount(10000).to_string(), "1");
+        assert_eq!(Amount(1_0000_0000).to_string(), "1");
     }
 
     #[test]
     fn donot_display_trailing() {
-        assert_eq!(Amount(1234500).to_string(), "123.45");
+        assert_eq!(Amount(123_4500_0000).to_string(), "123.45");
     }
 
     #[test]
     fn displays_intermidiat_zeros() {
-        assert_eq!(Amount(1230045).to_string(), "123.0045");
+        assert_eq!(Amount(123_0045_0000).to_string(), "123.0045");
     }
 
     #[test]
     fn from_str() {
-        assert_eq!(Amount(1234567), "123.4567".parse().unwrap());
+        assert_eq!(Amount(123_4567_0000), "123.4567".parse().unwrap());
     }
 
     #[test]
     fn from_str_21_90() {
-        assert_eq!("21.90".parse::<Amount>(), Ok(Amount(219000)));
+        assert_eq!("21.90".parse::<Amount>(), Ok(Amount(21_9000_0000)));
+    }
+
+    fn units(n: u8) -> MinorUnits {
+        MinorUnits::try_from(n).unwrap()
+    }
+
+    #[test]
+    fn from_str_8_decimals() {
+        assert_eq!("0.00000001".parse::<Amount>(), Ok(Amount(1)));
+        assert_eq!(
+            "0.000000001".parse::<Amount>(),
+            Err(from_str::Error::MaxDecimal)
+        );
+    }
+
+    #[test]
+    fn parse_with_rejects_extra_decimals() {
+        assert_eq!(
+            Amount::parse_with("150.5", units(0)),
+            Err(from_str::Error::MaxDecimal)
+        );
+        assert_eq!(Amount::parse_with("150", units(0)), "150".parse());
+    }
+
+    #[test]
+    fn displays_with_precision() {
+        let amount: Amount = "21.9".parse().unwrap();
+
+        assert_eq!(amount.with_precision(units(2)).to_string(), "21.90");
+        assert_eq!(amount.with_precision(units(0)).to_string(), "21.9");
+    }
+
+    #[test]
+    fn rounds_half_even() {
+        let round = |s: &str| {
+            s.parse::<Amount>()
+                .unwrap()
+                .checked_round(units(0), RoundingMode::HalfEven)
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(round("2.5"), "2");
+        assert_eq!(round("3.5"), "4");
+        assert_eq!(round("3.51"), "4");
+    }
+
+    #[test]
+    fn rounds_down() {
+        let amount: Amount = "10".parse().unwrap();
+        let third = amount
+            .checked_mul_div(1, 3, units(2), RoundingMode::Down)
+            .unwrap();
+
+        assert_eq!(third.to_string(), "3.33");
+    }
+
+    #[test]
+    fn rounds_up() {
+        let amount: Amount = "10".parse().unwrap();
+        let third = amount
+            .checked_mul_div(1, 3, units(2), RoundingMode::Up)
+            .unwrap();
+
+        assert_eq!(third.to_string(), "3.34");
+        assert_eq!(amount.checked_round(units(0), RoundingMode::Up), Some(amount));
+    }
+
+    #[test]
+    fn rescales_legacy_amounts() {
+        assert_eq!(Amount(219000).checked_rescale(4), "21.9".parse().ok());
+    }
+
+    #[test]
+    fn converts_with_rates() {
+        let amount: Amount = "100".parse().unwrap();
+        let rate: Amount = "3.756".parse().unwrap();
+
+        assert_eq!(
+            amount.checked_mul_rate(rate, units(2), RoundingMode::HalfEven),
+            "375.6".parse().ok()
+        );
+        assert_eq!(
+            amount.checked_div_rate(rate, units(2), RoundingMode::HalfEven),
+            "26.62".parse().ok()
+        );
     }
 }
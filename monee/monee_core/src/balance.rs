@@ -0,0 +1,154 @@
+use crate::{precision::WithPrecision, Amount, MinorUnits};
+
+/// Signed counterpart of [`Amount`], used by wallets that can go below zero.
+/// Shares the same fixed point scale as [`Amount`].
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct Balance(i64);
+
+impl Balance {
+    pub const ZERO: Balance = Balance(0);
+
+    pub fn checked_add(self, amount: Amount) -> Option<Balance> {
+        let amount = i64::try_from(amount.0).ok()?;
+        self.0.checked_add(amount).map(Balance)
+    }
+
+    pub fn checked_sub(self, amount: Amount) -> Option<Balance> {
+        let amount = i64::try_from(amount.0).ok()?;
+        self.0.checked_sub(amount).map(Balance)
+    }
+
+    pub fn saturating_add(self, amount: Amount) -> Balance {
+        let amount = i64::try_from(amount.0).unwrap_or(i64::MAX);
+        Balance(self.0.saturating_add(amount))
+    }
+
+    pub fn saturating_sub(self, amount: Amount) -> Balance {
+        let amount = i64::try_from(amount.0).unwrap_or(i64::MAX);
+        Balance(self.0.saturating_sub(amount))
+    }
+
+    /// How much `self` is over `rhs`, negative when it is under
+    pub fn checked_diff(self, rhs: Balance) -> Option<Balance> {
+        self.0.checked_sub(rhs.0).map(Balance)
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub const fn unsigned_abs(self) -> Amount {
+        Amount(self.0.unsigned_abs())
+    }
+
+    /// Reads a balance stored with `decimals` decimals, see [`Amount::checked_rescale`]
+    pub fn checked_rescale(self, decimals: u32) -> Option<Balance> {
+        let amount = self.unsigned_abs().checked_rescale(decimals)?;
+        let value = i64::try_from(amount.0).ok()?;
+
+        Some(Balance(if self.is_negative() { -value } else { value }))
+    }
+
+    pub const fn with_precision(self, units: MinorUnits) -> WithPrecision {
+        WithPrecision::new(self.0.unsigned_abs(), self.is_negative(), units)
+    }
+
+    /// The money that is actually held, zero when the balance is negative
+    pub const fn positive_part(self) -> Amount {
+        if self.is_negative() {
+            Amount(0)
+        } else {
+            self.unsigned_abs()
+        }
+    }
+
+    /// The money that is owed, zero when the balance is not negative
+    pub const fn negative_part(self) -> Amount {
+        if self.is_negative() {
+            self.unsigned_abs()
+        } else {
+            Amount(0)
+        }
+    }
+}
+
+impl TryFrom<Amount> for Balance {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(amount: Amount) -> Result<Self, Self::Error> {
+        i64::try_from(amount.0).map(Balance)
+    }
+}
+
+impl std::fmt::Display for Balance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_negative() {
+            write!(f, "-")?;
+        }
+
+        self.unsigned_abs().fmt(f)
+    }
+}
+
+impl std::str::FromStr for Balance {
+    type Err = crate::amount::from_str::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+
+        let amount: Amount = s.parse()?;
+        let value = i64::try_from(amount.0).map_err(|_| Self::Err::TooBig)?;
+
+        Ok(Balance(if negative { -value } else { value }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goes_below_zero() {
+        let balance = Balance::ZERO.checked_sub(Amount(15000)).unwrap();
+
+        assert!(balance.is_negative());
+        assert_eq!(balance.negative_part(), Amount(15000));
+        assert_eq!(balance.positive_part(), Amount(0));
+    }
+
+    #[test]
+    fn display_negative() {
+        assert_eq!(Balance(-123_4500_0000).to_string(), "-123.45");
+    }
+
+    #[test]
+    fn rejects_amounts_over_i64() {
+        assert_eq!(Balance::ZERO.checked_add(Amount(u64::MAX)), None);
+    }
+
+    #[test]
+    fn parses_negative() {
+        assert_eq!("-123.45".parse(), Ok(Balance(-123_4500_0000)));
+        assert_eq!(
+            "1203.4"
+                .parse::<Balance>()
+                .unwrap()
+                .checked_diff("1210".parse().unwrap()),
+            "-6.6".parse().ok()
+        );
+    }
+}
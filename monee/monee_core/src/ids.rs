@@ -65,3 +65,8 @@ crate::ids::impl_id!(ActorId, Id);
 pub struct EventId(Id);
 
 crate::ids::impl_id!(EventId, Id);
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct GoalId(Id);
+
+crate::ids::impl_id!(GoalId, Id);
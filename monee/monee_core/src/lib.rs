@@ -1,6 +1,12 @@
 mod amount;
 
-pub use amount::Amount;
+pub use amount::{from_str as amount_from_str, Amount};
+mod balance;
+pub use balance::Balance;
+mod allocation;
+pub use allocation::{allocate, AllocationError, InvalidPercent, Percent, Shares};
+mod precision;
+pub use precision::{InvalidMinorUnits, MinorUnits, RoundingMode, ToPrecision, WithPrecision};
 mod ids;
 pub use ids::*;
 
@@ -0,0 +1,166 @@
+use crate::amount::{DECIMALS, MULTIPLIER};
+
+/// Decimals a currency uses, e.g. 2 for USD, 0 for JPY and 8 for BTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct MinorUnits(u8);
+
+impl MinorUnits {
+    pub const MAX: u8 = DECIMALS as u8;
+
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+
+    /// Smallest representable step, in internal units
+    pub(crate) const fn step(self) -> u64 {
+        10_u64.pow(DECIMALS - self.0 as u32)
+    }
+}
+
+impl Default for MinorUnits {
+    fn default() -> Self {
+        Self(2)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidMinorUnits;
+
+impl std::fmt::Display for InvalidMinorUnits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Minor units must be between 0 and {}", MinorUnits::MAX)
+    }
+}
+
+impl std::error::Error for InvalidMinorUnits {}
+
+impl TryFrom<u8> for MinorUnits {
+    type Error = InvalidMinorUnits;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value > Self::MAX {
+            return Err(InvalidMinorUnits);
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl From<MinorUnits> for u8 {
+    fn from(value: MinorUnits) -> Self {
+        value.0
+    }
+}
+
+impl std::str::FromStr for MinorUnits {
+    type Err = InvalidMinorUnits;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<u8>().map_err(|_| InvalidMinorUnits)?.try_into()
+    }
+}
+
+impl std::fmt::Display for MinorUnits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// How to drop the decimals a currency cannot hold
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Rounds to the nearest value, ties go to the even one
+    #[default]
+    HalfEven,
+    /// Truncates the extra decimals
+    Down,
+    /// Rounds any extra decimal up, so the result never falls short
+    Up,
+}
+
+impl RoundingMode {
+    pub(crate) const fn divide(self, numerator: u128, denominator: u128) -> u128 {
+        let quotient = numerator / denominator;
+        let remainder = numerator % denominator;
+
+        match self {
+            Self::Down => quotient,
+            Self::Up => {
+                if remainder == 0 {
+                    quotient
+                } else {
+                    quotient + 1
+                }
+            }
+            Self::HalfEven => {
+                let twice = remainder * 2;
+                if twice > denominator || (twice == denominator && quotient % 2 == 1) {
+                    quotient + 1
+                } else {
+                    quotient
+                }
+            }
+        }
+    }
+}
+
+/// Money values that can be shown with their currency precision
+pub trait ToPrecision: Copy {
+    fn with_precision(self, units: MinorUnits) -> WithPrecision;
+}
+
+impl ToPrecision for crate::Amount {
+    fn with_precision(self, units: MinorUnits) -> WithPrecision {
+        crate::Amount::with_precision(self, units)
+    }
+}
+
+impl ToPrecision for crate::Balance {
+    fn with_precision(self, units: MinorUnits) -> WithPrecision {
+        crate::Balance::with_precision(self, units)
+    }
+}
+
+/// Displays an amount with a fixed number of decimals, see [`crate::Amount::with_precision`]
+pub struct WithPrecision {
+    raw: u64,
+    negative: bool,
+    units: MinorUnits,
+}
+
+impl WithPrecision {
+    pub(crate) const fn new(raw: u64, negative: bool, units: MinorUnits) -> Self {
+        Self {
+            raw,
+            negative,
+            units,
+        }
+    }
+}
+
+impl std::fmt::Display for WithPrecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+
+        write!(f, "{}", self.raw / MULTIPLIER)?;
+
+        let decimals = format!(
+            "{:0width$}",
+            self.raw % MULTIPLIER,
+            width = DECIMALS as usize
+        );
+        // Never hide digits, even if the amount does not fit the precision
+        let significant = decimals.trim_end_matches('0').len();
+        let len = significant.max(self.units.get() as usize);
+
+        if len > 0 {
+            write!(f, ".{}", &decimals[..len])?;
+        }
+
+        Ok(())
+    }
+}
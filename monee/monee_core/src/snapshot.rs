@@ -1,30 +1,56 @@
 use money::MoneyHost;
 use serde::{Deserialize, Serialize};
 
-use crate::{ActorId, Amount, CurrencyId, DebtId, WalletId};
+use crate::{ActorId, Amount, Balance, CurrencyId, DebtId, WalletId};
 
 pub use money::{Money, MoneyError, MoneyMap};
+pub use wallet_kind::{CreditUsage, WalletKind};
 
 mod money;
+mod wallet_kind;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Wallet {
-    #[serde(flatten)]
-    pub money: Money,
-}
-
-impl AsMut<Money> for Wallet {
-    fn as_mut(&mut self) -> &mut Money {
-        &mut self.money
-    }
+    #[serde(rename = "amount")]
+    pub balance: Balance,
+    pub currency_id: CurrencyId,
+    #[serde(default)]
+    pub kind: WalletKind,
 }
 
 impl MoneyHost for Wallet {
     type Key = WalletId;
-    type Data = ();
+    type Data = WalletKind;
 
-    fn create(money: Money, _: Self::Data) -> Self {
-        Self { money }
+    fn create(currency_id: CurrencyId, kind: Self::Data) -> Self {
+        Self {
+            balance: Balance::ZERO,
+            currency_id,
+            kind,
+        }
+    }
+
+    fn deposit(&mut self, amount: Amount) -> Result<(), MoneyError> {
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or(MoneyError::CannotAdd)?;
+
+        Ok(())
+    }
+
+    fn deduct(&mut self, amount: Amount) -> Result<(), MoneyError> {
+        let balance = self
+            .balance
+            .checked_sub(amount)
+            .ok_or(MoneyError::CannotSub)?;
+
+        if !self.kind.allows(balance) {
+            return Err(MoneyError::CannotSub);
+        }
+
+        self.balance = balance;
+        Ok(())
     }
 }
 
@@ -35,22 +61,25 @@ pub struct Debt {
     pub actor_id: ActorId,
 }
 
-impl AsMut<Money> for Debt {
-    fn as_mut(&mut self) -> &mut Money {
-        &mut self.money
-    }
-}
-
 impl MoneyHost for Debt {
     type Key = DebtId;
     type Data = ActorId;
 
-    fn create(money: Money, data: Self::Data) -> Self {
+    fn create(currency_id: CurrencyId, data: Self::Data) -> Self {
         Self {
-            money,
+            money: Money::new(currency_id),
             actor_id: data,
         }
     }
+
+    fn deposit(&mut self, amount: Amount) -> Result<(), MoneyError> {
+        self.money.deposit(amount);
+        Ok(())
+    }
+
+    fn deduct(&mut self, amount: Amount) -> Result<(), MoneyError> {
+        self.money.deduct(amount)
+    }
 }
 
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
@@ -68,6 +97,13 @@ pub enum WalletOperation {
     Create {
         wallet_id: WalletId,
         currency_id: CurrencyId,
+        #[serde(default)]
+        kind: WalletKind,
+    },
+    /// Changes how far below zero the wallet may go, the current balance is kept as is
+    ChangeKind {
+        wallet_id: WalletId,
+        kind: WalletKind,
     },
     /// Does not handle transference before deletion
     Delete {
@@ -127,6 +163,7 @@ impl std::fmt::Display for Error {
             match e {
                 MoneyError::NotFound => write!(f, "debt not found"),
                 MoneyError::CannotSub => write!(f, "debt amortize overflow"),
+                MoneyError::CannotAdd => write!(f, "debt accumulate overflow"),
                 MoneyError::AlreadyExists => write!(f, "debt already exists"),
             }
         }
@@ -134,7 +171,10 @@ impl std::fmt::Display for Error {
         match self {
             Error::Wallet(e) => match e {
                 MoneyError::NotFound => write!(f, "wallet not found"),
-                MoneyError::CannotSub => write!(f, "cannot deduct from wallet"),
+                MoneyError::CannotSub => {
+                    write!(f, "cannot deduct from wallet, not enough funds or credit")
+                }
+                MoneyError::CannotAdd => write!(f, "wallet balance overflow"),
                 MoneyError::AlreadyExists => write!(f, "wallet already exists"),
             },
             Error::Debt(e) => {
@@ -175,7 +215,17 @@ impl Snapshot {
                     WalletOperation::Create {
                         wallet_id,
                         currency_id,
-                    } => self.wallets.create(wallet_id, currency_id, ()),
+                        kind,
+                    } => self.wallets.create(wallet_id, currency_id, kind),
+                    WalletOperation::ChangeKind { wallet_id, kind } => {
+                        match self.wallets.get_mut(&wallet_id) {
+                            Some(wallet) => {
+                                wallet.kind = kind;
+                                Ok(())
+                            }
+                            None => Err(MoneyError::NotFound),
+                        }
+                    }
                     WalletOperation::Deposit { wallet_id, amount } => {
                         self.wallets.add(wallet_id, amount)
                     }
@@ -195,6 +245,33 @@ impl Snapshot {
             }
         }
     }
+
+    /// Moves every debt and loan held with `from` to `to`
+    pub fn replace_actor(&mut self, from: ActorId, to: ActorId) {
+        self.debts
+            .values_mut()
+            .chain(self.loans.values_mut())
+            .filter(|debt| debt.actor_id == from)
+            .for_each(|debt| debt.actor_id = to);
+    }
+
+    pub fn references_actor(&self, actor_id: ActorId) -> bool {
+        self.debts
+            .iter()
+            .chain(self.loans.iter())
+            .any(|(_, debt)| debt.actor_id == actor_id)
+    }
+
+    pub fn references_currency(&self, currency_id: CurrencyId) -> bool {
+        let wallets = self.wallets.iter().map(|(_, wallet)| wallet.currency_id);
+        let debts = self
+            .debts
+            .iter()
+            .chain(self.loans.iter())
+            .map(|(_, debt)| debt.money.currency_id);
+
+        wallets.chain(debts).any(|id| id == currency_id)
+    }
 }
 
 #[cfg(test)]
@@ -217,4 +294,86 @@ mod test {
 
         assert_eq!(snapshot.debts.len(), 1);
     }
+
+    #[test]
+    fn it_replaces_actor() {
+        let mut snapshot = Snapshot::default();
+        let debt_id = DebtId::new();
+        let loan_id = DebtId::new();
+        let currency_id = CurrencyId::new();
+        let from = ActorId::new();
+        let to = ActorId::new();
+
+        snapshot
+            .apply(Operation::Debt(DebtOperation::Incur {
+                debt_id,
+                currency_id,
+                actor_id: from,
+            }))
+            .unwrap();
+        snapshot
+            .apply(Operation::Loan(DebtOperation::Incur {
+                debt_id: loan_id,
+                currency_id,
+                actor_id: from,
+            }))
+            .unwrap();
+
+        snapshot.replace_actor(from, to);
+
+        assert!(!snapshot.references_actor(from));
+        assert_eq!(snapshot.debts.get(&debt_id).unwrap().actor_id, to);
+        assert_eq!(snapshot.loans.get(&loan_id).unwrap().actor_id, to);
+    }
+
+    fn snapshot_with_wallet(wallet_id: WalletId, kind: WalletKind) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        snapshot
+            .apply(Operation::Wallet(WalletOperation::Create {
+                wallet_id,
+                currency_id: CurrencyId::new(),
+                kind,
+            }))
+            .unwrap();
+
+        snapshot
+    }
+
+    #[test]
+    fn debit_wallet_cannot_go_negative() {
+        let wallet_id = WalletId::new();
+        let mut snapshot = snapshot_with_wallet(wallet_id, WalletKind::Debit);
+
+        let result = snapshot.apply(Operation::Wallet(WalletOperation::Deduct {
+            wallet_id,
+            amount: "1".parse().unwrap(),
+        }));
+
+        assert!(matches!(result, Err(Error::Wallet(MoneyError::CannotSub))));
+    }
+
+    #[test]
+    fn credit_wallet_goes_negative_down_to_limit() {
+        let wallet_id = WalletId::new();
+        let kind = WalletKind::CreditCard {
+            credit_limit: Some("100".parse().unwrap()),
+        };
+        let mut snapshot = snapshot_with_wallet(wallet_id, kind);
+
+        snapshot
+            .apply(Operation::Wallet(WalletOperation::Deduct {
+                wallet_id,
+                amount: "100".parse().unwrap(),
+            }))
+            .unwrap();
+
+        let wallet = snapshot.wallets.get(&wallet_id).unwrap();
+        assert_eq!(wallet.balance.to_string(), "-100");
+
+        let result = snapshot.apply(Operation::Wallet(WalletOperation::Deduct {
+            wallet_id,
+            amount: "0.01".parse().unwrap(),
+        }));
+        assert!(matches!(result, Err(Error::Wallet(MoneyError::CannotSub))));
+    }
 }
@@ -18,11 +18,37 @@ impl<M: MoneyHost> Default for MoneyMap<M> {
     }
 }
 
-pub trait MoneyHost: AsMut<Money> {
+pub trait MoneyHost {
     type Key: Hash + Eq + serde::Serialize + serde::de::DeserializeOwned;
     type Data: serde::Serialize + serde::de::DeserializeOwned;
 
-    fn create(money: Money, data: Self::Data) -> Self;
+    fn create(currency_id: CurrencyId, data: Self::Data) -> Self;
+
+    fn deposit(&mut self, amount: Amount) -> Result<(), MoneyError>;
+
+    fn deduct(&mut self, amount: Amount) -> Result<(), MoneyError>;
+}
+
+impl Money {
+    pub(crate) fn new(currency_id: CurrencyId) -> Self {
+        Self {
+            amount: Amount::default(),
+            currency_id,
+        }
+    }
+
+    pub(crate) fn deposit(&mut self, amount: Amount) {
+        self.amount += amount;
+    }
+
+    pub(crate) fn deduct(&mut self, amount: Amount) -> Result<(), MoneyError> {
+        self.amount = self
+            .amount
+            .checked_sub(amount)
+            .ok_or(MoneyError::CannotSub)?;
+
+        Ok(())
+    }
 }
 
 impl<M: MoneyHost> MoneyMap<M> {
@@ -36,36 +62,19 @@ impl<M: MoneyHost> MoneyMap<M> {
             return Err(MoneyError::AlreadyExists);
         }
 
-        self.0.insert(
-            key,
-            M::create(
-                Money {
-                    amount: Amount::default(),
-                    currency_id,
-                },
-                data,
-            ),
-        );
+        self.0.insert(key, M::create(currency_id, data));
 
         Ok(())
     }
 
     pub(crate) fn add(&mut self, key: M::Key, amount: Amount) -> Result<(), MoneyError> {
         let money = self.0.get_mut(&key).ok_or(MoneyError::NotFound)?;
-        money.as_mut().amount += amount;
-
-        Ok(())
+        money.deposit(amount)
     }
 
     pub(crate) fn sub(&mut self, key: M::Key, amount: Amount) -> Result<(), MoneyError> {
         let money = self.0.get_mut(&key).ok_or(MoneyError::NotFound)?;
-        let stored_amount = &mut money.as_mut().amount;
-        let result = stored_amount
-            .checked_sub(amount)
-            .ok_or(MoneyError::CannotSub)?;
-        *stored_amount = result;
-
-        Ok(())
+        money.deduct(amount)
     }
 
     pub(crate) fn remove(&mut self, key: M::Key) -> Result<(), MoneyError> {
@@ -102,6 +111,14 @@ impl<M: MoneyHost> MoneyMap<M> {
     pub fn iter(&self) -> std::collections::hash_map::Iter<'_, M::Key, M> {
         self.0.iter()
     }
+
+    pub(crate) fn get_mut(&mut self, k: &M::Key) -> Option<&mut M> {
+        self.0.get_mut(k)
+    }
+
+    pub(crate) fn values_mut(&mut self) -> std::collections::hash_map::ValuesMut<'_, M::Key, M> {
+        self.0.values_mut()
+    }
 }
 
 impl<M: MoneyHost> IntoIterator for MoneyMap<M> {
@@ -118,5 +135,6 @@ impl<M: MoneyHost> IntoIterator for MoneyMap<M> {
 pub enum MoneyError {
     NotFound,
     CannotSub,
+    CannotAdd,
     AlreadyExists,
 }
@@ -0,0 +1,110 @@
+use crate::{Amount, Balance};
+
+/// How far below zero a wallet may go
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletKind {
+    /// Holds only what was deposited
+    #[default]
+    Debit,
+    /// A bank account that may go below zero, without a limit when `credit_limit` is `None`
+    Overdraft { credit_limit: Option<Amount> },
+    CreditCard { credit_limit: Option<Amount> },
+}
+
+impl WalletKind {
+    pub fn credit_limit(&self) -> Option<Amount> {
+        match self {
+            Self::Debit => None,
+            Self::Overdraft { credit_limit } | Self::CreditCard { credit_limit } => *credit_limit,
+        }
+    }
+
+    pub fn is_credit(&self) -> bool {
+        !matches!(self, Self::Debit)
+    }
+
+    /// Whether the wallet may hold `balance`
+    pub fn allows(&self, balance: Balance) -> bool {
+        match self {
+            Self::Debit => !balance.is_negative(),
+            _ => self
+                .credit_limit()
+                .is_none_or(|limit| balance.negative_part() <= limit),
+        }
+    }
+
+    /// Reads a kind stored with `decimals` decimals, see [`Amount::checked_rescale`]
+    pub fn checked_rescale(self, decimals: u32) -> Option<Self> {
+        let rescale = |limit: Option<Amount>| match limit {
+            Some(limit) => limit.checked_rescale(decimals).map(Some),
+            None => Some(None),
+        };
+
+        Some(match self {
+            Self::Debit => Self::Debit,
+            Self::Overdraft { credit_limit } => Self::Overdraft {
+                credit_limit: rescale(credit_limit)?,
+            },
+            Self::CreditCard { credit_limit } => Self::CreditCard {
+                credit_limit: rescale(credit_limit)?,
+            },
+        })
+    }
+
+    /// How much credit is taken, `None` for debit wallets
+    pub fn credit_usage(&self, balance: Balance) -> Option<CreditUsage> {
+        if !self.is_credit() {
+            return None;
+        }
+
+        Some(CreditUsage {
+            used: balance.negative_part(),
+            limit: self.credit_limit(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CreditUsage {
+    pub used: Amount,
+    pub limit: Option<Amount>,
+}
+
+impl CreditUsage {
+    /// Credit left before reaching the limit, `None` when there is no limit
+    pub fn available(&self) -> Option<Amount> {
+        self.limit
+            .map(|limit| limit.checked_sub(self.used).unwrap_or_default())
+    }
+
+    /// Percentage of the limit in use, `None` when there is no limit
+    pub fn utilization(&self) -> Option<f64> {
+        let limit = self.limit.filter(|limit| limit.0 != 0)?;
+        Some(self.used.0 as f64 * 100.0 / limit.0 as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credit_usage_reports_available_and_utilization() {
+        let kind = WalletKind::CreditCard {
+            credit_limit: Some("200".parse().unwrap()),
+        };
+        let balance = Balance::ZERO.checked_sub("50".parse().unwrap()).unwrap();
+
+        let usage = kind.credit_usage(balance).unwrap();
+
+        assert_eq!(usage.used, "50".parse().unwrap());
+        assert_eq!(usage.available(), Some("150".parse().unwrap()));
+        assert_eq!(usage.utilization(), Some(25.0));
+    }
+
+    #[test]
+    fn debit_wallets_have_no_credit_usage() {
+        assert_eq!(WalletKind::Debit.credit_usage(Balance::ZERO), None);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod actors;
 pub mod currencies;
+pub mod debts;
 pub mod events;
+pub mod exchange_rates;
+pub mod goals;
 pub mod item_tags;
 pub mod wallets;
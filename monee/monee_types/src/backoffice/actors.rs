@@ -32,7 +32,10 @@ pub mod actor_name {
 pub mod actor_alias {
     use std::{fmt::Display, str::FromStr};
 
-    use crate::shared::alias::{from_str::Error, Alias};
+    use crate::shared::{
+        alias::{from_str::Error, Alias},
+        slug::Slug,
+    };
 
     #[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq, Eq)]
     pub struct ActorAlias(Alias);
@@ -43,6 +46,12 @@ pub mod actor_alias {
         }
     }
 
+    impl ActorAlias {
+        pub fn slug(&self) -> Slug {
+            self.0.slug()
+        }
+    }
+
     impl FromStr for ActorAlias {
         type Err = Error;
 
@@ -111,3 +120,61 @@ pub mod actor_created {
         }
     }
 }
+
+pub mod actor_updated {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct ActorUpdated {
+        pub id: monee_core::ActorId,
+    }
+
+    impl DomainEvent for ActorUpdated {
+        fn name(&self) -> &'static str {
+            "backoffice.actors.updated"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
+
+pub mod actor_deleted {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct ActorDeleted {
+        pub id: monee_core::ActorId,
+    }
+
+    impl DomainEvent for ActorDeleted {
+        fn name(&self) -> &'static str {
+            "backoffice.actors.deleted"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
+
+pub mod actors_merged {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct ActorsMerged {
+        pub source: monee_core::ActorId,
+        pub target: monee_core::ActorId,
+    }
+
+    impl DomainEvent for ActorsMerged {
+        fn name(&self) -> &'static str {
+            "backoffice.actors.merged"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod currency {
+    use monee_core::MinorUnits;
+
     use super::{
         currency_code::CurrencyCode, currency_name::CurrencyName, currency_symbol::CurrencySymbol,
     };
@@ -8,6 +10,14 @@ pub mod currency {
         pub name: CurrencyName,
         pub symbol: CurrencySymbol,
         pub code: CurrencyCode,
+        #[serde(default = "legacy_minor_units")]
+        pub minor_units: MinorUnits,
+    }
+
+    /// Amounts used to be stored with 4 decimals for every currency,
+    /// currencies created back then keep them so no amount is truncated
+    fn legacy_minor_units() -> MinorUnits {
+        MinorUnits::try_from(4).expect("4 to be valid minor units")
     }
 }
 
@@ -193,3 +203,41 @@ pub mod currency_created {
         }
     }
 }
+
+pub mod currency_updated {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct CurrencyUpdated {
+        pub id: monee_core::CurrencyId,
+    }
+
+    impl DomainEvent for CurrencyUpdated {
+        fn name(&self) -> &'static str {
+            "backoffice.currencies.updated"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
+
+pub mod currency_deleted {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct CurrencyDeleted {
+        pub id: monee_core::CurrencyId,
+    }
+
+    impl DomainEvent for CurrencyDeleted {
+        fn name(&self) -> &'static str {
+            "backoffice.currencies.deleted"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
@@ -0,0 +1,548 @@
+pub mod period {
+    use std::{fmt::Display, str::FromStr};
+
+    use chrono::{Days, Months};
+
+    use crate::shared::date::Date;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum Period {
+        Daily,
+        Weekly,
+        Monthly,
+        Yearly,
+    }
+
+    impl Period {
+        /// The date `n` periods after `date`, months keep the day when they have it
+        pub fn after(self, date: Date, n: u32) -> Option<Date> {
+            match self {
+                Self::Daily => date.checked_add_days(Days::new(n as u64)),
+                Self::Weekly => date.checked_add_days(Days::new(n as u64 * 7)),
+                Self::Monthly => date.checked_add_months(Months::new(n)),
+                Self::Yearly => date.checked_add_months(Months::new(n.checked_mul(12)?)),
+            }
+        }
+
+        /// Whole periods from `from` to `to`, zero when `to` is not after `from`
+        pub fn elapsed(self, from: Date, to: Date) -> u32 {
+            let mut n = 0;
+            while self.after(from, n + 1).is_some_and(|date| date <= to) {
+                n += 1;
+            }
+
+            n
+        }
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("Period must be one of daily, weekly, monthly or yearly")]
+    pub struct InvalidPeriod;
+
+    impl FromStr for Period {
+        type Err = InvalidPeriod;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "daily" => Ok(Self::Daily),
+                "weekly" => Ok(Self::Weekly),
+                "monthly" => Ok(Self::Monthly),
+                "yearly" => Ok(Self::Yearly),
+                _ => Err(InvalidPeriod),
+            }
+        }
+    }
+
+    impl Display for Period {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let period = match self {
+                Self::Daily => "daily",
+                Self::Weekly => "weekly",
+                Self::Monthly => "monthly",
+                Self::Yearly => "yearly",
+            };
+            write!(f, "{}", period)
+        }
+    }
+}
+
+pub mod debt_terms {
+    use monee_core::{Amount, MinorUnits, Percent, RoundingMode};
+
+    use crate::shared::date::Date;
+
+    use super::period::Period;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum InterestKind {
+        /// Interest over the balance only
+        Simple,
+        /// Interest over the balance plus the interest already accrued
+        Compound,
+    }
+
+    /// Interest charged every whole `period`, partial periods do not accrue
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    pub struct Interest {
+        pub rate: Percent,
+        pub kind: InterestKind,
+        pub period: Period,
+    }
+
+    /// Equal principal parts due every `period` from `first_due` on
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    pub struct InstallmentPlan {
+        pub count: u32,
+        pub period: Period,
+        pub first_due: Date,
+    }
+
+    #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    pub struct DebtTerms {
+        #[serde(default)]
+        pub interest: Option<Interest>,
+        #[serde(default)]
+        pub installments: Option<InstallmentPlan>,
+    }
+
+    impl Interest {
+        /// Interest of `balance` over `periods` whole periods, rounded to `units` decimals
+        pub fn accrue(&self, balance: Amount, periods: u32, units: MinorUnits) -> Option<Amount> {
+            let mode = RoundingMode::HalfEven;
+
+            match self.kind {
+                InterestKind::Simple => self.rate.of(balance, units, mode)?.checked_mul_div(
+                    periods as u64,
+                    1,
+                    units,
+                    mode,
+                ),
+                InterestKind::Compound => {
+                    let mut total = balance;
+                    for _ in 0..periods {
+                        total = total.checked_add(self.rate.of(total, units, mode)?)?;
+                    }
+
+                    total.checked_sub(balance)
+                }
+            }
+        }
+
+        /// Interest of `balance` for the whole periods from `since` up to `until`,
+        /// with the date it was accrued until. `None` when no whole period went by
+        pub fn accrue_until(
+            &self,
+            balance: Amount,
+            since: Date,
+            until: Date,
+            units: MinorUnits,
+        ) -> Option<(Amount, Date)> {
+            let periods = self.period.elapsed(since, until);
+            if periods == 0 {
+                return None;
+            }
+
+            let amount = self.accrue(balance, periods, units)?;
+            let accrued_until = self.period.after(since, periods)?;
+
+            Some((amount, accrued_until))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn interest(rate: &str, kind: InterestKind) -> Interest {
+            Interest {
+                rate: rate.parse().unwrap(),
+                kind,
+                period: Period::Monthly,
+            }
+        }
+
+        #[test]
+        fn accrues_simple_and_compound() {
+            let balance: Amount = "1000".parse().unwrap();
+            let units = MinorUnits::default();
+
+            let simple = interest("10", InterestKind::Simple).accrue(balance, 2, units);
+            let compound = interest("10", InterestKind::Compound).accrue(balance, 2, units);
+
+            assert_eq!(simple, "200".parse().ok());
+            assert_eq!(compound, "210".parse().ok());
+        }
+
+        #[test]
+        fn accrues_whole_periods_only() {
+            let date = |s: &str| s.parse::<Date>().unwrap();
+            let interest = interest("1", InterestKind::Simple);
+
+            let accrued = interest.accrue_until(
+                "100".parse().unwrap(),
+                date("2024-01-31"),
+                date("2024-04-15"),
+                MinorUnits::default(),
+            );
+
+            // Jan 31 + 2 months is Mar 31, Apr 30 is yet to come
+            assert_eq!(accrued, Some(("2".parse().unwrap(), date("2024-03-31"))));
+        }
+    }
+}
+
+pub mod amortization {
+    use monee_core::{allocate, AllocationError, Amount, MinorUnits, Shares};
+
+    use crate::shared::date::Date;
+
+    use super::debt_terms::{InstallmentPlan, Interest};
+
+    #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct Installment {
+        pub number: u32,
+        pub due: Date,
+        pub principal: Amount,
+        pub interest: Amount,
+        pub payment: Amount,
+        /// Principal left once the installment is paid
+        pub remaining: Amount,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum Error {
+        Allocation(AllocationError),
+        /// A due date or interest does not fit
+        Overflow,
+    }
+
+    /// Installments paying back `principal`, lent on `start`, in equal principal parts.
+    /// Each installment also pays the interest accrued on what is left since the last one
+    pub fn amortization_table(
+        principal: Amount,
+        start: Date,
+        interest: Option<&Interest>,
+        plan: &InstallmentPlan,
+        units: MinorUnits,
+    ) -> Result<Vec<Installment>, Error> {
+        let parts = allocate(principal, &Shares::Equal(plan.count as usize), units)
+            .map_err(Error::Allocation)?;
+
+        let mut remaining = principal;
+        let mut accrued_until = start;
+        let mut installments = Vec::with_capacity(parts.len());
+
+        for (i, part) in (0..).zip(parts) {
+            let due = plan
+                .period
+                .after(plan.first_due, i)
+                .ok_or(Error::Overflow)?;
+
+            let interest = match interest {
+                Some(interest) => {
+                    match interest.accrue_until(remaining, accrued_until, due, units) {
+                        Some((amount, until)) => {
+                            accrued_until = until;
+                            amount
+                        }
+                        None => Amount::default(),
+                    }
+                }
+                None => Amount::default(),
+            };
+
+            remaining = remaining.checked_sub(part).ok_or(Error::Overflow)?;
+            installments.push(Installment {
+                number: i + 1,
+                due,
+                principal: part,
+                interest,
+                payment: part.checked_add(interest).ok_or(Error::Overflow)?,
+                remaining,
+            });
+        }
+
+        Ok(installments)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::backoffice::debts::{debt_terms::InterestKind, period::Period};
+
+        use super::*;
+
+        #[test]
+        fn pays_principal_in_equal_parts_with_interest() {
+            let date = |s: &str| s.parse::<Date>().unwrap();
+            let plan = InstallmentPlan {
+                count: 3,
+                period: Period::Monthly,
+                first_due: date("2024-02-01"),
+            };
+            let interest = Interest {
+                rate: "1".parse().unwrap(),
+                kind: InterestKind::Simple,
+                period: Period::Monthly,
+            };
+
+            let table = amortization_table(
+                "300".parse().unwrap(),
+                date("2024-01-01"),
+                Some(&interest),
+                &plan,
+                MinorUnits::default(),
+            )
+            .unwrap();
+
+            let payments: Vec<_> = table.iter().map(|i| i.payment.to_string()).collect();
+            assert_eq!(payments, ["103", "102", "101"]);
+            assert_eq!(table[2].due, date("2024-04-01"));
+            assert_eq!(table[2].remaining, Amount::default());
+        }
+    }
+}
+
+pub mod registry {
+    use monee_core::{Amount, CurrencyId, MinorUnits};
+
+    use crate::{
+        backoffice::events::event::{DebtRegister, Event, InterestAccrual},
+        host::sync::node_changes::EventEntry,
+        shared::date::Date,
+    };
+
+    use super::{
+        amortization::{self, Installment},
+        debt_terms::InterestKind,
+    };
+
+    /// A debt or loan registered with a known id
+    pub struct Registered<'a> {
+        pub register: &'a DebtRegister,
+        pub is_loan: bool,
+        pub start: Date,
+        /// Interest was accrued up to this date, `start` when it never was
+        pub accrued_until: Date,
+        /// Interest accrued so far
+        pub accrued: Amount,
+    }
+
+    impl Registered<'_> {
+        /// Installments of the registered amount, `None` when it has no installment plan
+        pub fn amortization_table(
+            &self,
+            units: MinorUnits,
+        ) -> Option<Result<Vec<Installment>, amortization::Error>> {
+            let terms = self.register.terms.as_ref()?;
+            let plan = terms.installments.as_ref()?;
+
+            Some(amortization::amortization_table(
+                self.register.amount,
+                self.start,
+                terms.interest.as_ref(),
+                plan,
+                units,
+            ))
+        }
+    }
+
+    /// Debts and loans from `entries` with a known id, in the order they were registered
+    pub fn registered<'a>(
+        entries: impl IntoIterator<Item = &'a EventEntry>,
+    ) -> Vec<Registered<'a>> {
+        let mut registered: Vec<Registered> = Vec::new();
+
+        for entry in entries {
+            match &entry.event {
+                Event::RegisterDebt(register) | Event::RegisterLoan(register)
+                    if register.debt_id.is_some() =>
+                {
+                    let start = entry.created_at.date_naive();
+                    registered.push(Registered {
+                        register,
+                        is_loan: matches!(entry.event, Event::RegisterLoan(_)),
+                        start,
+                        accrued_until: start,
+                        accrued: Amount::default(),
+                    });
+                }
+                Event::AccrueDebtInterest(accrual) | Event::AccrueLoanInterest(accrual) => {
+                    let found = registered
+                        .iter_mut()
+                        .find(|debt| debt.register.debt_id == Some(accrual.debt_id));
+
+                    if let Some(debt) = found {
+                        debt.accrued_until = debt.accrued_until.max(accrual.until);
+                        debt.accrued = debt
+                            .accrued
+                            .checked_add(accrual.amount)
+                            .unwrap_or(debt.accrued);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        registered
+    }
+
+    /// Interest accrued by every debt and loan still owed in `snapshot` from the last accrual
+    /// up to `until`. Compound interest is charged on what is owed, simple interest on what is
+    /// owed but the interest accrued before
+    pub fn accruals(
+        registered: &[Registered],
+        snapshot: &monee_core::Snapshot,
+        until: Date,
+        units_of: impl Fn(CurrencyId) -> MinorUnits,
+    ) -> Vec<Event> {
+        registered
+            .iter()
+            .filter_map(|debt| {
+                let interest = debt.register.terms.as_ref()?.interest.as_ref()?;
+                let debt_id = debt.register.debt_id?;
+
+                let hosts = if debt.is_loan {
+                    &snapshot.loans
+                } else {
+                    &snapshot.debts
+                };
+                let owed = hosts.get(&debt_id)?.money.amount;
+                if owed == Amount::default() {
+                    return None;
+                }
+
+                let base = match interest.kind {
+                    InterestKind::Simple => owed.checked_sub(debt.accrued).unwrap_or_default(),
+                    InterestKind::Compound => owed,
+                };
+                if base == Amount::default() {
+                    return None;
+                }
+
+                let units = units_of(debt.register.currency_id);
+                let (amount, until) =
+                    interest.accrue_until(base, debt.accrued_until, until, units)?;
+
+                let accrual = InterestAccrual {
+                    debt_id,
+                    amount,
+                    until,
+                };
+
+                Some(if debt.is_loan {
+                    Event::AccrueLoanInterest(accrual)
+                } else {
+                    Event::AccrueDebtInterest(accrual)
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use monee_core::{ActorId, DebtId, EventId};
+
+        use crate::backoffice::{
+            debts::{
+                debt_terms::{DebtTerms, Interest},
+                period::Period,
+            },
+            events::apply_event::apply_event,
+        };
+
+        use super::*;
+
+        fn entry(event: Event, created_at: &str) -> EventEntry {
+            EventEntry {
+                id: EventId::default(),
+                event,
+                created_at: created_at.parse().unwrap(),
+            }
+        }
+
+        fn simple_loan(debt_id: DebtId) -> Event {
+            Event::RegisterLoan(DebtRegister {
+                amount: "100".parse().unwrap(),
+                currency_id: CurrencyId::new(),
+                actor_id: ActorId::new(),
+                payment_promise: None,
+                wallet_id: None,
+                debt_id: Some(debt_id),
+                terms: Some(DebtTerms {
+                    interest: Some(Interest {
+                        rate: "1".parse().unwrap(),
+                        kind: InterestKind::Simple,
+                        period: Period::Monthly,
+                    }),
+                    installments: None,
+                }),
+            })
+        }
+
+        #[test]
+        fn accrues_from_last_accrual() {
+            let debt_id = DebtId::new();
+            let loan = simple_loan(debt_id);
+
+            let mut snapshot = monee_core::Snapshot::default();
+            assert!(apply_event(&mut snapshot, &loan).is_ok());
+
+            let mut entries = vec![entry(loan, "2024-01-01T10:00:00Z")];
+            let until = "2024-03-15".parse().unwrap();
+            let units = |_| MinorUnits::default();
+
+            let pending = accruals(&registered(&entries), &snapshot, until, units);
+            assert!(matches!(
+                pending.as_slice(),
+                [Event::AccrueLoanInterest(accrual)]
+                    if accrual.amount.to_string() == "2" && accrual.until.to_string() == "2024-03-01"
+            ));
+
+            for event in pending {
+                assert!(apply_event(&mut snapshot, &event).is_ok());
+                entries.push(entry(event, "2024-03-15T10:00:00Z"));
+            }
+
+            assert!(accruals(&registered(&entries), &snapshot, until, units).is_empty());
+        }
+
+        #[test]
+        fn simple_interest_accrues_on_principal_left() {
+            let debt_id = DebtId::new();
+            let loan = simple_loan(debt_id);
+            let accrual = Event::AccrueLoanInterest(InterestAccrual {
+                debt_id,
+                amount: "1".parse().unwrap(),
+                until: "2024-02-01".parse().unwrap(),
+            });
+
+            let mut snapshot = monee_core::Snapshot::default();
+            assert!(apply_event(&mut snapshot, &loan).is_ok());
+            assert!(apply_event(&mut snapshot, &accrual).is_ok());
+            let payment = monee_core::DebtOperation::Amortize {
+                debt_id,
+                amount: "50".parse().unwrap(),
+            };
+            assert!(snapshot.apply(monee_core::Operation::Loan(payment)).is_ok());
+
+            let entries = [
+                entry(loan, "2024-01-01T10:00:00Z"),
+                entry(accrual, "2024-02-01T10:00:00Z"),
+            ];
+            let until = "2024-03-15".parse().unwrap();
+
+            // 51 owed, 1 of them interest
+            let pending = accruals(&registered(&entries), &snapshot, until, |_| {
+                MinorUnits::default()
+            });
+            assert!(matches!(
+                pending.as_slice(),
+                [Event::AccrueLoanInterest(accrual)]
+                    if accrual.amount == "0.5".parse().unwrap()
+                        && accrual.until.to_string() == "2024-03-01"
+            ));
+        }
+    }
+}
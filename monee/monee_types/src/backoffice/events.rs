@@ -1,7 +1,72 @@
+pub mod quantity {
+    use std::{fmt::Display, str::FromStr};
+
+    use monee_core::Amount;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+    pub enum Unit {
+        #[serde(rename = "kg")]
+        Kilogram,
+        #[serde(rename = "l")]
+        Liter,
+        #[serde(rename = "unit")]
+        Unit,
+    }
+
+    /// How much of an item was bought
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct Quantity {
+        pub value: Amount,
+        pub unit: Unit,
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("Unit must be one of kg, l or unit")]
+    pub struct InvalidUnit;
+
+    impl FromStr for Unit {
+        type Err = InvalidUnit;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "kg" => Ok(Self::Kilogram),
+                "l" => Ok(Self::Liter),
+                "unit" => Ok(Self::Unit),
+                _ => Err(InvalidUnit),
+            }
+        }
+    }
+
+    impl Display for Unit {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let unit = match self {
+                Self::Kilogram => "kg",
+                Self::Liter => "l",
+                Self::Unit => "unit",
+            };
+            write!(f, "{}", unit)
+        }
+    }
+
+    impl Display for Quantity {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} {}", self.value, self.unit)
+        }
+    }
+}
+
 pub mod event {
-    use monee_core::{ActorId, Amount, CurrencyId, ItemTagId, WalletId};
+    use monee_core::{
+        ActorId, Amount, Balance, CurrencyId, DebtId, EventId, ItemTagId, MinorUnits, RoundingMode,
+        WalletId,
+    };
+
+    use crate::{
+        backoffice::debts::debt_terms::DebtTerms,
+        shared::date::{Date, Datetime},
+    };
 
-    use crate::shared::date::Datetime;
+    use super::quantity::Quantity;
 
     #[derive(serde::Serialize, serde::Deserialize)]
     pub struct DebtRegister {
@@ -9,6 +74,22 @@ pub mod event {
         pub currency_id: CurrencyId,
         pub actor_id: ActorId,
         pub payment_promise: Option<Datetime>,
+        /// Wallet the lent money left from, or the borrowed money went into
+        #[serde(default)]
+        pub wallet_id: Option<WalletId>,
+        /// Id the debt gets in the snapshot, a new one on every replay when not given
+        #[serde(default)]
+        pub debt_id: Option<DebtId>,
+        #[serde(default)]
+        pub terms: Option<DebtTerms>,
+    }
+
+    /// Interest added to a debt or loan for the whole periods up to `until`
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct InterestAccrual {
+        pub debt_id: DebtId,
+        pub amount: Amount,
+        pub until: Date,
     }
 
     #[derive(serde::Serialize, serde::Deserialize)]
@@ -17,13 +98,48 @@ pub mod event {
         pub actors: Box<[ActorId]>,
         pub wallet_id: WalletId,
         pub amount: Amount,
+        /// How much was bought, purchases with it are tracked in unit price reports
+        #[serde(default)]
+        pub quantity: Option<Quantity>,
+    }
+
+    impl Purchase {
+        /// Amount paid per unit rounded to `units` decimals, `None` without a non zero quantity
+        pub fn unit_price(&self, units: MinorUnits) -> Option<Amount> {
+            let quantity = self.quantity?;
+            self.amount
+                .checked_div_rate(quantity.value, units, RoundingMode::HalfEven)
+        }
+    }
+
+    /// Money given back for a purchase, netted against its item in spending reports
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct Refund {
+        /// Purchase being refunded, refunds can not add up to more than it
+        pub purchase_id: Option<EventId>,
+        pub item: ItemTagId,
+        pub actors: Box<[ActorId]>,
+        /// Wallet the money goes back into
+        pub wallet_id: WalletId,
+        pub amount: Amount,
+    }
+
+    /// Charged on top of a transfer, spent on `item` like a purchase
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct Fee {
+        pub amount: Amount,
+        pub item: ItemTagId,
+        pub actors: Box<[ActorId]>,
     }
 
     #[derive(serde::Serialize, serde::Deserialize)]
     pub struct MoveValue {
         pub from: WalletId,
         pub to: WalletId,
+        /// What gets to `to`, `from` pays the fee besides it
         pub amount: Amount,
+        #[serde(default)]
+        pub fee: Option<Fee>,
     }
 
     #[derive(serde::Serialize, serde::Deserialize)]
@@ -32,6 +148,16 @@ pub mod event {
         pub amount: Amount,
     }
 
+    /// Balance the wallet was seen to have, e.g. on a bank statement.
+    /// The difference with what monee expected is deposited or deducted
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct ReconcileBalance {
+        pub wallet_id: WalletId,
+        pub observed: Balance,
+        /// Difference applied to the wallet, negative when it was deducted
+        pub adjustment: Balance,
+    }
+
     #[derive(serde::Serialize, serde::Deserialize)]
     pub struct PaymentReceived {
         pub actor_id: ActorId,
@@ -43,18 +169,88 @@ pub mod event {
     #[serde(rename_all = "snake_case", tag = "type")]
     pub enum Event {
         Purchase(Purchase),
+        Refund(Refund),
         MoveValue(MoveValue),
         RegisterBalance(RegisterBalance),
+        ReconcileBalance(ReconcileBalance),
         RegisterDebt(DebtRegister),
         RegisterLoan(DebtRegister),
+        AccrueDebtInterest(InterestAccrual),
+        AccrueLoanInterest(InterestAccrual),
         PaymentReceived(PaymentReceived),
     }
+
+    impl Event {
+        pub fn amount(&self) -> Amount {
+            match self {
+                Event::Purchase(Purchase { amount, .. })
+                | Event::Refund(Refund { amount, .. })
+                | Event::MoveValue(MoveValue { amount, .. })
+                | Event::RegisterBalance(RegisterBalance { amount, .. })
+                | Event::RegisterDebt(DebtRegister { amount, .. })
+                | Event::RegisterLoan(DebtRegister { amount, .. })
+                | Event::AccrueDebtInterest(InterestAccrual { amount, .. })
+                | Event::AccrueLoanInterest(InterestAccrual { amount, .. })
+                | Event::PaymentReceived(PaymentReceived { amount, .. }) => *amount,
+                Event::ReconcileBalance(ReconcileBalance { adjustment, .. }) => {
+                    adjustment.unsigned_abs()
+                }
+            }
+        }
+
+        pub fn fee(&self) -> Option<&Fee> {
+            match self {
+                Event::MoveValue(MoveValue { fee, .. }) => fee.as_ref(),
+                _ => None,
+            }
+        }
+
+        /// Currency the amount is expressed in, wallets, debts and loans are looked up in `snapshot`
+        pub fn currency_id(&self, snapshot: &monee_core::Snapshot) -> Option<CurrencyId> {
+            let wallet_id = match self {
+                Event::RegisterDebt(debt) | Event::RegisterLoan(debt) => {
+                    return Some(debt.currency_id)
+                }
+                Event::AccrueDebtInterest(accrual) => {
+                    return snapshot
+                        .debts
+                        .get(&accrual.debt_id)
+                        .map(|debt| debt.money.currency_id)
+                }
+                Event::AccrueLoanInterest(accrual) => {
+                    return snapshot
+                        .loans
+                        .get(&accrual.debt_id)
+                        .map(|loan| loan.money.currency_id)
+                }
+                Event::Purchase(Purchase { wallet_id, .. })
+                | Event::Refund(Refund { wallet_id, .. })
+                | Event::RegisterBalance(RegisterBalance { wallet_id, .. })
+                | Event::ReconcileBalance(ReconcileBalance { wallet_id, .. })
+                | Event::PaymentReceived(PaymentReceived { wallet_id, .. })
+                | Event::MoveValue(MoveValue {
+                    from: wallet_id, ..
+                }) => wallet_id,
+            };
+
+            snapshot
+                .wallets
+                .get(wallet_id)
+                .map(|wallet| wallet.currency_id)
+        }
+    }
 }
 
 pub mod apply_event {
-    use monee_core::{DebtId, WalletId};
+    use monee_core::WalletId;
 
-    use super::event::{Purchase, DebtRegister, Event, MoveValue, PaymentReceived, RegisterBalance};
+    use super::{
+        event::{
+            DebtRegister, Event, InterestAccrual, MoveValue, PaymentReceived, Purchase,
+            ReconcileBalance, Refund, RegisterBalance,
+        },
+        refund,
+    };
 
     pub fn apply_event(snapshot: &mut monee_core::Snapshot, event: &Event) -> Result<(), Error> {
         match event {
@@ -66,6 +262,14 @@ pub mod apply_event {
                     amount: *amount,
                 },
             ))?,
+            Event::Refund(Refund {
+                amount, wallet_id, ..
+            }) => snapshot.apply(monee_core::Operation::Wallet(
+                monee_core::WalletOperation::Deposit {
+                    wallet_id: *wallet_id,
+                    amount: *amount,
+                },
+            ))?,
             Event::RegisterBalance(RegisterBalance {
                 amount, wallet_id, ..
             }) => snapshot.apply(monee_core::Operation::Wallet(
@@ -74,17 +278,76 @@ pub mod apply_event {
                     amount: *amount,
                 },
             ))?,
+            Event::ReconcileBalance(ReconcileBalance {
+                wallet_id,
+                adjustment,
+                ..
+            }) => {
+                let amount = adjustment.unsigned_abs();
+                let operation = if adjustment.is_negative() {
+                    monee_core::WalletOperation::Deduct {
+                        wallet_id: *wallet_id,
+                        amount,
+                    }
+                } else {
+                    monee_core::WalletOperation::Deposit {
+                        wallet_id: *wallet_id,
+                        amount,
+                    }
+                };
+
+                snapshot.apply(monee_core::Operation::Wallet(operation))?;
+            }
             Event::RegisterDebt(debt_register) => {
+                if let Some(wallet_id) = debt_register.checked_wallet_id(snapshot)? {
+                    snapshot.apply(monee_core::Operation::Wallet(
+                        monee_core::WalletOperation::Deposit {
+                            wallet_id,
+                            amount: debt_register.amount,
+                        },
+                    ))?;
+                }
+
                 for operation in debt_register.create_operators() {
                     snapshot.apply(monee_core::Operation::Debt(operation))?;
                 }
             }
             Event::RegisterLoan(debt_register) => {
+                if let Some(wallet_id) = debt_register.checked_wallet_id(snapshot)? {
+                    snapshot.apply(monee_core::Operation::Wallet(
+                        monee_core::WalletOperation::Deduct {
+                            wallet_id,
+                            amount: debt_register.amount,
+                        },
+                    ))?;
+                }
+
                 for operation in debt_register.create_operators() {
                     snapshot.apply(monee_core::Operation::Loan(operation))?;
                 }
             }
-            Event::MoveValue(MoveValue { amount, to, from }) => {
+            Event::AccrueDebtInterest(InterestAccrual {
+                debt_id, amount, ..
+            }) => snapshot.apply(monee_core::Operation::Debt(
+                monee_core::DebtOperation::Accumulate {
+                    debt_id: *debt_id,
+                    amount: *amount,
+                },
+            ))?,
+            Event::AccrueLoanInterest(InterestAccrual {
+                debt_id, amount, ..
+            }) => snapshot.apply(monee_core::Operation::Loan(
+                monee_core::DebtOperation::Accumulate {
+                    debt_id: *debt_id,
+                    amount: *amount,
+                },
+            ))?,
+            Event::MoveValue(MoveValue {
+                amount,
+                to,
+                from,
+                fee,
+            }) => {
                 let from_wallet = snapshot
                     .wallets
                     .get(from)
@@ -95,14 +358,21 @@ pub mod apply_event {
                     .get(to)
                     .ok_or(MoveValueError::WalletNotFound(*to))?;
 
-                if from_wallet.money.currency_id != to_wallet.money.currency_id {
+                if from_wallet.currency_id != to_wallet.currency_id {
                     return Err(MoveValueError::CurrenciesNonEqual.into());
                 }
 
+                let mut total = *amount;
+                if let Some(fee) = fee {
+                    total = total
+                        .checked_add(fee.amount)
+                        .ok_or(monee_core::Error::Wallet(monee_core::MoneyError::CannotSub))?;
+                }
+
                 snapshot.apply(monee_core::Operation::Wallet(
                     monee_core::WalletOperation::Deduct {
                         wallet_id: *from,
-                        amount: *amount,
+                        amount: total,
                     },
                 ))?;
 
@@ -133,6 +403,9 @@ pub mod apply_event {
     pub enum Error {
         MoveValue(MoveValueError),
         Apply(monee_core::Error),
+        /// The amount has more decimals than its currency uses
+        Precision(monee_core::MinorUnits),
+        Refund(refund::Error),
     }
 
     #[derive(serde::Serialize, serde::Deserialize)]
@@ -148,6 +421,12 @@ pub mod apply_event {
         }
     }
 
+    impl From<refund::Error> for Error {
+        fn from(value: refund::Error) -> Self {
+            Self::Refund(value)
+        }
+    }
+
     impl From<monee_core::Error> for Error {
         fn from(value: monee_core::Error) -> Self {
             Self::Apply(value)
@@ -155,8 +434,29 @@ pub mod apply_event {
     }
 
     impl DebtRegister {
+        /// The wallet the money moves through, checking it holds the debt currency
+        fn checked_wallet_id(
+            &self,
+            snapshot: &monee_core::Snapshot,
+        ) -> Result<Option<WalletId>, MoveValueError> {
+            let Some(wallet_id) = self.wallet_id else {
+                return Ok(None);
+            };
+
+            let wallet = snapshot
+                .wallets
+                .get(&wallet_id)
+                .ok_or(MoveValueError::WalletNotFound(wallet_id))?;
+
+            if wallet.currency_id != self.currency_id {
+                return Err(MoveValueError::CurrenciesNonEqual);
+            }
+
+            Ok(Some(wallet_id))
+        }
+
         fn create_operators(&self) -> [monee_core::DebtOperation; 2] {
-            let debt_id = DebtId::new();
+            let debt_id = self.debt_id.unwrap_or_default();
             [
                 monee_core::DebtOperation::Incur {
                     currency_id: self.currency_id,
@@ -172,6 +472,482 @@ pub mod apply_event {
     }
 }
 
+pub mod refund {
+    use monee_core::{ActorId, Amount};
+
+    use super::event::{Purchase, Refund};
+
+    #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case", tag = "refund_error")]
+    pub enum Error {
+        /// There is no purchase the refund can come from
+        PurchaseNotFound,
+        /// The refund is for another item than the purchase
+        ItemMismatch,
+        /// The money goes back in another currency than it was paid
+        CurrenciesNonEqual,
+        /// Only `refundable` is left to refund from the purchase
+        ExceedsPurchase { refundable: Amount },
+    }
+
+    /// What is left to refund from `purchase` after the `previous` refunds
+    pub fn refundable<'a>(
+        purchase: &Purchase,
+        previous: impl IntoIterator<Item = &'a Refund>,
+    ) -> Amount {
+        previous.into_iter().fold(purchase.amount, |left, refund| {
+            left.checked_sub(refund.amount).unwrap_or_default()
+        })
+    }
+
+    impl Refund {
+        /// Checks the refund fits in `purchase`, wallets are looked up in `snapshot`
+        pub fn check<'a>(
+            &self,
+            purchase: &Purchase,
+            previous: impl IntoIterator<Item = &'a Refund>,
+            snapshot: &monee_core::Snapshot,
+        ) -> Result<(), Error> {
+            if self.item != purchase.item {
+                return Err(Error::ItemMismatch);
+            }
+
+            let currency = |wallet_id| {
+                snapshot
+                    .wallets
+                    .get(wallet_id)
+                    .map(|wallet| wallet.currency_id)
+            };
+            if currency(&self.wallet_id) != currency(&purchase.wallet_id) {
+                return Err(Error::CurrenciesNonEqual);
+            }
+
+            let refundable = refundable(purchase, previous);
+            if self.amount > refundable {
+                return Err(Error::ExceedsPurchase { refundable });
+            }
+
+            Ok(())
+        }
+
+        /// Checks the refund fits in what was bought of its item, in its currency and from its
+        /// actors, net of the `previous` refunds of the item, tied to a purchase or not
+        pub fn check_item<'a>(
+            &self,
+            purchases: impl IntoIterator<Item = &'a Purchase>,
+            previous: impl IntoIterator<Item = &'a Refund>,
+            snapshot: &monee_core::Snapshot,
+        ) -> Result<(), Error> {
+            let currency = |wallet_id| {
+                snapshot
+                    .wallets
+                    .get(wallet_id)
+                    .map(|wallet| wallet.currency_id)
+            };
+            let refund_currency = currency(&self.wallet_id);
+            let covers = |item, wallet_id, actors: &[ActorId]| {
+                item == self.item
+                    && currency(wallet_id) == refund_currency
+                    && self.actors.iter().all(|actor| actors.contains(actor))
+            };
+
+            let mut purchases = purchases
+                .into_iter()
+                .filter(|purchase| covers(purchase.item, &purchase.wallet_id, &purchase.actors))
+                .peekable();
+            if purchases.peek().is_none() {
+                return Err(Error::PurchaseNotFound);
+            }
+
+            let bought = purchases.fold(Amount::default(), |total, purchase| {
+                total.checked_add(purchase.amount).unwrap_or(total)
+            });
+            let refundable = previous
+                .into_iter()
+                .filter(|refund| covers(refund.item, &refund.wallet_id, &refund.actors))
+                .fold(bought, |left, refund| {
+                    left.checked_sub(refund.amount).unwrap_or_default()
+                });
+            if self.amount > refundable {
+                return Err(Error::ExceedsPurchase { refundable });
+            }
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use monee_core::{
+            CurrencyId, EventId, ItemTagId, Operation, Snapshot, WalletId, WalletOperation,
+        };
+
+        use super::*;
+
+        fn purchase(item: ItemTagId, wallet_id: WalletId, amount: &str) -> Purchase {
+            Purchase {
+                item,
+                actors: Box::new([ActorId::new()]),
+                wallet_id,
+                amount: amount.parse().unwrap(),
+                quantity: None,
+            }
+        }
+
+        fn refund(item: ItemTagId, wallet_id: WalletId, amount: &str) -> Refund {
+            Refund {
+                purchase_id: None,
+                item,
+                actors: Box::new([]),
+                wallet_id,
+                amount: amount.parse().unwrap(),
+            }
+        }
+
+        fn snapshot(wallets: &[(WalletId, CurrencyId)]) -> Snapshot {
+            let mut snapshot = Snapshot::default();
+            for (wallet_id, currency_id) in wallets {
+                snapshot
+                    .apply(Operation::Wallet(WalletOperation::Create {
+                        wallet_id: *wallet_id,
+                        currency_id: *currency_id,
+                        kind: Default::default(),
+                    }))
+                    .unwrap();
+            }
+
+            snapshot
+        }
+
+        #[test]
+        fn refunds_never_exceed_purchase() {
+            let (item, wallet_id) = (ItemTagId::new(), WalletId::new());
+            let snapshot = snapshot(&[(wallet_id, CurrencyId::new())]);
+            let purchase = purchase(item, wallet_id, "30");
+            let previous = [refund(item, wallet_id, "20")];
+
+            assert_eq!(
+                refund(item, wallet_id, "10").check(&purchase, &previous, &snapshot),
+                Ok(())
+            );
+            assert_eq!(
+                refund(item, wallet_id, "10.01").check(&purchase, &previous, &snapshot),
+                Err(Error::ExceedsPurchase {
+                    refundable: "10".parse().unwrap()
+                })
+            );
+        }
+
+        #[test]
+        fn item_refunds_are_limited_to_what_was_bought() {
+            let (item, wallet_id) = (ItemTagId::new(), WalletId::new());
+            let snapshot = snapshot(&[(wallet_id, CurrencyId::new())]);
+            let purchases = [
+                purchase(item, wallet_id, "30"),
+                purchase(item, wallet_id, "20"),
+            ];
+            let previous = [refund(item, wallet_id, "15")];
+
+            assert_eq!(
+                refund(item, wallet_id, "35").check_item(&purchases, &previous, &snapshot),
+                Ok(())
+            );
+            assert_eq!(
+                refund(item, wallet_id, "36").check_item(&purchases, &previous, &snapshot),
+                Err(Error::ExceedsPurchase {
+                    refundable: "35".parse().unwrap()
+                })
+            );
+            assert_eq!(
+                refund(ItemTagId::new(), wallet_id, "1").check_item(&purchases, [], &snapshot),
+                Err(Error::PurchaseNotFound)
+            );
+
+            let mut from_seller = refund(item, wallet_id, "1");
+            from_seller.actors = Box::new([ActorId::new()]);
+            assert_eq!(
+                from_seller.check_item(&purchases, [], &snapshot),
+                Err(Error::PurchaseNotFound)
+            );
+        }
+
+        #[test]
+        fn purchase_refunds_count_item_refunds() {
+            let (item, wallet_id) = (ItemTagId::new(), WalletId::new());
+            let snapshot = snapshot(&[(wallet_id, CurrencyId::new())]);
+            let purchases = [purchase(item, wallet_id, "30")];
+
+            let item_refund = refund(item, wallet_id, "30");
+            assert_eq!(item_refund.check_item(&purchases, [], &snapshot), Ok(()));
+
+            let mut linked = refund(item, wallet_id, "30");
+            linked.purchase_id = Some(EventId::new());
+            assert_eq!(linked.check(&purchases[0], [], &snapshot), Ok(()));
+            assert_eq!(
+                linked.check_item(&purchases, [&item_refund], &snapshot),
+                Err(Error::ExceedsPurchase {
+                    refundable: Amount::default()
+                })
+            );
+        }
+
+        #[test]
+        fn rejects_other_item_or_currency() {
+            let (item, wallet_id, other_wallet) =
+                (ItemTagId::new(), WalletId::new(), WalletId::new());
+            let snapshot = snapshot(&[
+                (wallet_id, CurrencyId::new()),
+                (other_wallet, CurrencyId::new()),
+            ]);
+            let purchase = purchase(item, wallet_id, "30");
+
+            assert_eq!(
+                refund(ItemTagId::new(), wallet_id, "1").check(&purchase, [], &snapshot),
+                Err(Error::ItemMismatch)
+            );
+            assert_eq!(
+                refund(item, other_wallet, "1").check(&purchase, [], &snapshot),
+                Err(Error::CurrenciesNonEqual)
+            );
+        }
+    }
+}
+
+pub mod split_purchase {
+    use monee_core::{ActorId, Amount, CurrencyId, ItemTagId, MinorUnits, Shares, WalletId};
+
+    use super::event::{DebtRegister, Event, Purchase};
+
+    /// A purchase paid from my wallet and shared with other actors
+    pub struct PurchaseSplit {
+        pub item: ItemTagId,
+        pub sellers: Box<[ActorId]>,
+        pub wallet_id: WalletId,
+        pub amount: Amount,
+        /// Actors sharing the purchase, besides me
+        pub participants: Box<[ActorId]>,
+        /// My share first, then one per participant
+        pub shares: Shares,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum Error {
+        /// There is not exactly one share for me plus one per participant
+        SharesMismatch,
+        Allocation(monee_core::AllocationError),
+    }
+
+    impl PurchaseSplit {
+        /// My share as a purchase and a loan, paid from the same wallet, for every other share.
+        /// Empty shares record nothing
+        pub fn into_events(
+            self,
+            currency_id: CurrencyId,
+            units: MinorUnits,
+        ) -> Result<Vec<Event>, Error> {
+            if self.shares.len() != self.participants.len() + 1 {
+                return Err(Error::SharesMismatch);
+            }
+
+            let parts =
+                monee_core::allocate(self.amount, &self.shares, units).map_err(Error::Allocation)?;
+            let mut parts = parts.into_iter();
+            let mine = parts.next().unwrap_or_default();
+
+            let purchase = Event::Purchase(Purchase {
+                item: self.item,
+                actors: self.sellers,
+                wallet_id: self.wallet_id,
+                amount: mine,
+                quantity: None,
+            });
+
+            let loans = IntoIterator::into_iter(self.participants)
+                .zip(parts)
+                .map(|(actor_id, amount)| {
+                    Event::RegisterLoan(DebtRegister {
+                        amount,
+                        currency_id,
+                        actor_id,
+                        payment_promise: None,
+                        wallet_id: Some(self.wallet_id),
+                        debt_id: None,
+                        terms: None,
+                    })
+                });
+
+            Ok(std::iter::once(purchase)
+                .chain(loans)
+                .filter(|event| event.amount() != Amount::default())
+                .collect())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn split(shares: Shares, participants: usize) -> PurchaseSplit {
+            PurchaseSplit {
+                item: ItemTagId::new(),
+                sellers: Box::new([]),
+                wallet_id: WalletId::new(),
+                amount: "10".parse().unwrap(),
+                participants: (0..participants).map(|_| ActorId::new()).collect(),
+                shares,
+            }
+        }
+
+        #[test]
+        fn records_my_share_as_purchase_and_others_as_loans() {
+            let events = split(Shares::Equal(3), 2)
+                .into_events(CurrencyId::new(), MinorUnits::default())
+                .unwrap();
+
+            let amounts: Vec<String> = events.iter().map(|e| e.amount().to_string()).collect();
+            assert_eq!(amounts, ["3.34", "3.33", "3.33"]);
+            assert!(matches!(events[0], Event::Purchase(_)));
+            assert!(events[1..]
+                .iter()
+                .all(|e| matches!(e, Event::RegisterLoan(DebtRegister { wallet_id: Some(_), .. }))));
+        }
+
+        #[test]
+        fn skips_empty_shares() {
+            let events = split(Shares::Weights(vec![0, 1]), 1)
+                .into_events(CurrencyId::new(), MinorUnits::default())
+                .unwrap();
+
+            assert_eq!(events.len(), 1);
+            assert!(matches!(events[0], Event::RegisterLoan(_)));
+        }
+
+        #[test]
+        fn rejects_shares_mismatch() {
+            let result = split(Shares::Equal(2), 2).into_events(CurrencyId::new(), MinorUnits::default());
+            assert_eq!(result.err(), Some(Error::SharesMismatch));
+        }
+    }
+}
+
+pub mod reconcile {
+    use monee_core::{Balance, MinorUnits, RoundingMode, WalletId};
+
+    use super::event::ReconcileBalance;
+
+    /// Adjustments over this percentage of the expected balance are worth a second look
+    pub const LARGE_DISCREPANCY_PERCENT: u64 = 5;
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum Error {
+        WalletNotFound(WalletId),
+        /// The difference does not fit in a balance
+        TooBig,
+    }
+
+    impl ReconcileBalance {
+        /// Reconciles the wallet against the balance `snapshot` expects it to have
+        pub fn against(
+            snapshot: &monee_core::Snapshot,
+            wallet_id: WalletId,
+            observed: Balance,
+        ) -> Result<Self, Error> {
+            let wallet = snapshot
+                .wallets
+                .get(&wallet_id)
+                .ok_or(Error::WalletNotFound(wallet_id))?;
+
+            let adjustment = observed
+                .checked_diff(wallet.balance)
+                .ok_or(Error::TooBig)?;
+
+            Ok(Self {
+                wallet_id,
+                observed,
+                adjustment,
+            })
+        }
+
+        /// Balance monee had before reconciling
+        pub fn expected(&self) -> Balance {
+            self.observed
+                .checked_diff(self.adjustment)
+                .unwrap_or(self.observed)
+        }
+
+        pub fn is_large_discrepancy(&self) -> bool {
+            is_large_discrepancy(self.expected(), self.adjustment)
+        }
+    }
+
+    /// Whether `adjustment` is over [`LARGE_DISCREPANCY_PERCENT`] of `expected`,
+    /// any adjustment is when nothing was expected
+    pub fn is_large_discrepancy(expected: Balance, adjustment: Balance) -> bool {
+        let exact = MinorUnits::try_from(MinorUnits::MAX).expect("to be a valid precision");
+        let threshold = expected.unsigned_abs().checked_mul_div(
+            LARGE_DISCREPANCY_PERCENT,
+            100,
+            exact,
+            RoundingMode::Down,
+        );
+
+        threshold.is_some_and(|threshold| adjustment.unsigned_abs() > threshold)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use monee_core::{CurrencyId, Operation, Snapshot, WalletKind, WalletOperation};
+
+        use super::*;
+
+        fn snapshot_with(wallet_id: WalletId, balance: &str) -> Snapshot {
+            let mut snapshot = Snapshot::default();
+            snapshot
+                .apply(Operation::Wallet(WalletOperation::Create {
+                    wallet_id,
+                    currency_id: CurrencyId::new(),
+                    kind: WalletKind::default(),
+                }))
+                .unwrap();
+            snapshot
+                .apply(Operation::Wallet(WalletOperation::Deposit {
+                    wallet_id,
+                    amount: balance.parse().unwrap(),
+                }))
+                .unwrap();
+
+            snapshot
+        }
+
+        #[test]
+        fn computes_adjustment_against_snapshot() {
+            let wallet_id = WalletId::new();
+            let snapshot = snapshot_with(wallet_id, "1210");
+
+            let reconcile =
+                ReconcileBalance::against(&snapshot, wallet_id, "1203.4".parse().unwrap())
+                    .unwrap();
+
+            assert_eq!(reconcile.adjustment.to_string(), "-6.6");
+            assert_eq!(reconcile.expected().to_string(), "1210");
+            assert!(!reconcile.is_large_discrepancy());
+        }
+
+        #[test]
+        fn flags_large_discrepancies() {
+            let wallet_id = WalletId::new();
+            let snapshot = snapshot_with(wallet_id, "100");
+
+            let reconcile =
+                ReconcileBalance::against(&snapshot, wallet_id, "94".parse().unwrap()).unwrap();
+            assert!(reconcile.is_large_discrepancy());
+
+            assert!(is_large_discrepancy(Balance::ZERO, "0.01".parse().unwrap()));
+            assert!(!is_large_discrepancy(Balance::ZERO, Balance::ZERO));
+        }
+    }
+}
+
 pub mod event_added {
     use cream_events_core::DomainEvent;
 
@@ -0,0 +1,184 @@
+pub mod rate {
+    use std::{fmt::Display, str::FromStr};
+
+    use monee_core::Amount;
+
+    /// Units of the target currency one unit of the source currency is worth, never zero
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(try_from = "Amount", into = "Amount")]
+    pub struct Rate(Amount);
+
+    impl Rate {
+        pub fn get(self) -> Amount {
+            self.0
+        }
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum Error {
+        #[error("Exchange rate must be over zero")]
+        Zero,
+        #[error("Exchange rate {0}")]
+        Invalid(#[from] monee_core::amount_from_str::Error),
+    }
+
+    impl TryFrom<Amount> for Rate {
+        type Error = Error;
+
+        fn try_from(value: Amount) -> Result<Self, Self::Error> {
+            if value == Amount::default() {
+                return Err(Error::Zero);
+            }
+
+            Ok(Self(value))
+        }
+    }
+
+    impl From<Rate> for Amount {
+        fn from(value: Rate) -> Self {
+            value.0
+        }
+    }
+
+    impl FromStr for Rate {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse::<Amount>()?.try_into()
+        }
+    }
+
+    impl Display for Rate {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+}
+
+pub mod exchange_rate {
+    use monee_core::CurrencyId;
+
+    use crate::shared::date::Date;
+
+    use super::rate::Rate;
+
+    /// Rate of a currency pair, in effect from `date` until the next rate of the same pair
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    pub struct ExchangeRate {
+        pub from: CurrencyId,
+        pub to: CurrencyId,
+        pub date: Date,
+        pub rate: Rate,
+    }
+}
+
+pub mod rate_table {
+    use monee_core::{Amount, CurrencyId, MinorUnits, RoundingMode};
+
+    use crate::shared::date::Date;
+
+    use super::exchange_rate::ExchangeRate;
+
+    /// Every known rate, looked up by the date they were in effect
+    pub struct RateTable(Vec<ExchangeRate>);
+
+    impl RateTable {
+        pub fn new(rates: Vec<ExchangeRate>) -> Self {
+            Self(rates)
+        }
+
+        fn latest(&self, from: CurrencyId, to: CurrencyId, date: Date) -> Option<&ExchangeRate> {
+            self.0
+                .iter()
+                .filter(|rate| rate.from == from && rate.to == to && rate.date <= date)
+                .max_by_key(|rate| rate.date)
+        }
+
+        /// Converts `amount` with the rate in effect at `date`, the pair may have been set
+        /// either way. `None` when there is no such rate
+        pub fn convert(
+            &self,
+            amount: Amount,
+            from: CurrencyId,
+            to: CurrencyId,
+            date: Date,
+            units: MinorUnits,
+        ) -> Option<Amount> {
+            if from == to {
+                return Some(amount);
+            }
+
+            let direct = self.latest(from, to, date);
+            let inverse = self.latest(to, from, date);
+
+            match (direct, inverse) {
+                (Some(direct), Some(inverse)) if inverse.date > direct.date => {
+                    amount.checked_div_rate(inverse.rate.get(), units, RoundingMode::HalfEven)
+                }
+                (Some(direct), _) => {
+                    amount.checked_mul_rate(direct.rate.get(), units, RoundingMode::HalfEven)
+                }
+                (None, Some(inverse)) => {
+                    amount.checked_div_rate(inverse.rate.get(), units, RoundingMode::HalfEven)
+                }
+                (None, None) => None,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn date(s: &str) -> Date {
+            s.parse().unwrap()
+        }
+
+        fn rate(from: CurrencyId, to: CurrencyId, on: &str, rate: &str) -> ExchangeRate {
+            ExchangeRate {
+                from,
+                to,
+                date: date(on),
+                rate: rate.parse().unwrap(),
+            }
+        }
+
+        #[test]
+        fn uses_rate_in_effect_at_date() {
+            let (usd, pen) = (CurrencyId::new(), CurrencyId::new());
+            let table = RateTable::new(vec![
+                rate(usd, pen, "2024-01-01", "3.7"),
+                rate(usd, pen, "2024-02-01", "3.8"),
+            ]);
+
+            let convert = |on| {
+                table.convert(
+                    "10".parse().unwrap(),
+                    usd,
+                    pen,
+                    date(on),
+                    Default::default(),
+                )
+            };
+
+            assert_eq!(convert("2024-01-15"), "37".parse().ok());
+            assert_eq!(convert("2024-02-01"), "38".parse().ok());
+            assert_eq!(convert("2023-12-31"), None);
+        }
+
+        #[test]
+        fn uses_inverse_pair() {
+            let (usd, pen) = (CurrencyId::new(), CurrencyId::new());
+            let table = RateTable::new(vec![rate(usd, pen, "2024-01-01", "4")]);
+
+            let converted = table.convert(
+                "10".parse().unwrap(),
+                pen,
+                usd,
+                date("2024-01-01"),
+                Default::default(),
+            );
+            assert_eq!(converted, "2.5".parse().ok());
+        }
+    }
+}
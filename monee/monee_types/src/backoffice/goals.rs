@@ -0,0 +1,267 @@
+pub mod goal {
+    use monee_core::{Amount, CurrencyId, WalletId};
+
+    use crate::shared::date::Date;
+
+    use super::goal_name::GoalName;
+
+    /// Money to gather by `deadline`, held in wallets of the goal currency
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct Goal {
+        pub name: GoalName,
+        pub currency_id: CurrencyId,
+        pub target: Amount,
+        pub deadline: Date,
+        pub wallets: Vec<WalletId>,
+    }
+}
+
+pub mod goal_name {
+    use std::{fmt::Display, str::FromStr};
+
+    use unicode_normalization::UnicodeNormalization;
+
+    use crate::shared::slug::Slug;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+    pub struct GoalName(String);
+
+    impl Display for GoalName {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl GoalName {
+        pub fn slug(&self) -> Slug {
+            Slug::new(&self.0)
+        }
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum Error {
+        #[error("Invalid character: {0:?}")]
+        InvalidCharacter(char),
+    }
+
+    impl TryFrom<String> for GoalName {
+        type Error = Error;
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            let value: String = value.nfc().collect();
+            match value
+                .chars()
+                .find(|c| !(c.is_alphanumeric() || matches!(*c, '-' | '_')))
+            {
+                Some(c) => Err(Error::InvalidCharacter(c)),
+                None => Ok(Self(value)),
+            }
+        }
+    }
+
+    impl FromStr for GoalName {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Self::try_from(s.to_string())
+        }
+    }
+}
+
+pub mod goal_progress {
+    use monee_core::{Amount, Balance, MinorUnits, RoundingMode};
+
+    use crate::{backoffice::debts::period::Period, shared::date::Date};
+
+    use super::goal::Goal;
+
+    /// Months of deposits the projection is based on
+    pub const DEPOSIT_RATE_MONTHS: u32 = 3;
+
+    /// Projections further than this are not worth giving
+    const MAX_PROJECTED_MONTHS: u32 = 1200;
+
+    #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct GoalProgress {
+        /// What the goal wallets hold
+        pub current: Amount,
+        pub remaining: Amount,
+        /// Months until the deadline, the current one included, zero once it passed
+        pub months_left: u32,
+        /// To put aside every month to make it, `None` when the deadline passed short of it
+        pub monthly_required: Option<Amount>,
+        /// Average the wallets grew per month over the last `DEPOSIT_RATE_MONTHS` months
+        pub monthly_deposits: Amount,
+        /// When the goal is met at that rate, `None` when it is not growing
+        pub projected: Option<Date>,
+    }
+
+    /// What `goal` wallets hold in `snapshot`, wallets below zero take from the rest.
+    /// `None` when the total does not fit a balance
+    pub fn held(goal: &Goal, snapshot: &monee_core::Snapshot) -> Option<Balance> {
+        goal.wallets
+            .iter()
+            .filter_map(|wallet_id| snapshot.wallets.get(wallet_id))
+            .try_fold(Balance::ZERO, |total, wallet| {
+                total
+                    .checked_add(wallet.balance.positive_part())?
+                    .checked_sub(wallet.balance.negative_part())
+            })
+    }
+
+    /// Progress on `today` given the goal wallets hold `current` and held `previous`
+    /// `DEPOSIT_RATE_MONTHS` months ago. Amounts are rounded to `units` decimals
+    pub fn progress(
+        goal: &Goal,
+        current: Balance,
+        previous: Balance,
+        today: Date,
+        units: MinorUnits,
+    ) -> GoalProgress {
+        let remaining = goal
+            .target
+            .checked_sub(current.positive_part())
+            .unwrap_or_default();
+
+        let months_left = months_until(today, goal.deadline);
+        let monthly_required = if remaining == Amount::default() {
+            Some(Amount::default())
+        } else if months_left == 0 {
+            None
+        } else {
+            remaining.checked_mul_div(1, months_left as u64, units, RoundingMode::Up)
+        };
+
+        let monthly_deposits = current
+            .checked_diff(previous)
+            .map(|grown| grown.positive_part())
+            .and_then(|grown| {
+                grown.checked_mul_div(1, DEPOSIT_RATE_MONTHS as u64, units, RoundingMode::Down)
+            })
+            .unwrap_or_default();
+
+        GoalProgress {
+            current: current.positive_part(),
+            remaining,
+            months_left,
+            monthly_required,
+            monthly_deposits,
+            projected: projected(today, remaining, monthly_deposits),
+        }
+    }
+
+    /// Months from `today` to `deadline`, a started month counts as a whole one
+    fn months_until(today: Date, deadline: Date) -> u32 {
+        if deadline < today {
+            return 0;
+        }
+
+        let whole = Period::Monthly.elapsed(today, deadline);
+        match Period::Monthly.after(today, whole) {
+            Some(date) if date < deadline => whole + 1,
+            _ => whole,
+        }
+    }
+
+    fn projected(today: Date, remaining: Amount, monthly_deposits: Amount) -> Option<Date> {
+        if remaining == Amount::default() {
+            return Some(today);
+        }
+        if monthly_deposits == Amount::default() {
+            return None;
+        }
+
+        let mut gathered = Amount::default();
+        for months in 1..=MAX_PROJECTED_MONTHS {
+            gathered = gathered.checked_add(monthly_deposits)?;
+            if gathered >= remaining {
+                return Period::Monthly.after(today, months);
+            }
+        }
+
+        None
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use monee_core::{CurrencyId, Operation, Snapshot, WalletId, WalletOperation};
+
+        use super::*;
+
+        fn date(s: &str) -> Date {
+            s.parse().unwrap()
+        }
+
+        fn balance(s: &str) -> Balance {
+            s.parse().unwrap()
+        }
+
+        fn goal(target: &str, deadline: &str) -> Goal {
+            Goal {
+                name: "vacation".parse().unwrap(),
+                currency_id: CurrencyId::new(),
+                target: target.parse().unwrap(),
+                deadline: date(deadline),
+                wallets: Vec::new(),
+            }
+        }
+
+        #[test]
+        fn requires_and_projects_monthly_contributions() {
+            let progress = progress(
+                &goal("3000", "2024-12-31"),
+                balance("1200"),
+                balance("900"),
+                date("2024-03-15"),
+                MinorUnits::default(),
+            );
+
+            assert_eq!(progress.remaining, "1800".parse().unwrap());
+            assert_eq!(progress.months_left, 10);
+            assert_eq!(progress.monthly_required, "180".parse().ok());
+            assert_eq!(progress.monthly_deposits, "100".parse().unwrap());
+            assert_eq!(progress.projected, Some(date("2025-09-15")));
+        }
+
+        #[test]
+        fn stops_projecting_when_not_growing() {
+            let progress = progress(
+                &goal("3000", "2024-01-31"),
+                balance("1200"),
+                balance("1500"),
+                date("2024-03-15"),
+                MinorUnits::default(),
+            );
+
+            assert_eq!(progress.months_left, 0);
+            assert_eq!(progress.monthly_required, None);
+            assert_eq!(progress.projected, None);
+        }
+
+        #[test]
+        fn refuses_holdings_that_do_not_fit() {
+            let mut goal = goal("3000", "2024-12-31");
+            let mut snapshot = Snapshot::default();
+            for _ in 0..2 {
+                let wallet_id = WalletId::new();
+                let operations = [
+                    WalletOperation::Create {
+                        wallet_id,
+                        currency_id: goal.currency_id,
+                        kind: Default::default(),
+                    },
+                    WalletOperation::Deposit {
+                        wallet_id,
+                        amount: "90000000000".parse().unwrap(),
+                    },
+                ];
+                for operation in operations {
+                    snapshot.apply(Operation::Wallet(operation)).unwrap();
+                }
+                goal.wallets.push(wallet_id);
+            }
+
+            assert_eq!(held(&goal, &snapshot), None);
+        }
+    }
+}
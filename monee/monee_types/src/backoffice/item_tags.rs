@@ -10,7 +10,10 @@ pub mod item_tag {
 pub mod item_name {
     use std::{fmt::Display, str::FromStr};
 
-    use crate::shared::alias::{from_str::Error, Alias};
+    use crate::shared::{
+        alias::{from_str::Error, Alias},
+        slug::Slug,
+    };
 
     #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
     pub struct ItemName(Alias);
@@ -21,6 +24,12 @@ pub mod item_name {
         }
     }
 
+    impl ItemName {
+        pub fn slug(&self) -> Slug {
+            self.0.slug()
+        }
+    }
+
     impl FromStr for ItemName {
         type Err = Error;
 
@@ -60,3 +69,61 @@ pub mod item_tag_created {
         }
     }
 }
+
+pub mod item_tag_updated {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct ItemTagUpdated {
+        pub id: monee_core::ItemTagId,
+    }
+
+    impl DomainEvent for ItemTagUpdated {
+        fn name(&self) -> &'static str {
+            "backoffice.item_tags.updated"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
+
+pub mod item_tag_deleted {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct ItemTagDeleted {
+        pub id: monee_core::ItemTagId,
+    }
+
+    impl DomainEvent for ItemTagDeleted {
+        fn name(&self) -> &'static str {
+            "backoffice.item_tags.deleted"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
+
+pub mod item_tags_merged {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct ItemTagsMerged {
+        pub source: monee_core::ItemTagId,
+        pub target: monee_core::ItemTagId,
+    }
+
+    impl DomainEvent for ItemTagsMerged {
+        fn name(&self) -> &'static str {
+            "backoffice.item_tags.merged"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
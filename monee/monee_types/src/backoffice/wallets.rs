@@ -6,12 +6,18 @@ pub mod wallet {
         pub currency_id: monee_core::CurrencyId,
         pub name: WalletName,
         pub description: String,
+        #[serde(default)]
+        pub kind: monee_core::WalletKind,
     }
 }
 
 pub mod wallet_name {
     use std::{fmt::Display, str::FromStr};
 
+    use unicode_normalization::UnicodeNormalization;
+
+    use crate::shared::slug::Slug;
+
     #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
     pub struct WalletName(String);
 
@@ -21,6 +27,12 @@ pub mod wallet_name {
         }
     }
 
+    impl WalletName {
+        pub fn slug(&self) -> Slug {
+            Slug::new(&self.0)
+        }
+    }
+
     #[derive(Debug, thiserror::Error)]
     pub enum Error {
         #[error("Invalid character: {0:?}")]
@@ -31,9 +43,10 @@ pub mod wallet_name {
         type Error = Error;
 
         fn try_from(value: String) -> Result<Self, Self::Error> {
+            let value: String = value.nfc().collect();
             match value
                 .chars()
-                .find(|c| !matches!(*c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_'))
+                .find(|c| !(c.is_alphanumeric() || matches!(*c, '-' | '_')))
             {
                 Some(c) => Err(Error::InvalidCharacter(c)),
                 None => Ok(Self(value)),
@@ -57,6 +70,8 @@ pub mod wallet_created {
     pub struct WalletCreated {
         pub id: monee_core::WalletId,
         pub currency_id: monee_core::CurrencyId,
+        #[serde(default)]
+        pub kind: monee_core::WalletKind,
     }
 
     impl DomainEvent for WalletCreated {
@@ -69,3 +84,61 @@ pub mod wallet_created {
         }
     }
 }
+
+pub mod wallet_updated {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct WalletUpdated {
+        pub id: monee_core::WalletId,
+    }
+
+    impl DomainEvent for WalletUpdated {
+        fn name(&self) -> &'static str {
+            "backoffice.wallets.updated"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
+
+pub mod wallet_kind_changed {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct WalletKindChanged {
+        pub id: monee_core::WalletId,
+        pub kind: monee_core::WalletKind,
+    }
+
+    impl DomainEvent for WalletKindChanged {
+        fn name(&self) -> &'static str {
+            "backoffice.wallets.kind_changed"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
+
+pub mod wallet_deleted {
+    use cream_events_core::DomainEvent;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    pub struct WalletDeleted {
+        pub id: monee_core::WalletId,
+    }
+
+    impl DomainEvent for WalletDeleted {
+        fn name(&self) -> &'static str {
+            "backoffice.wallets.deleted"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+}
@@ -0,0 +1,484 @@
+pub mod finding {
+    use monee_core::{
+        ActorId, Amount, Balance, CurrencyId, DebtId, EventId, ItemTagId, WalletId, WalletKind,
+    };
+
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+    )]
+    #[serde(rename_all = "snake_case")]
+    pub enum Severity {
+        /// Only reports are affected
+        Warning,
+        /// Balances are wrong or new events may fail
+        Error,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case", tag = "type", content = "id")]
+    pub enum Reference {
+        Wallet(WalletId),
+        Item(ItemTagId),
+        Actor(ActorId),
+        Currency(CurrencyId),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case", tag = "type")]
+    pub enum Finding {
+        /// The snapshot holds a wallet that has no `wallet` row
+        SnapshotWalletWithoutRow {
+            wallet_id: WalletId,
+            balance: Balance,
+        },
+        /// A `wallet` row the snapshot does not hold
+        WalletWithoutSnapshot {
+            wallet_id: WalletId,
+            currency_id: CurrencyId,
+            kind: WalletKind,
+        },
+        /// The wallet row and its snapshot money are in different currencies
+        CurrencyMismatch {
+            wallet_id: WalletId,
+            row: CurrencyId,
+            snapshot: CurrencyId,
+        },
+        /// A debt or loan of the snapshot is held with an actor that does not exist
+        DebtWithoutActor {
+            debt_id: DebtId,
+            actor_id: ActorId,
+            loan: bool,
+        },
+        /// An event points to a catalog entry that does not exist
+        MissingReference {
+            event_id: EventId,
+            reference: Reference,
+        },
+        /// Events can not be replayed past `event_id`, so divergences are not looked for
+        ReplayFailed { event_id: EventId },
+        /// The stored balance is not what replaying every event gives
+        WalletDivergence {
+            wallet_id: WalletId,
+            stored: Balance,
+            replayed: Balance,
+        },
+        /// What is owed with an actor in a currency is not what replaying every event gives
+        DebtDivergence {
+            actor_id: ActorId,
+            currency_id: CurrencyId,
+            loan: bool,
+            stored: Amount,
+            replayed: Amount,
+        },
+    }
+
+    impl Finding {
+        pub fn severity(&self) -> Severity {
+            match self {
+                Finding::MissingReference {
+                    reference: Reference::Item(_) | Reference::Actor(_),
+                    ..
+                }
+                | Finding::WalletDivergence { .. }
+                | Finding::DebtDivergence { .. } => Severity::Warning,
+                _ => Severity::Error,
+            }
+        }
+
+        /// Change to the snapshot that fixes the finding, `None` when it can not be fixed
+        /// without losing data.
+        /// Divergences are left as they are, nodes only keep the events since they last synced
+        pub fn fix(&self) -> Option<monee_core::Operation> {
+            let operation = match *self {
+                Finding::SnapshotWalletWithoutRow { wallet_id, balance }
+                    if balance == Balance::ZERO =>
+                {
+                    monee_core::WalletOperation::Delete { wallet_id }
+                }
+                Finding::WalletWithoutSnapshot {
+                    wallet_id,
+                    currency_id,
+                    kind,
+                } => monee_core::WalletOperation::Create {
+                    wallet_id,
+                    currency_id,
+                    kind,
+                },
+                _ => return None,
+            };
+
+            Some(monee_core::Operation::Wallet(operation))
+        }
+    }
+}
+
+pub mod audit {
+    use std::collections::{HashMap, HashSet};
+
+    use monee_core::{
+        ActorId, Amount, CurrencyId, EventId, ItemTagId, MoneyMap, Snapshot, WalletId, WalletKind,
+    };
+
+    use crate::backoffice::{
+        events::{
+            apply_event::apply_event,
+            event::{DebtRegister, Event, MoveValue, PaymentReceived, Purchase, Refund},
+        },
+        wallets::wallet::Wallet,
+    };
+
+    use super::finding::{Finding, Reference};
+
+    /// Everything the database holds that is checked against each other
+    pub struct Database {
+        pub wallets: Vec<(WalletId, Wallet)>,
+        pub actors: HashSet<ActorId>,
+        pub currencies: HashSet<CurrencyId>,
+        pub items: HashSet<ItemTagId>,
+        pub snapshot: Snapshot,
+        /// Every stored event, oldest first
+        pub events: Vec<(EventId, Event)>,
+    }
+
+    /// Catalog entries `event` points to
+    pub fn references(event: &Event) -> Vec<Reference> {
+        let spent = |item: ItemTagId, actors: &[ActorId]| {
+            let actors: Vec<_> = actors.iter().copied().map(Reference::Actor).collect();
+            std::iter::once(Reference::Item(item)).chain(actors)
+        };
+
+        match event {
+            Event::Purchase(Purchase {
+                item,
+                actors,
+                wallet_id,
+                ..
+            })
+            | Event::Refund(Refund {
+                item,
+                actors,
+                wallet_id,
+                ..
+            }) => std::iter::once(Reference::Wallet(*wallet_id))
+                .chain(spent(*item, actors))
+                .collect(),
+            Event::MoveValue(MoveValue { from, to, fee, .. }) => {
+                let mut references = vec![Reference::Wallet(*from), Reference::Wallet(*to)];
+                if let Some(fee) = fee {
+                    references.extend(spent(fee.item, &fee.actors));
+                }
+                references
+            }
+            Event::RegisterBalance(register) => vec![Reference::Wallet(register.wallet_id)],
+            Event::ReconcileBalance(reconcile) => vec![Reference::Wallet(reconcile.wallet_id)],
+            Event::RegisterDebt(DebtRegister {
+                currency_id,
+                actor_id,
+                wallet_id,
+                ..
+            })
+            | Event::RegisterLoan(DebtRegister {
+                currency_id,
+                actor_id,
+                wallet_id,
+                ..
+            }) => [Reference::Currency(*currency_id), Reference::Actor(*actor_id)]
+                .into_iter()
+                .chain(wallet_id.map(Reference::Wallet))
+                .collect(),
+            Event::AccrueDebtInterest(_) | Event::AccrueLoanInterest(_) => Vec::new(),
+            Event::PaymentReceived(PaymentReceived {
+                actor_id,
+                wallet_id,
+                ..
+            }) => vec![Reference::Actor(*actor_id), Reference::Wallet(*wallet_id)],
+        }
+    }
+
+    /// Looks for everything that does not add up in `database`
+    pub fn audit(database: &Database) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let snapshot = &database.snapshot;
+        let rows: HashSet<_> = database.wallets.iter().map(|(id, _)| *id).collect();
+
+        for (wallet_id, wallet) in database.wallets.iter() {
+            match snapshot.wallets.get(wallet_id) {
+                None => findings.push(Finding::WalletWithoutSnapshot {
+                    wallet_id: *wallet_id,
+                    currency_id: wallet.currency_id,
+                    kind: wallet.kind,
+                }),
+                Some(money) if money.currency_id != wallet.currency_id => {
+                    findings.push(Finding::CurrencyMismatch {
+                        wallet_id: *wallet_id,
+                        row: wallet.currency_id,
+                        snapshot: money.currency_id,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (wallet_id, money) in snapshot.wallets.iter() {
+            if !rows.contains(wallet_id) {
+                findings.push(Finding::SnapshotWalletWithoutRow {
+                    wallet_id: *wallet_id,
+                    balance: money.balance,
+                });
+            }
+        }
+
+        for (debts, loan) in [(&snapshot.debts, false), (&snapshot.loans, true)] {
+            for (debt_id, debt) in debts.iter() {
+                if !database.actors.contains(&debt.actor_id) {
+                    findings.push(Finding::DebtWithoutActor {
+                        debt_id: *debt_id,
+                        actor_id: debt.actor_id,
+                        loan,
+                    });
+                }
+            }
+        }
+
+        for (event_id, event) in database.events.iter() {
+            let missing = references(event).into_iter().filter(|reference| match reference {
+                Reference::Wallet(id) => !rows.contains(id),
+                Reference::Item(id) => !database.items.contains(id),
+                Reference::Actor(id) => !database.actors.contains(id),
+                Reference::Currency(id) => !database.currencies.contains(id),
+            });
+
+            findings.extend(missing.map(|reference| Finding::MissingReference {
+                event_id: *event_id,
+                reference,
+            }));
+        }
+
+        match replay(database) {
+            Ok(replayed) => findings.extend(divergences(snapshot, &replayed)),
+            Err(event_id) => findings.push(Finding::ReplayFailed { event_id }),
+        }
+
+        findings
+    }
+
+    /// Replays every event from empty wallets, the same way past balances are rebuilt
+    fn replay(database: &Database) -> Result<Snapshot, EventId> {
+        let mut snapshot = Snapshot::default();
+        for (wallet_id, wallet) in database.wallets.iter() {
+            snapshot
+                .apply(monee_core::Operation::Wallet(
+                    monee_core::WalletOperation::Create {
+                        wallet_id: *wallet_id,
+                        currency_id: wallet.currency_id,
+                        kind: WalletKind::Overdraft { credit_limit: None },
+                    },
+                ))
+                .expect("wallet rows to be unique");
+        }
+
+        for (event_id, event) in database.events.iter() {
+            apply_event(&mut snapshot, event).map_err(|_| *event_id)?;
+        }
+
+        Ok(snapshot)
+    }
+
+    fn divergences(stored: &Snapshot, replayed: &Snapshot) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        for (wallet_id, wallet) in stored.wallets.iter() {
+            let Some(expected) = replayed.wallets.get(wallet_id) else {
+                continue;
+            };
+
+            if wallet.balance != expected.balance {
+                findings.push(Finding::WalletDivergence {
+                    wallet_id: *wallet_id,
+                    stored: wallet.balance,
+                    replayed: expected.balance,
+                });
+            }
+        }
+
+        // Debt ids are not kept by every event, so they are compared per actor and currency
+        for (stored, replayed, loan) in [
+            (&stored.debts, &replayed.debts, false),
+            (&stored.loans, &replayed.loans, true),
+        ] {
+            let stored = owed(stored);
+            let mut replayed = owed(replayed);
+
+            for ((actor_id, currency_id), stored) in stored {
+                let replayed = replayed
+                    .remove(&(actor_id, currency_id))
+                    .unwrap_or_default();
+                if stored != replayed {
+                    findings.push(Finding::DebtDivergence {
+                        actor_id,
+                        currency_id,
+                        loan,
+                        stored,
+                        replayed,
+                    });
+                }
+            }
+
+            let only_replayed = replayed
+                .into_iter()
+                .filter(|(_, amount)| *amount != Amount::default());
+            findings.extend(only_replayed.map(|((actor_id, currency_id), replayed)| {
+                Finding::DebtDivergence {
+                    actor_id,
+                    currency_id,
+                    loan,
+                    stored: Amount::default(),
+                    replayed,
+                }
+            }));
+        }
+
+        findings
+    }
+
+    fn owed(debts: &MoneyMap<monee_core::Debt>) -> HashMap<(ActorId, CurrencyId), Amount> {
+        let mut owed: HashMap<_, Amount> = HashMap::new();
+        for (_, debt) in debts.iter() {
+            let amount = owed
+                .entry((debt.actor_id, debt.money.currency_id))
+                .or_default();
+            // An overflowing total is way past any real debt, it is left as it was
+            if let Some(total) = amount.checked_add(debt.money.amount) {
+                *amount = total;
+            }
+        }
+
+        owed
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use monee_core::{Operation, WalletOperation};
+
+        use crate::backoffice::events::event::RegisterBalance;
+
+        use super::*;
+
+        fn database(wallets: &[WalletId], currency_id: CurrencyId) -> Database {
+            Database {
+                wallets: wallets
+                    .iter()
+                    .map(|id| {
+                        let wallet = Wallet {
+                            currency_id,
+                            name: "cash".parse().unwrap(),
+                            description: String::new(),
+                            kind: WalletKind::Debit,
+                        };
+                        (*id, wallet)
+                    })
+                    .collect(),
+                actors: HashSet::new(),
+                currencies: HashSet::from([currency_id]),
+                items: HashSet::new(),
+                snapshot: Snapshot::default(),
+                events: Vec::new(),
+            }
+        }
+
+        fn create(snapshot: &mut Snapshot, wallet_id: WalletId, currency_id: CurrencyId) {
+            snapshot
+                .apply(Operation::Wallet(WalletOperation::Create {
+                    wallet_id,
+                    currency_id,
+                    kind: WalletKind::Debit,
+                }))
+                .unwrap();
+        }
+
+        #[test]
+        fn finds_wallets_out_of_the_snapshot_and_fixes_them() {
+            let (kept, unsaved, orphan) = (WalletId::new(), WalletId::new(), WalletId::new());
+            let currency_id = CurrencyId::new();
+            let mut database = database(&[kept, unsaved], currency_id);
+            create(&mut database.snapshot, kept, currency_id);
+            create(&mut database.snapshot, orphan, currency_id);
+
+            let findings = audit(&database);
+            assert_eq!(findings.len(), 2);
+            assert!(findings.contains(&Finding::WalletWithoutSnapshot {
+                wallet_id: unsaved,
+                currency_id,
+                kind: WalletKind::Debit,
+            }));
+            assert!(findings.contains(&Finding::SnapshotWalletWithoutRow {
+                wallet_id: orphan,
+                balance: Default::default(),
+            }));
+
+            for operation in findings.iter().filter_map(Finding::fix) {
+                database.snapshot.apply(operation).unwrap();
+            }
+            assert!(audit(&database).is_empty());
+        }
+
+        #[test]
+        fn finds_missing_references_and_divergences() {
+            let (wallet_id, missing) = (WalletId::new(), WalletId::new());
+            let currency_id = CurrencyId::new();
+            let mut database = database(&[wallet_id], currency_id);
+            create(&mut database.snapshot, wallet_id, currency_id);
+
+            let purchase_id = EventId::new();
+            let item = ItemTagId::new();
+            database.events = vec![
+                (
+                    EventId::new(),
+                    Event::RegisterBalance(RegisterBalance {
+                        wallet_id,
+                        amount: "20".parse().unwrap(),
+                    }),
+                ),
+                (
+                    purchase_id,
+                    Event::Purchase(Purchase {
+                        item,
+                        actors: Box::new([]),
+                        wallet_id,
+                        amount: "5".parse().unwrap(),
+                        quantity: None,
+                    }),
+                ),
+            ];
+
+            let findings = audit(&database);
+            assert!(findings.contains(&Finding::MissingReference {
+                event_id: purchase_id,
+                reference: Reference::Item(item),
+            }));
+            assert!(findings.contains(&Finding::WalletDivergence {
+                wallet_id,
+                stored: Default::default(),
+                replayed: "15".parse().unwrap(),
+            }));
+            assert!(findings.iter().all(|finding| finding.fix().is_none()));
+
+            let failing_id = EventId::new();
+            database.events.push((
+                failing_id,
+                Event::RegisterBalance(RegisterBalance {
+                    wallet_id: missing,
+                    amount: "1".parse().unwrap(),
+                }),
+            ));
+
+            let findings = audit(&database);
+            assert!(findings.contains(&Finding::ReplayFailed {
+                event_id: failing_id
+            }));
+            assert!(!findings
+                .iter()
+                .any(|finding| matches!(finding, Finding::WalletDivergence { .. })));
+        }
+    }
+}
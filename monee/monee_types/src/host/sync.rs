@@ -12,13 +12,15 @@ pub mod node_changes {
 
     use crate::{backoffice::events::event::Event, shared::date::Datetime};
 
-    use super::catalog::Catalog;
+    use super::{catalog::Catalog, catalog_removals::CatalogRemovals};
 
     #[derive(serde::Serialize, serde::Deserialize)]
     pub struct NodeChanges {
         pub events: Vec<EventEntry>,
         #[serde(flatten)]
         pub data: Catalog,
+        #[serde(default)]
+        pub removals: CatalogRemovals,
     }
 
     #[derive(serde::Serialize, serde::Deserialize)]
@@ -38,7 +40,7 @@ pub mod catalog {
         wallets::wallet::Wallet,
     };
 
-    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+    #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
     pub struct Catalog {
         pub actors: Vec<(ActorId, Actor)>,
         pub currencies: Vec<(CurrencyId, Currency)>,
@@ -47,6 +49,34 @@ pub mod catalog {
     }
 }
 
+pub mod catalog_removals {
+    use monee_core::{ActorId, CurrencyId, ItemTagId, WalletId};
+
+    /// Catalog entries deleted on a node, merged entries also carry the entry that absorbed them
+    #[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+    pub struct CatalogRemovals {
+        pub actors: Vec<Removal<ActorId>>,
+        pub currencies: Vec<CurrencyId>,
+        pub items: Vec<Removal<ItemTagId>>,
+        pub wallets: Vec<WalletId>,
+    }
+
+    impl CatalogRemovals {
+        pub fn is_empty(&self) -> bool {
+            self.actors.is_empty()
+                && self.currencies.is_empty()
+                && self.items.is_empty()
+                && self.wallets.is_empty()
+        }
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+    pub struct Removal<K> {
+        pub id: K,
+        pub merged_into: Option<K>,
+    }
+}
+
 pub mod host_state {
     use monee_core::Snapshot;
 
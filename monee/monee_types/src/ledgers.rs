@@ -0,0 +1,139 @@
+pub mod ledger_name {
+    use std::{fmt::Display, str::FromStr};
+
+    /// Names a ledger and the database it is kept in, so it is limited to what every engine
+    /// accepts as a database name
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+    #[serde(try_from = "String")]
+    pub struct LedgerName(String);
+
+    /// Always present, kept in the configured database
+    pub const MAIN: &str = "main";
+
+    const MAX_LEN: usize = 32;
+
+    impl LedgerName {
+        pub fn main() -> Self {
+            Self(MAIN.to_owned())
+        }
+
+        pub fn is_main(&self) -> bool {
+            self.0 == MAIN
+        }
+
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl Display for LedgerName {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum Error {
+        #[error("Ledger name can not be empty")]
+        Empty,
+        #[error("Ledger name can not be longer than {MAX_LEN} characters")]
+        TooLong,
+        #[error("Ledger name must start with a letter")]
+        InvalidStart,
+        #[error("Invalid character: {0:?}, use lowercase letters, digits, '-' or '_'")]
+        InvalidCharacter(char),
+    }
+
+    impl TryFrom<String> for LedgerName {
+        type Error = Error;
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            let Some(first) = value.chars().next() else {
+                return Err(Error::Empty);
+            };
+
+            if value.len() > MAX_LEN {
+                return Err(Error::TooLong);
+            }
+
+            if !first.is_ascii_lowercase() {
+                return Err(Error::InvalidStart);
+            }
+
+            match value.chars().find(|c| {
+                !(c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(*c, '-' | '_'))
+            }) {
+                Some(c) => Err(Error::InvalidCharacter(c)),
+                None => Ok(Self(value)),
+            }
+        }
+    }
+
+    impl FromStr for LedgerName {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Self::try_from(s.to_owned())
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::{Error, LedgerName};
+
+        #[test]
+        fn accepts_slugs() {
+            assert!("household".parse::<LedgerName>().is_ok());
+            assert!("trip-2026_jp".parse::<LedgerName>().is_ok());
+        }
+
+        #[test]
+        fn rejects_names_engines_could_not_use() {
+            assert!(matches!("".parse::<LedgerName>(), Err(Error::Empty)));
+            assert!(matches!(
+                "2026".parse::<LedgerName>(),
+                Err(Error::InvalidStart)
+            ));
+            assert!(matches!(
+                "House".parse::<LedgerName>(),
+                Err(Error::InvalidStart)
+            ));
+            assert!(matches!(
+                "my ledger".parse::<LedgerName>(),
+                Err(Error::InvalidCharacter(' '))
+            ));
+            assert!(matches!(
+                "a".repeat(33).parse::<LedgerName>(),
+                Err(Error::TooLong)
+            ));
+        }
+    }
+}
+
+pub mod ledger_list {
+    use super::ledger_name::LedgerName;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+    pub struct LedgerList {
+        /// Used when no other one is asked for
+        pub current: LedgerName,
+        /// Every ledger, the main one first
+        pub ledgers: Vec<LedgerName>,
+    }
+}
+
+pub mod errors {
+    use super::ledger_name::LedgerName;
+
+    #[derive(thiserror::Error, Debug, Clone, serde::Serialize, serde::Deserialize)]
+    pub enum LedgerError {
+        #[error("Ledger {0} not found")]
+        NotFound(LedgerName),
+        #[error("Ledger {0} already exists")]
+        AlreadyExists(LedgerName),
+        #[error("The main ledger can not be deleted")]
+        IsMain,
+        #[error("The current ledger can not be deleted, switch to another one first")]
+        IsCurrent,
+    }
+}
@@ -1,13 +1,18 @@
 pub mod apps;
 pub mod backoffice;
+pub mod doctor;
 pub mod host;
+pub mod ledgers;
 pub mod nodes;
 pub mod reports;
+pub mod vault;
 
 pub mod shared {
     pub mod alias;
+    pub mod slug;
     pub mod date {
         pub type Datetime = chrono::DateTime<chrono::Utc>;
+        pub type Date = chrono::NaiveDate;
         pub use chrono::Utc as Timezone;
     }
 
@@ -16,5 +21,26 @@ pub mod shared {
         pub enum UniqueSaveError {
             AlreadyExists(String),
         }
+
+        #[derive(serde::Serialize, Debug, Clone, serde::Deserialize)]
+        pub enum UpdateError {
+            NotFound,
+            AlreadyExists(String),
+            /// Stored amounts have more decimals than the update allows
+            PrecisionLoss,
+        }
+
+        #[derive(serde::Serialize, Debug, Clone, serde::Deserialize)]
+        pub enum DeleteError {
+            NotFound,
+            /// Events or snapshot entries still point to the entity
+            Referenced,
+        }
+
+        #[derive(serde::Serialize, Debug, Clone, serde::Deserialize)]
+        pub enum MergeError {
+            NotFound,
+            SameEntity,
+        }
     }
 }
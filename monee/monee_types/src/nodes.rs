@@ -47,12 +47,16 @@ pub mod sync {
     pub mod changes_record {
         use monee_core::{ActorId, CurrencyId, ItemTagId, WalletId};
 
+        use crate::host::sync::catalog_removals::CatalogRemovals;
+
         #[derive(Default, serde::Serialize, serde::Deserialize, Clone)]
         pub struct ChangesRecord {
             pub currencies: Vec<CurrencyId>,
             pub actors: Vec<ActorId>,
             pub wallets: Vec<WalletId>,
             pub items: Vec<ItemTagId>,
+            #[serde(default)]
+            pub removals: CatalogRemovals,
         }
     }
 
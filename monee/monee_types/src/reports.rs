@@ -1,2 +1,8 @@
+pub mod balances;
 pub mod events;
+pub mod goals;
+pub mod installments;
+pub mod net_worth;
+pub mod prices;
 pub mod snapshot;
+pub mod spending;
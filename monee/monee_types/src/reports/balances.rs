@@ -0,0 +1,284 @@
+pub mod actor_balance {
+    use monee_core::{ActorId, Balance};
+
+    use crate::{
+        backoffice::{actors::actor::Actor, currencies::currency::Currency},
+        reports::snapshot::snapshot::Snapshot,
+    };
+
+    use super::settle_up::Payment;
+
+    /// Net of every open loan and debt with an actor, positive when the actor owes me
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct ActorBalance {
+        pub actor_id: ActorId,
+        pub actor: Actor,
+        pub balance: Balance,
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct CurrencyBalances {
+        pub currency: Currency,
+        pub actors: Vec<ActorBalance>,
+        /// Payments that clear every balance above
+        pub settlement: Vec<Payment>,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum Error {
+        /// A net with an actor, or what everyone owes me together, does not fit a balance
+        Overflow,
+    }
+
+    /// Balances per currency, actors with nothing pending are left out.
+    /// When `group` is not empty only its actors are taken into account
+    pub fn actor_balances(
+        snapshot: &Snapshot,
+        group: &[ActorId],
+    ) -> Result<Vec<CurrencyBalances>, Error> {
+        let mut currencies: Vec<CurrencyBalances> = Vec::new();
+
+        let loans = snapshot.loans.values().map(|entry| (entry, true));
+        let debts = snapshot.debts.values().map(|entry| (entry, false));
+
+        for ((debt, money), is_loan) in loans.chain(debts) {
+            if !group.is_empty() && !group.contains(&debt.actor_id) {
+                continue;
+            }
+
+            let position = currencies
+                .iter()
+                .position(|balances| balances.currency == money.currency);
+            let balances = match position {
+                Some(i) => &mut currencies[i],
+                None => {
+                    currencies.push(CurrencyBalances {
+                        currency: money.currency.clone(),
+                        actors: Vec::new(),
+                        settlement: Vec::new(),
+                    });
+                    currencies.last_mut().expect("to have just pushed")
+                }
+            };
+
+            let position = balances
+                .actors
+                .iter()
+                .position(|actor| actor.actor_id == debt.actor_id);
+            let actor = match position {
+                Some(i) => &mut balances.actors[i],
+                None => {
+                    balances.actors.push(ActorBalance {
+                        actor_id: debt.actor_id,
+                        actor: debt.actor.clone(),
+                        balance: Balance::ZERO,
+                    });
+                    balances.actors.last_mut().expect("to have just pushed")
+                }
+            };
+
+            actor.balance = if is_loan {
+                actor.balance.checked_add(money.amount)
+            } else {
+                actor.balance.checked_sub(money.amount)
+            }
+            .ok_or(Error::Overflow)?;
+        }
+
+        for balances in currencies.iter_mut() {
+            balances.actors.retain(|actor| actor.balance != Balance::ZERO);
+            balances
+                .actors
+                .sort_by_key(|actor| actor.actor.name.to_string());
+
+            let positions = balances
+                .actors
+                .iter()
+                .map(|actor| (actor.actor_id, actor.balance));
+            balances.settlement = super::settle_up::settle_up(positions).ok_or(Error::Overflow)?;
+        }
+
+        currencies.retain(|balances| !balances.actors.is_empty());
+        currencies.sort_by(|a, b| a.currency.code.as_ref().cmp(b.currency.code.as_ref()));
+
+        Ok(currencies)
+    }
+}
+
+pub mod settle_up {
+    use std::cmp::Reverse;
+
+    use monee_core::{ActorId, Amount, Balance};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case", tag = "type", content = "id")]
+    pub enum Party {
+        Me,
+        Actor(ActorId),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct Payment {
+        pub from: Party,
+        pub to: Party,
+        pub amount: Amount,
+    }
+
+    /// Payments that leave everyone, me included, at zero given each actor balance with me
+    /// (positive when the actor owes me).
+    /// Parties owing the exact amount another is owed pay each other first, then the largest
+    /// debtor pays the largest creditor, so `n` parties never need more than `n - 1` payments.
+    /// `None` when what everyone owes me together does not fit a balance
+    pub fn settle_up(
+        balances: impl IntoIterator<Item = (ActorId, Balance)>,
+    ) -> Option<Vec<Payment>> {
+        let mut debtors = Vec::new();
+        let mut creditors = Vec::new();
+        let mut mine = Balance::ZERO;
+
+        for (actor_id, balance) in balances {
+            let party = Party::Actor(actor_id);
+            if balance.is_negative() {
+                creditors.push((party, balance.negative_part()));
+                mine = mine.checked_sub(balance.negative_part())?;
+            } else if balance != Balance::ZERO {
+                debtors.push((party, balance.positive_part()));
+                mine = mine.checked_add(balance.positive_part())?;
+            }
+        }
+
+        // What others owe me is what I am owed in the group, and the other way around
+        if mine.is_negative() {
+            debtors.push((Party::Me, mine.negative_part()));
+        } else if mine != Balance::ZERO {
+            creditors.push((Party::Me, mine.positive_part()));
+        }
+
+        let mut payments = Vec::new();
+
+        for debtor in debtors.iter_mut() {
+            let exact = creditors
+                .iter_mut()
+                .find(|creditor| creditor.1 == debtor.1 && creditor.1 != Amount::default());
+            if let Some(creditor) = exact {
+                payments.push(Payment {
+                    from: debtor.0,
+                    to: creditor.0,
+                    amount: debtor.1,
+                });
+                debtor.1 = Amount::default();
+                creditor.1 = Amount::default();
+            }
+        }
+
+        debtors.retain(|debtor| debtor.1 != Amount::default());
+        creditors.retain(|creditor| creditor.1 != Amount::default());
+        // Stable sorts keep the given order among equal amounts
+        debtors.sort_by_key(|debtor| Reverse(debtor.1));
+        creditors.sort_by_key(|creditor| Reverse(creditor.1));
+
+        let mut debtors = debtors.into_iter().peekable();
+        let mut creditors = creditors.into_iter().peekable();
+        while let (Some(debtor), Some(creditor)) = (debtors.peek_mut(), creditors.peek_mut()) {
+            let amount = debtor.1.min(creditor.1);
+            payments.push(Payment {
+                from: debtor.0,
+                to: creditor.0,
+                amount,
+            });
+
+            debtor.1 -= amount;
+            creditor.1 -= amount;
+
+            if debtor.1 == Amount::default() {
+                debtors.next();
+            }
+            if creditor.1 == Amount::default() {
+                creditors.next();
+            }
+        }
+
+        Some(payments)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn balance(s: &str) -> Balance {
+            match s.strip_prefix('-') {
+                Some(s) => Balance::ZERO.saturating_sub(s.parse().unwrap()),
+                None => Balance::ZERO.saturating_add(s.parse().unwrap()),
+            }
+        }
+
+        fn payment(from: Party, to: Party, amount: &str) -> Payment {
+            Payment {
+                from,
+                to,
+                amount: amount.parse().unwrap(),
+            }
+        }
+
+        #[test]
+        fn actors_pay_me_what_they_owe() {
+            let ana = ActorId::new();
+            let payments = settle_up([(ana, balance("45"))]).unwrap();
+
+            assert_eq!(payments, [payment(Party::Actor(ana), Party::Me, "45")]);
+        }
+
+        #[test]
+        fn debtors_pay_creditors_directly() {
+            let ana = ActorId::new();
+            let bob = ActorId::new();
+            let payments = settle_up([(ana, balance("45")), (bob, balance("-20"))]).unwrap();
+
+            assert_eq!(
+                payments,
+                [
+                    payment(Party::Actor(ana), Party::Me, "25"),
+                    payment(Party::Actor(ana), Party::Actor(bob), "20"),
+                ]
+            );
+        }
+
+        #[test]
+        fn matches_exact_amounts_first() {
+            let ana = ActorId::new();
+            let bob = ActorId::new();
+            let carl = ActorId::new();
+            let payments = settle_up([
+                (ana, balance("30")),
+                (bob, balance("10")),
+                (carl, balance("-10")),
+            ])
+            .unwrap();
+
+            assert_eq!(
+                payments,
+                [
+                    payment(Party::Actor(ana), Party::Me, "30"),
+                    payment(Party::Actor(bob), Party::Actor(carl), "10"),
+                ]
+            );
+        }
+
+        #[test]
+        fn nothing_to_settle() {
+            assert!(settle_up([(ActorId::new(), Balance::ZERO)])
+                .unwrap()
+                .is_empty());
+        }
+
+        #[test]
+        fn refuses_totals_that_do_not_fit() {
+            let payments = settle_up([
+                (ActorId::new(), balance("90000000000")),
+                (ActorId::new(), balance("90000000000")),
+            ]);
+
+            assert_eq!(payments, None);
+        }
+    }
+}
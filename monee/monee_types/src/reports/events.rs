@@ -1,14 +1,16 @@
 
 
 pub mod event {
-    use monee_core::Amount;
+    use monee_core::{Amount, Balance};
 
     use crate::{
         backoffice::{
-            actors::actor::Actor, currencies::currency::Currency,
+            actors::actor::Actor,
+            currencies::currency::Currency,
+            events::{quantity::Quantity, reconcile},
             wallets::wallet_name::WalletName,
         },
-        shared::date::Datetime,
+        shared::date::{Date, Datetime},
     };
 
     #[derive(serde::Deserialize, Debug)]
@@ -19,19 +21,59 @@ pub mod event {
             actors: Box<[Actor]>,
             wallet: WalletName,
             amount: Amount,
+            #[serde(default)]
+            quantity: Option<Quantity>,
+        },
+        Refund {
+            item: String,
+            actors: Box<[Actor]>,
+            wallet: WalletName,
+            amount: Amount,
         },
         MoveValue {
             from: WalletName,
             to: WalletName,
             amount: Amount,
+            #[serde(default)]
+            fee: Option<Amount>,
         },
         RegisterBalance {
             wallet: WalletName,
             amount: Amount,
         },
+        ReconcileBalance {
+            wallet: WalletName,
+            observed: Balance,
+            adjustment: Balance,
+        },
 
         RegisterDebt(DebtRegister),
         RegisterLoan(DebtRegister),
+        AccrueDebtInterest {
+            amount: Amount,
+            until: Date,
+        },
+        AccrueLoanInterest {
+            amount: Amount,
+            until: Date,
+        },
+    }
+
+    impl Event {
+        /// Whether the event is a reconciliation that had to adjust the wallet by a lot
+        pub fn is_large_discrepancy(&self) -> bool {
+            match self {
+                Event::ReconcileBalance {
+                    observed,
+                    adjustment,
+                    ..
+                } => {
+                    let expected = observed.checked_diff(*adjustment).unwrap_or(*observed);
+                    reconcile::is_large_discrepancy(expected, *adjustment)
+                }
+                _ => false,
+            }
+        }
     }
 
     #[derive(serde::Deserialize, Debug)]
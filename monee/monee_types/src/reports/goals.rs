@@ -0,0 +1,16 @@
+pub mod goal_report {
+    use monee_core::GoalId;
+
+    use crate::backoffice::{
+        currencies::currency::Currency,
+        goals::{goal::Goal, goal_progress::GoalProgress},
+    };
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct GoalReport {
+        pub id: GoalId,
+        pub goal: Goal,
+        pub currency: Currency,
+        pub progress: GoalProgress,
+    }
+}
@@ -0,0 +1,29 @@
+pub mod debt_schedule {
+    use monee_core::DebtId;
+
+    use crate::{
+        backoffice::{
+            actors::actor::Actor, currencies::currency::Currency, debts::amortization::Installment,
+        },
+        shared::date::Date,
+    };
+
+    /// Amortization table of a debt or loan with an installment plan
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct DebtSchedule {
+        pub debt_id: DebtId,
+        pub is_loan: bool,
+        pub actor: Actor,
+        pub currency: Currency,
+        pub installments: Vec<Installment>,
+    }
+
+    impl DebtSchedule {
+        /// Installments due on `today` or later
+        pub fn upcoming(&self, today: Date) -> impl Iterator<Item = &Installment> {
+            self.installments
+                .iter()
+                .filter(move |installment| installment.due >= today)
+        }
+    }
+}
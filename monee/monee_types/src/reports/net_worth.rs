@@ -0,0 +1,282 @@
+pub mod consolidate {
+    use monee_core::{Amount, Balance, CurrencyId, MinorUnits, Snapshot, WalletId, WalletKind};
+
+    use crate::{
+        backoffice::{
+            events::{apply_event, event::Event},
+            exchange_rates::rate_table::RateTable,
+        },
+        shared::date::Date,
+    };
+
+    /// What is held in one currency: wallets plus loans minus debts
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct Holding {
+        pub currency_id: CurrencyId,
+        pub balance: Balance,
+        /// The balance in the base currency, `None` when there is no rate to convert it
+        pub converted: Option<Balance>,
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct NetWorth {
+        pub date: Date,
+        pub base: CurrencyId,
+        /// Sum of every converted holding, the ones missing a rate are left out
+        pub total: Balance,
+        pub holdings: Vec<Holding>,
+    }
+
+    impl NetWorth {
+        /// Currencies that could not be converted, the total is incomplete if there is any
+        pub fn missing_rates(&self) -> impl Iterator<Item = CurrencyId> + '_ {
+            self.holdings
+                .iter()
+                .filter(|holding| holding.converted.is_none())
+                .map(|holding| holding.currency_id)
+        }
+    }
+
+    fn add(balance: Balance, amount: Amount, negative: bool) -> Balance {
+        if negative {
+            balance.saturating_sub(amount)
+        } else {
+            balance.saturating_add(amount)
+        }
+    }
+
+    /// Consolidates `snapshot` into `base` with the rates in effect at `date`
+    pub fn net_worth(
+        snapshot: &Snapshot,
+        base: CurrencyId,
+        units: MinorUnits,
+        rates: &RateTable,
+        date: Date,
+    ) -> NetWorth {
+        let mut holdings: Vec<Holding> = Vec::new();
+        let mut hold = |currency_id: CurrencyId, amount: Amount, negative: bool| match holdings
+            .iter_mut()
+            .find(|h| h.currency_id == currency_id)
+        {
+            Some(holding) => holding.balance = add(holding.balance, amount, negative),
+            None => holdings.push(Holding {
+                currency_id,
+                balance: add(Balance::ZERO, amount, negative),
+                converted: None,
+            }),
+        };
+
+        for (_, wallet) in snapshot.wallets.iter() {
+            let balance = wallet.balance;
+            hold(
+                wallet.currency_id,
+                balance.unsigned_abs(),
+                balance.is_negative(),
+            );
+        }
+        for (_, loan) in snapshot.loans.iter() {
+            hold(loan.money.currency_id, loan.money.amount, false);
+        }
+        for (_, debt) in snapshot.debts.iter() {
+            hold(debt.money.currency_id, debt.money.amount, true);
+        }
+
+        let mut total = Balance::ZERO;
+        for holding in holdings.iter_mut() {
+            let amount = holding.balance.unsigned_abs();
+            let negative = holding.balance.is_negative();
+
+            holding.converted = rates
+                .convert(amount, holding.currency_id, base, date, units)
+                .map(|converted| add(Balance::ZERO, converted, negative));
+
+            if let Some(converted) = holding.converted {
+                total = add(total, converted.unsigned_abs(), converted.is_negative());
+            }
+        }
+
+        holdings.retain(|holding| holding.balance != Balance::ZERO);
+
+        NetWorth {
+            date,
+            base,
+            total,
+            holdings,
+        }
+    }
+
+    /// Rebuilds the snapshot left by `events`, starting from every wallet empty.
+    /// Wallets may go below zero, what kind they were back then is not known
+    pub fn replay<'a>(
+        wallets: impl IntoIterator<Item = (WalletId, CurrencyId)>,
+        events: impl IntoIterator<Item = &'a Event>,
+    ) -> Result<Snapshot, apply_event::Error> {
+        replay_from(Snapshot::default(), wallets, events)
+    }
+
+    /// Applies `events` on top of `snapshot`, the wallets it is missing are added empty
+    /// the same way [`replay`] does
+    pub fn replay_from<'a>(
+        mut snapshot: Snapshot,
+        wallets: impl IntoIterator<Item = (WalletId, CurrencyId)>,
+        events: impl IntoIterator<Item = &'a Event>,
+    ) -> Result<Snapshot, apply_event::Error> {
+        for (wallet_id, currency_id) in wallets {
+            if snapshot.wallets.get(&wallet_id).is_some() {
+                continue;
+            }
+
+            snapshot.apply(monee_core::Operation::Wallet(
+                monee_core::WalletOperation::Create {
+                    wallet_id,
+                    currency_id,
+                    kind: WalletKind::Overdraft { credit_limit: None },
+                },
+            ))?;
+        }
+
+        for event in events {
+            apply_event::apply_event(&mut snapshot, event)?;
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Last day of each of the `months` months up to the one `date` is in, oldest first
+    pub fn month_ends(date: Date, months: u32) -> Vec<Date> {
+        use chrono::{Datelike, Months};
+
+        let Some(first) = date.with_day(1) else {
+            return Vec::new();
+        };
+
+        (0..months)
+            .rev()
+            .filter_map(|back| {
+                let start = first.checked_sub_months(Months::new(back))?;
+                start
+                    .checked_add_months(Months::new(1))?
+                    .pred_opt()
+                    .map(|end| end.min(date))
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use monee_core::{ActorId, DebtId, DebtOperation, Operation, WalletOperation};
+
+        use crate::backoffice::exchange_rates::exchange_rate::ExchangeRate;
+
+        use super::*;
+
+        fn date(s: &str) -> Date {
+            s.parse().unwrap()
+        }
+
+        #[test]
+        fn converts_every_holding_to_base() {
+            let (pen, usd, eur) = (CurrencyId::new(), CurrencyId::new(), CurrencyId::new());
+            let mut snapshot = Snapshot::default();
+
+            for (currency_id, amount) in [(pen, "100"), (usd, "10"), (eur, "5")] {
+                let wallet_id = WalletId::new();
+                snapshot
+                    .apply(Operation::Wallet(WalletOperation::Create {
+                        wallet_id,
+                        currency_id,
+                        kind: Default::default(),
+                    }))
+                    .unwrap();
+                snapshot
+                    .apply(Operation::Wallet(WalletOperation::Deposit {
+                        wallet_id,
+                        amount: amount.parse().unwrap(),
+                    }))
+                    .unwrap();
+            }
+
+            let debt_id = DebtId::new();
+            snapshot
+                .apply(Operation::Debt(DebtOperation::Incur {
+                    debt_id,
+                    currency_id: usd,
+                    actor_id: ActorId::new(),
+                }))
+                .unwrap();
+            snapshot
+                .apply(Operation::Debt(DebtOperation::Accumulate {
+                    debt_id,
+                    amount: "20".parse().unwrap(),
+                }))
+                .unwrap();
+
+            let rates = RateTable::new(vec![ExchangeRate {
+                from: usd,
+                to: pen,
+                date: date("2024-01-01"),
+                rate: "3.75".parse().unwrap(),
+            }]);
+
+            let net_worth = net_worth(
+                &snapshot,
+                pen,
+                MinorUnits::default(),
+                &rates,
+                date("2024-01-31"),
+            );
+
+            // 100 PEN + (10 - 20) USD * 3.75, EUR has no rate
+            assert_eq!(net_worth.total.to_string(), "62.5");
+            assert_eq!(net_worth.missing_rates().collect::<Vec<_>>(), [eur]);
+        }
+
+        #[test]
+        fn replays_on_top_of_a_checkpoint() {
+            use crate::backoffice::events::event::RegisterBalance;
+
+            let (kept, added) = (WalletId::new(), WalletId::new());
+            let currency_id = CurrencyId::new();
+            let mut checkpoint = Snapshot::default();
+            checkpoint
+                .apply(Operation::Wallet(WalletOperation::Create {
+                    wallet_id: kept,
+                    currency_id,
+                    kind: Default::default(),
+                }))
+                .unwrap();
+            checkpoint
+                .apply(Operation::Wallet(WalletOperation::Deposit {
+                    wallet_id: kept,
+                    amount: "100".parse().unwrap(),
+                }))
+                .unwrap();
+
+            let events = [(kept, "10"), (added, "5")].map(|(wallet_id, amount)| {
+                Event::RegisterBalance(RegisterBalance {
+                    wallet_id,
+                    amount: amount.parse().unwrap(),
+                })
+            });
+
+            let snapshot = replay_from(
+                checkpoint,
+                [(kept, currency_id), (added, currency_id)],
+                events.iter(),
+            )
+            .unwrap_or_else(|_| panic!("events to be replayed"));
+
+            let balance = |id| snapshot.wallets.get(&id).unwrap().balance.to_string();
+            assert_eq!(balance(kept), "110");
+            assert_eq!(balance(added), "5");
+        }
+
+        #[test]
+        fn lists_month_ends() {
+            assert_eq!(
+                month_ends(date("2024-03-15"), 3),
+                [date("2024-01-31"), date("2024-02-29"), date("2024-03-15")]
+            );
+        }
+    }
+}
@@ -0,0 +1,159 @@
+pub mod unit_price {
+    use monee_core::{ActorId, Amount, CurrencyId, ItemTagId, MinorUnits, WalletId};
+
+    use crate::{
+        backoffice::{
+            actors::actor::Actor,
+            currencies::currency::Currency,
+            events::{event::Event, quantity::Unit},
+            item_tags::item_name::ItemName,
+        },
+        host::sync::node_changes::EventEntry,
+        shared::date::Date,
+    };
+
+    #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct PricePoint {
+        pub date: Date,
+        pub price: Amount,
+    }
+
+    /// Unit prices paid for an item at a seller, oldest first
+    #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct PriceHistory {
+        pub item_id: ItemTagId,
+        /// `None` for purchases without a seller
+        pub seller: Option<ActorId>,
+        pub currency_id: CurrencyId,
+        pub unit: Unit,
+        pub prices: Vec<PricePoint>,
+    }
+
+    impl PriceHistory {
+        pub fn latest(&self) -> Option<&PricePoint> {
+            self.prices.last()
+        }
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct PriceEntry {
+        pub item: ItemName,
+        pub seller: Option<Actor>,
+        pub currency: Currency,
+        pub history: PriceHistory,
+    }
+
+    /// Unit price history per item, seller, currency and unit of the purchases with a quantity.
+    /// A purchase with several sellers counts for each of them.
+    /// Purchases on wallets `currency_of` does not know are left out
+    pub fn price_histories<'a>(
+        entries: impl IntoIterator<Item = &'a EventEntry>,
+        currency_of: impl Fn(WalletId) -> Option<(CurrencyId, MinorUnits)>,
+    ) -> Vec<PriceHistory> {
+        let mut histories: Vec<PriceHistory> = Vec::new();
+
+        for entry in entries {
+            let Event::Purchase(purchase) = &entry.event else {
+                continue;
+            };
+            let Some(quantity) = purchase.quantity else {
+                continue;
+            };
+            let Some((currency_id, units)) = currency_of(purchase.wallet_id) else {
+                continue;
+            };
+            let Some(price) = purchase.unit_price(units) else {
+                continue;
+            };
+
+            let sellers: Vec<Option<ActorId>> = if purchase.actors.is_empty() {
+                vec![None]
+            } else {
+                purchase.actors.iter().copied().map(Some).collect()
+            };
+
+            for seller in sellers {
+                let point = PricePoint {
+                    date: entry.created_at.date_naive(),
+                    price,
+                };
+
+                let found = histories.iter_mut().find(|history| {
+                    history.item_id == purchase.item
+                        && history.seller == seller
+                        && history.currency_id == currency_id
+                        && history.unit == quantity.unit
+                });
+
+                match found {
+                    Some(history) => history.prices.push(point),
+                    None => histories.push(PriceHistory {
+                        item_id: purchase.item,
+                        seller,
+                        currency_id,
+                        unit: quantity.unit,
+                        prices: vec![point],
+                    }),
+                }
+            }
+        }
+
+        histories
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use monee_core::EventId;
+
+        use crate::backoffice::events::{event::Purchase, quantity::Quantity};
+
+        use super::*;
+
+        #[test]
+        fn tracks_unit_price_per_seller() {
+            let item = ItemTagId::new();
+            let (store, market) = (ActorId::new(), ActorId::new());
+            let (wallet_id, currency_id) = (WalletId::new(), CurrencyId::new());
+
+            let purchase = |seller, amount: &str, kg: Option<&str>, date: &str| EventEntry {
+                id: EventId::default(),
+                event: Event::Purchase(Purchase {
+                    item,
+                    actors: Box::new([seller]),
+                    wallet_id,
+                    amount: amount.parse().unwrap(),
+                    quantity: kg.map(|kg| Quantity {
+                        value: kg.parse().unwrap(),
+                        unit: Unit::Kilogram,
+                    }),
+                }),
+                created_at: date.parse().unwrap(),
+            };
+            let entries = [
+                purchase(store, "7", Some("2"), "2024-01-05T10:00:00Z"),
+                purchase(market, "10", Some("2.5"), "2024-01-06T10:00:00Z"),
+                purchase(store, "9", None, "2024-01-07T10:00:00Z"),
+                purchase(store, "4", Some("1"), "2024-02-05T10:00:00Z"),
+            ];
+
+            let histories =
+                price_histories(&entries, |_| Some((currency_id, MinorUnits::default())));
+
+            let prices = |seller| {
+                histories
+                    .iter()
+                    .find(|history| history.seller == Some(seller))
+                    .map(|history| {
+                        history
+                            .prices
+                            .iter()
+                            .map(|point| point.price.to_string())
+                            .collect::<Vec<_>>()
+                    })
+            };
+            assert_eq!(histories.len(), 2);
+            assert_eq!(prices(store), Some(vec!["3.5".to_owned(), "4".to_owned()]));
+            assert_eq!(prices(market), Some(vec!["4".to_owned()]));
+        }
+    }
+}
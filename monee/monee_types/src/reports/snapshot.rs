@@ -1,7 +1,7 @@
 pub mod snapshot {
     use std::collections::HashMap;
 
-    use monee_core::{Amount, DebtId, WalletId};
+    use monee_core::{ActorId, Amount, Balance, CreditUsage, DebtId, WalletId, WalletKind};
 
     use crate::backoffice::{
         actors::actor::Actor, currencies::currency::Currency, wallets::wallet_name::WalletName,
@@ -9,19 +9,23 @@ pub mod snapshot {
 
     #[derive(serde::Deserialize, serde::Serialize)]
     pub struct Snapshot {
-        pub wallets: HashMap<WalletId, (Wallet, Money)>,
+        pub wallets: HashMap<WalletId, (Wallet, WalletMoney)>,
         pub debts: HashMap<DebtId, (Debt, Money)>,
         pub loans: HashMap<DebtId, (Debt, Money)>,
     }
 
     #[derive(serde::Deserialize, serde::Serialize)]
-    pub struct Money {
-        pub amount: Amount,
+    pub struct Money<A = Amount> {
+        pub amount: A,
         pub currency: Currency,
     }
 
+    /// Wallets may hold a negative balance when they are credit ones
+    pub type WalletMoney = Money<Balance>;
+
     #[derive(serde::Deserialize, serde::Serialize)]
     pub struct Debt {
+        pub actor_id: ActorId,
         pub actor: Actor,
     }
 
@@ -29,5 +33,14 @@ pub mod snapshot {
     pub struct Wallet {
         pub name: WalletName,
         pub description: String,
+        #[serde(default)]
+        pub kind: WalletKind,
+    }
+
+    impl Wallet {
+        /// Credit used and still available, `None` for debit wallets
+        pub fn credit_usage(&self, money: &WalletMoney) -> Option<CreditUsage> {
+            self.kind.credit_usage(money.amount)
+        }
     }
 }
@@ -0,0 +1,150 @@
+pub mod item_spending {
+    use monee_core::{Amount, Balance, CurrencyId, ItemTagId, WalletId};
+
+    use crate::backoffice::{
+        currencies::currency::Currency,
+        events::event::{Event, MoveValue, Purchase, Refund},
+        item_tags::item_name::ItemName,
+    };
+
+    /// Money spent on an item in a single currency
+    #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct ItemSpending {
+        pub item_id: ItemTagId,
+        pub currency_id: CurrencyId,
+        pub spent: Amount,
+        pub refunded: Amount,
+    }
+
+    impl ItemSpending {
+        /// What was spent once refunds are taken back, negative if more came back than went out
+        pub fn net(&self) -> Balance {
+            Balance::ZERO
+                .saturating_add(self.spent)
+                .saturating_sub(self.refunded)
+        }
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct SpendingEntry {
+        pub item: ItemName,
+        pub currency: Currency,
+        pub spending: ItemSpending,
+    }
+
+    /// Spending per item and currency, refunds are netted against the item they refund
+    /// and transfer fees are spent on their own item.
+    /// Events on wallets `currency_of` does not know are left out
+    pub fn item_spending<'a>(
+        events: impl IntoIterator<Item = &'a Event>,
+        currency_of: impl Fn(WalletId) -> Option<CurrencyId>,
+    ) -> Vec<ItemSpending> {
+        let mut spending: Vec<ItemSpending> = Vec::new();
+
+        for event in events {
+            let (item_id, wallet_id, amount, is_refund) = match event {
+                Event::Purchase(Purchase {
+                    item,
+                    wallet_id,
+                    amount,
+                    ..
+                }) => (*item, *wallet_id, *amount, false),
+                Event::Refund(Refund {
+                    item,
+                    wallet_id,
+                    amount,
+                    ..
+                }) => (*item, *wallet_id, *amount, true),
+                Event::MoveValue(MoveValue {
+                    from,
+                    fee: Some(fee),
+                    ..
+                }) => (fee.item, *from, fee.amount, false),
+                _ => continue,
+            };
+
+            let Some(currency_id) = currency_of(wallet_id) else {
+                continue;
+            };
+
+            let position = spending
+                .iter()
+                .position(|s| s.item_id == item_id && s.currency_id == currency_id);
+            let entry = match position {
+                Some(i) => &mut spending[i],
+                None => {
+                    spending.push(ItemSpending {
+                        item_id,
+                        currency_id,
+                        spent: Amount::default(),
+                        refunded: Amount::default(),
+                    });
+                    spending.last_mut().expect("to have just pushed")
+                }
+            };
+
+            if is_refund {
+                entry.refunded += amount;
+            } else {
+                entry.spent += amount;
+            }
+        }
+
+        spending
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use monee_core::ActorId;
+
+        use crate::backoffice::events::event::Fee;
+
+        use super::*;
+
+        #[test]
+        fn nets_refunds_and_counts_fees() {
+            let (item, other_item) = (ItemTagId::new(), ItemTagId::new());
+            let (wallet_id, currency_id) = (WalletId::new(), CurrencyId::new());
+
+            let purchase = |item, amount: &str| {
+                Event::Purchase(Purchase {
+                    item,
+                    actors: Box::new([ActorId::new()]),
+                    wallet_id,
+                    amount: amount.parse().unwrap(),
+                    quantity: None,
+                })
+            };
+            let events = [
+                purchase(item, "30"),
+                purchase(other_item, "5"),
+                purchase(item, "10"),
+                Event::Refund(Refund {
+                    purchase_id: None,
+                    item,
+                    actors: Box::new([]),
+                    wallet_id,
+                    amount: "12.5".parse().unwrap(),
+                }),
+                Event::MoveValue(MoveValue {
+                    from: wallet_id,
+                    to: WalletId::new(),
+                    amount: "100".parse().unwrap(),
+                    fee: Some(Fee {
+                        amount: "1.5".parse().unwrap(),
+                        item: other_item,
+                        actors: Box::new([]),
+                    }),
+                }),
+            ];
+
+            let spending = item_spending(&events, |_| Some(currency_id));
+
+            assert_eq!(spending.len(), 2);
+            assert_eq!(spending[0].spent, "40".parse().unwrap());
+            assert_eq!(spending[0].refunded, "12.5".parse().unwrap());
+            assert_eq!(spending[0].net().to_string(), "27.5");
+            assert_eq!(spending[1].net().to_string(), "6.5");
+        }
+    }
+}
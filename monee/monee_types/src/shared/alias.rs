@@ -13,7 +13,15 @@
         }
     }
 
+    impl Alias {
+        pub fn slug(&self) -> super::slug::Slug {
+            super::slug::Slug::new(&self.0)
+        }
+    }
+
     pub mod from_str {
+        use unicode_normalization::UnicodeNormalization;
+
         use super::Alias;
 
         #[derive(Debug)]
@@ -28,7 +36,7 @@
                     Error::Empty => write!(f, "Alias cannot be emtpy"),
                     Error::Invalid => write!(
                         f,
-                        "Alias must only contain 'a-z', 'A-Z', '0-9', '-', '_' characters"
+                        "Alias must only contain letters, numbers, '-' or '_' characters"
                     ),
                 }
             }
@@ -44,9 +52,10 @@
                     return Err(Error::Empty);
                 }
 
+                let s: String = s.nfc().collect();
                 let is_valid = s
                     .chars()
-                    .all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_'));
+                    .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_'));
 
                 if is_valid {
                     Ok(Alias(s.into()))
@@ -57,3 +66,27 @@
         }
     }
 
+
+    #[cfg(test)]
+    mod test {
+        use super::Alias;
+
+        #[test]
+        fn accepts_unicode_letters() {
+            let alias: Alias = "ahorros_niño".parse().unwrap();
+            assert_eq!(alias.as_ref(), "ahorros_niño");
+        }
+
+        #[test]
+        fn normalizes_to_nfc() {
+            let decomposed: Alias = "cafe\u{301}".parse().unwrap();
+            let composed: Alias = "caf\u{e9}".parse().unwrap();
+
+            assert_eq!(decomposed, composed);
+        }
+
+        #[test]
+        fn rejects_spaces() {
+            assert!("mi cafe".parse::<Alias>().is_err());
+        }
+    }
@@ -0,0 +1,83 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Case and accent folded form of a name, "Café" and "cafe" share the slug `cafe`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Slug(Box<str>);
+
+impl Slug {
+    pub fn new(name: &str) -> Self {
+        let slug: String = name
+            .nfd()
+            .filter(|c| !is_combining_mark(*c))
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        Self(slug.into())
+    }
+}
+
+impl AsRef<str> for Slug {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Slug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Picks the candidate named `name`. An exact match always wins, otherwise the
+/// candidate sharing its slug is returned, unless several of them do.
+pub fn find_match<T>(name: &str, candidates: impl IntoIterator<Item = (T, String)>) -> Option<T> {
+    let slug = Slug::new(name);
+    let mut found = None;
+    let mut ambiguous = false;
+
+    for (value, candidate) in candidates {
+        if candidate == name {
+            return Some(value);
+        }
+
+        if Slug::new(&candidate) == slug {
+            ambiguous = found.is_some();
+            found = Some(value);
+        }
+    }
+
+    if ambiguous {
+        None
+    } else {
+        found
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_match, Slug};
+
+    #[test]
+    fn folds_case_and_accents() {
+        assert_eq!(Slug::new("Ahorros_Niño"), Slug::new("ahorros_nino"));
+        assert_eq!(Slug::new("CAFÉ").as_ref(), "cafe");
+    }
+
+    #[test]
+    fn prefers_exact_match() {
+        let candidates = [(1, "cafe".to_owned()), (2, "café".to_owned())];
+        assert_eq!(find_match("café", candidates), Some(2));
+    }
+
+    #[test]
+    fn matches_by_slug() {
+        let candidates = [(1, "Café".to_owned()), (2, "ahorros".to_owned())];
+        assert_eq!(find_match("cafe", candidates), Some(1));
+    }
+
+    #[test]
+    fn ambiguous_slug_is_not_matched() {
+        let candidates = [(1, "Café".to_owned()), (2, "cafe".to_owned())];
+        assert_eq!(find_match("CAFE", candidates), None);
+    }
+}
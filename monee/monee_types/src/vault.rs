@@ -0,0 +1,19 @@
+pub mod errors {
+    #[derive(thiserror::Error, Debug, Clone, serde::Serialize, serde::Deserialize)]
+    pub enum VaultError {
+        #[error("Only embedded stores (file:// or rocksdb://) can be encrypted")]
+        Unsupported,
+        #[error("The database is locked")]
+        Locked,
+        #[error("The database is not locked")]
+        NotLocked,
+        #[error("Wrong passphrase")]
+        WrongPassphrase,
+        #[error("Not an encrypted monee file")]
+        InvalidFile,
+        #[error("The target file already exists")]
+        TargetExists,
+        #[error("The database already exists, move it away before restoring")]
+        StoreExists,
+    }
+}
@@ -1,6 +1,9 @@
 pub mod actors;
 pub mod currencies;
+pub mod debts;
 pub mod events;
+pub mod exchange_rates;
+pub mod goals;
 pub mod item_tags;
 pub(crate) mod snapshot;
 pub mod wallets;
@@ -73,3 +73,142 @@ pub mod get_all {
         }
     }
 }
+
+pub mod get_one {
+    use cream::context::FromContext;
+    use monee_core::ActorId;
+
+    use crate::{
+        backoffice::actors::domain::{actor::Actor, repository::Repository},
+        shared::{domain::context::AppContext, infrastructure::errors::InfrastructureError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct GetOne {
+        repository: Box<dyn Repository>,
+    }
+
+    impl GetOne {
+        pub async fn run(&self, id: ActorId) -> Result<Option<Actor>, InfrastructureError> {
+            self.repository.get_one(id).await
+        }
+    }
+}
+
+pub mod update_one {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::ActorId;
+    use monee_types::backoffice::actors::actor_updated::ActorUpdated;
+
+    use crate::{
+        backoffice::actors::domain::{actor::Actor, repository::Repository},
+        prelude::AppError,
+        shared::domain::{context::AppContext, errors::UpdateError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct UpdateOne {
+        repository: Box<dyn Repository>,
+        port: EventBusPort,
+    }
+
+    impl UpdateOne {
+        pub async fn run(&self, id: ActorId, actor: Actor) -> Result<(), AppError<UpdateError>> {
+            self.repository.update(id, actor).await?;
+            self.port.publish(ActorUpdated { id });
+
+            Ok(())
+        }
+    }
+}
+
+pub mod delete_one {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::ActorId;
+    use monee_types::backoffice::actors::actor_deleted::ActorDeleted;
+
+    use crate::{
+        backoffice::{
+            actors::domain::repository::Repository,
+            snapshot::application::snapshot_io::SnapshotIO,
+        },
+        prelude::AppError,
+        shared::domain::{context::AppContext, errors::DeleteError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct DeleteOne {
+        repository: Box<dyn Repository>,
+        snapshot_io: SnapshotIO,
+        port: EventBusPort,
+    }
+
+    impl DeleteOne {
+        pub async fn run(&self, id: ActorId) -> Result<(), AppError<DeleteError>> {
+            let snapshot = self.snapshot_io.read_last().await?;
+            if snapshot.references_actor(id) || self.repository.is_referenced(id).await? {
+                return Err(AppError::App(DeleteError::Referenced));
+            }
+
+            self.repository.delete(id).await?;
+            self.port.publish(ActorDeleted { id });
+
+            Ok(())
+        }
+    }
+}
+
+pub mod merge {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::ActorId;
+    use monee_types::backoffice::actors::actors_merged::ActorsMerged;
+
+    use crate::{
+        backoffice::{
+            actors::domain::repository::Repository,
+            snapshot::application::snapshot_io::SnapshotIO,
+        },
+        iprelude::CatchInfra,
+        prelude::AppError,
+        shared::domain::{context::AppContext, errors::MergeError, unit_of_work::UnitOfWork},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct Merge {
+        repository: Box<dyn Repository>,
+        snapshot_io: SnapshotIO,
+        unit_of_work: Box<dyn UnitOfWork>,
+        port: EventBusPort,
+    }
+
+    impl Merge {
+        /// Folds `source` into `target`, `source` no longer exists afterwards
+        pub async fn run(&self, source: ActorId, target: ActorId) -> Result<(), AppError<MergeError>> {
+            if source == target {
+                return Err(AppError::App(MergeError::SameEntity));
+            }
+
+            if self.repository.get_one(source).await?.is_none()
+                || self.repository.get_one(target).await?.is_none()
+            {
+                return Err(AppError::App(MergeError::NotFound));
+            }
+
+            let mut snapshot = self.snapshot_io.read_last().await?;
+            snapshot.replace_actor(source, target);
+
+            let mut tx = self.unit_of_work.begin();
+            self.repository.stage_merge(&mut tx, source, target);
+            self.snapshot_io.stage_save(&mut tx, snapshot);
+            tx.commit().await.catch_infra()?;
+
+            self.port.publish(ActorsMerged { source, target });
+
+            Ok(())
+        }
+    }
+}
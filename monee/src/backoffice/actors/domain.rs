@@ -4,7 +4,13 @@ pub mod repository {
 
     use crate::{
         prelude::AppError,
-        shared::{domain::errors::UniqueSaveError, infrastructure::errors::InfrastructureError},
+        shared::{
+            domain::{
+                errors::{DeleteError, UniqueSaveError, UpdateError},
+                unit_of_work::Transaction,
+            },
+            infrastructure::errors::InfrastructureError,
+        },
     };
 
     use super::{actor::Actor, actor_alias::ActorAlias};
@@ -19,5 +25,20 @@ pub mod repository {
         ) -> Result<Option<ActorId>, InfrastructureError>;
 
         async fn get_all(&self) -> Result<Vec<(ActorId, Actor)>, InfrastructureError>;
+
+        async fn get_one(&self, id: ActorId) -> Result<Option<Actor>, InfrastructureError>;
+
+        async fn update(&self, id: ActorId, actor: Actor) -> Result<(), AppError<UpdateError>>;
+
+        async fn delete(&self, id: ActorId) -> Result<(), AppError<DeleteError>>;
+
+        /// Whether any event still points to the actor
+        async fn is_referenced(&self, id: ActorId) -> Result<bool, InfrastructureError>;
+
+        /// Stages deleting the actor, a missing actor is left as is
+        fn stage_delete(&self, tx: &mut Transaction, id: ActorId);
+
+        /// Stages moving every event reference from `source` to `target`, then deleting `source`
+        fn stage_merge(&self, tx: &mut Transaction, source: ActorId, target: ActorId);
     }
 }
\ No newline at end of file
@@ -9,8 +9,13 @@ pub mod repository {
         iprelude::*,
         prelude::*,
         shared::{
-            domain::{context::DbContext, errors::UniqueSaveError},
-            infrastructure::database::{Connection, Entity},
+            domain::{
+                context::DbContext,
+                errors::{DeleteError, UniqueSaveError, UpdateError},
+                slug,
+                unit_of_work::Transaction,
+            },
+            infrastructure::database::{Connection, Entity, EntityKey},
         },
     };
 
@@ -39,13 +44,15 @@ pub mod repository {
         ) -> Result<Option<ActorId>, InfrastructureError> {
             let mut response = self
                 .0
-                .query("SELECT id FROM ONLY actor WHERE alias = $alias LIMIT 1")
-                .bind(("alias", alias))
+                .query("SELECT id, alias FROM actor WHERE alias != NONE")
                 .await?
                 .check()?;
 
-            let actor: Option<Entity<ActorId, ()>> = response.take(0)?;
-            Ok(actor.map(Entity::into_key))
+            let ids: Vec<EntityKey<ActorId>> = response.take("id")?;
+            let aliases: Vec<String> = response.take("alias")?;
+            let candidates = ids.into_iter().map(|k| k.0).zip(aliases);
+
+            Ok(slug::find_match(&alias.to_string(), candidates))
         }
 
         async fn get_all(&self) -> Result<Vec<(ActorId, Actor)>, InfrastructureError> {
@@ -54,5 +61,247 @@ pub mod repository {
             let actors: Vec<Entity<ActorId, Actor>> = response.take(0)?;
             Ok(actors.into_iter().map(From::from).collect())
         }
+
+        async fn get_one(&self, id: ActorId) -> Result<Option<Actor>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("SELECT * FROM ONLY type::thing('actor', $id)")
+                .bind(("id", id))
+                .await?
+                .check()?;
+
+            let actor: Option<Entity<ActorId, Actor>> = response.take(0)?;
+            Ok(actor.map(|e| e.1))
+        }
+
+        async fn update(&self, id: ActorId, actor: Actor) -> Result<(), AppError<UpdateError>> {
+            let result = self
+                .0
+                .query("UPDATE actor CONTENT $data WHERE id = type::thing('actor', $id)")
+                .bind(("id", id))
+                .bind(("data", actor))
+                .await
+                .catch_infra()?
+                .check();
+
+            let mut response = result.map_err(AppError::<UpdateError>::from)?;
+            let updated: Option<Entity<ActorId, ()>> = response.take(0).catch_infra()?;
+
+            match updated {
+                Some(_) => Ok(()),
+                None => Err(AppError::App(UpdateError::NotFound)),
+            }
+        }
+
+        async fn delete(&self, id: ActorId) -> Result<(), AppError<DeleteError>> {
+            let mut response = self
+                .0
+                .query("DELETE type::thing('actor', $id) RETURN BEFORE")
+                .bind(("id", id))
+                .await
+                .catch_infra()?
+                .check()
+                .catch_infra()?;
+
+            let deleted: Option<Entity<ActorId, ()>> = response.take(0).catch_infra()?;
+            match deleted {
+                Some(_) => Ok(()),
+                None => Err(AppError::App(DeleteError::NotFound)),
+            }
+        }
+
+        async fn is_referenced(&self, id: ActorId) -> Result<bool, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("LET $actor = type::thing('actor', $id)")
+                .query("SELECT count() FROM event WHERE actor_id = $actor OR actors CONTAINS $actor OR fee.actors CONTAINS $actor GROUP ALL")
+                .bind(("id", id))
+                .await?
+                .check()?;
+
+            let count: Option<i64> = response.take((1, "count"))?;
+            Ok(count.unwrap_or(0) > 0)
+        }
+
+        fn stage_delete(&self, tx: &mut Transaction, id: ActorId) {
+            tx.stage(move |query, i| {
+                query
+                    .query(format!("DELETE type::thing('actor', $actor_id{i})"))
+                    .bind((format!("actor_id{i}"), id))
+            });
+        }
+
+        fn stage_merge(&self, tx: &mut Transaction, source: ActorId, target: ActorId) {
+            tx.stage(move |query, i| {
+                query
+                    .query(format!("LET $source{i} = type::thing('actor', $source_id{i})"))
+                    .query(format!("LET $target{i} = type::thing('actor', $target_id{i})"))
+                    .query(format!("UPDATE event SET actor_id = $target{i} WHERE actor_id = $source{i}"))
+                    .query(format!("UPDATE event SET actors = array::distinct(array::append(array::complement(actors, [$source{i}]), $target{i})) WHERE actors CONTAINS $source{i}"))
+                    .query(format!("UPDATE event SET fee.actors = array::distinct(array::append(array::complement(fee.actors, [$source{i}]), $target{i})) WHERE fee.actors CONTAINS $source{i}"))
+                    .query(format!("DELETE $source{i}"))
+                    .bind((format!("source_id{i}"), source))
+                    .bind((format!("target_id{i}"), target))
+            });
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub use sqlite_repository::SqliteRepository;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_repository {
+    use cream::context::FromContext;
+    use monee_core::ActorId;
+
+    use crate::{
+        backoffice::actors::domain::{
+            actor::Actor, actor_alias::ActorAlias, repository::Repository,
+        },
+        iprelude::*,
+        prelude::*,
+        shared::{
+            domain::{
+                context::SqliteContext,
+                errors::{DeleteError, UniqueSaveError, UpdateError},
+                slug,
+                unit_of_work::Transaction,
+            },
+            infrastructure::sqlite::{self, Connection, Statement},
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(SqliteContext)]
+    pub struct SqliteRepository(Connection);
+
+    #[async_trait::async_trait]
+    impl Repository for SqliteRepository {
+        async fn save(&self, id: ActorId, actor: Actor) -> Result<(), AppError<UniqueSaveError>> {
+            sqlx::query("INSERT INTO actor (id, data) VALUES (?, ?)")
+                .bind(sqlite::key(id))
+                .bind(sqlite::encode(&actor))
+                .execute(&self.0)
+                .await
+                .catch_app()?;
+
+            Ok(())
+        }
+
+        async fn alias_resolve(
+            &self,
+            alias: &ActorAlias,
+        ) -> Result<Option<ActorId>, InfrastructureError> {
+            let rows: Vec<(String, String)> =
+                sqlx::query_as("SELECT id, alias FROM actor WHERE alias IS NOT NULL")
+                    .fetch_all(&self.0)
+                    .await?;
+
+            let candidates = rows
+                .into_iter()
+                .map(|(id, alias)| Ok((sqlite::decode_key(id)?, alias)))
+                .collect::<sqlite::Result<Vec<_>>>()?;
+
+            Ok(slug::find_match(&alias.to_string(), candidates))
+        }
+
+        async fn get_all(&self) -> Result<Vec<(ActorId, Actor)>, InfrastructureError> {
+            let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, data FROM actor")
+                .fetch_all(&self.0)
+                .await?;
+
+            let actors = rows
+                .into_iter()
+                .map(sqlite::decode_row)
+                .collect::<sqlite::Result<_>>()?;
+            Ok(actors)
+        }
+
+        async fn get_one(&self, id: ActorId) -> Result<Option<Actor>, InfrastructureError> {
+            let data: Option<String> = sqlx::query_scalar("SELECT data FROM actor WHERE id = ?")
+                .bind(sqlite::key(id))
+                .fetch_optional(&self.0)
+                .await?;
+
+            Ok(data.as_deref().map(sqlite::decode).transpose()?)
+        }
+
+        async fn update(&self, id: ActorId, actor: Actor) -> Result<(), AppError<UpdateError>> {
+            let result = sqlx::query("UPDATE actor SET data = ? WHERE id = ?")
+                .bind(sqlite::encode(&actor))
+                .bind(sqlite::key(id))
+                .execute(&self.0)
+                .await
+                .map_err(AppError::<UpdateError>::from)?;
+
+            match result.rows_affected() {
+                0 => Err(AppError::App(UpdateError::NotFound)),
+                _ => Ok(()),
+            }
+        }
+
+        async fn delete(&self, id: ActorId) -> Result<(), AppError<DeleteError>> {
+            let result = sqlx::query("DELETE FROM actor WHERE id = ?")
+                .bind(sqlite::key(id))
+                .execute(&self.0)
+                .await
+                .catch_infra()?;
+
+            match result.rows_affected() {
+                0 => Err(AppError::App(DeleteError::NotFound)),
+                _ => Ok(()),
+            }
+        }
+
+        async fn is_referenced(&self, id: ActorId) -> Result<bool, InfrastructureError> {
+            let referenced: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM event WHERE json_extract(data, '$.actor_id') = ?1
+                    OR EXISTS (SELECT 1 FROM json_each(data, '$.actors') WHERE value = ?1)
+                    OR EXISTS (SELECT 1 FROM json_each(data, '$.fee.actors') WHERE value = ?1))",
+            )
+            .bind(sqlite::key(id))
+            .fetch_one(&self.0)
+            .await?;
+
+            Ok(referenced)
+        }
+
+        fn stage_delete(&self, tx: &mut Transaction, id: ActorId) {
+            tx.stage_sqlite(Statement::new("DELETE FROM actor WHERE id = ?").bind(sqlite::key(id)));
+        }
+
+        fn stage_merge(&self, tx: &mut Transaction, source: ActorId, target: ActorId) {
+            let source = sqlite::key(source);
+            let target = sqlite::key(target);
+
+            tx.stage_sqlite(
+                Statement::new(
+                    "UPDATE event SET data = json_set(data, '$.actor_id', ?2)
+                    WHERE json_extract(data, '$.actor_id') = ?1",
+                )
+                .bind(source.as_str())
+                .bind(target.as_str()),
+            );
+
+            for path in ["$.actors", "$.fee.actors"] {
+                tx.stage_sqlite(
+                    Statement::new(format!(
+                        "UPDATE event SET data = json_set(data, '{path}', json((
+                            SELECT json_group_array(value) FROM (
+                                SELECT value FROM json_each(event.data, '{path}')
+                                WHERE value NOT IN (?1, ?2)
+                                UNION ALL SELECT ?2
+                            )
+                        )))
+                        WHERE EXISTS (SELECT 1 FROM json_each(data, '{path}') WHERE value = ?1)"
+                    ))
+                    .bind(source.as_str())
+                    .bind(target.as_str()),
+                );
+            }
+
+            tx.stage_sqlite(Statement::new("DELETE FROM actor WHERE id = ?").bind(source));
+        }
     }
 }
@@ -76,3 +76,150 @@ pub mod get_all {
         }
     }
 }
+
+pub mod get_one {
+    use cream::context::FromContext;
+    use monee_core::CurrencyId;
+
+    use crate::{
+        backoffice::currencies::domain::{currency::Currency, repository::Repository},
+        prelude::{AppContext, InfrastructureError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct GetOne {
+        repository: Box<dyn Repository>,
+    }
+
+    impl GetOne {
+        pub async fn run(&self, id: CurrencyId) -> Result<Option<Currency>, InfrastructureError> {
+            self.repository.get_one(id).await
+        }
+    }
+}
+
+pub mod update_one {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::{CurrencyId, MinorUnits};
+    use monee_types::backoffice::currencies::currency_updated::CurrencyUpdated;
+
+    use crate::{
+        backoffice::{
+            currencies::domain::{currency::Currency, repository::Repository},
+            events::domain::repository::Repository as EventsRepository,
+            goals::domain::repository::Repository as GoalsRepository,
+            snapshot::application::snapshot_io::SnapshotIO,
+        },
+        prelude::{AppError, InfrastructureError},
+        shared::domain::{context::AppContext, date::Timezone, errors::UpdateError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct UpdateOne {
+        repository: Box<dyn Repository>,
+        events: Box<dyn EventsRepository>,
+        goals: Box<dyn GoalsRepository>,
+        snapshot_io: SnapshotIO,
+        port: EventBusPort,
+    }
+
+    impl UpdateOne {
+        pub async fn run(
+            &self,
+            id: CurrencyId,
+            currency: Currency,
+        ) -> Result<(), AppError<UpdateError>> {
+            if let Some(current) = self.repository.get_one(id).await? {
+                if currency.minor_units.get() < current.minor_units.get()
+                    && !self.amounts_fit(id, currency.minor_units).await?
+                {
+                    return Err(AppError::App(UpdateError::PrecisionLoss));
+                }
+            }
+
+            self.repository.update(id, currency).await?;
+            self.port.publish(CurrencyUpdated { id });
+
+            Ok(())
+        }
+
+        /// Whether every stored amount in the currency `id` keeps its value with `units`
+        async fn amounts_fit(
+            &self,
+            id: CurrencyId,
+            units: MinorUnits,
+        ) -> Result<bool, InfrastructureError> {
+            let snapshot = self.snapshot_io.read_last().await?;
+            let wallets = snapshot
+                .wallets
+                .iter()
+                .filter(|(_, wallet)| wallet.currency_id == id)
+                .map(|(_, wallet)| wallet.balance.unsigned_abs());
+            let debts = snapshot
+                .debts
+                .iter()
+                .chain(snapshot.loans.iter())
+                .filter(|(_, debt)| debt.money.currency_id == id)
+                .map(|(_, debt)| debt.money.amount);
+            if !wallets.chain(debts).all(|amount| amount.fits(units)) {
+                return Ok(false);
+            }
+
+            let goals = self.goals.get_all().await?;
+            if goals
+                .iter()
+                .any(|(_, goal)| goal.currency_id == id && !goal.target.fits(units))
+            {
+                return Ok(false);
+            }
+
+            let events = self.events.get_until(Timezone::now()).await?;
+            Ok(events
+                .iter()
+                .filter(|entry| entry.event.currency_id(&snapshot) == Some(id))
+                .all(|entry| {
+                    let fee = entry.event.fee().map(|fee| fee.amount).unwrap_or_default();
+                    entry.event.amount().fits(units) && fee.fits(units)
+                }))
+        }
+    }
+}
+
+pub mod delete_one {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::CurrencyId;
+    use monee_types::backoffice::currencies::currency_deleted::CurrencyDeleted;
+
+    use crate::{
+        backoffice::{
+            currencies::domain::repository::Repository,
+            snapshot::application::snapshot_io::SnapshotIO,
+        },
+        prelude::AppError,
+        shared::domain::{context::AppContext, errors::DeleteError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct DeleteOne {
+        repository: Box<dyn Repository>,
+        snapshot_io: SnapshotIO,
+        port: EventBusPort,
+    }
+
+    impl DeleteOne {
+        pub async fn run(&self, id: CurrencyId) -> Result<(), AppError<DeleteError>> {
+            let snapshot = self.snapshot_io.read_last().await?;
+            if snapshot.references_currency(id) || self.repository.is_referenced(id).await? {
+                return Err(AppError::App(DeleteError::Referenced));
+            }
+
+            self.repository.delete(id).await?;
+            self.port.publish(CurrencyDeleted { id });
+
+            Ok(())
+        }
+    }
+}
@@ -4,7 +4,13 @@ pub mod repository {
 
     use crate::{
         prelude::AppError,
-        shared::{domain::errors::UniqueSaveError, infrastructure::errors::InfrastructureError},
+        shared::{
+            domain::{
+                errors::{DeleteError, UniqueSaveError, UpdateError},
+                unit_of_work::Transaction,
+            },
+            infrastructure::errors::InfrastructureError,
+        },
     };
 
     use super::{currency::Currency, currency_code::CurrencyCode};
@@ -23,5 +29,21 @@ pub mod repository {
         ) -> Result<Option<CurrencyId>, InfrastructureError>;
 
         async fn get_all(&self) -> Result<Vec<(CurrencyId, Currency)>, InfrastructureError>;
+
+        async fn get_one(&self, id: CurrencyId) -> Result<Option<Currency>, InfrastructureError>;
+
+        async fn update(
+            &self,
+            id: CurrencyId,
+            currency: Currency,
+        ) -> Result<(), AppError<UpdateError>>;
+
+        async fn delete(&self, id: CurrencyId) -> Result<(), AppError<DeleteError>>;
+
+        /// Stages deleting the currency, a missing currency is left as is
+        fn stage_delete(&self, tx: &mut Transaction, id: CurrencyId);
+
+        /// Whether any event, wallet, exchange rate or goal still points to the currency
+        async fn is_referenced(&self, id: CurrencyId) -> Result<bool, InfrastructureError>;
     }
 }
\ No newline at end of file
@@ -9,7 +9,11 @@ pub mod repository {
         iprelude::*,
         prelude::AppError,
         shared::{
-            domain::{context::DbContext, errors::UniqueSaveError},
+            domain::{
+                context::DbContext,
+                errors::{DeleteError, UniqueSaveError, UpdateError},
+                unit_of_work::Transaction,
+            },
             infrastructure::{
                 database::{Connection, Entity},
                 errors::InfrastructureError,
@@ -30,7 +34,7 @@ pub mod repository {
         ) -> Result<(), AppError<UniqueSaveError>> {
             let response = self
                 .0
-                .query("CREATE type::thing('currency', $id) SET name = $name, symbol = $symbol, code = $code")
+                .query("CREATE type::thing('currency', $id) SET name = $name, symbol = $symbol, code = $code, minor_units = $minor_units")
                 .bind(("id", id))
                 .bind(currency)
                 .await
@@ -57,11 +61,231 @@ pub mod repository {
         async fn get_all(&self) -> Result<Vec<(CurrencyId, Currency)>, InfrastructureError> {
             let mut response = self
                 .0
-                .query("SELECT id, name, symbol, code FROM currency")
+                .query("SELECT id, name, symbol, code, minor_units FROM currency")
                 .await?;
 
             let entities: Vec<Entity<CurrencyId, Currency>> = response.take(0)?;
             Ok(entities.into_iter().map(|e| (e.0, e.1)).collect())
         }
+
+        async fn get_one(&self, id: CurrencyId) -> Result<Option<Currency>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("SELECT id, name, symbol, code, minor_units FROM ONLY type::thing('currency', $id)")
+                .bind(("id", id))
+                .await?
+                .check()?;
+
+            let currency: Option<Entity<CurrencyId, Currency>> = response.take(0)?;
+            Ok(currency.map(|e| e.1))
+        }
+
+        async fn update(
+            &self,
+            id: CurrencyId,
+            currency: Currency,
+        ) -> Result<(), AppError<UpdateError>> {
+            let result = self
+                .0
+                .query("UPDATE currency SET name = $name, symbol = $symbol, code = $code, minor_units = $minor_units WHERE id = type::thing('currency', $id)")
+                .bind(("id", id))
+                .bind(currency)
+                .await
+                .catch_infra()?
+                .check();
+
+            let mut response = result.map_err(AppError::<UpdateError>::from)?;
+            let updated: Option<Entity<CurrencyId, ()>> = response.take(0).catch_infra()?;
+
+            match updated {
+                Some(_) => Ok(()),
+                None => Err(AppError::App(UpdateError::NotFound)),
+            }
+        }
+
+        async fn delete(&self, id: CurrencyId) -> Result<(), AppError<DeleteError>> {
+            let mut response = self
+                .0
+                .query("DELETE type::thing('currency', $id) RETURN BEFORE")
+                .bind(("id", id))
+                .await
+                .catch_infra()?
+                .check()
+                .catch_infra()?;
+
+            let deleted: Option<Entity<CurrencyId, ()>> = response.take(0).catch_infra()?;
+            match deleted {
+                Some(_) => Ok(()),
+                None => Err(AppError::App(DeleteError::NotFound)),
+            }
+        }
+
+        fn stage_delete(&self, tx: &mut Transaction, id: CurrencyId) {
+            tx.stage(move |query, i| {
+                query
+                    .query(format!("DELETE type::thing('currency', $currency_id{i})"))
+                    .bind((format!("currency_id{i}"), id))
+            });
+        }
+
+        async fn is_referenced(&self, id: CurrencyId) -> Result<bool, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("LET $currency = type::thing('currency', $id)")
+                .query("SELECT count() FROM event WHERE currency_id = $currency GROUP ALL")
+                .query("SELECT count() FROM wallet WHERE currency_id = $currency GROUP ALL")
+                .query("SELECT count() FROM exchange_rate WHERE from = $currency OR to = $currency GROUP ALL")
+                .query("SELECT count() FROM goal WHERE currency_id = $currency GROUP ALL")
+                .bind(("id", id))
+                .await?
+                .check()?;
+
+            let events: Option<i64> = response.take((1, "count"))?;
+            let wallets: Option<i64> = response.take((2, "count"))?;
+            let rates: Option<i64> = response.take((3, "count"))?;
+            let goals: Option<i64> = response.take((4, "count"))?;
+            Ok(
+                events.unwrap_or(0)
+                    + wallets.unwrap_or(0)
+                    + rates.unwrap_or(0)
+                    + goals.unwrap_or(0)
+                    > 0,
+            )
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub use sqlite_repository::SqliteRepository;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_repository {
+    use cream::context::FromContext;
+    use monee_core::CurrencyId;
+
+    use crate::{
+        backoffice::currencies::domain::{
+            currency::Currency, currency_code::CurrencyCode, repository::Repository,
+        },
+        iprelude::*,
+        prelude::AppError,
+        shared::{
+            domain::{
+                context::SqliteContext,
+                errors::{DeleteError, UniqueSaveError, UpdateError},
+                unit_of_work::Transaction,
+            },
+            infrastructure::{
+                errors::InfrastructureError,
+                sqlite::{self, Connection, Statement},
+            },
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(SqliteContext)]
+    pub struct SqliteRepository(Connection);
+
+    #[async_trait::async_trait]
+    impl Repository for SqliteRepository {
+        async fn save(
+            &self,
+            id: CurrencyId,
+            currency: Currency,
+        ) -> Result<(), AppError<UniqueSaveError>> {
+            sqlx::query("INSERT INTO currency (id, data) VALUES (?, ?)")
+                .bind(sqlite::key(id))
+                .bind(sqlite::encode(&currency))
+                .execute(&self.0)
+                .await
+                .catch_app()?;
+
+            Ok(())
+        }
+
+        async fn code_resolve(
+            &self,
+            code: &CurrencyCode,
+        ) -> Result<Option<CurrencyId>, InfrastructureError> {
+            let id: Option<String> =
+                sqlx::query_scalar("SELECT id FROM currency WHERE code = json_extract(?, '$')")
+                    .bind(sqlite::encode(code))
+                    .fetch_optional(&self.0)
+                    .await?;
+
+            Ok(id.map(sqlite::decode_key).transpose()?)
+        }
+
+        async fn get_all(&self) -> Result<Vec<(CurrencyId, Currency)>, InfrastructureError> {
+            let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, data FROM currency")
+                .fetch_all(&self.0)
+                .await?;
+
+            let currencies = rows
+                .into_iter()
+                .map(sqlite::decode_row)
+                .collect::<sqlite::Result<_>>()?;
+            Ok(currencies)
+        }
+
+        async fn get_one(&self, id: CurrencyId) -> Result<Option<Currency>, InfrastructureError> {
+            let data: Option<String> = sqlx::query_scalar("SELECT data FROM currency WHERE id = ?")
+                .bind(sqlite::key(id))
+                .fetch_optional(&self.0)
+                .await?;
+
+            Ok(data.as_deref().map(sqlite::decode).transpose()?)
+        }
+
+        async fn update(
+            &self,
+            id: CurrencyId,
+            currency: Currency,
+        ) -> Result<(), AppError<UpdateError>> {
+            let result = sqlx::query("UPDATE currency SET data = ? WHERE id = ?")
+                .bind(sqlite::encode(&currency))
+                .bind(sqlite::key(id))
+                .execute(&self.0)
+                .await
+                .map_err(AppError::<UpdateError>::from)?;
+
+            match result.rows_affected() {
+                0 => Err(AppError::App(UpdateError::NotFound)),
+                _ => Ok(()),
+            }
+        }
+
+        async fn delete(&self, id: CurrencyId) -> Result<(), AppError<DeleteError>> {
+            let result = sqlx::query("DELETE FROM currency WHERE id = ?")
+                .bind(sqlite::key(id))
+                .execute(&self.0)
+                .await
+                .catch_infra()?;
+
+            match result.rows_affected() {
+                0 => Err(AppError::App(DeleteError::NotFound)),
+                _ => Ok(()),
+            }
+        }
+
+        fn stage_delete(&self, tx: &mut Transaction, id: CurrencyId) {
+            tx.stage_sqlite(
+                Statement::new("DELETE FROM currency WHERE id = ?").bind(sqlite::key(id)),
+            );
+        }
+
+        async fn is_referenced(&self, id: CurrencyId) -> Result<bool, InfrastructureError> {
+            let referenced: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM event WHERE json_extract(data, '$.currency_id') = ?1)
+                    OR EXISTS (SELECT 1 FROM wallet WHERE json_extract(data, '$.currency_id') = ?1)
+                    OR EXISTS (SELECT 1 FROM exchange_rate WHERE from_id = ?1 OR to_id = ?1)
+                    OR EXISTS (SELECT 1 FROM goal WHERE json_extract(data, '$.currency_id') = ?1)",
+            )
+            .bind(sqlite::key(id))
+            .fetch_one(&self.0)
+            .await?;
+
+            Ok(referenced)
+        }
     }
 }
@@ -0,0 +1,94 @@
+pub mod domain {
+    pub use monee_types::backoffice::debts::*;
+}
+
+pub mod application {
+    pub mod accrue_interest {
+        use cream::{context::FromContext, events::bus::EventBusPort};
+        use monee_core::EventId;
+        use monee_types::backoffice::events::event_added::EventAdded;
+
+        use crate::{
+            backoffice::{
+                currencies::domain::repository::Repository as CurrencyRepository,
+                debts::domain::registry,
+                events::domain::{apply_event, repository::Repository as EventRepository},
+                snapshot::application::snapshot_io::SnapshotIO,
+            },
+            host::sync::domain::node_changes::EventEntry,
+            iprelude::CatchInfra,
+            shared::{
+                domain::{
+                    context::AppContext,
+                    date::{Date, Timezone},
+                    unit_of_work::UnitOfWork,
+                },
+                infrastructure::errors::AppError,
+            },
+        };
+
+        pub use apply_event::Error;
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct AccrueInterest {
+            repository: Box<dyn EventRepository>,
+            currency_repository: Box<dyn CurrencyRepository>,
+            snapshot_io: SnapshotIO,
+            unit_of_work: Box<dyn UnitOfWork>,
+            port: EventBusPort,
+        }
+
+        impl AccrueInterest {
+            /// Accrues the interest of every debt and loan up to `until`, today when not given.
+            /// Returns how many of them accrued interest
+            pub async fn run(&self, until: Option<Date>) -> Result<usize, AppError<Error>> {
+                let now = Timezone::now();
+                let until = until.unwrap_or(now.date_naive()).min(now.date_naive());
+
+                let mut snapshot = self.snapshot_io.read_last().await?;
+                let entries = self.repository.get_until(now).await?;
+                let currencies = self.currency_repository.get_all().await?;
+
+                let events = registry::accruals(
+                    &registry::registered(&entries),
+                    &snapshot,
+                    until,
+                    |currency_id| {
+                        currencies
+                            .iter()
+                            .find(|(id, _)| *id == currency_id)
+                            .map(|(_, currency)| currency.minor_units)
+                            .unwrap_or_default()
+                    },
+                );
+
+                for event in events.iter() {
+                    apply_event::apply_event(&mut snapshot, event).map_err(AppError::App)?;
+                }
+
+                let entries: Vec<_> = events
+                    .into_iter()
+                    .map(|event| EventEntry {
+                        id: EventId::default(),
+                        event,
+                        created_at: now,
+                    })
+                    .collect();
+                let ids: Vec<_> = entries.iter().map(|entry| entry.id).collect();
+
+                let mut tx = self.unit_of_work.begin();
+                self.repository.stage_many(&mut tx, entries);
+                self.snapshot_io
+                    .stage_save_events(&mut tx, snapshot, ids.clone());
+                tx.commit().await.catch_infra()?;
+
+                for id in ids.iter() {
+                    self.port.publish(EventAdded { id: *id });
+                }
+
+                Ok(ids.len())
+            }
+        }
+    }
+}
@@ -5,38 +5,312 @@ pub mod add {
 
     use crate::{
         backoffice::{
-            events::domain::{apply_event, repository::Repository},
+            currencies::domain::repository::Repository as CurrencyRepository,
+            events::domain::{apply_event, event::Refund, refund, repository::Repository},
             snapshot::application::snapshot_io::SnapshotIO,
         },
-        shared::{domain::context::AppContext, infrastructure::errors::AppError},
+        iprelude::CatchInfra,
+        shared::{
+            domain::{context::AppContext, unit_of_work::UnitOfWork},
+            infrastructure::errors::AppError,
+        },
     };
 
     pub use crate::backoffice::events::domain::event::Event;
     pub use apply_event::{Error, MoveValueError};
+    pub use refund::Error as RefundError;
 
     #[derive(FromContext)]
     #[context(AppContext)]
     pub struct Add {
         repository: Box<dyn Repository>,
+        currency_repository: Box<dyn CurrencyRepository>,
         snapshot_io: SnapshotIO,
+        unit_of_work: Box<dyn UnitOfWork>,
         port: EventBusPort,
     }
 
     impl Add {
         pub async fn run(&self, event: Event) -> Result<(), AppError<Error>> {
             let mut snapshot = self.snapshot_io.read_last().await?;
+            if let Event::Refund(refund) = &event {
+                self.check_refund(refund, &snapshot).await?;
+            }
+
             if let Err(e) = apply_event::apply_event(&mut snapshot, &event) {
                 return Err(AppError::App(e));
             }
 
+            if let Some(currency_id) = event.currency_id(&snapshot) {
+                let currency = self.currency_repository.get_one(currency_id).await?;
+                if let Some(currency) = currency {
+                    let fee = event.fee().map(|fee| fee.amount).unwrap_or_default();
+                    if !event.amount().fits(currency.minor_units) || !fee.fits(currency.minor_units)
+                    {
+                        return Err(AppError::App(Error::Precision(currency.minor_units)));
+                    }
+                }
+            }
+
             let id = EventId::default();
 
-            self.repository.add(id, event).await?;
-            self.snapshot_io.save(snapshot).await?;
+            // The event and the snapshot it leads to are stored together or not at all
+            let mut tx = self.unit_of_work.begin();
+            self.repository.stage_add(&mut tx, id, event);
+            self.snapshot_io
+                .stage_save_events(&mut tx, snapshot, vec![id]);
+            tx.commit().await.catch_infra()?;
 
             self.port.publish(EventAdded { id });
 
             Ok(())
         }
+
+        async fn check_refund(
+            &self,
+            refund: &Refund,
+            snapshot: &monee_core::Snapshot,
+        ) -> Result<(), AppError<Error>> {
+            // Every refund of the item counts, whether tied to a purchase or not, so the item as a
+            // whole is never refunded more than it was bought
+            let (mut purchases, mut previous) = (vec![], vec![]);
+            for event in self.repository.get_by_item(refund.item).await? {
+                match event {
+                    Event::Purchase(purchase) => purchases.push(purchase),
+                    Event::Refund(refund) => previous.push(refund),
+                    _ => {}
+                }
+            }
+
+            if let Some(purchase_id) = refund.purchase_id {
+                let Some(Event::Purchase(purchase)) = self.repository.get_one(purchase_id).await?
+                else {
+                    return Err(AppError::App(refund::Error::PurchaseNotFound.into()));
+                };
+
+                let linked = self.repository.get_refunds(purchase_id).await?;
+                refund
+                    .check(&purchase, &linked, snapshot)
+                    .map_err(|e| AppError::App(e.into()))?;
+            }
+
+            refund
+                .check_item(&purchases, &previous, snapshot)
+                .map_err(|e| AppError::App(e.into()))
+        }
+    }
+}
+
+pub mod split_purchase {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::EventId;
+    use monee_types::backoffice::events::event_added::EventAdded;
+
+    use crate::{
+        backoffice::{
+            currencies::domain::repository::Repository as CurrencyRepository,
+            events::domain::{apply_event, repository::Repository},
+            snapshot::application::snapshot_io::SnapshotIO,
+        },
+        host::sync::domain::node_changes::EventEntry,
+        iprelude::CatchInfra,
+        shared::{
+            domain::{context::AppContext, date::Timezone, unit_of_work::UnitOfWork},
+            infrastructure::errors::AppError,
+        },
+    };
+
+    pub use crate::backoffice::events::domain::split_purchase::{
+        Error as SplitError, PurchaseSplit,
+    };
+
+    pub enum Error {
+        WalletNotFound,
+        Split(SplitError),
+        Apply(apply_event::Error),
+    }
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct SplitPurchase {
+        repository: Box<dyn Repository>,
+        currency_repository: Box<dyn CurrencyRepository>,
+        snapshot_io: SnapshotIO,
+        unit_of_work: Box<dyn UnitOfWork>,
+        port: EventBusPort,
+    }
+
+    impl SplitPurchase {
+        /// Records every share at once, either all of them are saved or none
+        pub async fn run(&self, split: PurchaseSplit) -> Result<(), AppError<Error>> {
+            let mut snapshot = self.snapshot_io.read_last().await?;
+
+            let currency_id = snapshot
+                .wallets
+                .get(&split.wallet_id)
+                .map(|wallet| wallet.currency_id)
+                .ok_or(AppError::App(Error::WalletNotFound))?;
+
+            let units = self
+                .currency_repository
+                .get_one(currency_id)
+                .await?
+                .map(|currency| currency.minor_units)
+                .unwrap_or_default();
+
+            let events = split
+                .into_events(currency_id, units)
+                .map_err(|e| AppError::App(Error::Split(e)))?;
+
+            for event in events.iter() {
+                apply_event::apply_event(&mut snapshot, event)
+                    .map_err(|e| AppError::App(Error::Apply(e)))?;
+            }
+
+            let created_at = Timezone::now();
+            let entries: Vec<_> = events
+                .into_iter()
+                .map(|event| EventEntry {
+                    id: EventId::default(),
+                    event,
+                    created_at,
+                })
+                .collect();
+            let ids: Vec<_> = entries.iter().map(|entry| entry.id).collect();
+
+            let mut tx = self.unit_of_work.begin();
+            self.repository.stage_many(&mut tx, entries);
+            self.snapshot_io
+                .stage_save_events(&mut tx, snapshot, ids.clone());
+            tx.commit().await.catch_infra()?;
+
+            for id in ids {
+                self.port.publish(EventAdded { id });
+            }
+
+            Ok(())
+        }
+    }
+}
+
+pub mod reconcile_balance {
+    use cream::context::FromContext;
+    use monee_core::{Balance, WalletId};
+
+    use crate::{
+        backoffice::{
+            events::domain::{
+                event::{Event, ReconcileBalance},
+                reconcile,
+            },
+            snapshot::application::snapshot_io::SnapshotIO,
+        },
+        iprelude::CatchInfra,
+        shared::{domain::context::AppContext, infrastructure::errors::AppError},
+    };
+
+    use super::add::{self, Add};
+
+    pub enum Error {
+        Reconcile(reconcile::Error),
+        Add(add::Error),
+    }
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct Reconcile {
+        snapshot_io: SnapshotIO,
+        add: Add,
+    }
+
+    impl Reconcile {
+        /// Records that the wallet holds `observed`, adjusting it by the difference.
+        /// Returns the reconciliation, with the adjustment that was applied
+        pub async fn run(
+            &self,
+            wallet_id: WalletId,
+            observed: Balance,
+        ) -> Result<ReconcileBalance, AppError<Error>> {
+            let snapshot = self.snapshot_io.read_last().await?;
+            let reconciliation = ReconcileBalance::against(&snapshot, wallet_id, observed)
+                .map_err(|e| AppError::App(Error::Reconcile(e)))?;
+
+            let event = Event::ReconcileBalance(ReconcileBalance {
+                wallet_id,
+                observed,
+                adjustment: reconciliation.adjustment,
+            });
+            self.add
+                .run(event)
+                .await
+                .catch_infra()?
+                .map_err(|e| AppError::App(Error::Add(e)))?;
+
+            Ok(reconciliation)
+        }
+    }
+}
+
+pub mod refund_purchase {
+    use cream::context::FromContext;
+    use monee_core::{Amount, EventId, WalletId};
+
+    use crate::{
+        backoffice::events::domain::{
+            event::{Event, Refund},
+            refund,
+            repository::Repository,
+        },
+        shared::{domain::context::AppContext, infrastructure::errors::AppError},
+    };
+
+    use super::add::{Add, Error};
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct RefundPurchase {
+        repository: Box<dyn Repository>,
+        add: Add,
+    }
+
+    impl RefundPurchase {
+        /// Refunds the purchase `purchase_id` into `wallet_id`, the purchase's wallet by default.
+        /// Refunds whatever is left of it when no `amount` is given, returns what was refunded
+        pub async fn run(
+            &self,
+            purchase_id: EventId,
+            wallet_id: Option<WalletId>,
+            amount: Option<Amount>,
+        ) -> Result<Amount, AppError<Error>> {
+            let Some(Event::Purchase(purchase)) = self.repository.get_one(purchase_id).await?
+            else {
+                return Err(AppError::App(refund::Error::PurchaseNotFound.into()));
+            };
+
+            let amount = match amount {
+                Some(amount) => amount,
+                None => {
+                    let previous = self.repository.get_refunds(purchase_id).await?;
+                    let refundable = refund::refundable(&purchase, &previous);
+                    if refundable == Amount::default() {
+                        let error = refund::Error::ExceedsPurchase { refundable };
+                        return Err(AppError::App(error.into()));
+                    }
+
+                    refundable
+                }
+            };
+
+            let refund = Refund {
+                purchase_id: Some(purchase_id),
+                item: purchase.item,
+                actors: purchase.actors,
+                wallet_id: wallet_id.unwrap_or(purchase.wallet_id),
+                amount,
+            };
+            self.add.run(Event::Refund(refund)).await?;
+
+            Ok(amount)
+        }
     }
 }
@@ -1,17 +1,44 @@
 pub use monee_types::backoffice::events::*;
 pub mod repository {
-    use monee_core::EventId;
+    use monee_core::{EventId, ItemTagId};
 
     use crate::{
         host::sync::domain::node_changes::EventEntry,
-        shared::infrastructure::errors::InfrastructureError,
+        shared::{
+            domain::{date::Datetime, unit_of_work::Transaction},
+            infrastructure::errors::InfrastructureError,
+        },
     };
 
-    use super::event::Event;
+    use super::event::{Event, Refund};
 
     #[async_trait::async_trait]
     pub trait Repository: 'static + Send + Sync {
         async fn add(&self, id: EventId, event: Event) -> Result<(), InfrastructureError>;
         async fn save_many(&self, events: Vec<EventEntry>) -> Result<(), InfrastructureError>;
+
+        fn stage_add(&self, tx: &mut Transaction, id: EventId, event: Event);
+        fn stage_many(&self, tx: &mut Transaction, events: Vec<EventEntry>);
+
+        async fn get_one(&self, id: EventId) -> Result<Option<Event>, InfrastructureError>;
+
+        /// Refunds already made for the purchase `purchase_id`
+        async fn get_refunds(
+            &self,
+            purchase_id: EventId,
+        ) -> Result<Vec<Refund>, InfrastructureError>;
+
+        /// Purchases and refunds of `item`
+        async fn get_by_item(&self, item: ItemTagId) -> Result<Vec<Event>, InfrastructureError>;
+
+        /// Events created up to `date`, oldest first
+        async fn get_until(&self, date: Datetime) -> Result<Vec<EventEntry>, InfrastructureError>;
+
+        /// Events added after `event_id` up to `date`, oldest first
+        async fn get_after(
+            &self,
+            event_id: EventId,
+            date: Datetime,
+        ) -> Result<Vec<EventEntry>, InfrastructureError>;
     }
 }
@@ -1,13 +1,23 @@
 pub mod repository {
     use cream::context::FromContext;
-    use monee_core::{ActorId, Amount, CurrencyId, EventId, ItemTagId, WalletId};
-    use monee_types::backoffice::events::event::PaymentReceived;
+    use monee_core::{ActorId, Amount, Balance, CurrencyId, DebtId, EventId, ItemTagId, WalletId};
+    use monee_types::backoffice::{
+        debts::debt_terms::DebtTerms,
+        events::{
+            event::{Fee, InterestAccrual, PaymentReceived, ReconcileBalance, Refund},
+            quantity::Quantity,
+        },
+    };
 
     use crate::{
         backoffice::events::domain::{event::Event, repository::Repository},
         host::sync::domain::node_changes::EventEntry,
         shared::{
-            domain::{context::DbContext, date::Datetime},
+            domain::{
+                context::DbContext,
+                date::{Date, Datetime},
+                unit_of_work::Transaction,
+            },
             infrastructure::{database::EntityKey, errors::InfrastructureError},
         },
     };
@@ -16,6 +26,35 @@ pub mod repository {
     #[context(DbContext)]
     pub struct SurrealRepository(crate::shared::infrastructure::database::Connection);
 
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct SurrealFee {
+        amount: Amount,
+        item: EntityKey<ItemTagId>,
+        actors: Vec<EntityKey<ActorId>>,
+    }
+
+    impl From<Fee> for SurrealFee {
+        fn from(value: Fee) -> Self {
+            Self {
+                amount: value.amount,
+                item: EntityKey(value.item),
+                actors: IntoIterator::into_iter(value.actors)
+                    .map(EntityKey)
+                    .collect(),
+            }
+        }
+    }
+
+    impl From<SurrealFee> for Fee {
+        fn from(value: SurrealFee) -> Self {
+            Self {
+                amount: value.amount,
+                item: value.item.0,
+                actors: value.actors.into_iter().map(|k| k.0).collect(),
+            }
+        }
+    }
+
     #[derive(serde::Serialize, serde::Deserialize)]
     #[serde(rename_all = "snake_case", tag = "type")]
     pub enum SurrealMoneeEvent {
@@ -24,6 +63,17 @@ pub mod repository {
             amount: Amount,
             wallet_id: EntityKey<WalletId>,
             actors: Vec<EntityKey<ActorId>>,
+            #[serde(default)]
+            quantity: Option<Quantity>,
+        },
+
+        Refund {
+            #[serde(default)]
+            purchase_id: Option<EntityKey<EventId>>,
+            item: EntityKey<ItemTagId>,
+            actors: Vec<EntityKey<ActorId>>,
+            wallet_id: EntityKey<WalletId>,
+            amount: Amount,
         },
 
         RegisterBalance {
@@ -31,22 +81,54 @@ pub mod repository {
             amount: Amount,
         },
 
+        ReconcileBalance {
+            wallet_id: EntityKey<WalletId>,
+            observed: Balance,
+            adjustment: Balance,
+        },
+
         RegisterDebt {
             amount: Amount,
             currency_id: EntityKey<CurrencyId>,
             actor_id: EntityKey<ActorId>,
+            #[serde(default)]
+            wallet_id: Option<EntityKey<WalletId>>,
+            #[serde(default)]
+            debt_id: Option<DebtId>,
+            #[serde(default)]
+            terms: Option<DebtTerms>,
         },
 
         RegisterLoan {
             amount: Amount,
             currency_id: EntityKey<CurrencyId>,
             actor_id: EntityKey<ActorId>,
+            #[serde(default)]
+            wallet_id: Option<EntityKey<WalletId>>,
+            #[serde(default)]
+            debt_id: Option<DebtId>,
+            #[serde(default)]
+            terms: Option<DebtTerms>,
+        },
+
+        AccrueDebtInterest {
+            debt_id: DebtId,
+            amount: Amount,
+            until: Date,
+        },
+
+        AccrueLoanInterest {
+            debt_id: DebtId,
+            amount: Amount,
+            until: Date,
         },
 
         MoveValue {
             from: EntityKey<WalletId>,
             to: EntityKey<WalletId>,
             amount: Amount,
+            #[serde(default)]
+            fee: Option<SurrealFee>,
         },
 
         PaymentReceived {
@@ -64,25 +146,57 @@ pub mod repository {
                     amount: purchase.amount,
                     wallet_id: EntityKey(purchase.wallet_id),
                     actors: IntoIterator::into_iter(purchase.actors).map(EntityKey).collect(),
+                    quantity: purchase.quantity,
+                },
+                Event::Refund(refund) => SurrealMoneeEvent::Refund {
+                    purchase_id: refund.purchase_id.map(EntityKey),
+                    item: EntityKey(refund.item),
+                    actors: IntoIterator::into_iter(refund.actors)
+                        .map(EntityKey)
+                        .collect(),
+                    wallet_id: EntityKey(refund.wallet_id),
+                    amount: refund.amount,
                 },
                 Event::RegisterBalance(register) => SurrealMoneeEvent::RegisterBalance {
                     wallet_id: EntityKey(register.wallet_id),
                     amount: register.amount,
                 },
+                Event::ReconcileBalance(reconcile) => SurrealMoneeEvent::ReconcileBalance {
+                    wallet_id: EntityKey(reconcile.wallet_id),
+                    observed: reconcile.observed,
+                    adjustment: reconcile.adjustment,
+                },
                 Event::RegisterDebt(debt) => SurrealMoneeEvent::RegisterDebt {
                     amount: debt.amount,
                     currency_id: EntityKey(debt.currency_id),
                     actor_id: EntityKey(debt.actor_id),
+                    wallet_id: debt.wallet_id.map(EntityKey),
+                    debt_id: debt.debt_id,
+                    terms: debt.terms,
                 },
                 Event::RegisterLoan(loan) => SurrealMoneeEvent::RegisterLoan {
                     amount: loan.amount,
                     currency_id: EntityKey(loan.currency_id),
                     actor_id: EntityKey(loan.actor_id),
+                    wallet_id: loan.wallet_id.map(EntityKey),
+                    debt_id: loan.debt_id,
+                    terms: loan.terms,
+                },
+                Event::AccrueDebtInterest(accrual) => SurrealMoneeEvent::AccrueDebtInterest {
+                    debt_id: accrual.debt_id,
+                    amount: accrual.amount,
+                    until: accrual.until,
+                },
+                Event::AccrueLoanInterest(accrual) => SurrealMoneeEvent::AccrueLoanInterest {
+                    debt_id: accrual.debt_id,
+                    amount: accrual.amount,
+                    until: accrual.until,
                 },
                 Event::MoveValue(move_value) => SurrealMoneeEvent::MoveValue {
                     from: EntityKey(move_value.from),
                     to: EntityKey(move_value.to),
                     amount: move_value.amount,
+                    fee: move_value.fee.map(Into::into),
                 },
                 Event::PaymentReceived(payment) => SurrealMoneeEvent::PaymentReceived {
                     actor_id: EntityKey(payment.actor_id),
@@ -101,11 +215,26 @@ pub mod repository {
                     amount,
                     wallet_id,
                     actors,
+                    quantity,
                 } => Event::Purchase(monee_types::backoffice::events::event::Purchase {
                     item: item.0,
                     amount,
                     wallet_id: wallet_id.0,
                     actors: actors.into_iter().map(|k| k.0).collect(),
+                    quantity,
+                }),
+                SurrealMoneeEvent::Refund {
+                    purchase_id,
+                    item,
+                    actors,
+                    wallet_id,
+                    amount,
+                } => Event::Refund(Refund {
+                    purchase_id: purchase_id.map(|k| k.0),
+                    item: item.0,
+                    actors: actors.into_iter().map(|k| k.0).collect(),
+                    wallet_id: wallet_id.0,
+                    amount,
                 }),
                 SurrealMoneeEvent::RegisterBalance { wallet_id, amount } => Event::RegisterBalance(
                     monee_types::backoffice::events::event::RegisterBalance {
@@ -113,33 +242,76 @@ pub mod repository {
                         amount,
                     },
                 ),
+                SurrealMoneeEvent::ReconcileBalance {
+                    wallet_id,
+                    observed,
+                    adjustment,
+                } => Event::ReconcileBalance(ReconcileBalance {
+                    wallet_id: wallet_id.0,
+                    observed,
+                    adjustment,
+                }),
                 SurrealMoneeEvent::RegisterDebt {
                     amount,
                     currency_id,
                     actor_id,
+                    wallet_id,
+                    debt_id,
+                    terms,
                 } => Event::RegisterDebt(monee_types::backoffice::events::event::DebtRegister {
                     amount,
                     currency_id: currency_id.0,
                     actor_id: actor_id.0,
                     payment_promise: None,
+                    wallet_id: wallet_id.map(|k| k.0),
+                    debt_id,
+                    terms,
                 }),
                 SurrealMoneeEvent::RegisterLoan {
                     amount,
                     currency_id,
                     actor_id,
+                    wallet_id,
+                    debt_id,
+                    terms,
                 } => Event::RegisterLoan(monee_types::backoffice::events::event::DebtRegister {
                     amount,
                     currency_id: currency_id.0,
                     actor_id: actor_id.0,
                     payment_promise: None,
+                    wallet_id: wallet_id.map(|k| k.0),
+                    debt_id,
+                    terms,
+                }),
+                SurrealMoneeEvent::AccrueDebtInterest {
+                    debt_id,
+                    amount,
+                    until,
+                } => Event::AccrueDebtInterest(InterestAccrual {
+                    debt_id,
+                    amount,
+                    until,
+                }),
+                SurrealMoneeEvent::AccrueLoanInterest {
+                    debt_id,
+                    amount,
+                    until,
+                } => Event::AccrueLoanInterest(InterestAccrual {
+                    debt_id,
+                    amount,
+                    until,
+                }),
+                SurrealMoneeEvent::MoveValue {
+                    from,
+                    to,
+                    amount,
+                    fee,
+                } => Event::MoveValue(monee_types::backoffice::events::event::MoveValue {
+                    from: from.0,
+                    to: to.0,
+                    amount,
+                    fee: fee.map(Into::into),
                 }),
-                SurrealMoneeEvent::MoveValue { from, to, amount } => {
-                    Event::MoveValue(monee_types::backoffice::events::event::MoveValue {
-                        from: from.0,
-                        to: to.0,
-                        amount,
-                    })
-                }
                 SurrealMoneeEvent::PaymentReceived {
                     actor_id,
                     wallet_id,
@@ -153,6 +325,43 @@ pub mod repository {
         }
     }
 
+    #[derive(serde::Serialize)]
+    struct EventRow {
+        id: EntityKey<EventId>,
+        #[serde(flatten)]
+        event: SurrealMoneeEvent,
+        created_at: Datetime,
+    }
+
+    fn event_rows(events: Vec<EventEntry>) -> Vec<EventRow> {
+        events
+            .into_iter()
+            .map(|entry| EventRow {
+                id: EntityKey(entry.id),
+                event: SurrealMoneeEvent::from(entry.event),
+                created_at: entry.created_at,
+            })
+            .collect()
+    }
+
+    #[derive(serde::Deserialize)]
+    struct SurrealEventEntry {
+        id: EntityKey<EventId>,
+        #[serde(flatten)]
+        event: SurrealMoneeEvent,
+        created_at: Datetime,
+    }
+
+    impl From<SurrealEventEntry> for EventEntry {
+        fn from(entry: SurrealEventEntry) -> Self {
+            EventEntry {
+                id: entry.id.0,
+                event: entry.event.into(),
+                created_at: entry.created_at,
+            }
+        }
+    }
+
     #[async_trait::async_trait]
     impl Repository for SurrealRepository {
         async fn add(&self, id: EventId, event: Event) -> Result<(), InfrastructureError> {
@@ -166,30 +375,113 @@ pub mod repository {
         }
 
         async fn save_many(&self, events: Vec<EventEntry>) -> Result<(), InfrastructureError> {
-            #[derive(serde::Serialize)]
-            struct EventRow {
-                id: EntityKey<EventId>,
-                #[serde(flatten)]
-                event: SurrealMoneeEvent,
-                created_at: Datetime,
-            }
-
-            let rows: Vec<_> = events
-                .into_iter()
-                .map(|entry| EventRow {
-                    id: EntityKey(entry.id),
-                    event: SurrealMoneeEvent::from(entry.event),
-                    created_at: entry.created_at,
-                })
-                .collect();
-
             self.0
                 .query("INSERT INTO event $rows")
-                .bind(("rows", rows))
+                .bind(("rows", event_rows(events)))
                 .await?;
 
             Ok(())
         }
+
+        fn stage_add(&self, tx: &mut Transaction, id: EventId, event: Event) {
+            let event = SurrealMoneeEvent::from(event);
+            tx.stage(move |query, i| {
+                query
+                    .query(format!(
+                        "CREATE type::thing('event', $event_id{i}) CONTENT $event{i}"
+                    ))
+                    .bind((format!("event_id{i}"), id))
+                    .bind((format!("event{i}"), event))
+            });
+        }
+
+        fn stage_many(&self, tx: &mut Transaction, events: Vec<EventEntry>) {
+            let rows = event_rows(events);
+            tx.stage(move |query, i| {
+                query
+                    .query(format!("INSERT INTO event $events{i}"))
+                    .bind((format!("events{i}"), rows))
+            });
+        }
+
+        async fn get_one(&self, id: EventId) -> Result<Option<Event>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("SELECT * FROM ONLY type::thing('event', $id)")
+                .bind(("id", id))
+                .await?
+                .check()?;
+
+            let event: Option<SurrealMoneeEvent> = response.take(0)?;
+            Ok(event.map(Into::into))
+        }
+
+        async fn get_refunds(
+            &self,
+            purchase_id: EventId,
+        ) -> Result<Vec<Refund>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("SELECT * FROM event WHERE type = 'refund' AND purchase_id = type::thing('event', $id)")
+                .bind(("id", purchase_id))
+                .await?
+                .check()?;
+
+            let events: Vec<SurrealMoneeEvent> = response.take(0)?;
+            Ok(events
+                .into_iter()
+                .filter_map(|event| match event.into() {
+                    Event::Refund(refund) => Some(refund),
+                    _ => None,
+                })
+                .collect())
+        }
+
+        async fn get_by_item(&self, item: ItemTagId) -> Result<Vec<Event>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("SELECT * FROM event WHERE type IN ['purchase', 'refund'] AND item = type::thing('item_tag', $item)")
+                .bind(("item", item))
+                .await?
+                .check()?;
+
+            let events: Vec<SurrealMoneeEvent> = response.take(0)?;
+            Ok(events.into_iter().map(Into::into).collect())
+        }
+
+        async fn get_until(&self, date: Datetime) -> Result<Vec<EventEntry>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query(
+                    "SELECT * FROM event WHERE created_at <= <datetime> $date ORDER BY created_at",
+                )
+                .bind(("date", date))
+                .await?
+                .check()?;
+
+            let events: Vec<SurrealEventEntry> = response.take(0)?;
+            Ok(events.into_iter().map(Into::into).collect())
+        }
+
+        async fn get_after(
+            &self,
+            event_id: EventId,
+            date: Datetime,
+        ) -> Result<Vec<EventEntry>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query(
+                    "SELECT * FROM event WHERE created_at > $event.created_at
+                    AND created_at <= <datetime> $date ORDER BY created_at",
+                )
+                .bind(("event", EntityKey(event_id)))
+                .bind(("date", date))
+                .await?
+                .check()?;
+
+            let events: Vec<SurrealEventEntry> = response.take(0)?;
+            Ok(events.into_iter().map(Into::into).collect())
+        }
     }
 
     #[cfg(test)]
@@ -204,7 +496,7 @@ pub mod repository {
         #[cfg(feature = "db_test")]
         #[tokio::test]
         async fn saves_many() {
-            let db = connect().await.unwrap();
+            let db = connect("mem://").await.unwrap();
             let ctx = DbContext::new(db);
 
             let repo: SurrealRepository = ctx.provide();
@@ -215,6 +507,7 @@ pub mod repository {
                     amount: Amount::default(),
                     wallet_id: WalletId::default(),
                     actors: vec![].into(),
+                    quantity: None,
                 }),
                 created_at: Datetime::MIN_UTC,
             }])
@@ -223,3 +516,169 @@ pub mod repository {
         }
     }
 }
+
+#[cfg(feature = "sqlite")]
+pub use sqlite_repository::SqliteRepository;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_repository {
+    use cream::context::FromContext;
+    use monee_core::{EventId, ItemTagId};
+    use monee_types::backoffice::events::event::Refund;
+
+    use crate::{
+        backoffice::events::domain::{event::Event, repository::Repository},
+        host::sync::domain::node_changes::EventEntry,
+        shared::{
+            domain::{
+                context::SqliteContext,
+                date::{Datetime, Timezone},
+                unit_of_work::Transaction,
+            },
+            infrastructure::{
+                errors::InfrastructureError,
+                sqlite::{self, Connection, Statement},
+            },
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(SqliteContext)]
+    pub struct SqliteRepository(Connection);
+
+    fn insert(id: EventId, event: &Event, created_at: Datetime) -> Statement {
+        Statement::new("INSERT INTO event (id, data, created_at) VALUES (?, ?, ?)")
+            .bind(sqlite::key(id))
+            .bind(sqlite::encode(event))
+            .bind(sqlite::datetime(created_at))
+    }
+
+    fn decode_entry(
+        (id, data, created_at): (String, String, String),
+    ) -> sqlite::Result<EventEntry> {
+        Ok(EventEntry {
+            id: sqlite::decode_key(id)?,
+            event: sqlite::decode(&data)?,
+            created_at: sqlite::decode_datetime(&created_at)?,
+        })
+    }
+
+    #[async_trait::async_trait]
+    impl Repository for SqliteRepository {
+        async fn add(&self, id: EventId, event: Event) -> Result<(), InfrastructureError> {
+            let mut connection = self.0.acquire().await?;
+            insert(id, &event, Timezone::now())
+                .execute(&mut *connection)
+                .await?;
+
+            Ok(())
+        }
+
+        async fn save_many(&self, events: Vec<EventEntry>) -> Result<(), InfrastructureError> {
+            let mut tx = self.0.begin().await?;
+            for entry in events {
+                insert(entry.id, &entry.event, entry.created_at)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await?;
+
+            Ok(())
+        }
+
+        fn stage_add(&self, tx: &mut Transaction, id: EventId, event: Event) {
+            tx.stage_sqlite(insert(id, &event, Timezone::now()));
+        }
+
+        fn stage_many(&self, tx: &mut Transaction, events: Vec<EventEntry>) {
+            for entry in events {
+                tx.stage_sqlite(insert(entry.id, &entry.event, entry.created_at));
+            }
+        }
+
+        async fn get_one(&self, id: EventId) -> Result<Option<Event>, InfrastructureError> {
+            let data: Option<String> = sqlx::query_scalar("SELECT data FROM event WHERE id = ?")
+                .bind(sqlite::key(id))
+                .fetch_optional(&self.0)
+                .await?;
+
+            Ok(data.as_deref().map(sqlite::decode).transpose()?)
+        }
+
+        async fn get_refunds(
+            &self,
+            purchase_id: EventId,
+        ) -> Result<Vec<Refund>, InfrastructureError> {
+            let rows: Vec<String> = sqlx::query_scalar(
+                "SELECT data FROM event WHERE json_extract(data, '$.type') = 'refund'
+                AND json_extract(data, '$.purchase_id') = ?",
+            )
+            .bind(sqlite::key(purchase_id))
+            .fetch_all(&self.0)
+            .await?;
+
+            let mut refunds = Vec::with_capacity(rows.len());
+            for data in rows {
+                if let Event::Refund(refund) = sqlite::decode(&data)? {
+                    refunds.push(refund);
+                }
+            }
+
+            Ok(refunds)
+        }
+
+        async fn get_by_item(&self, item: ItemTagId) -> Result<Vec<Event>, InfrastructureError> {
+            let rows: Vec<String> = sqlx::query_scalar(
+                "SELECT data FROM event WHERE json_extract(data, '$.type') IN ('purchase', 'refund')
+                AND json_extract(data, '$.item') = ?",
+            )
+            .bind(sqlite::key(item))
+            .fetch_all(&self.0)
+            .await?;
+
+            let events = rows
+                .iter()
+                .map(|data| sqlite::decode(data))
+                .collect::<sqlite::Result<_>>()?;
+            Ok(events)
+        }
+
+        async fn get_until(&self, date: Datetime) -> Result<Vec<EventEntry>, InfrastructureError> {
+            let rows: Vec<(String, String, String)> = sqlx::query_as(
+                "SELECT id, data, created_at FROM event WHERE created_at <= ?
+                ORDER BY created_at, rowid",
+            )
+            .bind(sqlite::datetime(date))
+            .fetch_all(&self.0)
+            .await?;
+
+            let events = rows
+                .into_iter()
+                .map(decode_entry)
+                .collect::<sqlite::Result<_>>()?;
+            Ok(events)
+        }
+
+        async fn get_after(
+            &self,
+            event_id: EventId,
+            date: Datetime,
+        ) -> Result<Vec<EventEntry>, InfrastructureError> {
+            let rows: Vec<(String, String, String)> = sqlx::query_as(
+                "SELECT id, data, created_at FROM event
+                WHERE created_at > (SELECT created_at FROM event WHERE id = ?)
+                AND created_at <= ? ORDER BY created_at, rowid",
+            )
+            .bind(sqlite::key(event_id))
+            .bind(sqlite::datetime(date))
+            .fetch_all(&self.0)
+            .await?;
+
+            let events = rows
+                .into_iter()
+                .map(decode_entry)
+                .collect::<sqlite::Result<_>>()?;
+            Ok(events)
+        }
+    }
+}
@@ -0,0 +1,80 @@
+pub mod set_one {
+    use cream::context::FromContext;
+
+    use crate::{
+        backoffice::exchange_rates::domain::{exchange_rate::ExchangeRate, repository::Repository},
+        shared::{domain::context::AppContext, infrastructure::errors::AppError},
+    };
+
+    #[derive(Debug)]
+    pub enum Error {
+        /// A currency can not be exchanged with itself
+        SamePair,
+    }
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct SetOne {
+        repository: Box<dyn Repository>,
+    }
+
+    impl SetOne {
+        pub async fn run(&self, rate: ExchangeRate) -> Result<(), AppError<Error>> {
+            if rate.from == rate.to {
+                return Err(AppError::App(Error::SamePair));
+            }
+
+            self.repository.save(rate).await?;
+            Ok(())
+        }
+    }
+}
+
+pub mod get_all {
+    use cream::context::FromContext;
+
+    use crate::{
+        backoffice::exchange_rates::domain::{exchange_rate::ExchangeRate, repository::Repository},
+        prelude::{AppContext, InfrastructureError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct GetAll {
+        repository: Box<dyn Repository>,
+    }
+
+    impl GetAll {
+        pub async fn run(&self) -> Result<Vec<ExchangeRate>, InfrastructureError> {
+            self.repository.get_all().await
+        }
+    }
+}
+
+pub mod delete_one {
+    use cream::context::FromContext;
+    use monee_core::CurrencyId;
+
+    use crate::{
+        backoffice::exchange_rates::domain::repository::Repository,
+        prelude::AppError,
+        shared::domain::{context::AppContext, date::Date, errors::DeleteError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct DeleteOne {
+        repository: Box<dyn Repository>,
+    }
+
+    impl DeleteOne {
+        pub async fn run(
+            &self,
+            from: CurrencyId,
+            to: CurrencyId,
+            date: Date,
+        ) -> Result<(), AppError<DeleteError>> {
+            self.repository.delete(from, to, date).await
+        }
+    }
+}
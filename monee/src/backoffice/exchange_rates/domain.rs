@@ -0,0 +1,29 @@
+pub use monee_types::backoffice::exchange_rates::*;
+pub mod repository {
+    use monee_core::CurrencyId;
+
+    use crate::{
+        prelude::AppError,
+        shared::{
+            domain::{date::Date, errors::DeleteError},
+            infrastructure::errors::InfrastructureError,
+        },
+    };
+
+    use super::exchange_rate::ExchangeRate;
+
+    #[async_trait::async_trait]
+    pub trait Repository: 'static + Send + Sync {
+        /// Replaces the rate the pair had on the same date, if any
+        async fn save(&self, rate: ExchangeRate) -> Result<(), InfrastructureError>;
+
+        async fn get_all(&self) -> Result<Vec<ExchangeRate>, InfrastructureError>;
+
+        async fn delete(
+            &self,
+            from: CurrencyId,
+            to: CurrencyId,
+            date: Date,
+        ) -> Result<(), AppError<DeleteError>>;
+    }
+}
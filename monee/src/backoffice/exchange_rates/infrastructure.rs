@@ -0,0 +1,183 @@
+pub mod repository {
+    use cream::context::FromContext;
+    use monee_core::CurrencyId;
+    use surrealdb::sql::statements::{BeginStatement, CommitStatement};
+
+    use crate::{
+        backoffice::exchange_rates::domain::{
+            exchange_rate::ExchangeRate, rate::Rate, repository::Repository,
+        },
+        iprelude::*,
+        prelude::AppError,
+        shared::{
+            domain::{context::DbContext, date::Date, errors::DeleteError},
+            infrastructure::{
+                database::{Connection, EntityKey},
+                errors::InfrastructureError,
+            },
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(DbContext)]
+    pub struct SurrealRepository(Connection);
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct SurrealExchangeRate {
+        from: EntityKey<CurrencyId>,
+        to: EntityKey<CurrencyId>,
+        date: Date,
+        rate: Rate,
+    }
+
+    impl From<ExchangeRate> for SurrealExchangeRate {
+        fn from(value: ExchangeRate) -> Self {
+            Self {
+                from: EntityKey(value.from),
+                to: EntityKey(value.to),
+                date: value.date,
+                rate: value.rate,
+            }
+        }
+    }
+
+    impl From<SurrealExchangeRate> for ExchangeRate {
+        fn from(value: SurrealExchangeRate) -> Self {
+            Self {
+                from: value.from.0,
+                to: value.to.0,
+                date: value.date,
+                rate: value.rate,
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Repository for SurrealRepository {
+        async fn save(&self, rate: ExchangeRate) -> Result<(), InfrastructureError> {
+            self.0
+                .query(BeginStatement)
+                .query("DELETE exchange_rate WHERE from = $rate.from AND to = $rate.to AND date = $rate.date")
+                .query("CREATE exchange_rate CONTENT $rate")
+                .query(CommitStatement)
+                .bind(("rate", SurrealExchangeRate::from(rate)))
+                .await?
+                .check()?;
+
+            Ok(())
+        }
+
+        async fn get_all(&self) -> Result<Vec<ExchangeRate>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("SELECT from, to, date, rate FROM exchange_rate ORDER BY date")
+                .await?
+                .check()?;
+
+            let rates: Vec<SurrealExchangeRate> = response.take(0)?;
+            Ok(rates.into_iter().map(Into::into).collect())
+        }
+
+        async fn delete(
+            &self,
+            from: CurrencyId,
+            to: CurrencyId,
+            date: Date,
+        ) -> Result<(), AppError<DeleteError>> {
+            let mut response = self
+                .0
+                .query("DELETE exchange_rate WHERE from = $from AND to = $to AND date = $date RETURN BEFORE")
+                .bind(("from", EntityKey(from)))
+                .bind(("to", EntityKey(to)))
+                .bind(("date", date))
+                .await
+                .catch_infra()?
+                .check()
+                .catch_infra()?;
+
+            let deleted: Vec<SurrealExchangeRate> = response.take(0).catch_infra()?;
+            if deleted.is_empty() {
+                return Err(AppError::App(DeleteError::NotFound));
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub use sqlite_repository::SqliteRepository;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_repository {
+    use cream::context::FromContext;
+    use monee_core::CurrencyId;
+
+    use crate::{
+        backoffice::exchange_rates::domain::{exchange_rate::ExchangeRate, repository::Repository},
+        iprelude::*,
+        prelude::AppError,
+        shared::{
+            domain::{context::SqliteContext, date::Date, errors::DeleteError},
+            infrastructure::{
+                errors::InfrastructureError,
+                sqlite::{self, Connection},
+            },
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(SqliteContext)]
+    pub struct SqliteRepository(Connection);
+
+    #[async_trait::async_trait]
+    impl Repository for SqliteRepository {
+        async fn save(&self, rate: ExchangeRate) -> Result<(), InfrastructureError> {
+            sqlx::query(
+                "INSERT INTO exchange_rate (data) VALUES (?)
+                ON CONFLICT (from_id, to_id, date) DO UPDATE SET data = excluded.data",
+            )
+            .bind(sqlite::encode(&rate))
+            .execute(&self.0)
+            .await?;
+
+            Ok(())
+        }
+
+        async fn get_all(&self) -> Result<Vec<ExchangeRate>, InfrastructureError> {
+            let rows: Vec<String> =
+                sqlx::query_scalar("SELECT data FROM exchange_rate ORDER BY date")
+                    .fetch_all(&self.0)
+                    .await?;
+
+            let rates = rows
+                .iter()
+                .map(|data| sqlite::decode(data))
+                .collect::<sqlite::Result<_>>()?;
+            Ok(rates)
+        }
+
+        async fn delete(
+            &self,
+            from: CurrencyId,
+            to: CurrencyId,
+            date: Date,
+        ) -> Result<(), AppError<DeleteError>> {
+            let result = sqlx::query(
+                "DELETE FROM exchange_rate WHERE from_id = ? AND to_id = ?
+                AND date = json_extract(?, '$')",
+            )
+            .bind(sqlite::key(from))
+            .bind(sqlite::key(to))
+            .bind(sqlite::encode(&date))
+            .execute(&self.0)
+            .await
+            .catch_infra()?;
+
+            match result.rows_affected() {
+                0 => Err(AppError::App(DeleteError::NotFound)),
+                _ => Ok(()),
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+pub mod create_one {
+    use cream::context::FromContext;
+    use monee_core::{GoalId, WalletId};
+
+    use crate::{
+        backoffice::{
+            goals::domain::{goal::Goal, repository::Repository},
+            wallets::domain::repository::Repository as WalletRepository,
+        },
+        shared::{
+            domain::{context::AppContext, errors::UniqueSaveError},
+            infrastructure::errors::AppError,
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct CreateOne {
+        repository: Box<dyn Repository>,
+        wallet_repository: Box<dyn WalletRepository>,
+    }
+
+    impl CreateOne {
+        /// Every goal wallet must exist and hold the goal currency
+        pub async fn run(&self, goal: Goal) -> Result<(), AppError<Error>> {
+            if goal.wallets.is_empty() {
+                return Err(AppError::App(Error::NoWallets));
+            }
+
+            for &wallet_id in goal.wallets.iter() {
+                let wallet = self
+                    .wallet_repository
+                    .get_one(wallet_id)
+                    .await?
+                    .ok_or(AppError::App(Error::WalletNotFound(wallet_id)))?;
+
+                if wallet.currency_id != goal.currency_id {
+                    return Err(AppError::App(Error::CurrenciesNonEqual(wallet_id)));
+                }
+            }
+
+            match self.repository.save(GoalId::new(), goal).await {
+                Ok(()) => Ok(()),
+                Err(AppError::App(UniqueSaveError::AlreadyExists(_))) => {
+                    Err(AppError::App(Error::AlreadyExists))
+                }
+                Err(AppError::Infrastructure(e)) => Err(AppError::Infrastructure(e)),
+            }
+        }
+    }
+
+    #[derive(thiserror::Error, Debug)]
+    pub enum Error {
+        #[error("Goal needs at least one wallet")]
+        NoWallets,
+        #[error("Wallet {0} not found")]
+        WalletNotFound(WalletId),
+        #[error("Wallet {0} does not hold the goal currency")]
+        CurrenciesNonEqual(WalletId),
+        #[error("Goal name already exists")]
+        AlreadyExists,
+    }
+}
+
+pub mod name_resolve {
+    use cream::context::FromContext;
+
+    use crate::{
+        backoffice::goals::domain::{goal_name::GoalName, repository::Repository},
+        prelude::{AppContext, InfrastructureError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct NameResolve {
+        repository: Box<dyn Repository>,
+    }
+
+    impl NameResolve {
+        pub async fn run(
+            &self,
+            name: &GoalName,
+        ) -> Result<Option<monee_core::GoalId>, InfrastructureError> {
+            self.repository.find_by_name(name).await
+        }
+    }
+}
+
+pub mod get_all {
+    use cream::context::FromContext;
+    use monee_core::GoalId;
+
+    use crate::{
+        backoffice::goals::domain::{goal::Goal, repository::Repository},
+        prelude::{AppContext, InfrastructureError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct GetAll {
+        repository: Box<dyn Repository>,
+    }
+
+    impl GetAll {
+        pub async fn run(&self) -> Result<Vec<(GoalId, Goal)>, InfrastructureError> {
+            self.repository.get_all().await
+        }
+    }
+}
+
+pub mod delete_one {
+    use cream::context::FromContext;
+    use monee_core::GoalId;
+
+    use crate::{
+        backoffice::goals::domain::repository::Repository,
+        shared::{
+            domain::{context::AppContext, errors::DeleteError},
+            infrastructure::errors::AppError,
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct DeleteOne {
+        repository: Box<dyn Repository>,
+    }
+
+    impl DeleteOne {
+        /// Wallets are left untouched, only the goal is forgotten
+        pub async fn run(&self, id: GoalId) -> Result<(), AppError<DeleteError>> {
+            self.repository.delete(id).await
+        }
+    }
+}
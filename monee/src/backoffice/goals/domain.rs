@@ -0,0 +1,27 @@
+pub use monee_types::backoffice::goals::*;
+pub mod repository {
+    use monee_core::GoalId;
+
+    use crate::shared::{
+        domain::errors::{DeleteError, UniqueSaveError},
+        infrastructure::errors::{AppError, InfrastructureError},
+    };
+
+    use super::{goal::Goal, goal_name::GoalName};
+
+    #[async_trait::async_trait]
+    pub trait Repository: 'static + Send + Sync {
+        async fn save(&self, id: GoalId, goal: Goal) -> Result<(), AppError<UniqueSaveError>>;
+
+        async fn find_by_name(
+            &self,
+            name: &GoalName,
+        ) -> Result<Option<GoalId>, InfrastructureError>;
+
+        async fn get_all(&self) -> Result<Vec<(GoalId, Goal)>, InfrastructureError>;
+
+        async fn get_one(&self, id: GoalId) -> Result<Option<Goal>, InfrastructureError>;
+
+        async fn delete(&self, id: GoalId) -> Result<(), AppError<DeleteError>>;
+    }
+}
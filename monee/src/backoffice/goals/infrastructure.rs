@@ -0,0 +1,221 @@
+pub mod repository {
+    use cream::context::FromContext;
+    use monee_core::{CurrencyId, GoalId, WalletId};
+
+    use crate::{
+        backoffice::goals::domain::{goal::Goal, goal_name::GoalName, repository::Repository},
+        iprelude::{CatchApp, CatchInfra, MapResponse},
+        shared::{
+            domain::{
+                context::DbContext,
+                date::Date,
+                errors::{DeleteError, UniqueSaveError},
+                slug,
+            },
+            infrastructure::{
+                database::{Connection, Entity, EntityKey},
+                errors::{AppError, InfrastructureError},
+            },
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(DbContext)]
+    pub struct SurrealRepository(Connection);
+
+    #[async_trait::async_trait]
+    impl Repository for SurrealRepository {
+        async fn save(&self, id: GoalId, goal: Goal) -> Result<(), AppError<UniqueSaveError>> {
+            let result = self
+                .0
+                .query("CREATE ONLY type::thing('goal', $id) CONTENT $goal")
+                .bind(("id", id))
+                .bind(("goal", SurrealGoal::from(goal)))
+                .await
+                .catch_infra()?
+                .check();
+
+            result.catch_app().map_response()
+        }
+
+        async fn find_by_name(
+            &self,
+            name: &GoalName,
+        ) -> Result<Option<GoalId>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("SELECT id, name FROM goal")
+                .await
+                .catch_infra()?;
+
+            let ids: Vec<EntityKey<GoalId>> = response.take("id").catch_infra()?;
+            let names: Vec<String> = response.take("name").catch_infra()?;
+            let candidates = ids.into_iter().map(|k| k.0).zip(names);
+
+            Ok(slug::find_match(&name.to_string(), candidates))
+        }
+
+        async fn get_all(&self) -> Result<Vec<(GoalId, Goal)>, InfrastructureError> {
+            let mut response = self.0.query("SELECT * FROM goal").await?.check()?;
+
+            let goals: Vec<Entity<GoalId, SurrealGoal>> = response.take(0)?;
+            Ok(goals.into_iter().map(|e| (e.0, e.1.into())).collect())
+        }
+
+        async fn get_one(&self, id: GoalId) -> Result<Option<Goal>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("SELECT * FROM ONLY type::thing('goal', $id)")
+                .bind(("id", id))
+                .await?
+                .check()?;
+
+            let goal: Option<Entity<GoalId, SurrealGoal>> = response.take(0)?;
+            Ok(goal.map(|e| e.1.into()))
+        }
+
+        async fn delete(&self, id: GoalId) -> Result<(), AppError<DeleteError>> {
+            let mut response = self
+                .0
+                .query("DELETE type::thing('goal', $id) RETURN BEFORE")
+                .bind(("id", id))
+                .await
+                .catch_infra()?
+                .check()
+                .catch_infra()?;
+
+            let deleted: Option<EntityKey<GoalId>> = response.take("id").catch_infra()?;
+            match deleted {
+                Some(_) => Ok(()),
+                None => Err(AppError::App(DeleteError::NotFound)),
+            }
+        }
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct SurrealGoal {
+        name: GoalName,
+        currency_id: EntityKey<CurrencyId>,
+        target: monee_core::Amount,
+        deadline: Date,
+        wallets: Vec<EntityKey<WalletId>>,
+    }
+
+    impl From<Goal> for SurrealGoal {
+        fn from(goal: Goal) -> Self {
+            SurrealGoal {
+                name: goal.name,
+                currency_id: EntityKey(goal.currency_id),
+                target: goal.target,
+                deadline: goal.deadline,
+                wallets: goal.wallets.into_iter().map(EntityKey).collect(),
+            }
+        }
+    }
+
+    impl From<SurrealGoal> for Goal {
+        fn from(goal: SurrealGoal) -> Self {
+            Goal {
+                name: goal.name,
+                currency_id: goal.currency_id.0,
+                target: goal.target,
+                deadline: goal.deadline,
+                wallets: goal.wallets.into_iter().map(|k| k.0).collect(),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub use sqlite_repository::SqliteRepository;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_repository {
+    use cream::context::FromContext;
+    use monee_core::GoalId;
+
+    use crate::{
+        backoffice::goals::domain::{goal::Goal, goal_name::GoalName, repository::Repository},
+        iprelude::{CatchApp, CatchInfra},
+        shared::{
+            domain::{
+                context::SqliteContext,
+                errors::{DeleteError, UniqueSaveError},
+                slug,
+            },
+            infrastructure::{
+                errors::{AppError, InfrastructureError},
+                sqlite::{self, Connection},
+            },
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(SqliteContext)]
+    pub struct SqliteRepository(Connection);
+
+    #[async_trait::async_trait]
+    impl Repository for SqliteRepository {
+        async fn save(&self, id: GoalId, goal: Goal) -> Result<(), AppError<UniqueSaveError>> {
+            sqlx::query("INSERT INTO goal (id, data) VALUES (?, ?)")
+                .bind(sqlite::key(id))
+                .bind(sqlite::encode(&goal))
+                .execute(&self.0)
+                .await
+                .catch_app()?;
+
+            Ok(())
+        }
+
+        async fn find_by_name(
+            &self,
+            name: &GoalName,
+        ) -> Result<Option<GoalId>, InfrastructureError> {
+            let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, name FROM goal")
+                .fetch_all(&self.0)
+                .await
+                .catch_infra()?;
+
+            let candidates = rows
+                .into_iter()
+                .map(|(id, name)| Ok((sqlite::decode_key(id)?, name)))
+                .collect::<sqlite::Result<Vec<_>>>()?;
+
+            Ok(slug::find_match(&name.to_string(), candidates))
+        }
+
+        async fn get_all(&self) -> Result<Vec<(GoalId, Goal)>, InfrastructureError> {
+            let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, data FROM goal")
+                .fetch_all(&self.0)
+                .await?;
+
+            let goals = rows
+                .into_iter()
+                .map(sqlite::decode_row)
+                .collect::<sqlite::Result<_>>()?;
+            Ok(goals)
+        }
+
+        async fn get_one(&self, id: GoalId) -> Result<Option<Goal>, InfrastructureError> {
+            let data: Option<String> = sqlx::query_scalar("SELECT data FROM goal WHERE id = ?")
+                .bind(sqlite::key(id))
+                .fetch_optional(&self.0)
+                .await?;
+
+            Ok(data.as_deref().map(sqlite::decode).transpose()?)
+        }
+
+        async fn delete(&self, id: GoalId) -> Result<(), AppError<DeleteError>> {
+            let result = sqlx::query("DELETE FROM goal WHERE id = ?")
+                .bind(sqlite::key(id))
+                .execute(&self.0)
+                .await
+                .catch_infra()?;
+
+            match result.rows_affected() {
+                0 => Err(AppError::App(DeleteError::NotFound)),
+                _ => Ok(()),
+            }
+        }
+    }
+}
@@ -106,6 +106,57 @@ pub mod unlink {
     }
 }
 
+pub mod move_one {
+    use cream::context::FromContext;
+    use monee_core::ItemTagId;
+
+    use crate::{
+        backoffice::item_tags::domain::repository::{Repository, TagsRelation},
+        shared::{domain::context::AppContext, infrastructure::errors::InfrastructureError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct MoveOne {
+        repository: Box<dyn Repository>,
+    }
+
+    impl MoveOne {
+        /// Detaches `child_id` from all its parents and links it under `parent_id`
+        pub async fn run(
+            &self,
+            child_id: ItemTagId,
+            parent_id: ItemTagId,
+        ) -> Result<Status, InfrastructureError> {
+            if parent_id == child_id {
+                return Ok(Status::CyclicRelation);
+            }
+
+            if let TagsRelation::TargetNotFound =
+                self.repository.check_relation(child_id, parent_id).await?
+            {
+                return Ok(Status::NotFound(child_id));
+            }
+
+            // Check if child_id is already parent of parent_id
+            match self.repository.check_relation(parent_id, child_id).await? {
+                TagsRelation::TargetNotFound => return Ok(Status::NotFound(parent_id)),
+                TagsRelation::Ancestor => return Ok(Status::CyclicRelation),
+                TagsRelation::NotRelated => {}
+            };
+
+            self.repository.move_to(child_id, parent_id).await?;
+            Ok(Status::Moved)
+        }
+    }
+
+    pub enum Status {
+        Moved,
+        CyclicRelation,
+        NotFound(ItemTagId),
+    }
+}
+
 pub mod name_resolve {
     use cream::context::FromContext;
     use monee_core::ItemTagId;
@@ -148,3 +199,122 @@ pub mod get_all {
         }
     }
 }
+
+pub mod update_one {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::ItemTagId;
+    use monee_types::backoffice::item_tags::item_tag_updated::ItemTagUpdated;
+
+    use crate::{
+        backoffice::item_tags::domain::{item_tag::ItemTag, repository::Repository},
+        prelude::AppError,
+        shared::domain::{context::AppContext, errors::UpdateError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct UpdateOne {
+        repository: Box<dyn Repository>,
+        port: EventBusPort,
+    }
+
+    impl UpdateOne {
+        pub async fn run(&self, id: ItemTagId, tag: ItemTag) -> Result<(), AppError<UpdateError>> {
+            self.repository.update(id, tag).await?;
+            self.port.publish(ItemTagUpdated { id });
+
+            Ok(())
+        }
+    }
+}
+
+pub mod delete_one {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::ItemTagId;
+    use monee_types::backoffice::item_tags::item_tag_deleted::ItemTagDeleted;
+
+    use crate::{
+        backoffice::item_tags::domain::repository::Repository,
+        prelude::AppError,
+        shared::domain::{context::AppContext, errors::DeleteError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct DeleteOne {
+        repository: Box<dyn Repository>,
+        port: EventBusPort,
+    }
+
+    impl DeleteOne {
+        pub async fn run(&self, id: ItemTagId) -> Result<(), AppError<DeleteError>> {
+            if self.repository.is_referenced(id).await? {
+                return Err(AppError::App(DeleteError::Referenced));
+            }
+
+            self.repository.delete(id).await?;
+            self.port.publish(ItemTagDeleted { id });
+
+            Ok(())
+        }
+    }
+}
+
+pub mod merge {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::ItemTagId;
+    use monee_types::backoffice::item_tags::item_tags_merged::ItemTagsMerged;
+
+    use crate::{
+        backoffice::item_tags::domain::repository::{Repository, TagsRelation},
+        iprelude::CatchInfra,
+        shared::{
+            domain::{context::AppContext, unit_of_work::UnitOfWork},
+            infrastructure::errors::InfrastructureError,
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct Merge {
+        repository: Box<dyn Repository>,
+        unit_of_work: Box<dyn UnitOfWork>,
+        port: EventBusPort,
+    }
+
+    impl Merge {
+        /// Folds `source` into `target`, `source` children and parents are moved to `target`
+        pub async fn run(
+            &self,
+            source: ItemTagId,
+            target: ItemTagId,
+        ) -> Result<Status, InfrastructureError> {
+            if source == target {
+                return Ok(Status::SameTag);
+            }
+
+            // Moving the relations of related tags could close a cycle
+            for (tag, maybe_ancestor) in [(target, source), (source, target)] {
+                match self.repository.check_relation(tag, maybe_ancestor).await? {
+                    TagsRelation::TargetNotFound => return Ok(Status::NotFound(tag)),
+                    TagsRelation::Ancestor => return Ok(Status::Related),
+                    TagsRelation::NotRelated => {}
+                }
+            }
+
+            let mut tx = self.unit_of_work.begin();
+            self.repository.stage_merge(&mut tx, source, target);
+            tx.commit().await.catch_infra()?;
+            self.port.publish(ItemTagsMerged { source, target });
+
+            Ok(Status::Merged)
+        }
+    }
+
+    pub enum Status {
+        Merged,
+        SameTag,
+        Related,
+        NotFound(ItemTagId),
+    }
+}
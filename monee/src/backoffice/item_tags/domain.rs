@@ -4,7 +4,13 @@ pub mod repository {
 
     use crate::{
         prelude::AppError,
-        shared::{domain::errors::UniqueSaveError, infrastructure::errors::InfrastructureError},
+        shared::{
+            domain::{
+                errors::{DeleteError, UniqueSaveError, UpdateError},
+                unit_of_work::Transaction,
+            },
+            infrastructure::errors::InfrastructureError,
+        },
     };
 
     use super::{item_name::ItemName, item_tag::ItemTag, item_tag_node::ItemTagNode};
@@ -31,12 +37,33 @@ pub mod repository {
             child_id: ItemTagId,
         ) -> Result<(), InfrastructureError>;
 
+        /// Replaces every parent of `child_id` with `parent_id`
+        async fn move_to(
+            &self,
+            child_id: ItemTagId,
+            parent_id: ItemTagId,
+        ) -> Result<(), InfrastructureError>;
+
         async fn name_resolve(
             &self,
             name: &ItemName,
         ) -> Result<Option<ItemTagId>, InfrastructureError>;
 
         async fn get_all(&self) -> Result<Vec<ItemTagNode>, InfrastructureError>;
+
+        async fn update(&self, id: ItemTagId, tag: ItemTag) -> Result<(), AppError<UpdateError>>;
+
+        async fn delete(&self, id: ItemTagId) -> Result<(), AppError<DeleteError>>;
+
+        /// Whether any event still points to the tag, or it still contains other tags
+        async fn is_referenced(&self, id: ItemTagId) -> Result<bool, InfrastructureError>;
+
+        /// Stages deleting the tag along its relations, a missing tag is left as is
+        fn stage_delete(&self, tx: &mut Transaction, id: ItemTagId);
+
+        /// Stages moving every event reference and relation from `source` to `target`, then
+        /// deleting `source`
+        fn stage_merge(&self, tx: &mut Transaction, source: ItemTagId, target: ItemTagId);
     }
 
     pub enum TagsRelation {
@@ -1,6 +1,7 @@
 pub mod repository {
     use cream::context::FromContext;
     use monee_core::ItemTagId;
+    use surrealdb::sql::statements::{BeginStatement, CommitStatement};
 
     use crate::{
         backoffice::item_tags::domain::{
@@ -12,7 +13,12 @@ pub mod repository {
         iprelude::*,
         prelude::*,
         shared::{
-            domain::{context::DbContext, errors::UniqueSaveError},
+            domain::{
+                context::DbContext,
+                errors::{DeleteError, UniqueSaveError, UpdateError},
+                slug,
+                unit_of_work::Transaction,
+            },
             infrastructure::database::{Connection, EntityKey},
         },
     };
@@ -99,19 +105,41 @@ pub mod repository {
             Ok(())
         }
 
+        async fn move_to(
+            &self,
+            child_id: ItemTagId,
+            parent_id: ItemTagId,
+        ) -> Result<(), InfrastructureError> {
+            self.0
+                .query(BeginStatement)
+                .query("LET $child = type::thing('item_tag', $child_id)")
+                .query("LET $parent = type::thing('item_tag', $parent_id)")
+                .query("DELETE contains WHERE out = $child")
+                .query("RELATE $parent->contains->$child")
+                .query(CommitStatement)
+                .bind(("child_id", child_id))
+                .bind(("parent_id", parent_id))
+                .await?
+                .check()?;
+
+            Ok(())
+        }
+
         async fn name_resolve(
             &self,
             name: &ItemName,
         ) -> Result<Option<ItemTagId>, InfrastructureError> {
             let mut response = self
                 .0
-                .query("SELECT id FROM item_tag WHERE name = $name")
-                .bind(("name", name))
+                .query("SELECT id, name FROM item_tag")
                 .await?
                 .check()?;
 
-            let id: Option<EntityKey<monee_core::ItemTagId>> = response.take("id")?;
-            Ok(id.map(|k| k.0))
+            let ids: Vec<EntityKey<ItemTagId>> = response.take("id")?;
+            let names: Vec<String> = response.take("name")?;
+            let candidates = ids.into_iter().map(|k| k.0).zip(names);
+
+            Ok(slug::find_match(&name.to_string(), candidates))
         }
 
         async fn get_all(&self) -> Result<Vec<ItemTagNode>, InfrastructureError> {
@@ -142,6 +170,92 @@ pub mod repository {
             let nodes: Vec<SqlItemTagNode> = response.take(0)?;
             Ok(nodes.into_iter().map(Into::into).collect())
         }
+
+        async fn update(&self, id: ItemTagId, tag: ItemTag) -> Result<(), AppError<UpdateError>> {
+            let result = self
+                .0
+                .query("UPDATE item_tag CONTENT $data WHERE id = type::thing('item_tag', $id)")
+                .bind(("id", id))
+                .bind(("data", tag))
+                .await
+                .catch_infra()?
+                .check();
+
+            let mut response = result.map_err(AppError::<UpdateError>::from)?;
+            let updated: Option<EntityKey<ItemTagId>> = response.take("id").catch_infra()?;
+
+            match updated {
+                Some(_) => Ok(()),
+                None => Err(AppError::App(UpdateError::NotFound)),
+            }
+        }
+
+        async fn delete(&self, id: ItemTagId) -> Result<(), AppError<DeleteError>> {
+            let mut response = self
+                .0
+                .query(BeginStatement)
+                .query("LET $tag = type::thing('item_tag', $id)")
+                .query("DELETE contains WHERE in = $tag OR out = $tag")
+                .query("DELETE $tag RETURN BEFORE")
+                .query(CommitStatement)
+                .bind(("id", id))
+                .await
+                .catch_infra()?
+                .check()
+                .catch_infra()?;
+
+            // BEGIN and COMMIT give no results
+            let deleted: Option<EntityKey<ItemTagId>> = response.take((2, "id")).catch_infra()?;
+            match deleted {
+                Some(_) => Ok(()),
+                None => Err(AppError::App(DeleteError::NotFound)),
+            }
+        }
+
+        async fn is_referenced(&self, id: ItemTagId) -> Result<bool, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("LET $tag = type::thing('item_tag', $id)")
+                .query("SELECT count() FROM event WHERE item = $tag OR fee.item = $tag GROUP ALL")
+                .query("SELECT count() FROM contains WHERE in = $tag GROUP ALL")
+                .bind(("id", id))
+                .await?
+                .check()?;
+
+            let events: Option<i64> = response.take((1, "count"))?;
+            let children: Option<i64> = response.take((2, "count"))?;
+            Ok(events.unwrap_or(0) > 0 || children.unwrap_or(0) > 0)
+        }
+
+        fn stage_delete(&self, tx: &mut Transaction, id: ItemTagId) {
+            tx.stage(move |query, i| {
+                query
+                    .query(format!("LET $tag{i} = type::thing('item_tag', $tag_id{i})"))
+                    .query(format!(
+                        "DELETE contains WHERE in = $tag{i} OR out = $tag{i}"
+                    ))
+                    .query(format!("DELETE $tag{i}"))
+                    .bind((format!("tag_id{i}"), id))
+            });
+        }
+
+        fn stage_merge(&self, tx: &mut Transaction, source: ItemTagId, target: ItemTagId) {
+            tx.stage(move |query, i| {
+                query
+                    .query(format!("LET $source{i} = type::thing('item_tag', $source_id{i})"))
+                    .query(format!("LET $target{i} = type::thing('item_tag', $target_id{i})"))
+                    .query(format!("UPDATE event SET item = $target{i} WHERE item = $source{i}"))
+                    .query(format!("UPDATE event SET fee.item = $target{i} WHERE fee.item = $source{i}"))
+                    .query(format!("LET $children{i} = (SELECT VALUE out FROM contains WHERE in = $source{i} AND out != $target{i} AND out NOTINSIDE $target{i}->contains->item_tag)"))
+                    .query(format!("LET $parents{i} = (SELECT VALUE in FROM contains WHERE out = $source{i} AND in != $target{i} AND in NOTINSIDE $target{i}<-contains<-item_tag)"))
+                    .query(format!("FOR $child IN $children{i} {{ RELATE $target{i}->contains->$child; }}"))
+                    .query(format!("FOR $parent IN $parents{i} {{ RELATE $parent->contains->$target{i}; }}"))
+                    .query(format!("DELETE contains WHERE in = $source{i} OR out = $source{i}"))
+                    .query(format!("DELETE $source{i}"))
+                    .bind((format!("source_id{i}"), source))
+                    .bind((format!("target_id{i}"), target))
+            });
+        }
     }
 
     async fn check_multi_relation(
@@ -158,7 +272,7 @@ pub mod repository {
             .collect::<Vec<_>>();
 
         let mut response = connection
-            .query("SELECT <-contains<-item_tag as parents FROM ")
+            .query("SELECT <-contains<-item_tag as parents FROM $items")
             .bind(("items", parents))
             .await?
             .check()?;
@@ -181,3 +295,270 @@ pub mod repository {
         Box::pin(check_multi_relation(connection, &grand_parents, child_id)).await
     }
 }
+
+#[cfg(feature = "sqlite")]
+pub use sqlite_repository::SqliteRepository;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_repository {
+    use cream::context::FromContext;
+    use monee_core::ItemTagId;
+
+    use crate::{
+        backoffice::item_tags::domain::{
+            item_name::ItemName,
+            item_tag::ItemTag,
+            item_tag_node::ItemTagNode,
+            repository::{Repository, TagsRelation},
+        },
+        iprelude::*,
+        prelude::*,
+        shared::{
+            domain::{
+                context::SqliteContext,
+                errors::{DeleteError, UniqueSaveError, UpdateError},
+                slug,
+                unit_of_work::Transaction,
+            },
+            infrastructure::sqlite::{self, Connection, Statement},
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(SqliteContext)]
+    pub struct SqliteRepository(Connection);
+
+    #[async_trait::async_trait]
+    impl Repository for SqliteRepository {
+        async fn save(
+            &self,
+            id: ItemTagId,
+            item_tag: ItemTag,
+        ) -> Result<(), AppError<UniqueSaveError>> {
+            sqlx::query("INSERT INTO item_tag (id, data) VALUES (?, ?)")
+                .bind(sqlite::key(id))
+                .bind(sqlite::encode(&item_tag))
+                .execute(&self.0)
+                .await
+                .catch_app()?;
+
+            Ok(())
+        }
+
+        async fn check_relation(
+            &self,
+            target_tag: ItemTagId,
+            maybe_ancestor: ItemTagId,
+        ) -> Result<TagsRelation, InfrastructureError> {
+            let target = sqlite::key(target_tag);
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM item_tag WHERE id = ?)")
+                    .bind(&target)
+                    .fetch_one(&self.0)
+                    .await?;
+
+            if !exists {
+                return Ok(TagsRelation::TargetNotFound);
+            }
+
+            let is_ancestor: bool = sqlx::query_scalar(
+                "WITH RECURSIVE ancestor(id) AS (
+                    SELECT parent_id FROM contains WHERE child_id = ?1
+                    UNION
+                    SELECT contains.parent_id FROM contains
+                    JOIN ancestor ON contains.child_id = ancestor.id
+                )
+                SELECT EXISTS (SELECT 1 FROM ancestor WHERE id = ?2)",
+            )
+            .bind(&target)
+            .bind(sqlite::key(maybe_ancestor))
+            .fetch_one(&self.0)
+            .await?;
+
+            Ok(match is_ancestor {
+                true => TagsRelation::Ancestor,
+                false => TagsRelation::NotRelated,
+            })
+        }
+
+        async fn link(
+            &self,
+            parent_id: ItemTagId,
+            child_id: ItemTagId,
+        ) -> Result<(), AppError<UniqueSaveError>> {
+            sqlx::query("INSERT INTO contains (parent_id, child_id) VALUES (?, ?)")
+                .bind(sqlite::key(parent_id))
+                .bind(sqlite::key(child_id))
+                .execute(&self.0)
+                .await
+                .catch_app()?;
+
+            Ok(())
+        }
+
+        async fn unlink(
+            &self,
+            parent_id: ItemTagId,
+            child_id: ItemTagId,
+        ) -> Result<(), InfrastructureError> {
+            sqlx::query("DELETE FROM contains WHERE parent_id = ? AND child_id = ?")
+                .bind(sqlite::key(parent_id))
+                .bind(sqlite::key(child_id))
+                .execute(&self.0)
+                .await?;
+
+            Ok(())
+        }
+
+        async fn move_to(
+            &self,
+            child_id: ItemTagId,
+            parent_id: ItemTagId,
+        ) -> Result<(), InfrastructureError> {
+            let child = sqlite::key(child_id);
+
+            let mut tx = self.0.begin().await?;
+            sqlx::query("DELETE FROM contains WHERE child_id = ?")
+                .bind(&child)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("INSERT INTO contains (parent_id, child_id) VALUES (?, ?)")
+                .bind(sqlite::key(parent_id))
+                .bind(&child)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+
+            Ok(())
+        }
+
+        async fn name_resolve(
+            &self,
+            name: &ItemName,
+        ) -> Result<Option<ItemTagId>, InfrastructureError> {
+            let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, name FROM item_tag")
+                .fetch_all(&self.0)
+                .await?;
+
+            let candidates = rows
+                .into_iter()
+                .map(|(id, name)| Ok((sqlite::decode_key(id)?, name)))
+                .collect::<sqlite::Result<Vec<_>>>()?;
+
+            Ok(slug::find_match(&name.to_string(), candidates))
+        }
+
+        async fn get_all(&self) -> Result<Vec<ItemTagNode>, InfrastructureError> {
+            let rows: Vec<(String, String, String)> = sqlx::query_as(
+                "SELECT id, data, (
+                    SELECT json_group_array(parent.name) FROM contains
+                    JOIN item_tag AS parent ON parent.id = contains.parent_id
+                    WHERE contains.child_id = item_tag.id
+                ) FROM item_tag",
+            )
+            .fetch_all(&self.0)
+            .await?;
+
+            let nodes = rows
+                .into_iter()
+                .map(|(id, data, parents_name)| {
+                    Ok(ItemTagNode {
+                        id: sqlite::decode_key(id)?,
+                        tag: sqlite::decode(&data)?,
+                        parents_name: sqlite::decode(&parents_name)?,
+                    })
+                })
+                .collect::<sqlite::Result<_>>()?;
+            Ok(nodes)
+        }
+
+        async fn update(&self, id: ItemTagId, tag: ItemTag) -> Result<(), AppError<UpdateError>> {
+            let result = sqlx::query("UPDATE item_tag SET data = ? WHERE id = ?")
+                .bind(sqlite::encode(&tag))
+                .bind(sqlite::key(id))
+                .execute(&self.0)
+                .await
+                .map_err(AppError::<UpdateError>::from)?;
+
+            match result.rows_affected() {
+                0 => Err(AppError::App(UpdateError::NotFound)),
+                _ => Ok(()),
+            }
+        }
+
+        async fn delete(&self, id: ItemTagId) -> Result<(), AppError<DeleteError>> {
+            let id = sqlite::key(id);
+
+            let mut tx = self.0.begin().await.catch_infra()?;
+            sqlx::query("DELETE FROM contains WHERE parent_id = ?1 OR child_id = ?1")
+                .bind(&id)
+                .execute(&mut *tx)
+                .await
+                .catch_infra()?;
+            let result = sqlx::query("DELETE FROM item_tag WHERE id = ?")
+                .bind(&id)
+                .execute(&mut *tx)
+                .await
+                .catch_infra()?;
+            tx.commit().await.catch_infra()?;
+
+            match result.rows_affected() {
+                0 => Err(AppError::App(DeleteError::NotFound)),
+                _ => Ok(()),
+            }
+        }
+
+        async fn is_referenced(&self, id: ItemTagId) -> Result<bool, InfrastructureError> {
+            let referenced: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM event WHERE json_extract(data, '$.item') = ?1
+                    OR json_extract(data, '$.fee.item') = ?1)
+                    OR EXISTS (SELECT 1 FROM contains WHERE parent_id = ?1)",
+            )
+            .bind(sqlite::key(id))
+            .fetch_one(&self.0)
+            .await?;
+
+            Ok(referenced)
+        }
+
+        fn stage_delete(&self, tx: &mut Transaction, id: ItemTagId) {
+            let id = sqlite::key(id);
+
+            tx.stage_sqlite(
+                Statement::new("DELETE FROM contains WHERE parent_id = ?1 OR child_id = ?1")
+                    .bind(id.as_str()),
+            );
+            tx.stage_sqlite(Statement::new("DELETE FROM item_tag WHERE id = ?").bind(id));
+        }
+
+        fn stage_merge(&self, tx: &mut Transaction, source: ItemTagId, target: ItemTagId) {
+            let source = sqlite::key(source);
+            let target = sqlite::key(target);
+
+            let moves = [
+                "UPDATE event SET data = json_set(data, '$.item', ?2)
+                WHERE json_extract(data, '$.item') = ?1",
+                "UPDATE event SET data = json_set(data, '$.fee.item', ?2)
+                WHERE json_extract(data, '$.fee.item') = ?1",
+                "INSERT OR IGNORE INTO contains (parent_id, child_id)
+                SELECT ?2, child_id FROM contains WHERE parent_id = ?1 AND child_id != ?2",
+                "INSERT OR IGNORE INTO contains (parent_id, child_id)
+                SELECT parent_id, ?2 FROM contains WHERE child_id = ?1 AND parent_id != ?2",
+            ];
+
+            for statement in moves {
+                tx.stage_sqlite(
+                    Statement::new(statement)
+                        .bind(source.as_str())
+                        .bind(target.as_str()),
+                );
+            }
+
+            tx.stage_sqlite(
+                Statement::new("DELETE FROM contains WHERE parent_id = ?1 OR child_id = ?1")
+                    .bind(source.as_str()),
+            );
+            tx.stage_sqlite(Statement::new("DELETE FROM item_tag WHERE id = ?").bind(source));
+        }
+    }
+}
@@ -1,14 +1,53 @@
 pub mod domain {
     pub mod repository {
-        use monee_core::Snapshot;
+        use monee_core::{EventId, Snapshot};
 
-        use crate::shared::infrastructure::errors::InfrastructureError;
+        use crate::shared::{
+            domain::{date::Datetime, unit_of_work::Transaction},
+            infrastructure::errors::InfrastructureError,
+        };
+
+        use super::checkpoint::Checkpoint;
 
         #[async_trait::async_trait]
         pub trait SnapshotRepository: Send + Sync {
             async fn read_last(&self) -> Result<Option<Snapshot>, InfrastructureError>;
             async fn save(&self, snapshot: Snapshot) -> Result<(), InfrastructureError>;
             async fn delete_all(&self) -> Result<(), InfrastructureError>;
+
+            /// Stages replacing every stored snapshot with `snapshot`
+            fn stage_replace(&self, tx: &mut Transaction, snapshot: Snapshot);
+
+            /// Stages `snapshot` as the checkpoint of the day of the newest of `events`, unless
+            /// an event stored before them is newer. Checkpoints taken after the oldest of
+            /// `events` are dropped, they miss it
+            fn stage_checkpoint(
+                &self,
+                tx: &mut Transaction,
+                snapshot: Snapshot,
+                events: Vec<EventId>,
+            );
+
+            /// Latest checkpoint taken at or before `until`
+            async fn read_checkpoint(
+                &self,
+                until: Datetime,
+            ) -> Result<Option<Checkpoint>, InfrastructureError>;
+            fn stage_delete_checkpoints(&self, tx: &mut Transaction);
+        }
+    }
+
+    pub mod checkpoint {
+        use monee_core::{EventId, Snapshot};
+
+        use crate::shared::domain::date::Datetime;
+
+        /// The snapshot as it was right after the event `event_id` was applied.
+        /// One is kept per day, so past balances only need the events of that day onwards
+        pub struct Checkpoint {
+            pub snapshot: Snapshot,
+            pub event_id: EventId,
+            pub created_at: Datetime,
         }
     }
 }
@@ -42,6 +81,91 @@ pub mod application {
                     monee_core::WalletOperation::Create {
                         currency_id: event.currency_id,
                         wallet_id: event.id,
+                        kind: event.kind,
+                    },
+                ));
+
+                if result.is_ok() {
+                    self.snapshot_io
+                        .save(snapshot)
+                        .await
+                        .expect("to save snapshot");
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    pub mod on_wallet_deleted {
+        use cream::{context::FromContext, events::Handler};
+
+        use crate::shared::domain::context::AppContext;
+
+        use super::snapshot_io::SnapshotIO;
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct OnWalletDeleted {
+            snapshot_io: SnapshotIO,
+        }
+
+        impl Handler for OnWalletDeleted {
+            type Event = crate::backoffice::wallets::domain::wallet_deleted::WalletDeleted;
+
+            async fn handle(self, event: Self::Event) -> Result<(), cream::events::Error> {
+                let mut snapshot = self
+                    .snapshot_io
+                    .read_last()
+                    .await
+                    .expect("to read snapshot");
+
+                // If snapshot does not have this wallet, do nothing
+                let result = snapshot.apply(monee_core::Operation::Wallet(
+                    monee_core::WalletOperation::Delete {
+                        wallet_id: event.id,
+                    },
+                ));
+
+                if result.is_ok() {
+                    self.snapshot_io
+                        .save(snapshot)
+                        .await
+                        .expect("to save snapshot");
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    pub mod on_wallet_kind_changed {
+        use cream::{context::FromContext, events::Handler};
+
+        use crate::shared::domain::context::AppContext;
+
+        use super::snapshot_io::SnapshotIO;
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct OnWalletKindChanged {
+            snapshot_io: SnapshotIO,
+        }
+
+        impl Handler for OnWalletKindChanged {
+            type Event = crate::backoffice::wallets::domain::wallet_kind_changed::WalletKindChanged;
+
+            async fn handle(self, event: Self::Event) -> Result<(), cream::events::Error> {
+                let mut snapshot = self
+                    .snapshot_io
+                    .read_last()
+                    .await
+                    .expect("to read snapshot");
+
+                let result = snapshot.apply(monee_core::Operation::Wallet(
+                    monee_core::WalletOperation::ChangeKind {
+                        wallet_id: event.id,
+                        kind: event.kind,
                     },
                 ));
 
@@ -59,16 +183,27 @@ pub mod application {
 
     pub mod snapshot_io {
         use cream::context::FromContext;
+        use monee_core::EventId;
 
         use crate::{
             backoffice::snapshot::domain::repository::SnapshotRepository,
-            shared::{domain::context::AppContext, infrastructure::errors::InfrastructureError},
+            backoffice::snapshot::domain::checkpoint::Checkpoint,
+            iprelude::CatchInfra,
+            shared::{
+                domain::{
+                    context::AppContext,
+                    date::Datetime,
+                    unit_of_work::{Transaction, UnitOfWork},
+                },
+                infrastructure::errors::InfrastructureError,
+            },
         };
 
         #[derive(FromContext)]
         #[context(AppContext)]
         pub struct SnapshotIO {
             repository: Box<dyn SnapshotRepository>,
+            unit_of_work: Box<dyn UnitOfWork>,
         }
 
         impl SnapshotIO {
@@ -83,10 +218,39 @@ pub mod application {
                 &self,
                 snapshot: monee_core::Snapshot,
             ) -> Result<(), InfrastructureError> {
-                self.repository.delete_all().await?;
-                self.repository.save(snapshot).await?;
+                let mut tx = self.unit_of_work.begin();
+                self.stage_save(&mut tx, snapshot);
+                tx.commit().await.catch_infra()
+            }
 
-                Ok(())
+            /// Stages `snapshot` as the last one, to be committed along other changes
+            pub fn stage_save(&self, tx: &mut Transaction, snapshot: monee_core::Snapshot) {
+                self.repository.stage_replace(tx, snapshot);
+            }
+
+            /// Stages `snapshot` as the last one, left by the `events` staged before it. Only
+            /// then it is kept as a checkpoint, other changes do not happen at an event time
+            pub fn stage_save_events(
+                &self,
+                tx: &mut Transaction,
+                snapshot: monee_core::Snapshot,
+                events: Vec<EventId>,
+            ) {
+                self.repository.stage_replace(tx, snapshot.clone());
+                self.repository.stage_checkpoint(tx, snapshot, events);
+            }
+
+            pub async fn read_checkpoint(
+                &self,
+                until: Datetime,
+            ) -> Result<Option<Checkpoint>, InfrastructureError> {
+                self.repository.read_checkpoint(until).await
+            }
+
+            /// Stages forgetting every checkpoint, for when the events they were taken from
+            /// are gone
+            pub fn stage_delete_checkpoints(&self, tx: &mut Transaction) {
+                self.repository.stage_delete_checkpoints(tx);
             }
         }
     }
@@ -96,13 +260,14 @@ pub mod infrastructure {
     pub mod snapshot_repository {
         use cream::context::FromContext;
         use monee_core::{
-            ActorId, Amount, CurrencyId, Debt, DebtId, Money, MoneyMap, Snapshot, Wallet, WalletId,
+            ActorId, Amount, Balance, CurrencyId, Debt, DebtId, EventId, Money, MoneyMap, Snapshot,
+            Wallet, WalletId, WalletKind,
         };
 
         use crate::{
-            backoffice::snapshot::domain::repository::SnapshotRepository,
+            backoffice::snapshot::domain::{checkpoint::Checkpoint, repository::SnapshotRepository},
             shared::{
-                domain::context::DbContext,
+                domain::{context::DbContext, date::Datetime, unit_of_work::Transaction},
                 infrastructure::{
                     database::{EntityKey, Connection, Entity},
                     errors::InfrastructureError,
@@ -115,7 +280,7 @@ pub mod infrastructure {
         pub struct SnapshotSurrealRepository(Connection);
 
         #[derive(serde::Serialize, serde::Deserialize)]
-        struct SurrealSnapshot {
+        pub(crate) struct SurrealSnapshot {
             wallets: Vec<Entity<WalletId, SurrealWallet>>,
             debts: Vec<Entity<DebtId, SurrealDebt>>,
             loans: Vec<Entity<DebtId, SurrealDebt>>,
@@ -129,8 +294,10 @@ pub mod infrastructure {
 
         #[derive(serde::Serialize, serde::Deserialize)]
         struct SurrealWallet {
-            #[serde(flatten)]
-            money: SurrealMoney,
+            amount: Balance,
+            currency_id: EntityKey<CurrencyId>,
+            #[serde(default)]
+            kind: WalletKind,
         }
 
         #[derive(serde::Serialize, serde::Deserialize)]
@@ -152,7 +319,9 @@ pub mod infrastructure {
         impl From<Wallet> for SurrealWallet {
             fn from(value: Wallet) -> Self {
                 Self {
-                    money: value.money.into(),
+                    amount: value.balance,
+                    currency_id: EntityKey(value.currency_id),
+                    kind: value.kind,
                 }
             }
         }
@@ -200,7 +369,9 @@ pub mod infrastructure {
         impl From<SurrealWallet> for Wallet {
             fn from(wallet: SurrealWallet) -> Self {
                 Self {
-                    money: wallet.money.into(),
+                    balance: wallet.amount,
+                    currency_id: wallet.currency_id.0,
+                    kind: wallet.kind,
                 }
             }
         }
@@ -239,6 +410,44 @@ pub mod infrastructure {
             }
         }
 
+        impl SurrealSnapshot {
+            fn checked_rescale(mut self, decimals: u32) -> Option<Self> {
+                for Entity(_, wallet) in self.wallets.iter_mut() {
+                    wallet.amount = wallet.amount.checked_rescale(decimals)?;
+                    wallet.kind = wallet.kind.checked_rescale(decimals)?;
+                }
+
+                for Entity(_, debt) in self.debts.iter_mut().chain(self.loans.iter_mut()) {
+                    debt.money.amount = debt.money.amount.checked_rescale(decimals)?;
+                }
+
+                Some(self)
+            }
+        }
+
+        /// Reads the last snapshot, stored with `decimals` decimals, rescaled to the current
+        /// precision. Used by the amounts migration
+        pub(crate) async fn read_rescaled(
+            connection: &Connection,
+            decimals: u32,
+        ) -> surrealdb::Result<Option<impl serde::Serialize>> {
+            let mut response = connection
+                .query("SELECT * FROM ONLY snapshot ORDER BY created_at DESC LIMIT 1")
+                .await?
+                .check()?;
+
+            let snapshot: Option<SurrealSnapshot> = response.take(0)?;
+            snapshot
+                .map(|snapshot| {
+                    snapshot.checked_rescale(decimals).ok_or_else(|| {
+                        surrealdb::Error::Api(surrealdb::error::Api::Query(
+                            "snapshot amounts are too big to be rescaled".to_owned(),
+                        ))
+                    })
+                })
+                .transpose()
+        }
+
         #[async_trait::async_trait]
         impl SnapshotRepository for SnapshotSurrealRepository {
             async fn read_last(&self) -> Result<Option<Snapshot>, InfrastructureError> {
@@ -269,6 +478,216 @@ pub mod infrastructure {
                 self.0.query("DELETE FROM snapshot").await?.check()?;
                 Ok(())
             }
+
+            fn stage_replace(&self, tx: &mut Transaction, snapshot: monee_core::Snapshot) {
+                let snapshot = SurrealSnapshot::from(snapshot);
+                tx.stage(move |query, i| {
+                    query
+                        .query("DELETE FROM snapshot")
+                        .query(format!("CREATE snapshot CONTENT $snapshot{i}"))
+                        .bind((format!("snapshot{i}"), snapshot))
+                });
+            }
+
+            fn stage_checkpoint(
+                &self,
+                tx: &mut Transaction,
+                snapshot: monee_core::Snapshot,
+                events: Vec<EventId>,
+            ) {
+                let snapshot = SurrealSnapshot::from(snapshot);
+                let events: Vec<_> = events.into_iter().map(EntityKey).collect();
+                tx.stage(move |query, i| {
+                    query
+                        .query(format!(
+                            "LET $added{i} = (SELECT id, created_at FROM event
+                            WHERE id IN $events{i} ORDER BY created_at)"
+                        ))
+                        .query(format!("LET $oldest{i} = array::first($added{i})"))
+                        .query(format!("LET $newest{i} = array::last($added{i})"))
+                        .query(format!(
+                            "IF $oldest{i} != NONE {{
+                                DELETE snapshot_checkpoint
+                                WHERE created_at > $oldest{i}.created_at
+                            }}"
+                        ))
+                        .query(format!(
+                            "IF $newest{i} != NONE AND array::len((SELECT id FROM event
+                                WHERE created_at > $newest{i}.created_at)) == 0 {{
+                                UPDATE type::thing('snapshot_checkpoint',
+                                    time::format($newest{i}.created_at, '%Y-%m-%d'))
+                                SET wallets = $checkpoint{i}.wallets,
+                                    debts = $checkpoint{i}.debts,
+                                    loans = $checkpoint{i}.loans,
+                                    event_id = $newest{i}.id,
+                                    created_at = $newest{i}.created_at
+                            }}"
+                        ))
+                        .bind((format!("events{i}"), events))
+                        .bind((format!("checkpoint{i}"), snapshot))
+                });
+            }
+
+            async fn read_checkpoint(
+                &self,
+                until: Datetime,
+            ) -> Result<Option<Checkpoint>, InfrastructureError> {
+                #[derive(serde::Deserialize)]
+                struct SurrealCheckpoint {
+                    #[serde(flatten)]
+                    snapshot: SurrealSnapshot,
+                    event_id: EntityKey<EventId>,
+                    created_at: Datetime,
+                }
+
+                let mut response = self
+                    .0
+                    .query(
+                        "SELECT * FROM ONLY snapshot_checkpoint WHERE created_at <= <datetime> $until
+                        ORDER BY created_at DESC LIMIT 1",
+                    )
+                    .bind(("until", until))
+                    .await?
+                    .check()?;
+
+                let checkpoint: Option<SurrealCheckpoint> = response.take(0)?;
+                Ok(checkpoint.map(|checkpoint| Checkpoint {
+                    snapshot: checkpoint.snapshot.into(),
+                    event_id: checkpoint.event_id.0,
+                    created_at: checkpoint.created_at,
+                }))
+            }
+
+            fn stage_delete_checkpoints(&self, tx: &mut Transaction) {
+                tx.stage(|query, _| query.query("DELETE FROM snapshot_checkpoint"));
+            }
+        }
+
+        #[cfg(feature = "sqlite")]
+        pub use sqlite_repository::SnapshotSqliteRepository;
+
+        #[cfg(feature = "sqlite")]
+        mod sqlite_repository {
+            use cream::context::FromContext;
+            use monee_core::{EventId, Snapshot};
+
+            use crate::{
+                backoffice::snapshot::domain::{
+                    checkpoint::Checkpoint, repository::SnapshotRepository,
+                },
+                shared::{
+                    domain::{
+                        context::SqliteContext,
+                        date::{Datetime, Timezone},
+                        unit_of_work::Transaction,
+                    },
+                    infrastructure::{
+                        errors::InfrastructureError,
+                        sqlite::{self, Connection, Statement},
+                    },
+                },
+            };
+
+            #[derive(FromContext)]
+            #[context(SqliteContext)]
+            pub struct SnapshotSqliteRepository(Connection);
+
+            #[async_trait::async_trait]
+            impl SnapshotRepository for SnapshotSqliteRepository {
+                async fn read_last(&self) -> Result<Option<Snapshot>, InfrastructureError> {
+                    let data: Option<String> = sqlx::query_scalar(
+                        "SELECT data FROM snapshot ORDER BY created_at DESC, id DESC LIMIT 1",
+                    )
+                    .fetch_optional(&self.0)
+                    .await?;
+
+                    Ok(data.as_deref().map(sqlite::decode).transpose()?)
+                }
+
+                async fn save(&self, snapshot: Snapshot) -> Result<(), InfrastructureError> {
+                    sqlx::query("INSERT INTO snapshot (data, created_at) VALUES (?, ?)")
+                        .bind(sqlite::encode(&snapshot))
+                        .bind(sqlite::datetime(Timezone::now()))
+                        .execute(&self.0)
+                        .await?;
+
+                    Ok(())
+                }
+
+                async fn delete_all(&self) -> Result<(), InfrastructureError> {
+                    sqlx::query("DELETE FROM snapshot").execute(&self.0).await?;
+                    Ok(())
+                }
+
+                fn stage_replace(&self, tx: &mut Transaction, snapshot: Snapshot) {
+                    tx.stage_sqlite(Statement::new("DELETE FROM snapshot"));
+                    tx.stage_sqlite(
+                        Statement::new("INSERT INTO snapshot (data, created_at) VALUES (?, ?)")
+                            .bind(sqlite::encode(&snapshot))
+                            .bind(sqlite::datetime(Timezone::now())),
+                    );
+                }
+
+                fn stage_checkpoint(
+                    &self,
+                    tx: &mut Transaction,
+                    snapshot: Snapshot,
+                    events: Vec<EventId>,
+                ) {
+                    let ids: Vec<_> = events.into_iter().map(sqlite::key).collect();
+                    let ids = sqlite::encode(&ids);
+
+                    tx.stage_sqlite(
+                        Statement::new(
+                            "DELETE FROM snapshot_checkpoint WHERE created_at > (
+                                SELECT min(created_at) FROM event
+                                WHERE id IN (SELECT value FROM json_each(?))
+                            )",
+                        )
+                        .bind(ids.clone()),
+                    );
+                    tx.stage_sqlite(
+                        Statement::new(
+                            "INSERT INTO snapshot_checkpoint (day, data, event_id, created_at)
+                            SELECT substr(created_at, 1, 10), ?, id, created_at FROM event AS added
+                            WHERE id IN (SELECT value FROM json_each(?))
+                            AND NOT EXISTS (SELECT 1 FROM event WHERE created_at > added.created_at)
+                            ORDER BY created_at DESC, rowid DESC LIMIT 1
+                            ON CONFLICT (day) DO UPDATE SET data = excluded.data,
+                                event_id = excluded.event_id, created_at = excluded.created_at",
+                        )
+                        .bind(sqlite::encode(&snapshot))
+                        .bind(ids),
+                    );
+                }
+
+                async fn read_checkpoint(
+                    &self,
+                    until: Datetime,
+                ) -> Result<Option<Checkpoint>, InfrastructureError> {
+                    let row: Option<(String, String, String)> = sqlx::query_as(
+                        "SELECT data, event_id, created_at FROM snapshot_checkpoint
+                        WHERE created_at <= ? ORDER BY created_at DESC LIMIT 1",
+                    )
+                    .bind(sqlite::datetime(until))
+                    .fetch_optional(&self.0)
+                    .await?;
+
+                    let Some((data, event_id, created_at)) = row else {
+                        return Ok(None);
+                    };
+
+                    Ok(Some(Checkpoint {
+                        snapshot: sqlite::decode(&data)?,
+                        event_id: sqlite::decode_key(event_id)?,
+                        created_at: sqlite::decode_datetime(&created_at)?,
+                    }))
+                }
+
+                fn stage_delete_checkpoints(&self, tx: &mut Transaction) {
+                    tx.stage_sqlite(Statement::new("DELETE FROM snapshot_checkpoint"));
+                }
+            }
         }
 
         #[cfg(all(test, feature = "db_test"))]
@@ -295,6 +714,7 @@ pub mod infrastructure {
                         monee_core::WalletOperation::Create {
                             wallet_id,
                             currency_id,
+                            kind: Default::default(),
                         },
                     ))
                     .unwrap();
@@ -313,12 +733,13 @@ pub mod infrastructure {
                     currency_id,
                     name: "wallet_1".parse().unwrap(),
                     description: "".into(),
+                    kind: Default::default(),
                 };
 
                 tokio::runtime::Runtime::new()
                     .unwrap()
                     .block_on(async move {
-                        let db = crate::shared::infrastructure::database::connect()
+                        let db = crate::shared::infrastructure::database::connect("mem://")
                             .await
                             .unwrap();
 
@@ -330,6 +751,60 @@ pub mod infrastructure {
                         repo.save(snapshot).await.unwrap();
                     });
             }
+
+            #[tokio::test]
+            async fn back_dated_events_drop_later_checkpoints() {
+                use crate::{
+                    backoffice::events::{
+                        domain::{
+                            event::{Event, Purchase},
+                            repository::Repository as _,
+                        },
+                        infrastructure::repository::SurrealRepository as EventsRepository,
+                    },
+                    host::sync::domain::node_changes::EventEntry,
+                    shared::infrastructure::database::transaction::Transaction,
+                };
+
+                let db = crate::shared::infrastructure::database::connect("mem://")
+                    .await
+                    .unwrap();
+                let ctx = crate::shared::domain::context::DbContext::new(db.clone());
+                let repo: super::SnapshotSurrealRepository = ctx.provide();
+                let events: EventsRepository = ctx.provide();
+
+                let add = |created_at: &str| {
+                    let entry = EventEntry {
+                        id: EventId::new(),
+                        event: Event::Purchase(Purchase {
+                            item: Default::default(),
+                            actors: vec![].into(),
+                            wallet_id: WalletId::default(),
+                            amount: Amount::default(),
+                            quantity: None,
+                        }),
+                        created_at: created_at.parse().unwrap(),
+                    };
+                    let id = entry.id;
+
+                    let mut tx = Transaction::new(db.clone());
+                    events.stage_many(&mut tx, vec![entry]);
+                    repo.stage_checkpoint(&mut tx, Snapshot::default(), vec![id]);
+                    async move {
+                        tx.commit().await.unwrap();
+                        id
+                    }
+                };
+                let until = "2024-01-31T00:00:00Z".parse().unwrap();
+
+                let last = add("2024-01-10T00:00:00Z").await;
+                let checkpoint = repo.read_checkpoint(until).await.unwrap();
+                assert!(checkpoint.is_some_and(|checkpoint| checkpoint.event_id == last));
+
+                add("2024-01-05T00:00:00Z").await;
+                let checkpoint = repo.read_checkpoint(until).await.unwrap();
+                assert!(checkpoint.is_none());
+            }
         }
     }
 }
@@ -47,9 +47,14 @@ pub mod create_one {
         pub async fn run(&self, wallet: Wallet) -> Result<(), AppError<UniqueSaveError>> {
             let id = WalletId::new();
             let currency_id = wallet.currency_id;
+            let kind = wallet.kind;
 
             self.repository.save(id, wallet).await?;
-            self.bus.publish(WalletCreated { id, currency_id });
+            self.bus.publish(WalletCreated {
+                id,
+                currency_id,
+                kind,
+            });
 
             Ok(())
         }
@@ -65,13 +70,14 @@ pub mod create_one {
 }
 
 pub mod update_one {
-    use cream::context::FromContext;
+    use cream::{context::FromContext, events::bus::EventBusPort};
     use monee_core::WalletId;
 
     use crate::{
         backoffice::wallets::domain::{
             repository::{Repository, UpdateError},
             wallet_name::WalletName,
+            wallet_updated::WalletUpdated,
         },
         shared::domain::context::AppContext,
     };
@@ -80,6 +86,7 @@ pub mod update_one {
     #[context(AppContext)]
     pub struct UpdateOne {
         repository: Box<dyn Repository>,
+        bus: EventBusPort,
     }
 
     impl UpdateOne {
@@ -90,6 +97,142 @@ pub mod update_one {
             description: String,
         ) -> Result<(), UpdateError> {
             self.repository.update(id, name, description).await?;
+            self.bus.publish(WalletUpdated { id });
+
+            Ok(())
+        }
+    }
+}
+
+pub mod change_kind {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::{WalletId, WalletKind};
+
+    use crate::{
+        backoffice::{
+            snapshot::application::snapshot_io::SnapshotIO,
+            wallets::domain::{
+                repository::{Repository, UpdateError},
+                wallet_kind_changed::WalletKindChanged,
+            },
+        },
+        shared::{domain::context::AppContext, infrastructure::errors::AppError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct ChangeKind {
+        repository: Box<dyn Repository>,
+        snapshot_io: SnapshotIO,
+        bus: EventBusPort,
+    }
+
+    impl ChangeKind {
+        /// Refuses kinds that could not hold the current balance, e.g. making a wallet in
+        /// overdraft a debit one
+        pub async fn run(&self, id: WalletId, kind: WalletKind) -> Result<(), AppError<Error>> {
+            let snapshot = self.snapshot_io.read_last().await?;
+            if let Some(wallet) = snapshot.wallets.get(&id) {
+                if !kind.allows(wallet.balance) {
+                    return Err(AppError::App(Error::BalanceOutOfRange));
+                }
+            }
+
+            match self.repository.change_kind(id, kind).await {
+                Ok(()) => {}
+                Err(UpdateError::NotFound) => return Err(AppError::App(Error::NotFound)),
+                Err(UpdateError::AlreadyExists) => unreachable!("name is not changed"),
+                Err(UpdateError::Unspecified(e)) => return Err(AppError::Infrastructure(e)),
+            }
+
+            self.bus.publish(WalletKindChanged { id, kind });
+            Ok(())
+        }
+    }
+
+    #[derive(thiserror::Error, Debug)]
+    pub enum Error {
+        #[error("Wallet id not found")]
+        NotFound,
+        #[error("Wallet balance is not allowed by the new kind")]
+        BalanceOutOfRange,
+    }
+}
+
+pub mod get_all {
+    use cream::context::FromContext;
+    use monee_core::WalletId;
+
+    use crate::{
+        backoffice::wallets::domain::{repository::Repository, wallet::Wallet},
+        prelude::{AppContext, InfrastructureError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct GetAll {
+        repository: Box<dyn Repository>,
+    }
+
+    impl GetAll {
+        pub async fn run(&self) -> Result<Vec<(WalletId, Wallet)>, InfrastructureError> {
+            self.repository.get_all().await
+        }
+    }
+}
+
+pub mod get_one {
+    use cream::context::FromContext;
+    use monee_core::WalletId;
+
+    use crate::{
+        backoffice::wallets::domain::{repository::Repository, wallet::Wallet},
+        prelude::{AppContext, InfrastructureError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct GetOne {
+        repository: Box<dyn Repository>,
+    }
+
+    impl GetOne {
+        pub async fn run(&self, id: WalletId) -> Result<Option<Wallet>, InfrastructureError> {
+            self.repository.get_one(id).await
+        }
+    }
+}
+
+pub mod delete_one {
+    use cream::{context::FromContext, events::bus::EventBusPort};
+    use monee_core::WalletId;
+
+    use crate::{
+        backoffice::wallets::domain::{repository::Repository, wallet_deleted::WalletDeleted},
+        shared::{
+            domain::{context::AppContext, errors::DeleteError},
+            infrastructure::errors::AppError,
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct DeleteOne {
+        repository: Box<dyn Repository>,
+        bus: EventBusPort,
+    }
+
+    impl DeleteOne {
+        /// The wallet snapshot entry is dropped once `WalletDeleted` is handled
+        pub async fn run(&self, id: WalletId) -> Result<(), AppError<DeleteError>> {
+            // Without events pointing to it, the wallet balance can only be zero
+            if self.repository.is_referenced(id).await? {
+                return Err(AppError::App(DeleteError::Referenced));
+            }
+
+            self.repository.delete(id).await?;
+            self.bus.publish(WalletDeleted { id });
+
             Ok(())
         }
     }
@@ -1,9 +1,12 @@
 pub use monee_types::backoffice::wallets::*;
 pub mod repository {
-    use monee_core::WalletId;
+    use monee_core::{WalletId, WalletKind};
 
     use crate::shared::{
-        domain::errors::UniqueSaveError,
+        domain::{
+            errors::{DeleteError, UniqueSaveError},
+            unit_of_work::Transaction,
+        },
         infrastructure::errors::{AppError, InfrastructureError},
     };
 
@@ -24,10 +27,24 @@ pub mod repository {
             description: String,
         ) -> Result<(), UpdateError>;
 
+        async fn change_kind(&self, id: WalletId, kind: WalletKind) -> Result<(), UpdateError>;
+
         async fn find_by_name(
             &self,
             name: &WalletName,
         ) -> Result<Option<WalletId>, InfrastructureError>;
+
+        async fn get_all(&self) -> Result<Vec<(WalletId, Wallet)>, InfrastructureError>;
+
+        async fn get_one(&self, id: WalletId) -> Result<Option<Wallet>, InfrastructureError>;
+
+        async fn delete(&self, id: WalletId) -> Result<(), AppError<DeleteError>>;
+
+        /// Stages deleting the wallet, a missing wallet is left as is
+        fn stage_delete(&self, tx: &mut Transaction, id: WalletId);
+
+        /// Whether any event or goal still points to the wallet
+        async fn is_referenced(&self, id: WalletId) -> Result<bool, InfrastructureError>;
     }
 
     #[derive(thiserror::Error, Debug)]
@@ -1,6 +1,6 @@
 pub mod repository {
     use cream::context::FromContext;
-    use monee_core::WalletId;
+    use monee_core::{WalletId, WalletKind};
 
     use crate::{
         backoffice::wallets::domain::{
@@ -10,9 +10,14 @@ pub mod repository {
         },
         iprelude::{CatchApp, CatchInfra, MapResponse},
         shared::{
-            domain::{context::DbContext, errors::UniqueSaveError},
+            domain::{
+                context::DbContext,
+                errors::{DeleteError, UniqueSaveError},
+                slug,
+                unit_of_work::Transaction,
+            },
             infrastructure::{
-                database::{Connection, EntityKey},
+                database::{Connection, Entity, EntityKey},
                 errors::{AppError, InfrastructureError},
             },
         },
@@ -30,11 +35,12 @@ pub mod repository {
             wallet: Wallet,
         ) -> Result<(), AppError<UniqueSaveError>> {
             let result = self.0
-                .query("CREATE ONLY type::thing('wallet', $id) SET currency_id = type::thing('currency', $currency_id), name = $name, description = $description")
+                .query("CREATE ONLY type::thing('wallet', $id) SET currency_id = type::thing('currency', $currency_id), name = $name, description = $description, kind = $kind")
                 .bind(("id", id))
                 .bind(("currency_id", wallet.currency_id))
                 .bind(("name", wallet.name))
                 .bind(("description", wallet.description))
+                .bind(("kind", wallet.kind))
                 .await
                 .catch_infra()?
                 .check();
@@ -73,19 +79,120 @@ pub mod repository {
             }
         }
 
+        async fn change_kind(&self, id: WalletId, kind: WalletKind) -> Result<(), UpdateError> {
+            let mut response = self
+                .0
+                .query("UPDATE wallet SET kind = $kind WHERE id = type::thing('wallet', $id)")
+                .bind(("id", id))
+                .bind(("kind", kind))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| UpdateError::Unspecified(e.into()))?;
+
+            let updated: Option<Entity<WalletId, ()>> = response
+                .take(0)
+                .map_err(|e| UpdateError::Unspecified(e.into()))?;
+
+            match updated {
+                Some(_) => Ok(()),
+                None => Err(UpdateError::NotFound),
+            }
+        }
+
         async fn find_by_name(
             &self,
             name: &WalletName,
         ) -> Result<Option<WalletId>, InfrastructureError> {
             let mut response = self
                 .0
-                .query("SELECT VALUE id FROM wallet WHERE name = $name")
-                .bind(("name", name))
+                .query("SELECT id, name FROM wallet")
                 .await
                 .catch_infra()?;
 
-            let wallet_id: Option<EntityKey<WalletId>> = response.take(0).catch_infra()?;
-            Ok(wallet_id.map(|w| w.0))
+            let ids: Vec<EntityKey<WalletId>> = response.take("id").catch_infra()?;
+            let names: Vec<String> = response.take("name").catch_infra()?;
+            let candidates = ids.into_iter().map(|k| k.0).zip(names);
+
+            Ok(slug::find_match(&name.to_string(), candidates))
+        }
+
+        async fn get_all(&self) -> Result<Vec<(WalletId, Wallet)>, InfrastructureError> {
+            let mut response = self.0.query("SELECT * FROM wallet").await?.check()?;
+
+            let wallets: Vec<Entity<WalletId, SurrealWallet>> = response.take(0)?;
+            Ok(wallets.into_iter().map(|e| (e.0, e.1.into())).collect())
+        }
+
+        async fn get_one(&self, id: WalletId) -> Result<Option<Wallet>, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("SELECT * FROM ONLY type::thing('wallet', $id)")
+                .bind(("id", id))
+                .await?
+                .check()?;
+
+            let wallet: Option<Entity<WalletId, SurrealWallet>> = response.take(0)?;
+            Ok(wallet.map(|e| e.1.into()))
+        }
+
+        async fn delete(&self, id: WalletId) -> Result<(), AppError<DeleteError>> {
+            let mut response = self
+                .0
+                .query("DELETE type::thing('wallet', $id) RETURN BEFORE")
+                .bind(("id", id))
+                .await
+                .catch_infra()?
+                .check()
+                .catch_infra()?;
+
+            let deleted: Option<EntityKey<WalletId>> = response.take("id").catch_infra()?;
+            match deleted {
+                Some(_) => Ok(()),
+                None => Err(AppError::App(DeleteError::NotFound)),
+            }
+        }
+
+        fn stage_delete(&self, tx: &mut Transaction, id: WalletId) {
+            tx.stage(move |query, i| {
+                query
+                    .query(format!("DELETE type::thing('wallet', $wallet_id{i})"))
+                    .bind((format!("wallet_id{i}"), id))
+            });
+        }
+
+        async fn is_referenced(&self, id: WalletId) -> Result<bool, InfrastructureError> {
+            let mut response = self
+                .0
+                .query("LET $wallet = type::thing('wallet', $id)")
+                .query("SELECT count() FROM event WHERE wallet_id = $wallet OR from = $wallet OR to = $wallet GROUP ALL")
+                .query("SELECT count() FROM goal WHERE $wallet INSIDE wallets GROUP ALL")
+                .bind(("id", id))
+                .await?
+                .check()?;
+
+            let events: Option<i64> = response.take((1, "count"))?;
+            let goals: Option<i64> = response.take((2, "count"))?;
+            Ok(events.unwrap_or(0) + goals.unwrap_or(0) > 0)
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    struct SurrealWallet {
+        currency_id: EntityKey<monee_core::CurrencyId>,
+        name: WalletName,
+        description: String,
+        #[serde(default)]
+        kind: monee_core::WalletKind,
+    }
+
+    impl From<SurrealWallet> for Wallet {
+        fn from(wallet: SurrealWallet) -> Self {
+            Wallet {
+                currency_id: wallet.currency_id.0,
+                name: wallet.name,
+                description: wallet.description,
+                kind: wallet.kind,
+            }
         }
     }
 
@@ -100,7 +207,7 @@ pub mod repository {
         fn can_save() {
             return;
             tokio::runtime::Runtime::new().unwrap().block_on(async {
-                let db = crate::shared::infrastructure::database::connect().await.unwrap();
+                let db = crate::shared::infrastructure::database::connect("mem://").await.unwrap();
                 let ctx = crate::shared::domain::context::DbContext::new(db.clone());
                 let wallet_repo: crate::backoffice::wallets::infrastructure::repository::SurrealRepository = ctx.provide();
 
@@ -109,6 +216,7 @@ pub mod repository {
                     currency_id: CurrencyId::new(),
                     name: "foo".parse().unwrap(),
                     description: "description".into(),
+                    kind: Default::default(),
                 };
                 wallet_repo.save(id, wallet).await.unwrap();
 
@@ -121,3 +229,166 @@ pub mod repository {
     }
 }
 
+#[cfg(feature = "sqlite")]
+pub use sqlite_repository::SqliteRepository;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_repository {
+    use cream::context::FromContext;
+    use monee_core::{WalletId, WalletKind};
+
+    use crate::{
+        backoffice::wallets::domain::{
+            repository::{Repository, UpdateError},
+            wallet::Wallet,
+            wallet_name::WalletName,
+        },
+        iprelude::{CatchApp, CatchInfra},
+        shared::{
+            domain::{
+                context::SqliteContext,
+                errors::{DeleteError, UniqueSaveError},
+                slug,
+                unit_of_work::Transaction,
+            },
+            infrastructure::{
+                errors::{AppError, InfrastructureError},
+                sqlite::{self, Connection, Statement},
+            },
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(SqliteContext)]
+    pub struct SqliteRepository(Connection);
+
+    #[async_trait::async_trait]
+    impl Repository for SqliteRepository {
+        async fn save(
+            &self,
+            id: WalletId,
+            wallet: Wallet,
+        ) -> Result<(), AppError<UniqueSaveError>> {
+            sqlx::query("INSERT INTO wallet (id, data) VALUES (?, ?)")
+                .bind(sqlite::key(id))
+                .bind(sqlite::encode(&wallet))
+                .execute(&self.0)
+                .await
+                .catch_app()?;
+
+            Ok(())
+        }
+
+        async fn update(
+            &self,
+            id: WalletId,
+            name: Option<WalletName>,
+            description: String,
+        ) -> Result<(), UpdateError> {
+            let result = sqlx::query(
+                "UPDATE wallet SET data = json_set(data, '$.name', json(?), '$.description', ?)
+                WHERE id = ?",
+            )
+            .bind(sqlite::encode(&name))
+            .bind(description)
+            .bind(sqlite::key(id))
+            .execute(&self.0)
+            .await;
+
+            match result {
+                Ok(result) if result.rows_affected() == 0 => Err(UpdateError::NotFound),
+                Ok(_) => Ok(()),
+                Err(e) if sqlite::unique_violation(&e).is_some() => Err(UpdateError::AlreadyExists),
+                Err(e) => Err(UpdateError::Unspecified(e.into())),
+            }
+        }
+
+        async fn change_kind(&self, id: WalletId, kind: WalletKind) -> Result<(), UpdateError> {
+            let result = sqlx::query(
+                "UPDATE wallet SET data = json_set(data, '$.kind', json(?)) WHERE id = ?",
+            )
+            .bind(sqlite::encode(&kind))
+            .bind(sqlite::key(id))
+            .execute(&self.0)
+            .await
+            .map_err(|e| UpdateError::Unspecified(e.into()))?;
+
+            match result.rows_affected() {
+                0 => Err(UpdateError::NotFound),
+                _ => Ok(()),
+            }
+        }
+
+        async fn find_by_name(
+            &self,
+            name: &WalletName,
+        ) -> Result<Option<WalletId>, InfrastructureError> {
+            let rows: Vec<(String, String)> =
+                sqlx::query_as("SELECT id, name FROM wallet WHERE name IS NOT NULL")
+                    .fetch_all(&self.0)
+                    .await
+                    .catch_infra()?;
+
+            let candidates = rows
+                .into_iter()
+                .map(|(id, name)| Ok((sqlite::decode_key(id)?, name)))
+                .collect::<sqlite::Result<Vec<_>>>()?;
+
+            Ok(slug::find_match(&name.to_string(), candidates))
+        }
+
+        async fn get_all(&self) -> Result<Vec<(WalletId, Wallet)>, InfrastructureError> {
+            let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, data FROM wallet")
+                .fetch_all(&self.0)
+                .await?;
+
+            let wallets = rows
+                .into_iter()
+                .map(sqlite::decode_row)
+                .collect::<sqlite::Result<_>>()?;
+            Ok(wallets)
+        }
+
+        async fn get_one(&self, id: WalletId) -> Result<Option<Wallet>, InfrastructureError> {
+            let data: Option<String> = sqlx::query_scalar("SELECT data FROM wallet WHERE id = ?")
+                .bind(sqlite::key(id))
+                .fetch_optional(&self.0)
+                .await?;
+
+            Ok(data.as_deref().map(sqlite::decode).transpose()?)
+        }
+
+        async fn delete(&self, id: WalletId) -> Result<(), AppError<DeleteError>> {
+            let result = sqlx::query("DELETE FROM wallet WHERE id = ?")
+                .bind(sqlite::key(id))
+                .execute(&self.0)
+                .await
+                .catch_infra()?;
+
+            match result.rows_affected() {
+                0 => Err(AppError::App(DeleteError::NotFound)),
+                _ => Ok(()),
+            }
+        }
+
+        fn stage_delete(&self, tx: &mut Transaction, id: WalletId) {
+            tx.stage_sqlite(
+                Statement::new("DELETE FROM wallet WHERE id = ?").bind(sqlite::key(id)),
+            );
+        }
+
+        async fn is_referenced(&self, id: WalletId) -> Result<bool, InfrastructureError> {
+            let referenced: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM event WHERE json_extract(data, '$.wallet_id') = ?1
+                        OR json_extract(data, '$.from') = ?1 OR json_extract(data, '$.to') = ?1)
+                    OR EXISTS (SELECT 1 FROM goal, json_each(goal.data, '$.wallets')
+                        WHERE json_each.value = ?1)",
+            )
+            .bind(sqlite::key(id))
+            .fetch_one(&self.0)
+            .await?;
+
+            Ok(referenced)
+        }
+    }
+}
@@ -0,0 +1,92 @@
+pub mod domain {
+    pub use monee_types::doctor::*;
+}
+
+pub mod application {
+    pub mod doctor {
+        use cream::context::FromContext;
+
+        use crate::{
+            backoffice::{
+                actors::domain::repository::Repository as ActorRepository,
+                currencies::domain::repository::Repository as CurrencyRepository,
+                events::domain::repository::Repository as EventRepository,
+                item_tags::domain::repository::Repository as ItemTagRepository,
+                snapshot::application::snapshot_io::SnapshotIO,
+                wallets::domain::repository::Repository as WalletRepository,
+            },
+            doctor::domain::{
+                audit::{self, Database},
+                finding::Finding,
+            },
+            shared::{
+                domain::{context::AppContext, date::Timezone},
+                infrastructure::errors::InfrastructureError,
+            },
+        };
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct Doctor {
+            wallet_repository: Box<dyn WalletRepository>,
+            actor_repository: Box<dyn ActorRepository>,
+            currency_repository: Box<dyn CurrencyRepository>,
+            item_tag_repository: Box<dyn ItemTagRepository>,
+            event_repository: Box<dyn EventRepository>,
+            snapshot_io: SnapshotIO,
+        }
+
+        impl Doctor {
+            /// Everything that does not add up in the database
+            pub async fn check(&self) -> Result<Vec<Finding>, InfrastructureError> {
+                let database = self.database().await?;
+                Ok(audit::audit(&database))
+            }
+
+            /// Checks the database and fixes what can be fixed without losing data.
+            /// Every finding is returned along with whether it was fixed
+            pub async fn fix(&self) -> Result<Vec<(Finding, bool)>, InfrastructureError> {
+                let database = self.database().await?;
+                let findings = audit::audit(&database);
+                let mut snapshot = database.snapshot;
+
+                let mut fixed_any = false;
+                let findings: Vec<_> = findings
+                    .into_iter()
+                    .map(|finding| {
+                        let fixed = finding
+                            .fix()
+                            .is_some_and(|operation| snapshot.apply(operation).is_ok());
+                        fixed_any |= fixed;
+                        (finding, fixed)
+                    })
+                    .collect();
+
+                if fixed_any {
+                    self.snapshot_io.save(snapshot).await?;
+                }
+
+                Ok(findings)
+            }
+
+            async fn database(&self) -> Result<Database, InfrastructureError> {
+                let actors = self.actor_repository.get_all().await?;
+                let currencies = self.currency_repository.get_all().await?;
+                let items = self.item_tag_repository.get_all().await?;
+                let events = self.event_repository.get_until(Timezone::now()).await?;
+
+                Ok(Database {
+                    wallets: self.wallet_repository.get_all().await?,
+                    actors: actors.into_iter().map(|(id, _)| id).collect(),
+                    currencies: currencies.into_iter().map(|(id, _)| id).collect(),
+                    items: items.into_iter().map(|node| node.id).collect(),
+                    snapshot: self.snapshot_io.read_last().await?,
+                    events: events
+                        .into_iter()
+                        .map(|entry| (entry.id, entry.event))
+                        .collect(),
+                })
+            }
+        }
+    }
+}
@@ -36,6 +36,49 @@ pub mod repository {
         }
     }
 
+    #[cfg(feature = "sqlite")]
+    pub use sqlite_repository::SqliteRepository;
+
+    #[cfg(feature = "sqlite")]
+    mod sqlite_repository {
+        use monee_types::apps::{app_id::AppId, app_manifest::AppManifest};
+
+        use crate::{
+            host::nodes::domain::repository::Repository,
+            shared::{
+                domain::context::SqliteContext,
+                infrastructure::sqlite::{self, Connection},
+            },
+        };
+        use crate::{iprelude::*, prelude::*};
+
+        #[derive(FromContext)]
+        #[context(SqliteContext)]
+        pub struct SqliteRepository(Connection);
+
+        #[async_trait::async_trait]
+        impl Repository for SqliteRepository {
+            async fn save(&self, id: AppId, app: AppManifest) -> Result<(), InfrastructureError> {
+                sqlx::query("INSERT INTO node (id, data) VALUES (?, ?)")
+                    .bind(sqlite::key(id))
+                    .bind(sqlite::encode(&app))
+                    .execute(&self.0)
+                    .await?;
+
+                Ok(())
+            }
+
+            async fn exists(&self, id: AppId) -> Result<bool, InfrastructureError> {
+                let found: Option<i64> = sqlx::query_scalar("SELECT 1 FROM node WHERE id = ?")
+                    .bind(sqlite::key(id))
+                    .fetch_optional(&self.0)
+                    .await?;
+
+                Ok(found.is_some())
+            }
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         #![allow(unused)]
@@ -46,7 +89,7 @@ pub mod repository {
         #[cfg(feature = "db_test")]
         #[tokio::test]
         async fn checks_exists() {
-            let db = connect().await.unwrap();
+            let db = connect("mem://").await.unwrap();
             let ctx = DbContext::new(db);
 
             let repo: SurrealRepository = ctx.provide();
@@ -61,7 +104,7 @@ pub mod repository {
         #[cfg(feature = "db_test")]
         #[tokio::test]
         async fn fails_doesnt_exist() {
-            let db = connect().await.unwrap();
+            let db = connect("mem://").await.unwrap();
             let ctx = DbContext::new(db);
 
             let repo: SurrealRepository = ctx.provide();
@@ -14,7 +14,10 @@ pub mod get_sync_guide {
 }
 
 pub mod sync_node_changes {
-    use monee_types::apps::app_id::AppId;
+    use monee_types::{
+        apps::app_id::AppId,
+        doctor::{audit::references, finding::Reference},
+    };
 
     use crate::backoffice::events::domain::{
         apply_event::apply_event, repository::Repository as EventsRepository,
@@ -23,15 +26,20 @@ pub mod sync_node_changes {
     use crate::host::sync::domain::node_synced::NodeSynced;
     use crate::host::sync::domain::sync_error::SyncError;
     use crate::host::sync::domain::{repository::Repository, node_changes::NodeChanges};
+    use crate::shared::domain::unit_of_work::UnitOfWork;
     use crate::{iprelude::*, prelude::*};
     use cream::events::bus::EventBusPort;
 
+    use super::apply_removals::ApplyRemovals;
+
     #[derive(FromContext)]
     #[context(AppContext)]
     pub struct SyncNodeChanges {
         sync_repo: Box<dyn Repository>,
         snapshot_io: SnapshotIO,
         events_repo: Box<dyn EventsRepository>,
+        apply_removals: ApplyRemovals,
+        unit_of_work: Box<dyn UnitOfWork>,
         event_bus: EventBusPort,
     }
 
@@ -44,11 +52,11 @@ pub mod sync_node_changes {
             self.sync_repo.save_sync(client_id, &sync).await?;
 
             let mut snapshot = self.snapshot_io.read_last().await?;
-            let events_apply_result = sync
-                .events
-                .iter()
-                .map(|entry| &entry.event)
-                .try_for_each(|event| apply_event(&mut snapshot, event));
+            let events_apply_result = sync.events.iter().try_for_each(|entry| {
+                let _span = tracing::debug_span!("event", event_id = %entry.id).entered();
+                apply_event(&mut snapshot, &entry.event)
+                    .inspect_err(|_| tracing::warn!("event does not apply to the snapshot"))
+            });
 
             if let Err(e) = events_apply_result {
                 let error = SyncError::Event(e);
@@ -56,26 +64,312 @@ pub mod sync_node_changes {
                 return Err(AppError::App(error));
             }
 
-            let save_result = self
-                .sync_repo
-                .save_changes(&sync.data)
-                .await
-                .catch_infra()?;
+            let events = sync.events.len();
+
+            let ids: Vec<_> = sync.events.iter().map(|entry| entry.id).collect();
+            let incoming: Vec<Reference> = sync
+                .events
+                .iter()
+                .flat_map(|entry| references(&entry.event))
+                .collect();
+
+            // The catalog, the events, the removals and the snapshot they lead to are stored
+            // together or not at all. Removals go after the events so merges rewrite them too
+            let mut tx = self.unit_of_work.begin();
+            self.sync_repo.stage_changes(&mut tx, sync.data);
+            self.events_repo.stage_many(&mut tx, sync.events);
+            let removals = self
+                .apply_removals
+                .stage(&mut tx, &mut snapshot, &sync.removals, &incoming)
+                .await?;
+            self.snapshot_io.stage_save_events(&mut tx, snapshot, ids);
 
+            let save_result = tx.commit().await.catch_app().catch_infra()?;
             if let Err(e) = save_result {
                 let error = SyncError::Save(e);
                 self.sync_repo.save_sync_error(client_id, &error).await?;
                 return Err(AppError::App(error));
             }
 
-            self.events_repo.save_many(sync.events).await?;
-            self.snapshot_io.save(snapshot).await?;
-
+            self.apply_removals.publish(removals);
             self.event_bus.publish(NodeSynced(client_id));
+            tracing::info!(node_id = %client_id, events, "node changes synced");
 
             Ok(())
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        #[cfg(feature = "db_test")]
+        #[tokio::test]
+        async fn merges_rewrite_synced_events() {
+            use super::*;
+            use crate::host::sync::domain::{
+                catalog::Catalog,
+                catalog_removals::{CatalogRemovals, Removal},
+                node_changes::EventEntry,
+            };
+            use crate::shared::domain::{context::AppContextBuilder, date::Timezone};
+            use crate::shared::infrastructure::database::config::DatabaseConfig;
+            use monee_core::{ActorId, CurrencyId, EventId};
+            use monee_types::backoffice::{
+                actors::{actor::Actor, actor_type::ActorType},
+                events::event::{DebtRegister, Event},
+            };
+
+            let ctx = AppContextBuilder {
+                database: DatabaseConfig::from_url("mem://"),
+                migrate: true,
+            }
+            .build()
+            .await
+            .unwrap()
+            .setup();
+            let sync: SyncNodeChanges = ctx.provide();
+            let events: Box<dyn EventsRepository> = ctx.provide();
+
+            let (source, target) = (ActorId::new(), ActorId::new());
+            let actor = |name: &str| Actor {
+                name: name.parse().unwrap(),
+                actor_type: ActorType::Natural,
+                alias: None,
+            };
+            let catalog = |actors| Catalog {
+                actors,
+                currencies: vec![],
+                items: vec![],
+                wallets: vec![],
+            };
+
+            let client_id = AppId::default();
+            sync.run(
+                client_id,
+                NodeChanges {
+                    events: vec![],
+                    data: catalog(vec![(source, actor("source")), (target, actor("target"))]),
+                    removals: CatalogRemovals::default(),
+                },
+            )
+            .await
+            .unwrap();
+
+            sync.run(
+                client_id,
+                NodeChanges {
+                    events: vec![EventEntry {
+                        id: EventId::new(),
+                        event: Event::RegisterDebt(DebtRegister {
+                            amount: "10".parse().unwrap(),
+                            currency_id: CurrencyId::new(),
+                            actor_id: source,
+                            payment_promise: None,
+                            wallet_id: None,
+                            debt_id: None,
+                            terms: None,
+                        }),
+                        created_at: Timezone::now(),
+                    }],
+                    data: catalog(vec![]),
+                    removals: CatalogRemovals {
+                        actors: vec![Removal {
+                            id: source,
+                            merged_into: Some(target),
+                        }],
+                        ..Default::default()
+                    },
+                },
+            )
+            .await
+            .unwrap();
+
+            let stored = events.get_until(Timezone::now()).await.unwrap();
+            assert!(matches!(
+                &stored[..],
+                [EventEntry {
+                    event: Event::RegisterDebt(DebtRegister { actor_id, .. }),
+                    ..
+                }] if *actor_id == target
+            ));
+        }
+    }
+}
+
+pub mod apply_removals {
+    use cream::events::bus::EventBusPort;
+    use monee_core::{ItemTagId, Operation, Snapshot, WalletOperation};
+    use monee_types::{
+        backoffice::{
+            actors::{actor_deleted::ActorDeleted, actors_merged::ActorsMerged},
+            currencies::currency_deleted::CurrencyDeleted,
+            item_tags::{item_tag_deleted::ItemTagDeleted, item_tags_merged::ItemTagsMerged},
+            wallets::wallet_deleted::WalletDeleted,
+        },
+        doctor::finding::Reference,
+    };
+
+    use crate::backoffice::{
+        actors::domain::repository::Repository as ActorsRepository,
+        currencies::domain::repository::Repository as CurrenciesRepository,
+        item_tags::domain::repository::{Repository as ItemsRepository, TagsRelation},
+        wallets::domain::repository::Repository as WalletsRepository,
+    };
+    use crate::host::sync::domain::catalog_removals::CatalogRemovals;
+    use crate::shared::domain::unit_of_work::Transaction;
+    use crate::{iprelude::*, prelude::*};
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct ApplyRemovals {
+        actors: Box<dyn ActorsRepository>,
+        currencies: Box<dyn CurrenciesRepository>,
+        items: Box<dyn ItemsRepository>,
+        wallets: Box<dyn WalletsRepository>,
+        port: EventBusPort,
+    }
+
+    impl ApplyRemovals {
+        /// Stages the removals that can be applied and folds them into `snapshot`, returning them.
+        /// Entries still referenced on the host, by its events or by the `incoming` events, are
+        /// kept, the next host state brings them back to the node
+        pub async fn stage(
+            &self,
+            tx: &mut Transaction,
+            snapshot: &mut Snapshot,
+            removals: &CatalogRemovals,
+            incoming: &[Reference],
+        ) -> Result<CatalogRemovals, InfrastructureError> {
+            let mut staged = CatalogRemovals::default();
+
+            for removal in removals.actors.iter() {
+                match removal.merged_into {
+                    Some(target) => {
+                        if removal.id == target
+                            || self.actors.get_one(removal.id).await?.is_none()
+                            || self.actors.get_one(target).await?.is_none()
+                        {
+                            continue;
+                        }
+
+                        self.actors.stage_merge(tx, removal.id, target);
+                        snapshot.replace_actor(removal.id, target);
+                    }
+                    None => {
+                        if snapshot.references_actor(removal.id)
+                            || incoming.contains(&Reference::Actor(removal.id))
+                            || self.actors.is_referenced(removal.id).await?
+                        {
+                            continue;
+                        }
+
+                        self.actors.stage_delete(tx, removal.id);
+                    }
+                };
+                staged.actors.push(removal.clone());
+            }
+
+            for id in removals.currencies.iter() {
+                if snapshot.references_currency(*id)
+                    || incoming.contains(&Reference::Currency(*id))
+                    || self.currencies.is_referenced(*id).await?
+                {
+                    continue;
+                }
+
+                self.currencies.stage_delete(tx, *id);
+                staged.currencies.push(*id);
+            }
+
+            for removal in removals.items.iter() {
+                match removal.merged_into {
+                    Some(target) => {
+                        if !self.can_merge_items(removal.id, target).await? {
+                            continue;
+                        }
+
+                        self.items.stage_merge(tx, removal.id, target);
+                    }
+                    None => {
+                        if incoming.contains(&Reference::Item(removal.id))
+                            || self.items.is_referenced(removal.id).await?
+                        {
+                            continue;
+                        }
+
+                        self.items.stage_delete(tx, removal.id);
+                    }
+                };
+                staged.items.push(removal.clone());
+            }
+
+            for id in removals.wallets.iter() {
+                // Without events pointing to it, the wallet balance can only be zero
+                if incoming.contains(&Reference::Wallet(*id))
+                    || self.wallets.is_referenced(*id).await?
+                {
+                    continue;
+                }
+
+                self.wallets.stage_delete(tx, *id);
+                let delete = WalletOperation::Delete { wallet_id: *id };
+                let _ = snapshot.apply(Operation::Wallet(delete));
+                staged.wallets.push(*id);
+            }
+
+            Ok(staged)
+        }
+
+        /// Tells about removals staged by [`ApplyRemovals::stage`], once they are committed
+        pub fn publish(&self, removals: CatalogRemovals) {
+            for removal in removals.actors {
+                match removal.merged_into {
+                    Some(target) => self.port.publish(ActorsMerged {
+                        source: removal.id,
+                        target,
+                    }),
+                    None => self.port.publish(ActorDeleted { id: removal.id }),
+                }
+            }
+
+            for id in removals.currencies {
+                self.port.publish(CurrencyDeleted { id });
+            }
+
+            for removal in removals.items {
+                match removal.merged_into {
+                    Some(target) => self.port.publish(ItemTagsMerged {
+                        source: removal.id,
+                        target,
+                    }),
+                    None => self.port.publish(ItemTagDeleted { id: removal.id }),
+                }
+            }
+
+            for id in removals.wallets {
+                self.port.publish(WalletDeleted { id });
+            }
+        }
+
+        /// Moving the relations of related tags could close a cycle
+        async fn can_merge_items(
+            &self,
+            source: ItemTagId,
+            target: ItemTagId,
+        ) -> Result<bool, InfrastructureError> {
+            if source == target {
+                return Ok(false);
+            }
+
+            for (tag, maybe_ancestor) in [(target, source), (source, target)] {
+                match self.items.check_relation(tag, maybe_ancestor).await? {
+                    TagsRelation::TargetNotFound | TagsRelation::Ancestor => return Ok(false),
+                    TagsRelation::NotRelated => {}
+                }
+            }
+
+            Ok(true)
+        }
+    }
 }
 
 pub mod get_host_state {
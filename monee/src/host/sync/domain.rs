@@ -5,7 +5,7 @@ pub mod repository {
 
     use crate::{
         prelude::{AppError, InfrastructureError},
-        shared::domain::errors::UniqueSaveError,
+        shared::domain::{errors::UniqueSaveError, unit_of_work::Transaction},
     };
 
     use super::{
@@ -34,6 +34,8 @@ pub mod repository {
             data: &Catalog,
         ) -> Result<(), AppError<UniqueSaveError>>;
 
+        fn stage_changes(&self, tx: &mut Transaction, data: Catalog);
+
         async fn get_context_data(&self) -> Result<Catalog, InfrastructureError>;
     }
 }
@@ -9,7 +9,6 @@ pub mod repository {
             wallets::{wallet::Wallet, wallet_name::WalletName},
         },
     };
-    use surrealdb::sql::statements::{BeginStatement, CommitStatement};
 
     use crate::{
         host::sync::domain::{
@@ -20,7 +19,7 @@ pub mod repository {
         prelude::*,
         shared::{
             domain::{context::DbContext, date::Datetime, errors::UniqueSaveError},
-            infrastructure::database::{Connection, Entity, EntityKey},
+            infrastructure::database::{transaction::Transaction, Connection, Entity, EntityKey},
         },
     };
 
@@ -77,6 +76,10 @@ pub mod repository {
             save_changes(&self.0, data).await
         }
 
+        fn stage_changes(&self, tx: &mut Transaction, data: Catalog) {
+            stage_changes(tx, data)
+        }
+
         async fn get_context_data(&self) -> Result<Catalog, InfrastructureError> {
             let mut response = self
                 .0
@@ -92,6 +95,8 @@ pub mod repository {
                 pub currency_id: EntityKey<monee_core::CurrencyId>,
                 pub name: WalletName,
                 pub description: String,
+                #[serde(default)]
+                pub kind: monee_core::WalletKind,
             }
 
             impl From<SurrealWallet> for Wallet {
@@ -100,6 +105,7 @@ pub mod repository {
                         currency_id: value.currency_id.0,
                         name: value.name,
                         description: value.description,
+                        kind: value.kind,
                     }
                 }
             }
@@ -122,66 +128,208 @@ pub mod repository {
         con: &Connection,
         data: &Catalog,
     ) -> Result<(), AppError<UniqueSaveError>> {
-        let mut query = con.query(BeginStatement::default());
-
-        for (i, (id, currency)) in data.currencies.iter().enumerate() {
-            query = query
-                .query(format!(
-                    "UPDATE type::thing('currency', $currency_id{i}) CONTENT $currency{i}"
-                ))
-                .bind((format!("currency_id{i}"), id))
-                .bind((format!("currency{i}"), currency));
-        }
+        let mut tx = Transaction::new(con.clone());
+        stage_changes(&mut tx, data.clone());
+        tx.commit().await.catch_app()
+    }
+
+    /// Stages upserting every entry of `data`
+    pub fn stage_changes(tx: &mut Transaction, data: Catalog) {
+        tx.stage(move |mut query, t| {
+            for (i, (id, currency)) in data.currencies.iter().enumerate() {
+                query = query
+                    .query(format!(
+                        "UPDATE type::thing('currency', $currency_id{t}_{i}) CONTENT $currency{t}_{i}"
+                    ))
+                    .bind((format!("currency_id{t}_{i}"), id))
+                    .bind((format!("currency{t}_{i}"), currency));
+            }
+
+            for (i, (id, item)) in data.items.iter().enumerate() {
+                query = query
+                    .query(format!(
+                        "UPDATE type::thing('item_tag', $item_tag_id{t}_{i}) CONTENT $item_tag{t}_{i}"
+                    ))
+                    .bind((format!("item_tag_id{t}_{i}"), id))
+                    .bind((format!("item_tag{t}_{i}"), item));
+            }
+
+            for (i, (id, actor)) in data.actors.iter().enumerate() {
+                query = query
+                    .query(format!(
+                        "UPDATE type::thing('actor', $actor_id{t}_{i}) CONTENT $actor{t}_{i}"
+                    ))
+                    .bind((format!("actor_id{t}_{i}"), id))
+                    .bind((format!("actor{t}_{i}"), actor));
+            }
+
+            for (i, (id, wallet)) in data.wallets.iter().enumerate() {
+                #[derive(serde::Serialize)]
+                struct SurrealWallet<'w> {
+                    pub currency_id: EntityKey<monee_core::CurrencyId>,
+                    pub name: &'w WalletName,
+                    pub description: &'w String,
+                    pub kind: &'w monee_core::WalletKind,
+                }
+
+                query = query
+                    .query(format!(
+                        "UPDATE type::thing('wallet', $wallet_id{t}_{i}) CONTENT $wallet{t}_{i}"
+                    ))
+                    .bind((format!("wallet_id{t}_{i}"), id))
+                    .bind((
+                        format!("wallet{t}_{i}"),
+                        SurrealWallet {
+                            currency_id: EntityKey(wallet.currency_id),
+                            name: &wallet.name,
+                            description: &wallet.description,
+                            kind: &wallet.kind,
+                        },
+                    ));
+            }
+
+            query
+        });
+    }
 
-        for (i, (id, item)) in data.items.iter().enumerate() {
-            query = query
-                .query(format!(
-                    "UPDATE type::thing('item_tag', $item_tag_id{i}) CONTENT $item_tag{i}"
-                ))
-                .bind((format!("item_tag_id{i}"), id))
-                .bind((format!("item_tag{i}"), item));
+    #[cfg(feature = "sqlite")]
+    pub use sqlite_repository::{stage_changes as stage_sqlite_changes, SqliteRepository};
+
+    #[cfg(feature = "sqlite")]
+    mod sqlite_repository {
+        use monee_types::apps::app_id::AppId;
+
+        use crate::{
+            host::sync::domain::{
+                catalog::Catalog, node_changes::NodeChanges, repository::Repository,
+                sync_error::SyncError, sync_guide::SyncGuide,
+            },
+            iprelude::*,
+            prelude::*,
+            shared::{
+                domain::{context::SqliteContext, errors::UniqueSaveError},
+                infrastructure::{
+                    database::transaction::Transaction,
+                    sqlite::{self, Connection, Statement},
+                },
+            },
+        };
+
+        #[derive(FromContext)]
+        #[context(SqliteContext)]
+        pub struct SqliteRepository(Connection);
+
+        async fn load<K, T>(connection: &Connection, sql: &str) -> sqlite::Result<Vec<(K, T)>>
+        where
+            K: serde::de::DeserializeOwned,
+            T: serde::de::DeserializeOwned,
+        {
+            let rows: Vec<(String, String)> = sqlx::query_as(sql).fetch_all(connection).await?;
+            rows.into_iter().map(sqlite::decode_row).collect()
         }
 
-        for (i, (id, actor)) in data.actors.iter().enumerate() {
-            query = query
-                .query(format!(
-                    "UPDATE type::thing('actor', $actor_id{i}) CONTENT $actor{i}"
-                ))
-                .bind((format!("actor_id{i}"), id))
-                .bind((format!("actor{i}"), actor));
+        fn upsert<K: serde::Serialize, T: serde::Serialize>(
+            table: &str,
+            id: K,
+            data: &T,
+        ) -> Statement {
+            Statement::new(format!(
+                "INSERT INTO {table} (id, data) VALUES (?, ?)
+                ON CONFLICT (id) DO UPDATE SET data = excluded.data"
+            ))
+            .bind(sqlite::key(id))
+            .bind(sqlite::encode(data))
         }
 
-        for (i, (id, wallet)) in data.wallets.iter().enumerate() {
-            #[derive(serde::Serialize)]
-            struct SurrealWallet<'w> {
-                pub currency_id: EntityKey<monee_core::CurrencyId>,
-                pub name: &'w WalletName,
-                pub description: &'w String,
+        #[async_trait::async_trait]
+        impl Repository for SqliteRepository {
+            async fn get_sync_guide(&self) -> Result<SyncGuide, InfrastructureError> {
+                let last_event_date: Option<String> = sqlx::query_scalar(
+                    "SELECT created_at FROM event ORDER BY created_at DESC LIMIT 1",
+                )
+                .fetch_optional(&self.0)
+                .await?;
+
+                Ok(SyncGuide {
+                    last_event_date: last_event_date
+                        .as_deref()
+                        .map(sqlite::decode_datetime)
+                        .transpose()?,
+                })
             }
 
-            query = query
-                .query(format!(
-                    "UPDATE type::thing('wallet', $wallet_id{i}) CONTENT $wallet{i}"
-                ))
-                .bind((format!("wallet_id{i}"), id))
-                .bind((
-                    format!("wallet{i}"),
-                    SurrealWallet {
-                        currency_id: EntityKey(wallet.currency_id),
-                        name: &wallet.name,
-                        description: &wallet.description,
-                    },
-                ));
+            async fn save_sync(
+                &self,
+                client_id: AppId,
+                sync: &NodeChanges,
+            ) -> Result<(), InfrastructureError> {
+                sqlx::query(
+                    "INSERT INTO client_sync (id, data, error) VALUES (?, ?, NULL)
+                    ON CONFLICT (id) DO UPDATE SET data = excluded.data, error = NULL",
+                )
+                .bind(sqlite::key(client_id))
+                .bind(sqlite::encode(sync))
+                .execute(&self.0)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn save_sync_error(
+                &self,
+                client_id: AppId,
+                error: &SyncError,
+            ) -> Result<(), InfrastructureError> {
+                sqlx::query(
+                    "INSERT INTO client_sync (id, error) VALUES (?, ?)
+                    ON CONFLICT (id) DO UPDATE SET error = excluded.error",
+                )
+                .bind(sqlite::key(client_id))
+                .bind(sqlite::encode(error))
+                .execute(&self.0)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn save_changes(&self, data: &Catalog) -> Result<(), AppError<UniqueSaveError>> {
+                let mut tx = Transaction::new_sqlite(self.0.clone());
+                stage_changes(&mut tx, data.clone());
+                tx.commit().await.catch_app()
+            }
+
+            fn stage_changes(&self, tx: &mut Transaction, data: Catalog) {
+                stage_changes(tx, data)
+            }
+
+            async fn get_context_data(&self) -> Result<Catalog, InfrastructureError> {
+                Ok(Catalog {
+                    currencies: load(&self.0, "SELECT id, data FROM currency").await?,
+                    items: load(&self.0, "SELECT id, data FROM item_tag").await?,
+                    actors: load(&self.0, "SELECT id, data FROM actor").await?,
+                    wallets: load(&self.0, "SELECT id, data FROM wallet").await?,
+                })
+            }
         }
 
-        query
-            .query(CommitStatement::default())
-            .await
-            .catch_infra()?
-            .check()
-            .catch_app()?;
+        /// Stages upserting every entry of `data`
+        pub fn stage_changes(tx: &mut Transaction, data: Catalog) {
+            for (id, currency) in &data.currencies {
+                tx.stage_sqlite(upsert("currency", id, currency));
+            }
 
-        Ok(())
+            for (id, item) in &data.items {
+                tx.stage_sqlite(upsert("item_tag", id, item));
+            }
+
+            for (id, actor) in &data.actors {
+                tx.stage_sqlite(upsert("actor", id, actor));
+            }
+
+            for (id, wallet) in &data.wallets {
+                tx.stage_sqlite(upsert("wallet", id, wallet));
+            }
+        }
     }
 
     #[cfg(test)]
@@ -194,7 +342,7 @@ pub mod repository {
             use monee_core::ItemTagId;
             use monee_types::backoffice::item_tags::item_tag::ItemTag;
 
-            let con = crate::shared::infrastructure::database::connect()
+            let con = crate::shared::infrastructure::database::connect("mem://")
                 .await
                 .unwrap();
             let ctx = DbContext::new(con);
@@ -227,7 +375,7 @@ pub mod repository {
                 currencies::currency::Currency, wallets::wallet::Wallet,
             };
 
-            let con = crate::shared::infrastructure::database::connect()
+            let con = crate::shared::infrastructure::database::connect("mem://")
                 .await
                 .unwrap();
             let ctx = DbContext::new(con);
@@ -242,6 +390,7 @@ pub mod repository {
                         name: "sol".to_owned().into(),
                         symbol: "S/".parse().unwrap(),
                         code: "PEN".parse().unwrap(),
+                        minor_units: Default::default(),
                     },
                 )],
                 items: vec![],
@@ -252,6 +401,7 @@ pub mod repository {
                         currency_id,
                         name: "mine".parse().unwrap(),
                         description: "".to_owned(),
+                        kind: Default::default(),
                     },
                 )],
             })
@@ -272,7 +422,7 @@ pub mod repository {
                 currencies::currency::Currency, wallets::wallet::Wallet,
             };
 
-            let con = crate::shared::infrastructure::database::connect()
+            let con = crate::shared::infrastructure::database::connect("mem://")
                 .await
                 .unwrap();
             let ctx = DbContext::new(con);
@@ -288,6 +438,7 @@ pub mod repository {
                             name: "sol".to_owned().into(),
                             symbol: "S/".parse().unwrap(),
                             code: "PEN".parse().unwrap(),
+                            minor_units: Default::default(),
                         },
                     )],
                     items: vec![],
@@ -298,6 +449,7 @@ pub mod repository {
                             currency_id,
                             name: "mine".parse().unwrap(),
                             description: "".to_owned(),
+                            kind: Default::default(),
                         },
                     )],
                 })
@@ -319,7 +471,7 @@ pub mod repository {
                 currencies::currency::Currency, wallets::wallet::Wallet,
             };
 
-            let con = crate::shared::infrastructure::database::connect()
+            let con = crate::shared::infrastructure::database::connect("mem://")
                 .await
                 .unwrap();
             let ctx = DbContext::new(con);
@@ -336,6 +488,7 @@ pub mod repository {
                             name: "sol".to_owned().into(),
                             symbol: "S/".parse().unwrap(),
                             code: "PEN".parse().unwrap(),
+                            minor_units: Default::default(),
                         },
                     ),
                     (
@@ -344,6 +497,7 @@ pub mod repository {
                             name: "dollar".to_owned().into(),
                             symbol: "$".parse().unwrap(),
                             code: "USD".parse().unwrap(),
+                            minor_units: Default::default(),
                         },
                     ),
                 ],
@@ -356,6 +510,7 @@ pub mod repository {
                             currency_id: currency_id1,
                             name: "mine".parse().unwrap(),
                             description: "".to_owned(),
+                            kind: Default::default(),
                         },
                     ),
                     (
@@ -364,6 +519,7 @@ pub mod repository {
                             currency_id: currency_id2,
                             name: "othermine".parse().unwrap(),
                             description: "".to_owned(),
+                            kind: Default::default(),
                         },
                     ),
                 ],
@@ -385,7 +541,7 @@ pub mod repository {
             use monee_types::backoffice::{
                 actors::actor::Actor, currencies::currency::Currency, item_tags::item_tag::ItemTag,
             };
-            let con = crate::shared::infrastructure::database::connect()
+            let con = crate::shared::infrastructure::database::connect("mem://")
                 .await
                 .unwrap();
             let ctx = DbContext::new(con);
@@ -399,6 +555,7 @@ pub mod repository {
                         name: "sol".to_owned().into(),
                         symbol: "S/".parse().unwrap(),
                         code: "PEN".parse().unwrap(),
+                        minor_units: Default::default(),
                     },
                 )],
                 items: vec![
@@ -0,0 +1,226 @@
+pub mod domain {
+    pub use monee_types::ledgers::*;
+}
+
+pub mod application {
+    pub mod ledgers {
+        use std::path::PathBuf;
+
+        use crate::{
+            ledgers::{
+                domain::{ledger_list::LedgerList, ledger_name::LedgerName},
+                infrastructure::registry::Registry,
+            },
+            prelude::{AppError, InfrastructureError},
+            shared::infrastructure::database::{self, config::DatabaseConfig, Connection},
+        };
+
+        pub use crate::ledgers::domain::errors::LedgerError as Error;
+
+        /// Ledgers are picked before there is a context to build repositories from, so they
+        /// are kept in a registry file next to the database config
+        pub struct Ledgers {
+            registry: Registry,
+            database: DatabaseConfig,
+        }
+
+        impl Ledgers {
+            pub fn new(registry_path: PathBuf, database: DatabaseConfig) -> Self {
+                Self {
+                    registry: Registry::new(registry_path),
+                    database,
+                }
+            }
+
+            pub fn list(&self) -> Result<LedgerList, InfrastructureError> {
+                self.registry.load()
+            }
+
+            /// Where `ledger` is kept, the current one when not given
+            pub fn database(
+                &self,
+                ledger: Option<&LedgerName>,
+            ) -> Result<DatabaseConfig, AppError<Error>> {
+                let list = self.registry.load()?;
+                let ledger = ledger.unwrap_or(&list.current);
+                if !list.ledgers.contains(ledger) {
+                    return Err(AppError::App(Error::NotFound(ledger.clone())));
+                }
+
+                Ok(self.database.for_ledger(ledger))
+            }
+
+            /// Its database is created and migrated the first time it is used
+            pub fn create(&self, name: LedgerName) -> Result<(), AppError<Error>> {
+                let mut list = self.registry.load()?;
+                if list.ledgers.contains(&name) {
+                    return Err(AppError::App(Error::AlreadyExists(name)));
+                }
+
+                list.ledgers.push(name);
+                self.registry.save(&list)?;
+
+                Ok(())
+            }
+
+            pub fn switch(&self, name: LedgerName) -> Result<(), AppError<Error>> {
+                let mut list = self.registry.load()?;
+                if !list.ledgers.contains(&name) {
+                    return Err(AppError::App(Error::NotFound(name)));
+                }
+
+                list.current = name;
+                self.registry.save(&list)?;
+
+                Ok(())
+            }
+
+            /// Drops the ledger database, the main and the current ledgers can not be deleted
+            pub async fn delete(&self, name: LedgerName) -> Result<(), AppError<Error>> {
+                let (mut list, position) = self.deletable(&name)?;
+
+                let database = self.database.for_ledger(&name);
+                match database.url.strip_prefix("sqlite://") {
+                    Some(path) => match std::fs::remove_file(path) {
+                        Ok(()) => {}
+                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(e) => return Err(InfrastructureError::from(e).into()),
+                    },
+                    None => database::remove(&database).await?,
+                }
+
+                list.ledgers.remove(position);
+                self.registry.save(&list)?;
+
+                Ok(())
+            }
+
+            /// Like [`Ledgers::delete`], for a store this process already has open through
+            /// `connection`, which can not be opened a second time
+            pub async fn delete_open(
+                &self,
+                name: LedgerName,
+                connection: &Connection,
+            ) -> Result<(), AppError<Error>> {
+                let (mut list, position) = self.deletable(&name)?;
+
+                let database = self.database.for_ledger(&name);
+                database::remove_from(connection, &database.database).await?;
+
+                list.ledgers.remove(position);
+                self.registry.save(&list)?;
+
+                Ok(())
+            }
+
+            /// The ledger list and where `name` is in it, when it can be deleted
+            fn deletable(&self, name: &LedgerName) -> Result<(LedgerList, usize), AppError<Error>> {
+                let list = self.registry.load()?;
+                if name.is_main() {
+                    return Err(AppError::App(Error::IsMain));
+                }
+                if list.current == *name {
+                    return Err(AppError::App(Error::IsCurrent));
+                }
+
+                let Some(position) = list.ledgers.iter().position(|ledger| ledger == name) else {
+                    return Err(AppError::App(Error::NotFound(name.clone())));
+                };
+
+                Ok((list, position))
+            }
+        }
+    }
+}
+
+pub mod infrastructure {
+    pub mod registry {
+        use std::path::PathBuf;
+
+        use crate::{
+            ledgers::domain::{ledger_list::LedgerList, ledger_name::LedgerName},
+            prelude::InfrastructureError,
+        };
+
+        /// The main ledger is always there, so only the others are written down
+        #[derive(serde::Serialize, serde::Deserialize, Default)]
+        struct RegistryFile {
+            current: Option<LedgerName>,
+            #[serde(default)]
+            ledgers: Vec<LedgerName>,
+        }
+
+        pub struct Registry(PathBuf);
+
+        impl Registry {
+            pub fn new(path: PathBuf) -> Self {
+                Self(path)
+            }
+
+            pub fn load(&self) -> Result<LedgerList, InfrastructureError> {
+                let file: RegistryFile = match std::fs::read_to_string(&self.0) {
+                    Ok(content) => toml::from_str(&content)?,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Default::default(),
+                    Err(e) => return Err(e.into()),
+                };
+
+                let mut ledgers = vec![LedgerName::main()];
+                ledgers.extend(file.ledgers.into_iter().filter(|ledger| !ledger.is_main()));
+
+                Ok(LedgerList {
+                    current: file.current.unwrap_or_else(LedgerName::main),
+                    ledgers,
+                })
+            }
+
+            pub fn save(&self, list: &LedgerList) -> Result<(), InfrastructureError> {
+                let file = RegistryFile {
+                    current: Some(list.current.clone()),
+                    ledgers: list
+                        .ledgers
+                        .iter()
+                        .filter(|ledger| !ledger.is_main())
+                        .cloned()
+                        .collect(),
+                };
+
+                if let Some(dir) = self.0.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(&self.0, toml::to_string(&file)?)?;
+
+                Ok(())
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn main_ledger_is_always_listed() {
+                let path =
+                    std::env::temp_dir().join(format!("monee-ledgers-{}.toml", std::process::id()));
+                let registry = Registry::new(path.clone());
+
+                let list = registry.load().unwrap();
+                assert_eq!(list.current, LedgerName::main());
+                assert_eq!(list.ledgers, vec![LedgerName::main()]);
+
+                let household: LedgerName = "household".parse().unwrap();
+                registry
+                    .save(&LedgerList {
+                        current: household.clone(),
+                        ledgers: vec![LedgerName::main(), household.clone()],
+                    })
+                    .unwrap();
+
+                let list = registry.load().unwrap();
+                std::fs::remove_file(path).unwrap();
+
+                assert_eq!(list.current, household);
+                assert_eq!(list.ledgers, vec![LedgerName::main(), household]);
+            }
+        }
+    }
+}
@@ -1,9 +1,13 @@
 pub mod backoffice;
+pub mod doctor;
 pub mod host;
+pub mod ledgers;
+pub mod migrations;
 pub mod reports;
 pub mod shared;
 pub mod self_config;
 pub mod nodes;
+pub mod vault;
 
 pub mod prelude {
     pub use crate::shared::domain::context::AppContext;
@@ -19,7 +23,9 @@ pub mod prelude {
 pub(crate) mod iprelude {
     use crate::{
         prelude::{AppError, InfrastructureError},
-        shared::domain::errors::UniqueSaveError,
+        shared::{
+            domain::errors::UniqueSaveError, infrastructure::database::transaction::CommitError,
+        },
     };
 
     pub use cream::context::FromContext;
@@ -36,6 +42,21 @@ pub(crate) mod iprelude {
         }
     }
 
+    #[cfg(feature = "sqlite")]
+    impl<T> CatchInfra for Result<T, sqlx::Error> {
+        type Output = T;
+        fn catch_infra(self) -> Result<Self::Output, InfrastructureError> {
+            self.map_err(Into::into)
+        }
+    }
+
+    impl<T> CatchInfra for Result<T, CommitError> {
+        type Output = T;
+        fn catch_infra(self) -> Result<Self::Output, InfrastructureError> {
+            self.map_err(Into::into)
+        }
+    }
+
     impl<T, E> CatchInfra for Result<T, AppError<E>> {
         type Output = Result<T, E>;
         fn catch_infra(self) -> Result<Self::Output, InfrastructureError> {
@@ -59,6 +80,21 @@ pub(crate) mod iprelude {
         }
     }
 
+    #[cfg(feature = "sqlite")]
+    impl<T> CatchApp<UniqueSaveError> for Result<T, sqlx::Error> {
+        type Output = T;
+        fn catch_app(self) -> Result<Self::Output, AppError<UniqueSaveError>> {
+            self.map_err(Into::into)
+        }
+    }
+
+    impl<T> CatchApp<UniqueSaveError> for Result<T, CommitError> {
+        type Output = T;
+        fn catch_app(self) -> Result<Self::Output, AppError<UniqueSaveError>> {
+            self.map_err(Into::into)
+        }
+    }
+
     pub trait MapResponse<O, E> {
         fn map_response(self) -> Result<O, E>;
     }
@@ -0,0 +1,338 @@
+pub mod application {
+    pub mod status {
+        use crate::{
+            iprelude::*, migrations::domain::migration_status::MigrationStatus,
+            migrations::domain::repository::Repository, prelude::*,
+        };
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct Status {
+            repository: Box<dyn Repository>,
+        }
+
+        impl Status {
+            /// Every known migration, applied or pending, in the order they apply
+            pub async fn run(&self) -> Result<Vec<MigrationStatus>, InfrastructureError> {
+                self.repository.status().await
+            }
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    pub mod export_sqlite {
+        use std::path::{Path, PathBuf};
+
+        use cream::context::{Context, FromContext};
+
+        use crate::{
+            backoffice::{
+                events::domain::repository::Repository as EventsRepository,
+                exchange_rates::domain::repository::Repository as ExchangeRatesRepository,
+                goals::domain::repository::Repository as GoalsRepository,
+                item_tags::domain::repository::Repository as ItemTagsRepository,
+                snapshot::domain::repository::SnapshotRepository,
+            },
+            host::sync::domain::repository::Repository as SyncRepository,
+            nodes::{
+                changes::domain::repository::Repository as ChangesRepository,
+                hosts::domain::repository::Repository as HostsRepository,
+            },
+            prelude::{AppContext, AppError},
+            self_config::domain::repository::Repository as SelfConfigRepository,
+            shared::{
+                domain::{context::SqliteContext, date::Timezone, errors::UniqueSaveError},
+                infrastructure::{errors::InfrastructureError, sqlite},
+            },
+        };
+
+        use crate::{
+            backoffice::{
+                events::infrastructure::repository::SqliteRepository as SqliteEvents,
+                exchange_rates::infrastructure::repository::SqliteRepository as SqliteExchangeRates,
+                goals::infrastructure::repository::SqliteRepository as SqliteGoals,
+                item_tags::infrastructure::repository::SqliteRepository as SqliteItemTags,
+                snapshot::infrastructure::snapshot_repository::SnapshotSqliteRepository as SqliteSnapshot,
+            },
+            host::sync::infrastructure::repository::SqliteRepository as SqliteCatalog,
+            nodes::{
+                changes::infrastructure::repository::SqliteRepository as SqliteChanges,
+                hosts::infrastructure::repository::SqliteRepository as SqliteHosts,
+            },
+            self_config::infrastructure::repository::SqliteRepository as SqliteSelfConfig,
+        };
+
+        /// Copies the current database into a new SQLite file. Snapshot checkpoints are not
+        /// copied, past balances replay from the first event until new ones are taken. Nodes
+        /// registered with this host are not copied either
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct ExportSqlite {
+            catalog: Box<dyn SyncRepository>,
+            item_tags: Box<dyn ItemTagsRepository>,
+            exchange_rates: Box<dyn ExchangeRatesRepository>,
+            goals: Box<dyn GoalsRepository>,
+            events: Box<dyn EventsRepository>,
+            snapshot: Box<dyn SnapshotRepository>,
+            self_config: Box<dyn SelfConfigRepository>,
+            hosts: Box<dyn HostsRepository>,
+            changes: Box<dyn ChangesRepository>,
+        }
+
+        impl ExportSqlite {
+            pub async fn run(&self, path: &Path) -> Result<(), AppError<Error>> {
+                if path.exists() {
+                    return Err(AppError::App(Error::TargetExists));
+                }
+
+                // Written aside and moved in place once complete, so a failed copy leaves
+                // nothing at `path`
+                let partial = partial_path(path);
+                let _ = std::fs::remove_file(&partial);
+
+                let result = match sqlite::connect(&partial).await {
+                    Ok(db) => {
+                        let result = self.copy(&SqliteContext::new(db.clone())).await;
+                        db.close().await;
+                        result
+                    }
+                    Err(e) => Err(InfrastructureError::from(e).into()),
+                };
+
+                match result {
+                    Ok(()) => std::fs::rename(&partial, path).map_err(InfrastructureError::from)?,
+                    Err(e) => {
+                        let _ = std::fs::remove_file(&partial);
+                        return Err(e);
+                    }
+                }
+
+                Ok(())
+            }
+
+            async fn copy(&self, target: &SqliteContext) -> Result<(), AppError<Error>> {
+                let catalog = self.catalog.get_context_data().await?;
+                let sqlite_catalog: SqliteCatalog = target.provide();
+                sqlite_catalog
+                    .save_changes(&catalog)
+                    .await
+                    .map_err(already_exists)?;
+
+                let sqlite_item_tags: SqliteItemTags = target.provide();
+                let nodes = self.item_tags.get_all().await?;
+                for node in nodes.iter() {
+                    for parent_name in node.parents_name.iter() {
+                        let Some(parent) =
+                            nodes.iter().find(|other| other.tag.name == *parent_name)
+                        else {
+                            continue;
+                        };
+
+                        sqlite_item_tags
+                            .link(parent.id, node.id)
+                            .await
+                            .map_err(already_exists)?;
+                    }
+                }
+
+                let sqlite_rates: SqliteExchangeRates = target.provide();
+                for rate in self.exchange_rates.get_all().await? {
+                    sqlite_rates.save(rate).await?;
+                }
+
+                let sqlite_goals: SqliteGoals = target.provide();
+                for (id, goal) in self.goals.get_all().await? {
+                    sqlite_goals.save(id, goal).await.map_err(already_exists)?;
+                }
+
+                let sqlite_events: SqliteEvents = target.provide();
+                let events = self.events.get_until(Timezone::now()).await?;
+                sqlite_events.save_many(events).await?;
+
+                let sqlite_snapshot: SqliteSnapshot = target.provide();
+                if let Some(snapshot) = self.snapshot.read_last().await? {
+                    sqlite_snapshot.save(snapshot).await?;
+                }
+
+                let sqlite_self: SqliteSelfConfig = target.provide();
+                if let Some(app_id) = self.self_config.get_self().await? {
+                    sqlite_self.save_app_id(app_id).await?;
+                }
+
+                let sqlite_hosts: SqliteHosts = target.provide();
+                if let Some(binding) = self.hosts.get_host_binding().await? {
+                    sqlite_hosts.save_host_binding(&binding).await?;
+                }
+
+                let sqlite_changes: SqliteChanges = target.provide();
+                if let Some(changes) = self.changes.load().await? {
+                    sqlite_changes.save(&changes).await?;
+                }
+
+                Ok(())
+            }
+        }
+
+        fn partial_path(path: &Path) -> PathBuf {
+            let mut name = path.file_name().unwrap_or_default().to_owned();
+            name.push(".partial");
+            path.with_file_name(name)
+        }
+
+        fn already_exists(error: AppError<UniqueSaveError>) -> AppError<Error> {
+            match error {
+                AppError::App(UniqueSaveError::AlreadyExists(table)) => {
+                    AppError::App(Error::AlreadyExists(table))
+                }
+                AppError::Infrastructure(e) => AppError::Infrastructure(e),
+            }
+        }
+
+        #[derive(thiserror::Error, Debug)]
+        pub enum Error {
+            #[error("Target file already exists")]
+            TargetExists,
+            #[error("Duplicated {0} while copying")]
+            AlreadyExists(String),
+        }
+    }
+}
+
+pub mod domain {
+    pub mod migration_status {
+        use crate::shared::domain::date::Datetime;
+
+        #[derive(Debug)]
+        pub struct MigrationStatus {
+            pub version: u32,
+            pub name: String,
+            /// `None` while pending
+            pub applied_at: Option<Datetime>,
+        }
+
+        /// Every `known` migration as `(version, name)` along with when it was `applied`, by
+        /// version. Migrations applied but not known are listed too
+        pub fn merge(
+            known: impl IntoIterator<Item = (u32, &'static str)>,
+            applied: impl IntoIterator<Item = (u32, String, Datetime)>,
+        ) -> Vec<MigrationStatus> {
+            let mut applied: Vec<_> = applied.into_iter().collect();
+
+            let mut status: Vec<_> = known
+                .into_iter()
+                .map(|(version, name)| {
+                    let position = applied
+                        .iter()
+                        .position(|(applied, _, _)| *applied == version);
+
+                    MigrationStatus {
+                        version,
+                        name: name.to_owned(),
+                        applied_at: position.map(|i| applied.swap_remove(i).2),
+                    }
+                })
+                .collect();
+
+            // Written by a newer version of monee
+            status.extend(
+                applied
+                    .into_iter()
+                    .map(|(version, name, applied_at)| MigrationStatus {
+                        version,
+                        name,
+                        applied_at: Some(applied_at),
+                    }),
+            );
+            status.sort_by_key(|migration| migration.version);
+
+            status
+        }
+    }
+
+    pub mod repository {
+        use crate::prelude::InfrastructureError;
+
+        use super::migration_status::MigrationStatus;
+
+        #[async_trait::async_trait]
+        pub trait Repository: Send + Sync + 'static {
+            async fn status(&self) -> Result<Vec<MigrationStatus>, InfrastructureError>;
+        }
+    }
+}
+
+pub mod infrastructure {
+    pub mod repository {
+        use cream::context::FromContext;
+
+        use crate::{
+            migrations::domain::{
+                migration_status::{self, MigrationStatus},
+                repository::Repository,
+            },
+            prelude::InfrastructureError,
+            shared::{
+                domain::context::DbContext,
+                infrastructure::database::{migrations, Connection},
+            },
+        };
+
+        #[derive(FromContext)]
+        #[context(DbContext)]
+        pub struct SurrealRepository(Connection);
+
+        #[async_trait::async_trait]
+        impl Repository for SurrealRepository {
+            async fn status(&self) -> Result<Vec<MigrationStatus>, InfrastructureError> {
+                let applied = migrations::applied(&self.0).await?;
+                Ok(migration_status::merge(
+                    migrations::MIGRATIONS
+                        .iter()
+                        .map(|migration| (migration.version, migration.name)),
+                    applied
+                        .into_iter()
+                        .map(|applied| (applied.version, applied.name, applied.applied_at)),
+                ))
+            }
+        }
+
+        #[cfg(feature = "sqlite")]
+        pub use sqlite_repository::SqliteRepository;
+
+        #[cfg(feature = "sqlite")]
+        mod sqlite_repository {
+            use cream::context::FromContext;
+
+            use crate::{
+                migrations::domain::{
+                    migration_status::{self, MigrationStatus},
+                    repository::Repository,
+                },
+                prelude::InfrastructureError,
+                shared::{
+                    domain::context::SqliteContext,
+                    infrastructure::sqlite::{migrations, Connection},
+                },
+            };
+
+            #[derive(FromContext)]
+            #[context(SqliteContext)]
+            pub struct SqliteRepository(Connection);
+
+            #[async_trait::async_trait]
+            impl Repository for SqliteRepository {
+                async fn status(&self) -> Result<Vec<MigrationStatus>, InfrastructureError> {
+                    let applied = migrations::applied(&self.0).await?;
+                    Ok(migration_status::merge(
+                        migrations::MIGRATIONS
+                            .iter()
+                            .map(|migration| (migration.version, migration.name)),
+                        applied
+                            .into_iter()
+                            .map(|applied| (applied.version, applied.name, applied.applied_at)),
+                    ))
+                }
+            }
+        }
+    }
+}
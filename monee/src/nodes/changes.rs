@@ -93,6 +93,51 @@ pub mod infrastructure {
                 Ok(changes)
             }
         }
+
+        #[cfg(feature = "sqlite")]
+        pub use sqlite_repository::SqliteRepository;
+
+        #[cfg(feature = "sqlite")]
+        mod sqlite_repository {
+            use cream::context::FromContext;
+            use monee_types::nodes::sync::changes_record::ChangesRecord;
+
+            use crate::{
+                nodes::changes::domain::repository::Repository,
+                prelude::*,
+                shared::{
+                    domain::context::SqliteContext,
+                    infrastructure::sqlite::{self, Connection},
+                },
+            };
+
+            #[derive(FromContext)]
+            #[context(SqliteContext)]
+            pub struct SqliteRepository(Connection);
+
+            #[async_trait::async_trait]
+            impl Repository for SqliteRepository {
+                async fn save(&self, changes: &ChangesRecord) -> Result<(), InfrastructureError> {
+                    sqlx::query(
+                        "INSERT INTO changes_record (id, data) VALUES (1, ?)
+                        ON CONFLICT (id) DO UPDATE SET data = excluded.data",
+                    )
+                    .bind(sqlite::encode(changes))
+                    .execute(&self.0)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn load(&self) -> Result<Option<ChangesRecord>, InfrastructureError> {
+                    let data: Option<String> =
+                        sqlx::query_scalar("SELECT data FROM changes_record WHERE id = 1")
+                            .fetch_optional(&self.0)
+                            .await?;
+
+                    Ok(data.as_deref().map(sqlite::decode).transpose()?)
+                }
+            }
+        }
     }
 }
-
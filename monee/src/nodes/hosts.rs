@@ -53,6 +53,53 @@ pub mod infrastructure {
             }
         }
 
+        #[cfg(feature = "sqlite")]
+        pub use sqlite_repository::SqliteRepository;
+
+        #[cfg(feature = "sqlite")]
+        mod sqlite_repository {
+            use super::super::super::domain::repository::Repository;
+            use crate::shared::{
+                domain::context::SqliteContext,
+                infrastructure::sqlite::{self, Connection},
+            };
+            use crate::{iprelude::*, prelude::*};
+            use monee_types::nodes::host::host_binding::HostBinding;
+
+            #[derive(FromContext)]
+            #[context(SqliteContext)]
+            pub struct SqliteRepository(Connection);
+
+            #[async_trait::async_trait]
+            impl Repository for SqliteRepository {
+                async fn save_host_binding(
+                    &self,
+                    host_binding: &HostBinding,
+                ) -> Result<(), InfrastructureError> {
+                    sqlx::query(
+                        "INSERT INTO host_binding (id, data) VALUES (1, ?)
+                        ON CONFLICT (id) DO UPDATE SET data = excluded.data",
+                    )
+                    .bind(sqlite::encode(host_binding))
+                    .execute(&self.0)
+                    .await?;
+
+                    Ok(())
+                }
+
+                async fn get_host_binding(
+                    &self,
+                ) -> Result<Option<HostBinding>, InfrastructureError> {
+                    let data: Option<String> =
+                        sqlx::query_scalar("SELECT data FROM host_binding WHERE id = 1")
+                            .fetch_optional(&self.0)
+                            .await?;
+
+                    Ok(data.as_deref().map(sqlite::decode).transpose()?)
+                }
+            }
+        }
+
         #[cfg(test)]
         mod tests {
             #[cfg(feature = "db_test")]
@@ -62,7 +109,7 @@ pub mod infrastructure {
                 use cream::context::Context;
                 use monee_types::{apps::app_id::AppId, nodes::host::host_binding::HostBinding};
 
-                let con = crate::shared::infrastructure::database::connect()
+                let con = crate::shared::infrastructure::database::connect("mem://")
                     .await
                     .unwrap();
                 let ctx = DbContext::new(con);
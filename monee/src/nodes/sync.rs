@@ -6,12 +6,17 @@ pub mod domain {
             shared::errors::UniqueSaveError,
         };
 
-        use crate::prelude::{AppError, InfrastructureError};
+        use crate::{
+            prelude::{AppError, InfrastructureError},
+            shared::domain::unit_of_work::Transaction,
+        };
 
         #[async_trait::async_trait]
         pub trait Repository: Send + Sync + 'static {
             async fn truncate_events(&self) -> Result<(), InfrastructureError>;
+            fn stage_truncate_events(&self, tx: &mut Transaction);
             async fn save_catalog(&self, data: &Catalog) -> Result<(), AppError<UniqueSaveError>>;
+            fn stage_catalog(&self, tx: &mut Transaction, data: Catalog);
             async fn get_catalog(
                 &self,
                 changes: &ChangesRecord,
@@ -33,6 +38,7 @@ pub mod application {
         use crate::backoffice::snapshot::application::snapshot_io::SnapshotIO;
 
         use super::super::domain::repository::Repository;
+        use crate::shared::domain::unit_of_work::UnitOfWork;
         use crate::{iprelude::*, prelude::*};
 
         #[derive(FromContext)]
@@ -40,16 +46,19 @@ pub mod application {
         pub struct RewriteSystem {
             repo: Box<dyn Repository>,
             snapshot_io: SnapshotIO,
+            unit_of_work: Box<dyn UnitOfWork>,
         }
 
         impl RewriteSystem {
             pub async fn run(&self, data: HostState) -> Result<(), AppError<UniqueSaveError>> {
-                self.repo.save_catalog(&data.data).await?;
-                self.snapshot_io.save(data.snapshot).await?;
-
-                self.repo.truncate_events().await?;
+                // The events are gone, and with them what checkpoints were taken from
+                let mut tx = self.unit_of_work.begin();
+                self.repo.stage_catalog(&mut tx, data.data);
+                self.repo.stage_truncate_events(&mut tx);
+                self.snapshot_io.stage_delete_checkpoints(&mut tx);
+                self.snapshot_io.stage_save(&mut tx, data.snapshot);
 
-                Ok(())
+                tx.commit().await.catch_app()
             }
         }
     }
@@ -77,7 +86,11 @@ pub mod application {
             ) -> Result<NodeChanges, InfrastructureError> {
                 let events = self.repo.get_events(guide).await?;
                 let data = self.repo.get_catalog(changes).await?;
-                Ok(NodeChanges { events, data })
+                Ok(NodeChanges {
+                    events,
+                    data,
+                    removals: changes.removals.clone(),
+                })
             }
         }
     }
@@ -1,13 +1,13 @@
 pub mod repository {
     use crate::{
         backoffice::events::infrastructure::repository::SurrealMoneeEvent,
-        host::sync::infrastructure::repository::save_changes,
+        host::sync::infrastructure::repository::{save_changes, stage_changes},
         iprelude::*,
         nodes::sync::domain::repository::Repository,
         prelude::*,
         shared::{
             domain::context::DbContext,
-            infrastructure::database::{Connection, Entity, EntityKey},
+            infrastructure::database::{transaction::Transaction, Connection, Entity, EntityKey},
         },
     };
     use monee_core::{CurrencyId, ItemTagId};
@@ -33,6 +33,10 @@ pub mod repository {
             Ok(())
         }
 
+        fn stage_truncate_events(&self, tx: &mut Transaction) {
+            tx.stage(|query, _| query.query("DELETE event"));
+        }
+
         async fn save_catalog(
             &self,
             data: &monee_types::host::sync::catalog::Catalog,
@@ -40,6 +44,14 @@ pub mod repository {
             save_changes(&self.0, data).await
         }
 
+        fn stage_catalog(
+            &self,
+            tx: &mut Transaction,
+            data: monee_types::host::sync::catalog::Catalog,
+        ) {
+            stage_changes(tx, data)
+        }
+
         async fn get_catalog(
             &self,
             changes: &monee_types::nodes::sync::changes_record::ChangesRecord,
@@ -93,6 +105,8 @@ pub mod repository {
                 pub currency_id: EntityKey<monee_core::CurrencyId>,
                 pub name: WalletName,
                 pub description: String,
+                #[serde(default)]
+                pub kind: monee_core::WalletKind,
             }
 
             let currencies: Vec<Entity<CurrencyId, Currency>> = response.take(0)?;
@@ -113,6 +127,7 @@ pub mod repository {
                                 currency_id: wallet.currency_id.0,
                                 name: wallet.name,
                                 description: wallet.description,
+                                kind: wallet.kind,
                             },
                         )
                     })
@@ -151,6 +166,117 @@ pub mod repository {
         }
     }
 
+    #[cfg(feature = "sqlite")]
+    pub use sqlite_repository::SqliteRepository;
+
+    #[cfg(feature = "sqlite")]
+    mod sqlite_repository {
+        use monee_types::{
+            host::sync::{catalog::Catalog, node_changes::EventEntry, sync_guide::SyncGuide},
+            nodes::sync::changes_record::ChangesRecord,
+            shared::errors::UniqueSaveError,
+        };
+
+        use crate::{
+            host::sync::infrastructure::repository::stage_sqlite_changes,
+            iprelude::*,
+            nodes::sync::domain::repository::Repository,
+            prelude::*,
+            shared::{
+                domain::context::SqliteContext,
+                infrastructure::{
+                    database::transaction::Transaction,
+                    sqlite::{self, Connection, Statement},
+                },
+            },
+        };
+
+        #[derive(FromContext)]
+        #[context(SqliteContext)]
+        pub struct SqliteRepository(Connection);
+
+        /// Rows of `table` whose id is among `ids`
+        async fn load<K, T>(
+            connection: &Connection,
+            table: &str,
+            ids: &impl serde::Serialize,
+        ) -> sqlite::Result<Vec<(K, T)>>
+        where
+            K: serde::de::DeserializeOwned,
+            T: serde::de::DeserializeOwned,
+        {
+            let rows: Vec<(String, String)> = sqlx::query_as(&format!(
+                "SELECT id, data FROM {table} WHERE id IN (SELECT value FROM json_each(?))"
+            ))
+            .bind(sqlite::encode(ids))
+            .fetch_all(connection)
+            .await?;
+
+            rows.into_iter().map(sqlite::decode_row).collect()
+        }
+
+        #[async_trait::async_trait]
+        impl Repository for SqliteRepository {
+            async fn truncate_events(&self) -> Result<(), InfrastructureError> {
+                sqlx::query("DELETE FROM event").execute(&self.0).await?;
+                Ok(())
+            }
+
+            fn stage_truncate_events(&self, tx: &mut Transaction) {
+                tx.stage_sqlite(Statement::new("DELETE FROM event"));
+            }
+
+            async fn save_catalog(&self, data: &Catalog) -> Result<(), AppError<UniqueSaveError>> {
+                let mut tx = Transaction::new_sqlite(self.0.clone());
+                stage_sqlite_changes(&mut tx, data.clone());
+                tx.commit().await.catch_app()
+            }
+
+            fn stage_catalog(&self, tx: &mut Transaction, data: Catalog) {
+                stage_sqlite_changes(tx, data)
+            }
+
+            async fn get_catalog(
+                &self,
+                changes: &ChangesRecord,
+            ) -> Result<Catalog, InfrastructureError> {
+                Ok(Catalog {
+                    currencies: load(&self.0, "currency", &changes.currencies).await?,
+                    actors: load(&self.0, "actor", &changes.actors).await?,
+                    wallets: load(&self.0, "wallet", &changes.wallets).await?,
+                    items: load(&self.0, "item_tag", &changes.items).await?,
+                })
+            }
+
+            async fn get_events(
+                &self,
+                guide: SyncGuide,
+            ) -> Result<Vec<EventEntry>, InfrastructureError> {
+                let rows: Vec<(String, String, String)> = sqlx::query_as(
+                    "SELECT id, data, created_at FROM event
+                    WHERE ?1 IS NULL OR created_at > ?1
+                    ORDER BY created_at, rowid",
+                )
+                .bind(guide.last_event_date.map(sqlite::datetime))
+                .fetch_all(&self.0)
+                .await?;
+
+                let events = rows
+                    .into_iter()
+                    .map(|(id, data, created_at)| {
+                        Ok(EventEntry {
+                            id: sqlite::decode_key(id)?,
+                            event: sqlite::decode(&data)?,
+                            created_at: sqlite::decode_datetime(&created_at)?,
+                        })
+                    })
+                    .collect::<sqlite::Result<_>>()?;
+
+                Ok(events)
+            }
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         #![allow(unused)]
@@ -191,6 +317,7 @@ pub mod repository {
                         name: "test".to_owned().into(),
                         symbol: "S$".parse().unwrap(),
                         code: "DON".parse().unwrap(),
+                        minor_units: Default::default(),
                     },
                 )],
                 actors: vec![(
@@ -207,6 +334,7 @@ pub mod repository {
                         currency_id,
                         name: "test".parse().unwrap(),
                         description: "test".into(),
+                        kind: Default::default(),
                     },
                 )],
             };
@@ -220,7 +348,7 @@ pub mod repository {
         #[tokio::test]
         async fn gets_catalog() {
             use crate::shared::infrastructure::database::connect;
-            let db = connect().await.unwrap();
+            let db = connect("mem://").await.unwrap();
             let ctx = DbContext::new(db);
 
             let repo: SurrealRepository = ctx.provide();
@@ -235,6 +363,7 @@ pub mod repository {
                 currencies: org_catalog.currencies.iter().map(|(id, _)| *id).collect(),
                 actors: org_catalog.actors.iter().map(|(id, _)| *id).collect(),
                 wallets: org_catalog.wallets.iter().map(|(id, _)| *id).collect(),
+                removals: Default::default(),
             };
             let catalog = repo.get_catalog(&record).await.expect("should get catalog");
 
@@ -245,7 +374,7 @@ pub mod repository {
         #[tokio::test]
         async fn gets_events() {
             use crate::shared::infrastructure::database::connect;
-            let db = connect().await.unwrap();
+            let db = connect("mem://").await.unwrap();
             let ctx = DbContext::new(db);
 
             let back_repo: crate::backoffice::events::infrastructure::repository::SurrealRepository =
@@ -259,6 +388,7 @@ pub mod repository {
                         actors: vec![ActorId::default()].into(),
                         wallet_id: WalletId::default(),
                         amount: "1.00".parse().unwrap(),
+                        quantity: None,
                     }),
                 )
                 .await
@@ -281,7 +411,7 @@ pub mod repository {
         #[tokio::test]
         async fn gets_events_with_date() {
             use crate::shared::infrastructure::database::connect;
-            let db = connect().await.unwrap();
+            let db = connect("mem://").await.unwrap();
             let ctx = DbContext::new(db);
 
             let back_repo: crate::backoffice::events::infrastructure::repository::SurrealRepository =
@@ -295,6 +425,7 @@ pub mod repository {
                         actors: vec![ActorId::default()].into(),
                         wallet_id: WalletId::default(),
                         amount: "1.00".parse().unwrap(),
+                        quantity: None,
                     }),
                 )
                 .await
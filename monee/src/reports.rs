@@ -1,10 +1,15 @@
 pub mod events;
+pub mod goals;
+pub mod installments;
+pub mod net_worth;
+pub mod prices;
 pub mod snapshot;
+pub mod spending;
 pub mod wallets {
     pub mod application {
         pub mod get_all {
             use monee_core::WalletId;
-            pub use monee_types::reports::snapshot::snapshot::{Money, Wallet};
+            pub use monee_types::reports::snapshot::snapshot::{Wallet, WalletMoney};
 
             use crate::reports::snapshot::domain::repository::Repository;
             pub use crate::{iprelude::*, prelude::*};
@@ -18,7 +23,7 @@ pub mod wallets {
             impl GetAll {
                 pub async fn run(
                     &self,
-                ) -> Result<Vec<(WalletId, (Wallet, Money))>, InfrastructureError> {
+                ) -> Result<Vec<(WalletId, (Wallet, WalletMoney))>, InfrastructureError> {
                     self.repository.get_wallets().await
                 }
             }
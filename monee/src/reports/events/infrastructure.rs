@@ -19,9 +19,9 @@ pub mod repository {
             let mut response = self
                 .0
                 .query(
-                    "SELECT type, amount, wallet_id.name as wallet,
+                    "SELECT type, amount, quantity, observed, adjustment, until, wallet_id.name as wallet,
 item.name as item, actors, 
-from.name as from, to.name as to,
+from.name as from, to.name as to, fee.amount as fee,
 currency_id as currency, actor_id as actor, payment_promise FROM event FETCH actors, currency, actor",
                 )
                 .await?
@@ -31,6 +31,156 @@ currency_id as currency, actor_id as actor, payment_promise FROM event FETCH act
         }
     }
 
+    #[cfg(feature = "sqlite")]
+    pub use sqlite_repository::SqliteRepository;
+
+    #[cfg(feature = "sqlite")]
+    mod sqlite_repository {
+        use std::collections::HashMap;
+
+        use cream::context::FromContext;
+        use monee_core::{ActorId, CurrencyId, ItemTagId, WalletId};
+
+        use crate::{
+            backoffice::{
+                actors::domain::actor::Actor,
+                currencies::domain::currency::Currency,
+                events::domain::event::Event as StoredEvent,
+                item_tags::domain::item_tag::ItemTag,
+                wallets::domain::{wallet::Wallet, wallet_name::WalletName},
+            },
+            reports::events::domain::{
+                event::{DebtRegister, Event},
+                repository::Repository,
+            },
+            shared::{
+                domain::context::SqliteContext,
+                infrastructure::{
+                    errors::InfrastructureError,
+                    sqlite::{self, Connection},
+                },
+            },
+        };
+
+        #[derive(FromContext)]
+        #[context(SqliteContext)]
+        pub struct SqliteRepository(Connection);
+
+        async fn load<K, T>(connection: &Connection, sql: &str) -> sqlite::Result<HashMap<K, T>>
+        where
+            K: serde::de::DeserializeOwned + std::hash::Hash + Eq,
+            T: serde::de::DeserializeOwned,
+        {
+            let rows: Vec<(String, String)> = sqlx::query_as(sql).fetch_all(connection).await?;
+            rows.into_iter().map(sqlite::decode_row).collect()
+        }
+
+        struct Catalog {
+            actors: HashMap<ActorId, Actor>,
+            currencies: HashMap<CurrencyId, Currency>,
+            item_tags: HashMap<ItemTagId, ItemTag>,
+            wallets: HashMap<WalletId, Wallet>,
+        }
+
+        impl Catalog {
+            fn wallet(&self, id: &WalletId) -> Option<WalletName> {
+                self.wallets.get(id).map(|wallet| wallet.name.clone())
+            }
+
+            fn item(&self, id: &ItemTagId) -> Option<String> {
+                self.item_tags.get(id).map(|item| item.name.to_string())
+            }
+
+            fn actors(&self, ids: &[ActorId]) -> Option<Box<[Actor]>> {
+                ids.iter().map(|id| self.actors.get(id).cloned()).collect()
+            }
+
+            fn debt(
+                &self,
+                debt: crate::backoffice::events::domain::event::DebtRegister,
+            ) -> Option<DebtRegister> {
+                Some(DebtRegister {
+                    amount: debt.amount,
+                    currency: self.currencies.get(&debt.currency_id)?.clone(),
+                    actor: self.actors.get(&debt.actor_id)?.clone(),
+                    payment_promise: debt.payment_promise,
+                })
+            }
+
+            /// Events referring to something no longer there are left out, as are the ones
+            /// with no report counterpart
+            fn resolve(&self, event: StoredEvent) -> Option<Event> {
+                Some(match event {
+                    StoredEvent::Purchase(purchase) => Event::Purchase {
+                        item: self.item(&purchase.item)?,
+                        actors: self.actors(&purchase.actors)?,
+                        wallet: self.wallet(&purchase.wallet_id)?,
+                        amount: purchase.amount,
+                        quantity: purchase.quantity,
+                    },
+                    StoredEvent::Refund(refund) => Event::Refund {
+                        item: self.item(&refund.item)?,
+                        actors: self.actors(&refund.actors)?,
+                        wallet: self.wallet(&refund.wallet_id)?,
+                        amount: refund.amount,
+                    },
+                    StoredEvent::MoveValue(move_value) => Event::MoveValue {
+                        from: self.wallet(&move_value.from)?,
+                        to: self.wallet(&move_value.to)?,
+                        amount: move_value.amount,
+                        fee: move_value.fee.map(|fee| fee.amount),
+                    },
+                    StoredEvent::RegisterBalance(register) => Event::RegisterBalance {
+                        wallet: self.wallet(&register.wallet_id)?,
+                        amount: register.amount,
+                    },
+                    StoredEvent::ReconcileBalance(reconcile) => Event::ReconcileBalance {
+                        wallet: self.wallet(&reconcile.wallet_id)?,
+                        observed: reconcile.observed,
+                        adjustment: reconcile.adjustment,
+                    },
+                    StoredEvent::RegisterDebt(debt) => Event::RegisterDebt(self.debt(debt)?),
+                    StoredEvent::RegisterLoan(loan) => Event::RegisterLoan(self.debt(loan)?),
+                    StoredEvent::AccrueDebtInterest(accrual) => Event::AccrueDebtInterest {
+                        amount: accrual.amount,
+                        until: accrual.until,
+                    },
+                    StoredEvent::AccrueLoanInterest(accrual) => Event::AccrueLoanInterest {
+                        amount: accrual.amount,
+                        until: accrual.until,
+                    },
+                    StoredEvent::PaymentReceived(_) => return None,
+                })
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl Repository for SqliteRepository {
+            async fn get_all(&self) -> Result<Vec<Event>, InfrastructureError> {
+                let catalog = Catalog {
+                    actors: load(&self.0, "SELECT id, data FROM actor").await?,
+                    currencies: load(&self.0, "SELECT id, data FROM currency").await?,
+                    item_tags: load(&self.0, "SELECT id, data FROM item_tag").await?,
+                    wallets: load(&self.0, "SELECT id, data FROM wallet").await?,
+                };
+
+                let events: Vec<String> =
+                    sqlx::query_scalar("SELECT data FROM event ORDER BY created_at, rowid")
+                        .fetch_all(&self.0)
+                        .await?;
+
+                let mut resolved = Vec::with_capacity(events.len());
+                for data in events {
+                    if let Some(event) = catalog.resolve(sqlite::decode(&data)?) {
+                        resolved.push(event);
+                    }
+                }
+
+                Ok(resolved)
+            }
+        }
+    }
+
     #[cfg(all(test, feature = "db_test"))]
     mod tests {
         #![allow(unused_imports)]
@@ -55,7 +205,7 @@ currency_id as currency, actor_id as actor, payment_promise FROM event FETCH act
         fn can_get_purchase_events() {
             return;
             tokio::runtime::Runtime::new().unwrap().block_on(async {
-                let db = crate::shared::infrastructure::database::connect().await.unwrap();
+                let db = crate::shared::infrastructure::database::connect("mem://").await.unwrap();
                 let ctx = crate::shared::domain::context::DbContext::new(db);
 
                 let repo: super::SurrealRepository = ctx.provide();
@@ -81,6 +231,7 @@ currency_id as currency, actor_id as actor, payment_promise FROM event FETCH act
                     currency_id: CurrencyId::new(),
                     name: "wallet_1".parse().unwrap(),
                     description: "".to_owned().into(),
+                    kind: Default::default(),
                 };
                 wallet_repo.save(wallet_id, wallet).await.unwrap();
 
@@ -89,6 +240,7 @@ currency_id as currency, actor_id as actor, payment_promise FROM event FETCH act
                     actors: vec![actor_id].into(),
                     wallet_id: WalletId::new(),
                     amount: "1.00".parse().unwrap(),
+                    quantity: None,
                 })).await.unwrap();
 
                 let events = repo.get_all().await;
@@ -98,7 +250,7 @@ currency_id as currency, actor_id as actor, payment_promise FROM event FETCH act
 
         #[tokio::test]
         async fn can_get_debt_events() {
-            let db = crate::shared::infrastructure::database::connect()
+            let db = crate::shared::infrastructure::database::connect("mem://")
                 .await
                 .unwrap();
             let ctx = crate::shared::domain::context::DbContext::new(db);
@@ -125,6 +277,7 @@ currency_id as currency, actor_id as actor, payment_promise FROM event FETCH act
                         name: "currency_1".to_owned().into(),
                         symbol: "Sf".parse().unwrap(),
                         code: "DON".parse().unwrap(),
+                        minor_units: Default::default(),
                     },
                 )
                 .await
@@ -138,6 +291,9 @@ currency_id as currency, actor_id as actor, payment_promise FROM event FETCH act
                         currency_id,
                         actor_id,
                         payment_promise: None,
+                        wallet_id: None,
+                        debt_id: None,
+                        terms: None,
                     }),
                 )
                 .await
@@ -0,0 +1,99 @@
+pub mod domain {
+    pub use monee_types::reports::goals::*;
+}
+
+pub mod application {
+    pub mod goals_report {
+        use cream::context::FromContext;
+
+        use crate::{
+            backoffice::{
+                currencies::domain::repository::Repository as CurrencyRepository,
+                events::domain::{apply_event, repository::Repository as EventRepository},
+                goals::domain::{
+                    goal_progress::{self, DEPOSIT_RATE_MONTHS},
+                    repository::Repository as GoalRepository,
+                },
+                snapshot::application::snapshot_io::SnapshotIO,
+                wallets::domain::repository::Repository as WalletRepository,
+            },
+            reports::{goals::domain::goal_report::GoalReport, net_worth::domain::consolidate},
+            shared::{
+                domain::{context::AppContext, date::Timezone},
+                infrastructure::errors::AppError,
+            },
+        };
+
+        pub enum Error {
+            /// Past events could not be replayed to measure the recent deposits
+            Replay(apply_event::Error),
+            /// What the wallets of a goal hold does not fit a balance
+            Overflow,
+        }
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct GoalsReport {
+            repository: Box<dyn GoalRepository>,
+            currency_repository: Box<dyn CurrencyRepository>,
+            wallet_repository: Box<dyn WalletRepository>,
+            event_repository: Box<dyn EventRepository>,
+            snapshot_io: SnapshotIO,
+        }
+
+        impl GoalsReport {
+            /// Progress of every goal, deposits measured over the last `DEPOSIT_RATE_MONTHS`
+            /// months
+            pub async fn run(&self) -> Result<Vec<GoalReport>, AppError<Error>> {
+                let now = Timezone::now();
+                let today = now.date_naive();
+
+                let goals = self.repository.get_all().await?;
+                let currencies = self.currency_repository.get_all().await?;
+                let current = self.snapshot_io.read_last().await?;
+
+                let since = now
+                    .checked_sub_months(chrono::Months::new(DEPOSIT_RATE_MONTHS))
+                    .unwrap_or(now);
+                let wallets = self.wallet_repository.get_all().await?;
+                let events = self.event_repository.get_until(since).await?;
+                let previous = consolidate::replay(
+                    wallets
+                        .into_iter()
+                        .map(|(id, wallet)| (id, wallet.currency_id)),
+                    events.iter().map(|entry| &entry.event),
+                )
+                .map_err(|e| AppError::App(Error::Replay(e)))?;
+
+                let mut reports = Vec::with_capacity(goals.len());
+                for (id, goal) in goals {
+                    let Some((_, currency)) =
+                        currencies.iter().find(|(id, _)| *id == goal.currency_id)
+                    else {
+                        continue;
+                    };
+
+                    let held = |snapshot| {
+                        goal_progress::held(&goal, snapshot).ok_or(AppError::App(Error::Overflow))
+                    };
+                    let progress = goal_progress::progress(
+                        &goal,
+                        held(&current)?,
+                        held(&previous)?,
+                        today,
+                        currency.minor_units,
+                    );
+
+                    reports.push(GoalReport {
+                        id,
+                        goal,
+                        currency: currency.clone(),
+                        progress,
+                    });
+                }
+
+                Ok(reports)
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+pub mod domain {
+    pub use monee_types::reports::installments::*;
+}
+
+pub mod application {
+    pub mod installments_report {
+        use cream::context::FromContext;
+
+        use crate::{
+            backoffice::{
+                actors::domain::repository::Repository as ActorRepository,
+                currencies::domain::repository::Repository as CurrencyRepository,
+                debts::domain::registry, events::domain::repository::Repository as EventRepository,
+                snapshot::application::snapshot_io::SnapshotIO,
+            },
+            reports::installments::domain::debt_schedule::DebtSchedule,
+            shared::{
+                domain::{context::AppContext, date::Timezone},
+                infrastructure::errors::InfrastructureError,
+            },
+        };
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct InstallmentsReport {
+            event_repository: Box<dyn EventRepository>,
+            actor_repository: Box<dyn ActorRepository>,
+            currency_repository: Box<dyn CurrencyRepository>,
+            snapshot_io: SnapshotIO,
+        }
+
+        impl InstallmentsReport {
+            /// Amortization table of every debt and loan still owed with an installment plan,
+            /// in the order they were registered
+            pub async fn run(&self) -> Result<Vec<DebtSchedule>, InfrastructureError> {
+                let entries = self.event_repository.get_until(Timezone::now()).await?;
+                let snapshot = self.snapshot_io.read_last().await?;
+                let actors = self.actor_repository.get_all().await?;
+                let currencies = self.currency_repository.get_all().await?;
+
+                let schedules = registry::registered(&entries)
+                    .iter()
+                    .filter_map(|debt| {
+                        let debt_id = debt.register.debt_id?;
+                        let hosts = if debt.is_loan {
+                            &snapshot.loans
+                        } else {
+                            &snapshot.debts
+                        };
+                        hosts.get(&debt_id)?;
+
+                        let (_, actor) = actors
+                            .iter()
+                            .find(|(id, _)| *id == debt.register.actor_id)?;
+                        let (_, currency) = currencies
+                            .iter()
+                            .find(|(id, _)| *id == debt.register.currency_id)?;
+
+                        let installments = debt.amortization_table(currency.minor_units)?.ok()?;
+
+                        Some(DebtSchedule {
+                            debt_id,
+                            is_loan: debt.is_loan,
+                            actor: actor.clone(),
+                            currency: currency.clone(),
+                            installments,
+                        })
+                    })
+                    .collect();
+
+                Ok(schedules)
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+pub mod domain {
+    pub use monee_types::reports::net_worth::*;
+}
+
+pub mod application {
+    pub mod net_worth_report {
+        use cream::context::FromContext;
+        use monee_core::{CurrencyId, MinorUnits};
+
+        use crate::{
+            backoffice::{
+                currencies::domain::repository::Repository as CurrencyRepository,
+                events::domain::{apply_event, repository::Repository as EventRepository},
+                exchange_rates::domain::{
+                    rate_table::RateTable, repository::Repository as RateRepository,
+                },
+                snapshot::application::snapshot_io::SnapshotIO,
+                wallets::domain::repository::Repository as WalletRepository,
+            },
+            reports::net_worth::domain::consolidate::{self, NetWorth},
+            shared::{
+                domain::{
+                    context::AppContext,
+                    date::{Date, Timezone},
+                },
+                infrastructure::errors::AppError,
+            },
+        };
+
+        pub enum Error {
+            CurrencyNotFound,
+            /// Past events could not be replayed to rebuild an older snapshot
+            Replay(apply_event::Error),
+        }
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct NetWorthReport {
+            snapshot_io: SnapshotIO,
+            currency_repository: Box<dyn CurrencyRepository>,
+            rate_repository: Box<dyn RateRepository>,
+            wallet_repository: Box<dyn WalletRepository>,
+            event_repository: Box<dyn EventRepository>,
+        }
+
+        impl NetWorthReport {
+            /// Net worth in `base` at the end of `date`, today when not given
+            pub async fn run(
+                &self,
+                base: CurrencyId,
+                date: Option<Date>,
+            ) -> Result<NetWorth, AppError<Error>> {
+                let today = Timezone::now().date_naive();
+                let date = date.unwrap_or(today).min(today);
+
+                let units = self.base_units(base).await?;
+                let rates = RateTable::new(self.rate_repository.get_all().await?);
+
+                self.at(base, units, &rates, date, today).await
+            }
+
+            /// Net worth in `base` at the end of each of the last `months` months,
+            /// the current one included, oldest first
+            pub async fn history(
+                &self,
+                base: CurrencyId,
+                months: u32,
+            ) -> Result<Vec<NetWorth>, AppError<Error>> {
+                let today = Timezone::now().date_naive();
+
+                let units = self.base_units(base).await?;
+                let rates = RateTable::new(self.rate_repository.get_all().await?);
+
+                let mut history = Vec::new();
+                for date in consolidate::month_ends(today, months) {
+                    history.push(self.at(base, units, &rates, date, today).await?);
+                }
+
+                Ok(history)
+            }
+
+            async fn base_units(&self, base: CurrencyId) -> Result<MinorUnits, AppError<Error>> {
+                self.currency_repository
+                    .get_one(base)
+                    .await?
+                    .map(|currency| currency.minor_units)
+                    .ok_or(AppError::App(Error::CurrencyNotFound))
+            }
+
+            async fn at(
+                &self,
+                base: CurrencyId,
+                units: MinorUnits,
+                rates: &RateTable,
+                date: Date,
+                today: Date,
+            ) -> Result<NetWorth, AppError<Error>> {
+                let snapshot = if date >= today {
+                    self.snapshot_io.read_last().await?
+                } else {
+                    let until = date
+                        .and_hms_opt(23, 59, 59)
+                        .expect("to be a valid time")
+                        .and_utc();
+
+                    let wallets = self.wallet_repository.get_all().await?;
+                    let events = self.event_repository.get_until(until).await?;
+
+                    consolidate::replay(
+                        wallets
+                            .into_iter()
+                            .map(|(id, wallet)| (id, wallet.currency_id)),
+                        events.iter().map(|entry| &entry.event),
+                    )
+                    .map_err(|e| AppError::App(Error::Replay(e)))?
+                };
+
+                Ok(consolidate::net_worth(&snapshot, base, units, rates, date))
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+pub mod domain {
+    pub use monee_types::reports::prices::*;
+}
+
+pub mod application {
+    pub mod prices_report {
+        use cream::context::FromContext;
+        use monee_core::{ActorId, ItemTagId};
+
+        use crate::{
+            backoffice::{
+                actors::domain::repository::Repository as ActorRepository,
+                currencies::domain::repository::Repository as CurrencyRepository,
+                events::domain::repository::Repository as EventRepository,
+                item_tags::domain::repository::Repository as ItemTagRepository,
+                wallets::domain::repository::Repository as WalletRepository,
+            },
+            reports::prices::domain::unit_price::{self, PriceEntry},
+            shared::{
+                domain::{context::AppContext, date::Timezone},
+                infrastructure::errors::InfrastructureError,
+            },
+        };
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct PricesReport {
+            event_repository: Box<dyn EventRepository>,
+            wallet_repository: Box<dyn WalletRepository>,
+            actor_repository: Box<dyn ActorRepository>,
+            item_tag_repository: Box<dyn ItemTagRepository>,
+            currency_repository: Box<dyn CurrencyRepository>,
+        }
+
+        impl PricesReport {
+            /// Unit price history per item and seller, optionally of a single item or seller.
+            /// Sellers of the same item come cheapest first by their latest price
+            pub async fn run(
+                &self,
+                item: Option<ItemTagId>,
+                seller: Option<ActorId>,
+            ) -> Result<Vec<PriceEntry>, InfrastructureError> {
+                let events = self.event_repository.get_until(Timezone::now()).await?;
+                let wallets = self.wallet_repository.get_all().await?;
+                let actors = self.actor_repository.get_all().await?;
+                let tags = self.item_tag_repository.get_all().await?;
+                let currencies = self.currency_repository.get_all().await?;
+
+                let histories = unit_price::price_histories(&events, |wallet_id| {
+                    let (_, wallet) = wallets.iter().find(|(id, _)| *id == wallet_id)?;
+                    let (_, currency) = currencies
+                        .iter()
+                        .find(|(id, _)| *id == wallet.currency_id)?;
+
+                    Some((wallet.currency_id, currency.minor_units))
+                });
+
+                let mut entries: Vec<_> = histories
+                    .into_iter()
+                    .filter(|history| item.is_none_or(|item| history.item_id == item))
+                    .filter(|history| seller.is_none_or(|seller| history.seller == Some(seller)))
+                    .filter_map(|history| {
+                        let item = tags.iter().find(|node| node.id == history.item_id)?;
+                        let (_, currency) = currencies
+                            .iter()
+                            .find(|(id, _)| *id == history.currency_id)?;
+                        let seller = match history.seller {
+                            Some(seller) => {
+                                let (_, actor) = actors.iter().find(|(id, _)| *id == seller)?;
+                                Some(actor.clone())
+                            }
+                            None => None,
+                        };
+
+                        Some(PriceEntry {
+                            item: item.tag.name.clone(),
+                            seller,
+                            currency: currency.clone(),
+                            history,
+                        })
+                    })
+                    .collect();
+
+                entries.sort_by_cached_key(|entry| {
+                    (
+                        entry.item.to_string(),
+                        entry.history.unit.to_string(),
+                        entry.currency.code.to_string(),
+                        entry.history.latest().map(|point| point.price),
+                    )
+                });
+
+                Ok(entries)
+            }
+        }
+    }
+}
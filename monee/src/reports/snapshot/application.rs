@@ -20,3 +20,117 @@ pub mod snapshot_report {
     }
 }
 
+pub mod snapshot_at {
+    use cream::context::FromContext;
+    use monee_types::doctor::{audit::references, finding::Reference};
+
+    use crate::{
+        backoffice::{
+            events::domain::{apply_event, repository::Repository as EventRepository},
+            snapshot::application::snapshot_io::SnapshotIO,
+            wallets::domain::repository::Repository as WalletRepository,
+        },
+        reports::{
+            net_worth::domain::consolidate,
+            snapshot::domain::{repository::Repository, snapshot::Snapshot},
+        },
+        shared::{
+            domain::{
+                context::AppContext,
+                date::{Date, Timezone},
+            },
+            infrastructure::errors::AppError,
+        },
+    };
+
+    pub enum Error {
+        /// Events could not be replayed on top of the checkpoint
+        Replay(apply_event::Error),
+    }
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct SnapshotAt {
+        repository: Box<dyn Repository>,
+        snapshot_io: SnapshotIO,
+        event_repository: Box<dyn EventRepository>,
+        wallet_repository: Box<dyn WalletRepository>,
+    }
+
+    impl SnapshotAt {
+        /// Every balance at the end of `date`, rebuilt from the nearest checkpoint before it
+        pub async fn run(&self, date: Date) -> Result<Snapshot, AppError<Error>> {
+            if date >= Timezone::now().date_naive() {
+                return Ok(self.repository.read().await?);
+            }
+
+            let until = date
+                .and_hms_opt(23, 59, 59)
+                .expect("to be a valid time")
+                .and_utc();
+
+            let (checkpoint, events) = match self.snapshot_io.read_checkpoint(until).await? {
+                Some(checkpoint) => {
+                    let events = self
+                        .event_repository
+                        .get_after(checkpoint.event_id, until)
+                        .await?;
+                    (checkpoint.snapshot, events)
+                }
+                None => (
+                    monee_core::Snapshot::default(),
+                    self.event_repository.get_until(until).await?,
+                ),
+            };
+
+            // Wallets created after `date` are left out, only the ones the events touch are seeded
+            let referenced: Vec<Reference> = events
+                .iter()
+                .flat_map(|entry| references(&entry.event))
+                .collect();
+            let wallets = self.wallet_repository.get_all().await?;
+            let snapshot = consolidate::replay_from(
+                checkpoint,
+                wallets
+                    .into_iter()
+                    .filter(|(id, _)| referenced.contains(&Reference::Wallet(*id)))
+                    .map(|(id, wallet)| (id, wallet.currency_id)),
+                events.iter().map(|entry| &entry.event),
+            )
+            .map_err(|e| AppError::App(Error::Replay(e)))?;
+
+            Ok(self.repository.resolve(snapshot).await?)
+        }
+    }
+}
+
+pub mod actor_balances {
+    use cream::context::FromContext;
+    use monee_core::ActorId;
+
+    use crate::{
+        reports::snapshot::domain::{
+            actor_balance::{self, CurrencyBalances, Error},
+            repository::Repository,
+        },
+        shared::{domain::context::AppContext, infrastructure::errors::AppError},
+    };
+
+    #[derive(FromContext)]
+    #[context(AppContext)]
+    pub struct ActorBalances {
+        repository: Box<dyn Repository>,
+    }
+
+    impl ActorBalances {
+        /// Net balance with every actor and how to settle up, per currency.
+        /// An empty `group` takes every actor into account
+        pub async fn run(
+            &self,
+            group: &[ActorId],
+        ) -> Result<Vec<CurrencyBalances>, AppError<Error>> {
+            let snapshot = self.repository.read().await?;
+            actor_balance::actor_balances(&snapshot, group).map_err(AppError::App)
+        }
+    }
+}
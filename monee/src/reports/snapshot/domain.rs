@@ -1,7 +1,8 @@
+pub use monee_types::reports::balances::*;
 pub use monee_types::reports::snapshot::*;
 pub mod repository {
     use monee_core::WalletId;
-    use monee_types::reports::snapshot::snapshot::{Money, Wallet};
+    use monee_types::reports::snapshot::snapshot::{Wallet, WalletMoney};
 
     use crate::shared::infrastructure::errors::InfrastructureError;
 
@@ -10,9 +11,14 @@ pub mod repository {
     #[async_trait::async_trait]
     pub trait Repository: 'static + Send + Sync {
         async fn read(&self) -> Result<Snapshot, InfrastructureError>;
+        /// Looks up what `snapshot` refers to, as [`Repository::read`] does with the last one
+        async fn resolve(
+            &self,
+            snapshot: monee_core::Snapshot,
+        ) -> Result<Snapshot, InfrastructureError>;
         async fn get_wallets(
             &self,
-        ) -> Result<Vec<(WalletId, (Wallet, Money))>, InfrastructureError>;
+        ) -> Result<Vec<(WalletId, (Wallet, WalletMoney))>, InfrastructureError>;
     }
 }
 
@@ -1,12 +1,15 @@
 pub mod repository {
     use cream::context::FromContext;
-    use monee_core::{Amount, DebtId, WalletId};
+    use monee_core::{ActorId, Amount, Balance, DebtId, WalletId};
 
     use crate::{
-        backoffice::{actors::domain::actor::Actor, currencies::domain::currency::Currency},
+        backoffice::{
+            actors::domain::actor::Actor, currencies::domain::currency::Currency,
+            snapshot::infrastructure::snapshot_repository::SurrealSnapshot,
+        },
         reports::snapshot::domain::{
             self,
-            snapshot::{Debt, Money, Snapshot, Wallet},
+            snapshot::{Debt, Money, Snapshot, Wallet, WalletMoney},
         },
         shared::{
             domain::context::DbContext,
@@ -29,8 +32,8 @@ pub mod repository {
     }
 
     #[derive(serde::Deserialize)]
-    struct SurrealMoney {
-        amount: Amount,
+    struct SurrealMoney<A = Amount> {
+        amount: A,
         #[serde(rename = "currency_id")]
         currency: Currency,
     }
@@ -40,7 +43,7 @@ pub mod repository {
         #[serde(rename = "id")]
         data: Entity<WalletId, Wallet>,
         #[serde(flatten)]
-        money: SurrealMoney,
+        money: SurrealMoney<Balance>,
     }
 
     #[derive(serde::Deserialize)]
@@ -54,11 +57,11 @@ pub mod repository {
     #[derive(serde::Deserialize)]
     struct SurrealDebtData {
         #[serde(rename = "actor_id")]
-        actor: Actor,
+        actor: Entity<ActorId, Actor>,
     }
 
-    impl From<SurrealMoney> for Money {
-        fn from(value: SurrealMoney) -> Self {
+    impl<A> From<SurrealMoney<A>> for Money<A> {
+        fn from(value: SurrealMoney<A>) -> Self {
             Money {
                 amount: value.amount,
                 currency: value.currency,
@@ -68,27 +71,14 @@ pub mod repository {
 
     impl From<SurrealDebtData> for Debt {
         fn from(value: SurrealDebtData) -> Self {
-            Debt { actor: value.actor }
+            let (actor_id, actor) = value.actor.into_inner();
+            Debt { actor_id, actor }
         }
     }
 
-    #[async_trait::async_trait]
-    impl domain::repository::Repository for SurrealRepository {
-        async fn read(&self) -> Result<Snapshot, InfrastructureError> {
-            let mut response = self
-                .0
-                .query(
-                    "SELECT * FROM snapshot FETCH 
-wallets.currency_id, wallets.id,
-debts.currency_id, debts.actor_id, 
-loans.currency_id, loans.actor_id",
-                )
-                .await?
-                .check()?;
-
-            let snapshot: Option<SnapshotDto> = response.take(0)?;
-            let snapshot = snapshot.unwrap_or_default();
-            Ok(Snapshot {
+    impl From<SnapshotDto> for Snapshot {
+        fn from(snapshot: SnapshotDto) -> Self {
+            Snapshot {
                 wallets: snapshot
                     .wallets
                     .into_iter()
@@ -104,12 +94,51 @@ loans.currency_id, loans.actor_id",
                     .into_iter()
                     .map(|d| (d.0, (d.1.data.into(), d.1.money.into())))
                     .collect(),
-            })
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl domain::repository::Repository for SurrealRepository {
+        async fn read(&self) -> Result<Snapshot, InfrastructureError> {
+            let mut response = self
+                .0
+                .query(
+                    "SELECT * FROM snapshot FETCH 
+wallets.currency_id, wallets.id,
+debts.currency_id, debts.actor_id, 
+loans.currency_id, loans.actor_id",
+                )
+                .await?
+                .check()?;
+
+            let snapshot: Option<SnapshotDto> = response.take(0)?;
+            Ok(snapshot.unwrap_or_default().into())
+        }
+
+        async fn resolve(
+            &self,
+            snapshot: monee_core::Snapshot,
+        ) -> Result<Snapshot, InfrastructureError> {
+            let mut response = self
+                .0
+                .query(
+                    "SELECT * FROM $snapshot FETCH 
+wallets.currency_id, wallets.id,
+debts.currency_id, debts.actor_id, 
+loans.currency_id, loans.actor_id",
+                )
+                .bind(("snapshot", SurrealSnapshot::from(snapshot)))
+                .await?
+                .check()?;
+
+            let snapshot: Option<SnapshotDto> = response.take(0)?;
+            Ok(snapshot.unwrap_or_default().into())
         }
 
         async fn get_wallets(
             &self,
-        ) -> Result<Vec<(WalletId, (Wallet, Money))>, InfrastructureError> {
+        ) -> Result<Vec<(WalletId, (Wallet, WalletMoney))>, InfrastructureError> {
             let mut response = self
                 .0
                 .query("SELECT wallets FROM snapshot FETCH wallets.currency_id, wallets.id")
@@ -127,3 +156,163 @@ loans.currency_id, loans.actor_id",
         }
     }
 }
+
+#[cfg(feature = "sqlite")]
+pub use sqlite_repository::SqliteRepository;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_repository {
+    use std::collections::HashMap;
+
+    use cream::context::FromContext;
+    use monee_core::{ActorId, CurrencyId, WalletId};
+
+    use crate::{
+        backoffice::{
+            actors::domain::actor::Actor, currencies::domain::currency::Currency,
+            wallets::domain::wallet::Wallet as WalletRow,
+        },
+        reports::snapshot::domain::{
+            self,
+            snapshot::{Debt, Money, Snapshot, Wallet, WalletMoney},
+        },
+        shared::{
+            domain::context::SqliteContext,
+            infrastructure::{
+                errors::InfrastructureError,
+                sqlite::{self, Connection},
+            },
+        },
+    };
+
+    #[derive(FromContext)]
+    #[context(SqliteContext)]
+    pub struct SqliteRepository(Connection);
+
+    /// Everything a snapshot refers to, snapshot entries whose references are gone are left
+    /// out of the report
+    struct Catalog {
+        currencies: HashMap<CurrencyId, Currency>,
+        actors: HashMap<ActorId, Actor>,
+        wallets: HashMap<WalletId, WalletRow>,
+    }
+
+    impl Catalog {
+        fn resolve_wallets(
+            &self,
+            snapshot: &monee_core::Snapshot,
+        ) -> Vec<(WalletId, (Wallet, WalletMoney))> {
+            snapshot
+                .wallets
+                .iter()
+                .filter_map(|(id, wallet)| {
+                    let row = self.wallets.get(id)?;
+                    let currency = self.currencies.get(&wallet.currency_id)?;
+
+                    Some((
+                        *id,
+                        (
+                            Wallet {
+                                name: row.name.clone(),
+                                description: row.description.clone(),
+                                kind: row.kind,
+                            },
+                            Money {
+                                amount: wallet.balance,
+                                currency: currency.clone(),
+                            },
+                        ),
+                    ))
+                })
+                .collect()
+        }
+
+        fn resolve_debts(
+            &self,
+            debts: &monee_core::MoneyMap<monee_core::Debt>,
+        ) -> HashMap<monee_core::DebtId, (Debt, Money)> {
+            debts
+                .iter()
+                .filter_map(|(id, debt)| {
+                    let actor = self.actors.get(&debt.actor_id)?;
+                    let currency = self.currencies.get(&debt.money.currency_id)?;
+
+                    Some((
+                        *id,
+                        (
+                            Debt {
+                                actor_id: debt.actor_id,
+                                actor: actor.clone(),
+                            },
+                            Money {
+                                amount: debt.money.amount,
+                                currency: currency.clone(),
+                            },
+                        ),
+                    ))
+                })
+                .collect()
+        }
+    }
+
+    impl SqliteRepository {
+        async fn catalog(&self) -> sqlite::Result<Catalog> {
+            async fn load<K, T>(connection: &Connection, sql: &str) -> sqlite::Result<HashMap<K, T>>
+            where
+                K: serde::de::DeserializeOwned + std::hash::Hash + Eq,
+                T: serde::de::DeserializeOwned,
+            {
+                let rows: Vec<(String, String)> = sqlx::query_as(sql).fetch_all(connection).await?;
+                rows.into_iter().map(sqlite::decode_row).collect()
+            }
+
+            Ok(Catalog {
+                currencies: load(&self.0, "SELECT id, data FROM currency").await?,
+                actors: load(&self.0, "SELECT id, data FROM actor").await?,
+                wallets: load(&self.0, "SELECT id, data FROM wallet").await?,
+            })
+        }
+
+        async fn read_last(&self) -> sqlite::Result<Option<monee_core::Snapshot>> {
+            let data: Option<String> = sqlx::query_scalar(
+                "SELECT data FROM snapshot ORDER BY created_at DESC, id DESC LIMIT 1",
+            )
+            .fetch_optional(&self.0)
+            .await?;
+
+            data.as_deref().map(sqlite::decode).transpose()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl domain::repository::Repository for SqliteRepository {
+        async fn read(&self) -> Result<Snapshot, InfrastructureError> {
+            let snapshot = self.read_last().await?.unwrap_or_default();
+            self.resolve(snapshot).await
+        }
+
+        async fn resolve(
+            &self,
+            snapshot: monee_core::Snapshot,
+        ) -> Result<Snapshot, InfrastructureError> {
+            let catalog = self.catalog().await?;
+
+            Ok(Snapshot {
+                wallets: catalog.resolve_wallets(&snapshot).into_iter().collect(),
+                debts: catalog.resolve_debts(&snapshot.debts),
+                loans: catalog.resolve_debts(&snapshot.loans),
+            })
+        }
+
+        async fn get_wallets(
+            &self,
+        ) -> Result<Vec<(WalletId, (Wallet, WalletMoney))>, InfrastructureError> {
+            let Some(snapshot) = self.read_last().await? else {
+                return Ok(Vec::new());
+            };
+
+            let catalog = self.catalog().await?;
+            Ok(catalog.resolve_wallets(&snapshot))
+        }
+    }
+}
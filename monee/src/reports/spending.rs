@@ -0,0 +1,75 @@
+pub mod domain {
+    pub use monee_types::reports::spending::*;
+}
+
+pub mod application {
+    pub mod spending_report {
+        use cream::context::FromContext;
+
+        use crate::{
+            backoffice::{
+                currencies::domain::repository::Repository as CurrencyRepository,
+                events::domain::repository::Repository as EventRepository,
+                item_tags::domain::repository::Repository as ItemTagRepository,
+                wallets::domain::repository::Repository as WalletRepository,
+            },
+            reports::spending::domain::item_spending::{self, SpendingEntry},
+            shared::{
+                domain::{context::AppContext, date::Timezone},
+                infrastructure::errors::InfrastructureError,
+            },
+        };
+
+        #[derive(FromContext)]
+        #[context(AppContext)]
+        pub struct SpendingReport {
+            event_repository: Box<dyn EventRepository>,
+            wallet_repository: Box<dyn WalletRepository>,
+            item_tag_repository: Box<dyn ItemTagRepository>,
+            currency_repository: Box<dyn CurrencyRepository>,
+        }
+
+        impl SpendingReport {
+            /// Spending per item and currency, net of refunds
+            pub async fn run(&self) -> Result<Vec<SpendingEntry>, InfrastructureError> {
+                let events = self.event_repository.get_until(Timezone::now()).await?;
+                let wallets = self.wallet_repository.get_all().await?;
+                let tags = self.item_tag_repository.get_all().await?;
+                let currencies = self.currency_repository.get_all().await?;
+
+                let spending = item_spending::item_spending(
+                    events.iter().map(|entry| &entry.event),
+                    |wallet_id| {
+                        wallets
+                            .iter()
+                            .find(|(id, _)| *id == wallet_id)
+                            .map(|(_, wallet)| wallet.currency_id)
+                    },
+                );
+
+                let mut entries: Vec<_> = spending
+                    .into_iter()
+                    .filter_map(|spending| {
+                        let item = tags.iter().find(|node| node.id == spending.item_id)?;
+                        let (_, currency) = currencies
+                            .iter()
+                            .find(|(id, _)| *id == spending.currency_id)?;
+
+                        Some(SpendingEntry {
+                            item: item.tag.name.clone(),
+                            currency: currency.clone(),
+                            spending,
+                        })
+                    })
+                    .collect();
+
+                entries.sort_by(|a, b| {
+                    (a.currency.code.as_ref(), a.item.to_string())
+                        .cmp(&(b.currency.code.as_ref(), b.item.to_string()))
+                });
+
+                Ok(entries)
+            }
+        }
+    }
+}
@@ -74,5 +74,47 @@ pub mod infrastructure {
                 Ok(())
             }
         }
+
+        #[cfg(feature = "sqlite")]
+        pub use sqlite_repository::SqliteRepository;
+
+        #[cfg(feature = "sqlite")]
+        mod sqlite_repository {
+            use cream::context::FromContext;
+            use monee_types::apps::app_id::AppId;
+
+            use crate::{
+                prelude::InfrastructureError,
+                self_config::domain::repository::Repository,
+                shared::{
+                    domain::context::SqliteContext,
+                    infrastructure::sqlite::{self, Connection},
+                },
+            };
+
+            #[derive(FromContext)]
+            #[context(SqliteContext)]
+            pub struct SqliteRepository(Connection);
+
+            #[async_trait::async_trait]
+            impl Repository for SqliteRepository {
+                async fn get_self(&self) -> Result<Option<AppId>, InfrastructureError> {
+                    let id: Option<String> = sqlx::query_scalar("SELECT id FROM self_app LIMIT 1")
+                        .fetch_optional(&self.0)
+                        .await?;
+
+                    Ok(id.map(sqlite::decode_key).transpose()?)
+                }
+
+                async fn save_app_id(&self, id: AppId) -> Result<(), InfrastructureError> {
+                    sqlx::query("INSERT INTO self_app (id) VALUES (?)")
+                        .bind(sqlite::key(id))
+                        .execute(&self.0)
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        }
     }
 }
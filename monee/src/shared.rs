@@ -17,7 +17,7 @@ pub mod application {
             pub fn error(&self, err: InfrastructureError) {
                 let result = self.repository.log(format_args!("{}", err));
                 if let Err(e) = result {
-                    println!("error logging error: {:?}", e);
+                    eprintln!("error logging error: {:?}", e);
                 }
             }
         }
@@ -6,3 +6,16 @@ pub mod date {
     pub use monee_types::shared::date::*;
 }
 
+pub mod slug {
+    pub use monee_types::shared::slug::*;
+}
+
+pub mod unit_of_work {
+    pub use crate::shared::infrastructure::database::transaction::Transaction;
+
+    /// Starts transactions repositories stage their writes into, so changes spanning several
+    /// of them are committed at once
+    pub trait UnitOfWork: Send + Sync + 'static {
+        fn begin(&self) -> Transaction;
+    }
+}
@@ -6,17 +6,40 @@ use cream::{
     },
 };
 
-use crate::shared::infrastructure::errors::InfrastructureError;
+use crate::shared::infrastructure::{
+    database::config::DatabaseConfig, errors::InfrastructureError,
+};
 
 #[derive(Clone)]
 pub struct AppContext {
     cream: CreamContext,
     events_ctx: EventsContext,
-    db: DbContext,
+    db: Storage,
 }
 
 impl Context for AppContext {}
 
+impl AppContext {
+    /// The SurrealDB connection repositories share, `None` when the data is kept in SQLite
+    pub fn surreal_connection(
+        &self,
+    ) -> Option<crate::shared::infrastructure::database::Connection> {
+        match &self.db {
+            Storage::Surreal(ctx) => Some(ctx.0.clone()),
+            #[cfg(feature = "sqlite")]
+            Storage::Sqlite(_) => None,
+        }
+    }
+}
+
+/// Database repositories are provided from, chosen when the context is built
+#[derive(Clone)]
+enum Storage {
+    Surreal(DbContext),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteContext),
+}
+
 #[derive(Clone)]
 pub struct DbContext(crate::shared::infrastructure::database::Connection);
 impl Context for DbContext {}
@@ -28,46 +51,58 @@ impl DbContext {
     }
 }
 
+#[cfg(feature = "sqlite")]
+#[derive(Clone)]
+pub struct SqliteContext(crate::shared::infrastructure::sqlite::Connection);
+#[cfg(feature = "sqlite")]
+impl Context for SqliteContext {}
+
+#[cfg(feature = "sqlite")]
+impl SqliteContext {
+    pub(crate) fn new(connection: crate::shared::infrastructure::sqlite::Connection) -> Self {
+        Self(connection)
+    }
+}
+
 pub struct AppContextBuilder {
-    #[cfg(feature = "embedded")]
-    pub base_dir: std::path::PathBuf,
-    #[cfg(feature = "remote")]
-    pub db_url: String,
+    /// Where the data is kept and how to sign in. Besides the SurrealDB urls, `sqlite://`
+    /// followed by a path stores everything in that SQLite file (needs the `sqlite` feature)
+    pub database: DatabaseConfig,
+    /// Whether pending database migrations are applied on build
+    pub migrate: bool,
 }
 
 impl Default for AppContextBuilder {
     fn default() -> Self {
         Self {
-            #[cfg(feature = "embedded")]
-            base_dir: crate::shared::infrastructure::filesystem::create_local_path(),
-            #[cfg(feature = "remote")]
-            db_url: "0.0.0.0:6767".to_owned(),
+            database: DatabaseConfig::default(),
+            migrate: true,
         }
     }
 }
 
 impl AppContextBuilder {
     pub async fn build(self) -> Result<AppContextSetup, InfrastructureError> {
-        #[cfg(feature = "embedded")]
-        let db = crate::shared::infrastructure::database::connect(self.base_dir).await?;
-        #[cfg(feature = "db_test")]
-        let db = crate::shared::infrastructure::database::connect().await?;
-        #[cfg(feature = "remote")]
-        let db = crate::shared::infrastructure::database::connect(self.db_url).await?;
+        #[cfg(feature = "sqlite")]
+        if let Some(path) = self.database.url.strip_prefix("sqlite://") {
+            use crate::shared::infrastructure::sqlite::{migrations, open};
 
-        let cream = CreamContext::default();
-        let (events_ctx, setup) = cream.provide::<EventsContextBuilder>().build();
+            let db = open(std::path::Path::new(path)).await?;
+            if self.migrate {
+                migrations::run(&db).await?;
+            }
 
-        let ctx = AppContext {
-            events_ctx,
-            cream,
-            db: DbContext(db),
-        };
+            return Ok(AppContextSetup::new(Storage::Sqlite(SqliteContext(db))));
+        }
 
-        Ok(AppContextSetup {
-            ctx,
-            events_setup: Some(setup),
-        })
+        use crate::shared::infrastructure::database::{migrations, open};
+
+        let db = open(&self.database).await?;
+        if self.migrate {
+            migrations::run(&db).await?;
+        }
+
+        Ok(AppContextSetup::new(Storage::Surreal(DbContext(db))))
     }
 }
 
@@ -83,6 +118,22 @@ pub struct AppEventsBuilder<'c> {
 }
 
 impl AppContextSetup {
+    fn new(db: Storage) -> Self {
+        let cream = CreamContext::default();
+        let (events_ctx, setup) = cream.provide::<EventsContextBuilder>().build();
+
+        let ctx = AppContext {
+            events_ctx,
+            cream,
+            db,
+        };
+
+        Self {
+            ctx,
+            events_setup: Some(setup),
+        }
+    }
+
     pub fn setup(mut self) -> AppContext {
         let _ = self.try_cfg_events(|builder| {
             builder
@@ -111,6 +162,11 @@ impl AppContextSetup {
         let mut dispatcher = cream::events::dispatcher::Dispatcher::<AppContext>::default();
         dispatcher
             .add::<crate::backoffice::snapshot::application::on_wallet_created::OnWalletCreated>();
+        dispatcher
+            .add::<crate::backoffice::snapshot::application::on_wallet_deleted::OnWalletDeleted>();
+        dispatcher.add::<
+            crate::backoffice::snapshot::application::on_wallet_kind_changed::OnWalletKindChanged,
+        >();
 
         let builder = AppEventsBuilder {
             ctx: &self.ctx,
@@ -131,19 +187,20 @@ impl FromContext<DbContext> for crate::shared::infrastructure::database::Connect
     }
 }
 
+#[cfg(feature = "sqlite")]
+impl FromContext<SqliteContext> for crate::shared::infrastructure::sqlite::Connection {
+    fn from_context(ctx: &SqliteContext) -> Self {
+        ctx.0.clone()
+    }
+}
+
 mod extends {
     use cream::{
         context::{ContextExtend, CreamContext},
         events::context::EventsContext,
     };
 
-    use super::{AppContext, DbContext};
-
-    impl ContextExtend<DbContext> for AppContext {
-        fn provide_ctx(&self) -> &DbContext {
-            &self.db
-        }
-    }
+    use super::AppContext;
 
     impl ContextExtend<CreamContext> for AppContext {
         fn provide_ctx(&self) -> &CreamContext {
@@ -185,6 +242,14 @@ mod provides_config {
             domain::repository::Repository as EventsRepository,
             infrastructure::repository::SurrealRepository as EventsSurrealRepository,
         },
+        exchange_rates::{
+            domain::repository::Repository as ExchangeRatesRepository,
+            infrastructure::repository::SurrealRepository as ExchangeRatesSurrealRepository,
+        },
+        goals::{
+            domain::repository::Repository as GoalsRepository,
+            infrastructure::repository::SurrealRepository as GoalsSurrealRepository,
+        },
         item_tags::{
             domain::repository::Repository as ItemTagsRepository,
             infrastructure::repository::SurrealRepository as ItemTagsSurrealRepository,
@@ -225,52 +290,91 @@ mod provides_config {
         },
     };
 
+    use crate::shared::{
+        domain::unit_of_work::UnitOfWork, infrastructure::database::transaction::SurrealUnitOfWork,
+    };
+
+    use crate::migrations::{
+        domain::repository::Repository as MigrationsRepository,
+        infrastructure::repository::SurrealRepository as MigrationsSurrealRepository,
+    };
+
     use crate::self_config::{
         domain::repository::Repository as SelfConfigRepository,
         infrastructure::repository::SurrealRepository as SelfConfigSurrealRepository,
     };
 
-    use super::{AppContext, DbContext};
+    use super::{AppContext, Storage};
 
-    macro_rules! provide_map (($ctx: path { $($service: path: $real_service: path),* $(,)* }) => {
+    macro_rules! provide_map (($($service: path: $surreal_service: path | $sqlite_service: path),* $(,)*) => {
         $(
         impl cream::context::FromContext<AppContext> for Box<dyn $service> {
             fn from_context(app_ctx: &AppContext) -> Self {
-                use cream::context::{ContextExtend, Context};
-                let ctx: &$ctx = app_ctx.provide_ctx();
-                let real_service: $real_service = ctx.provide();
-                Box::new(real_service)
+                use cream::context::Context;
+                match &app_ctx.db {
+                    Storage::Surreal(ctx) => {
+                        let real_service: $surreal_service = ctx.provide();
+                        Box::new(real_service)
+                    }
+                    #[cfg(feature = "sqlite")]
+                    Storage::Sqlite(ctx) => {
+                        let real_service: $sqlite_service = ctx.provide();
+                        Box::new(real_service)
+                    }
+                }
             }
         }
         )*
     });
 
-    provide_map! {DbContext {
-        SnapshotRepository: SnapshotSurrealRepository,
-        WalletsRepository: WalletsSurrealRepository,
-        ActorsRepository: ActorsSurrealRepository,
-        CurrenciesRepository: CurrenciesSurrealRepository,
-        ItemTagsRepository: ItemTagsSurrealRepository,
-        EventsRepository: EventsSurrealRepository,
-        crate::reports::snapshot::domain::repository::Repository: crate::reports::snapshot::infrastructure::repository::SurrealRepository,
-        crate::reports::events::domain::repository::Repository: crate::reports::events::infrastructure::repository::SurrealRepository,
-
-        SelfConfigRepository: SelfConfigSurrealRepository,
-
-        ClientRepository: ClientSurrealRepository,
-        SyncRepository: SyncSurrealRepository,
-
-        HostsRepository: HostsSurrealRepository,
-        NodeSyncRepository: NodeSyncSurrealRepository,
-
-        ChangesRepository: ChangesSurrealRepository,
-    }}
+    provide_map! {
+        SnapshotRepository: SnapshotSurrealRepository
+            | crate::backoffice::snapshot::infrastructure::snapshot_repository::SnapshotSqliteRepository,
+        WalletsRepository: WalletsSurrealRepository
+            | crate::backoffice::wallets::infrastructure::repository::SqliteRepository,
+        ActorsRepository: ActorsSurrealRepository
+            | crate::backoffice::actors::infrastructure::repository::SqliteRepository,
+        CurrenciesRepository: CurrenciesSurrealRepository
+            | crate::backoffice::currencies::infrastructure::repository::SqliteRepository,
+        ItemTagsRepository: ItemTagsSurrealRepository
+            | crate::backoffice::item_tags::infrastructure::repository::SqliteRepository,
+        EventsRepository: EventsSurrealRepository
+            | crate::backoffice::events::infrastructure::repository::SqliteRepository,
+        ExchangeRatesRepository: ExchangeRatesSurrealRepository
+            | crate::backoffice::exchange_rates::infrastructure::repository::SqliteRepository,
+        GoalsRepository: GoalsSurrealRepository
+            | crate::backoffice::goals::infrastructure::repository::SqliteRepository,
+        crate::reports::snapshot::domain::repository::Repository: crate::reports::snapshot::infrastructure::repository::SurrealRepository
+            | crate::reports::snapshot::infrastructure::repository::SqliteRepository,
+        crate::reports::events::domain::repository::Repository: crate::reports::events::infrastructure::repository::SurrealRepository
+            | crate::reports::events::infrastructure::repository::SqliteRepository,
+
+        SelfConfigRepository: SelfConfigSurrealRepository
+            | crate::self_config::infrastructure::repository::SqliteRepository,
+        MigrationsRepository: MigrationsSurrealRepository
+            | crate::migrations::infrastructure::repository::SqliteRepository,
+        UnitOfWork: SurrealUnitOfWork
+            | crate::shared::infrastructure::sqlite::SqliteUnitOfWork,
+
+        ClientRepository: ClientSurrealRepository
+            | crate::host::nodes::infrastructure::repository::SqliteRepository,
+        SyncRepository: SyncSurrealRepository
+            | crate::host::sync::infrastructure::repository::SqliteRepository,
+
+        HostsRepository: HostsSurrealRepository
+            | crate::nodes::hosts::infrastructure::repository::SqliteRepository,
+        NodeSyncRepository: NodeSyncSurrealRepository
+            | crate::nodes::sync::infrastructure::repository::SqliteRepository,
+
+        ChangesRepository: ChangesSurrealRepository
+            | crate::nodes::changes::infrastructure::repository::SqliteRepository,
+    }
 
     impl cream::context::FromContext<super::AppContext>
         for Box<dyn crate::shared::domain::logging::LogRepository>
     {
         fn from_context(_ctx: &super::AppContext) -> Self {
-            Box::new(crate::shared::infrastructure::logging::FileLogRepository)
+            Box::new(crate::shared::infrastructure::logging::TracingLogRepository)
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::prelude::AppError;
+use crate::{prelude::AppError, shared::infrastructure::database::transaction::CommitError};
 
 pub use monee_types::shared::errors::*;
 
@@ -16,3 +16,48 @@ impl From<surrealdb::Error> for AppError<UniqueSaveError> {
         }
     }
 }
+
+impl From<surrealdb::Error> for AppError<UpdateError> {
+    fn from(value: surrealdb::Error) -> Self {
+        use surrealdb::{error, Error};
+        match value {
+            Error::Api(error::Api::Query { .. }) => {
+                AppError::App(UpdateError::AlreadyExists("unknown".to_owned()))
+            }
+            Error::Db(error::Db::IndexExists { thing, .. }) => {
+                AppError::App(UpdateError::AlreadyExists(thing.tb))
+            }
+            e => AppError::Infrastructure(e.into()),
+        }
+    }
+}
+
+impl From<CommitError> for AppError<UniqueSaveError> {
+    fn from(value: CommitError) -> Self {
+        match value {
+            CommitError::Surreal(e) => e.into(),
+            #[cfg(feature = "sqlite")]
+            CommitError::Sqlite(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<sqlx::Error> for AppError<UniqueSaveError> {
+    fn from(value: sqlx::Error) -> Self {
+        match crate::shared::infrastructure::sqlite::unique_violation(&value) {
+            Some(table) => AppError::App(UniqueSaveError::AlreadyExists(table)),
+            None => AppError::Infrastructure(value.into()),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<sqlx::Error> for AppError<UpdateError> {
+    fn from(value: sqlx::Error) -> Self {
+        match crate::shared::infrastructure::sqlite::unique_violation(&value) {
+            Some(table) => AppError::App(UpdateError::AlreadyExists(table)),
+            None => AppError::Infrastructure(value.into()),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod database;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 
 pub mod errors {
     use std::panic::Location;
@@ -25,6 +27,12 @@ pub mod errors {
     pub enum InfrastructureError {
         #[error("authentication failed")]
         Auth,
+        /// The url asks for a storage engine left out of this build
+        #[error("`{scheme}://` databases need monee built with the `{feature}` feature")]
+        EngineNotBuilt {
+            scheme: String,
+            feature: &'static str,
+        },
         #[error(transparent)]
         Unspecified(UnspecifiedError),
     }
@@ -37,6 +45,47 @@ pub mod errors {
         }
     }
 
+    #[cfg(feature = "sqlite")]
+    impl From<sqlx::Error> for InfrastructureError {
+        #[track_caller]
+        #[inline]
+        fn from(err: sqlx::Error) -> Self {
+            Self::Unspecified(UnspecifiedError::new(err))
+        }
+    }
+
+    impl From<std::io::Error> for InfrastructureError {
+        #[track_caller]
+        #[inline]
+        fn from(err: std::io::Error) -> Self {
+            Self::Unspecified(UnspecifiedError::new(err))
+        }
+    }
+
+    impl From<toml::de::Error> for InfrastructureError {
+        #[track_caller]
+        #[inline]
+        fn from(err: toml::de::Error) -> Self {
+            Self::Unspecified(UnspecifiedError::new(err))
+        }
+    }
+
+    impl From<toml::ser::Error> for InfrastructureError {
+        #[track_caller]
+        #[inline]
+        fn from(err: toml::ser::Error) -> Self {
+            Self::Unspecified(UnspecifiedError::new(err))
+        }
+    }
+
+    impl From<super::database::transaction::CommitError> for InfrastructureError {
+        #[track_caller]
+        #[inline]
+        fn from(err: super::database::transaction::CommitError) -> Self {
+            Self::Unspecified(UnspecifiedError::new(err))
+        }
+    }
+
     #[derive(Debug)]
     pub enum AppError<E> {
         App(E),
@@ -53,27 +102,131 @@ pub mod errors {
 }
 
 pub mod logging {
+    use std::path::PathBuf;
+
+    use tracing_appender::{
+        non_blocking::WorkerGuard,
+        rolling::{RollingFileAppender, Rotation},
+    };
+    use tracing_subscriber::{
+        fmt::MakeWriter, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt,
+        EnvFilter, Layer,
+    };
+
     use crate::shared::domain::logging::LogRepository;
 
-    pub struct FileLogRepository;
+    use super::errors::{InfrastructureError, UnspecifiedError};
+
+    /// Hands errors to whatever subscriber [`init`] installed
+    pub struct TracingLogRepository;
 
-    impl LogRepository for FileLogRepository {
-        fn log(
-            &self,
-            message: std::fmt::Arguments,
-        ) -> Result<(), super::errors::InfrastructureError> {
-            println!("Unhandable error: {}", message);
+    impl LogRepository for TracingLogRepository {
+        fn log(&self, message: std::fmt::Arguments) -> Result<(), InfrastructureError> {
+            tracing::error!("{}", message);
             Ok(())
         }
     }
+
+    /// Levels are taken from `MONEE_LOG` (e.g. `debug` or `warn,monee=trace`), `info` when unset
+    pub const FILTER_VAR: &str = "MONEE_LOG";
+
+    const KEPT_FILES: usize = 7;
+
+    pub struct LogOptions {
+        /// Daily rotated `monee.<date>.log` files are written here, the last week is kept
+        pub dir: Option<PathBuf>,
+        pub stdout: bool,
+        /// One JSON object per line, with the spans each event happened in
+        pub json: bool,
+    }
+
+    /// Flushes the file sink when dropped, keep it until the program ends
+    pub struct LogGuard(Option<WorkerGuard>);
+
+    fn fmt_layer<S, W>(writer: W, json: bool, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+    where
+        S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        let layer = tracing_subscriber::fmt::layer().with_writer(writer);
+        if json {
+            layer
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .boxed()
+        } else {
+            layer.with_ansi(ansi).boxed()
+        }
+    }
+
+    pub fn init(options: LogOptions) -> Result<LogGuard, InfrastructureError> {
+        let mut layers = Vec::new();
+
+        let guard = match options.dir {
+            Some(dir) => {
+                std::fs::create_dir_all(&dir)?;
+                let appender = RollingFileAppender::builder()
+                    .rotation(Rotation::DAILY)
+                    .filename_prefix("monee")
+                    .filename_suffix("log")
+                    .max_log_files(KEPT_FILES)
+                    .build(dir)
+                    .map_err(|e| InfrastructureError::Unspecified(UnspecifiedError::new(e)))?;
+
+                let (writer, guard) = tracing_appender::non_blocking(appender);
+                layers.push(fmt_layer(writer, options.json, false));
+                Some(guard)
+            }
+            None => None,
+        };
+
+        if options.stdout {
+            layers.push(fmt_layer(std::io::stdout, options.json, true));
+        }
+
+        let filter = EnvFilter::try_from_env(FILTER_VAR).unwrap_or_else(|_| EnvFilter::new("info"));
+        tracing_subscriber::registry()
+            .with(layers)
+            .with(filter)
+            .try_init()
+            .map_err(|e| InfrastructureError::Unspecified(UnspecifiedError::new(e)))?;
+
+        Ok(LogGuard(guard))
+    }
 }
 
 pub mod filesystem {
-    #[cfg(feature = "embedded")]
-    pub fn create_local_path() -> std::path::PathBuf {
-        use std::{fs, path::PathBuf};
+    /// `monee` under `XDG_CONFIG_HOME`, or `~/.config` when unset
+    pub fn config_dir() -> std::path::PathBuf {
+        use std::path::PathBuf;
 
-        let share_dir = std::env::var("XDG_DATA_HOME")
+        std::env::var("XDG_CONFIG_HOME")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var("HOME")
+                    .ok()
+                    .map(|home| PathBuf::from(home).join(".config"))
+            })
+            .expect("To get config directory")
+            .join("monee")
+    }
+
+    pub fn config_path() -> std::path::PathBuf {
+        config_dir().join("config.toml")
+    }
+
+    /// Ledgers besides the main one and which is the current
+    pub fn ledgers_path() -> std::path::PathBuf {
+        config_dir().join("ledgers.toml")
+    }
+
+    /// `monee` under `XDG_DATA_HOME`, or `~/.local/share` when unset
+    pub fn data_dir() -> std::path::PathBuf {
+        use std::path::PathBuf;
+
+        std::env::var("XDG_DATA_HOME")
             .ok()
             .map(PathBuf::from)
             .or_else(|| {
@@ -81,10 +234,18 @@ pub mod filesystem {
                     .ok()
                     .map(|home| PathBuf::from(home).join(".local/share"))
             })
-            .expect("To get share directory");
-        let path = share_dir.join("monee");
+            .expect("To get share directory")
+            .join("monee")
+    }
 
-        fs::create_dir_all(&path).expect("To create monee data directory");
+    pub fn logs_dir() -> std::path::PathBuf {
+        data_dir().join("logs")
+    }
+
+    #[cfg(feature = "embedded")]
+    pub fn create_local_path() -> std::path::PathBuf {
+        let path = data_dir();
+        std::fs::create_dir_all(&path).expect("To create monee data directory");
         path
     }
 }
@@ -1,127 +1,149 @@
+pub mod config;
+pub mod migrations;
+pub mod transaction;
+
+use config::{AuthLevel, Credentials, DatabaseConfig};
+
+use super::errors::InfrastructureError;
+
 pub use surrealdb::Result;
 
-#[cfg(feature = "embedded")]
-pub type Engine = surrealdb::engine::local::Db;
-#[cfg(feature = "remote")]
-pub type Engine = surrealdb::engine::remote::ws::Client;
-#[cfg(feature = "db_test")]
-pub type Engine = surrealdb::engine::local::Db;
+/// Chosen at runtime from the scheme of the url given to [`open`]
+pub type Engine = surrealdb::engine::any::Any;
 
 pub type Connection = surrealdb::Surreal<Engine>;
 
 pub type Error = surrealdb::Error;
 
-#[cfg(feature = "embedded")]
 const DB_DIR: &str = "monee.db";
 
-async fn init(connection: &Connection) -> Result<()> {
-    init_backoffice(connection).await?;
-    init_host(connection).await?;
-
-    Ok(())
+/// Url of the embedded store kept under `base_dir`
+pub fn file_url(base_dir: &std::path::Path) -> String {
+    format!("file://{}", base_dir.join(DB_DIR).display())
 }
 
-async fn init_backoffice(connection: &Connection) -> Result<()> {
-    connection
-        .query("DEFINE TABLE event")
-        .query("DEFINE FIELD created_at ON event VALUE time::now()")
-        .await?
-        .check()?;
+/// The embedded store under the data directory when it is built in, a local server otherwise
+pub fn default_url() -> String {
+    #[cfg(feature = "embedded")]
+    return file_url(&crate::shared::infrastructure::filesystem::create_local_path());
 
-    connection
-        .query("DEFINE TABLE wallet")
-        .query("DEFINE FIELD name ON wallet TYPE option<string>")
-        .query("DEFINE FIELD description ON wallet TYPE string")
-        .query("DEFINE FIELD currency_id ON wallet TYPE record<currency>")
-        .query("DEFINE INDEX wallet_name ON wallet FIELDS name UNIQUE")
-        .await?
-        .check()?;
+    #[cfg(not(feature = "embedded"))]
+    "ws://0.0.0.0:6767".to_owned()
+}
 
-    connection
-        .query("DEFINE TABLE currency")
-        .query("DEFINE FIELD name ON currency TYPE string")
-        .query("DEFINE FIELD symbol ON currency TYPE string")
-        .query("DEFINE FIELD code ON currency TYPE string")
-        .query("DEFINE INDEX currency_code ON currency FIELDS code UNIQUE")
-        .await?
-        .check()?;
+/// Opens the database as it is, without applying pending migrations. The scheme of the url
+/// picks the engine: `file://` or `rocksdb://` for an embedded store (needs the `embedded`
+/// feature), `mem://` for an in-memory one (needs `db_test`), `ws://` or `wss://` for a server.
+/// Credentials rejected on sign in are reported as [`InfrastructureError::Auth`]
+pub async fn open(config: &DatabaseConfig) -> std::result::Result<Connection, InfrastructureError> {
+    check_engine(&config.url)?;
+    let db = surrealdb::engine::any::connect(config.url.as_str()).await?;
+
+    if let Some(credentials) = &config.credentials {
+        signin(&db, config, credentials).await.map_err(|e| {
+            if is_invalid_auth(&e) {
+                InfrastructureError::Auth
+            } else {
+                e.into()
+            }
+        })?;
+    }
 
-    connection
-        .query("DEFINE TABLE actor")
-        .query("DEFINE FIELD name ON actor TYPE string")
-        .query("DEFINE FIELD type ON actor TYPE string")
-        .query("DEFINE FIELD alias ON actor TYPE option<string>")
-        .query("DEFINE INDEX actor_alias ON actor FIELDS alias UNIQUE")
-        .await?
-        .check()?;
+    db.use_ns(&config.namespace)
+        .use_db(&config.database)
+        .await?;
 
-    connection
-        .query("DEFINE TABLE item_tag")
-        .query("DEFINE FIELD name ON item_tag TYPE string")
-        .query("DEFINE INDEX item_tag_name ON item_tag FIELDS name UNIQUE")
-        .await?
-        .check()?;
+    Ok(db)
+}
 
-    Ok(())
+/// Fails with [`InfrastructureError::EngineNotBuilt`] when the scheme of `url` needs an engine
+/// this build leaves out
+pub fn check_engine(url: &str) -> std::result::Result<(), InfrastructureError> {
+    let scheme = url.split_once("://").map_or(url, |(scheme, _)| scheme);
+    let feature = match scheme {
+        "file" | "rocksdb" if cfg!(not(feature = "embedded")) => "embedded",
+        "mem" if cfg!(not(feature = "db_test")) => "db_test",
+        "sqlite" if cfg!(not(feature = "sqlite")) => "sqlite",
+        _ => return Ok(()),
+    };
+
+    Err(InfrastructureError::EngineNotBuilt {
+        scheme: scheme.to_owned(),
+        feature,
+    })
 }
 
-async fn init_host(connection: &Connection) -> Result<()> {
-    connection
-        .query("DEFINE TABLE client")
-        .query("DEFINE FIELD name ON host TYPE option<string>")
-        .await?
-        .check()?;
+/// Whether the store refused the credentials, as opposed to failing to process the sign in
+fn is_invalid_auth(error: &Error) -> bool {
+    use surrealdb::error::{Api, Db};
 
-    Ok(())
+    match error {
+        Error::Db(Db::InvalidAuth) => true,
+        // Remote engines only pass the message of the error along
+        Error::Api(Api::Query(message)) => *message == Db::InvalidAuth.to_string(),
+        _ => false,
+    }
 }
 
-#[cfg(feature = "embedded")]
-async fn create_connection(base_dir: std::path::PathBuf) -> surrealdb::Result<(Connection, bool)> {
-    let path = base_dir.join(DB_DIR);
-    // For now, just run definition queries
-    /* let exists = tokio::fs::try_exists(&path).await.unwrap_or_else(|_| {
-        println!("WARNING: Failed to check if db exists");
-        false
-    }); */
-    let exists = false;
-
-    let db = surrealdb::Surreal::new::<surrealdb::engine::local::File>(format!(
-        "file://{}",
-        path.display()
-    ))
-    .await?;
-
-    Ok((db, exists))
-}
+async fn signin(
+    db: &Connection,
+    config: &DatabaseConfig,
+    credentials: &Credentials,
+) -> surrealdb::Result<()> {
+    use surrealdb::opt::auth::{Database, Namespace, Root};
+
+    let username = credentials.username.as_str();
+    let password = credentials.password.as_str();
+    match credentials.level {
+        AuthLevel::Root => db.signin(Root { username, password }).await?,
+        AuthLevel::Namespace => {
+            db.signin(Namespace {
+                namespace: &config.namespace,
+                username,
+                password,
+            })
+            .await?
+        }
+        AuthLevel::Database => {
+            db.signin(Database {
+                namespace: &config.namespace,
+                database: &config.database,
+                username,
+                password,
+            })
+            .await?
+        }
+    };
 
-#[cfg(feature = "db_test")]
-pub async fn create_connection() -> surrealdb::Result<(Connection, bool)> {
-    let db = surrealdb::Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
-    Ok((db, false))
+    Ok(())
 }
 
-#[cfg(feature = "remote")]
-async fn create_connection(url: String) -> surrealdb::Result<(Connection, bool)> {
-    let db: Connection = surrealdb::Surreal::new::<surrealdb::engine::remote::ws::Ws>(url).await?;
-    Ok((db, false))
+/// Drops the database `config` points to along with everything in it
+pub async fn remove(config: &DatabaseConfig) -> std::result::Result<(), InfrastructureError> {
+    let db = open(config).await?;
+    remove_from(&db, &config.database).await
 }
 
-pub async fn connect(
-    #[cfg(feature = "embedded")] base_dir: std::path::PathBuf,
-    #[cfg(feature = "remote")] url: String,
-) -> surrealdb::Result<Connection> {
-    #[cfg(feature = "embedded")]
-    let (db, exists) = create_connection(base_dir).await?;
-    #[cfg(feature = "remote")]
-    let (db, exists) = create_connection(url).await?;
-    #[cfg(feature = "db_test")]
-    let (db, exists) = create_connection().await?;
+/// Drops `database` from the namespace `connection` uses. Embedded stores can only be open
+/// once per process, so a store already open is reached through its connection
+pub async fn remove_from(
+    connection: &Connection,
+    database: &str,
+) -> std::result::Result<(), InfrastructureError> {
+    // Not bindable, ledger names are limited to what needs no escaping besides the backticks
+    connection
+        .query(format!("REMOVE DATABASE `{}`", database))
+        .await?
+        .check()?;
 
-    db.use_ns("monee").use_db("monee").await?;
+    Ok(())
+}
 
-    if !exists {
-        init(&db).await?;
-    }
+/// Opens the default namespace and database at `url` without credentials, then applies the
+/// pending migrations
+pub async fn connect(url: &str) -> std::result::Result<Connection, InfrastructureError> {
+    let db = open(&DatabaseConfig::from_url(url)).await?;
+    migrations::run(&db).await?;
 
     Ok(db)
 }
@@ -294,6 +316,11 @@ mod entity {
         const TABLE: &'static str = "debt";
     }
 
+    impl SqlId for monee_core::GoalId {
+        type Flavor = StringId;
+        const TABLE: &'static str = "goal";
+    }
+
     impl SqlId for AppId {
         type Flavor = StringId;
         // TODO
@@ -305,3 +332,26 @@ mod entity {
         const TABLE: &'static str = "event";
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{check_engine, InfrastructureError};
+
+    #[test]
+    fn servers_need_no_feature() {
+        assert!(check_engine("ws://0.0.0.0:6767").is_ok());
+        assert!(check_engine("wss://example.com").is_ok());
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    #[test]
+    fn sqlite_needs_its_feature() {
+        assert!(matches!(
+            check_engine("sqlite:///home/me/monee.db"),
+            Err(InfrastructureError::EngineNotBuilt {
+                feature: "sqlite",
+                ..
+            })
+        ));
+    }
+}
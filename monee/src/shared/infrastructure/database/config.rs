@@ -0,0 +1,256 @@
+use std::path::PathBuf;
+
+use monee_types::ledgers::ledger_name::LedgerName;
+
+/// How to reach the database: read from the `[database]` table of the config file, then
+/// overridden by the `MONEE_DB_*` environment variables
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// Its scheme picks the engine, see [`super::open`]
+    pub url: String,
+    pub namespace: String,
+    pub database: String,
+    /// Signed in with on connect, servers started without authentication need none
+    pub credentials: Option<Credentials>,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self::from_url(super::default_url())
+    }
+}
+
+#[derive(Clone, serde::Deserialize)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+    #[serde(default)]
+    pub level: AuthLevel,
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("level", &self.level)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Which kind of SurrealDB user the credentials belong to
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthLevel {
+    #[default]
+    Root,
+    Namespace,
+    Database,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("could not read {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid config at {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+#[derive(serde::Deserialize, Default)]
+struct ConfigFile {
+    #[serde(default)]
+    database: DatabaseConfig,
+}
+
+impl DatabaseConfig {
+    /// The default namespace and database at `url`, without credentials
+    pub fn from_url(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            namespace: "monee".to_owned(),
+            database: "monee".to_owned(),
+            credentials: None,
+        }
+    }
+
+    /// Where `ledger` is kept: the main one in the configured database, any other in its own
+    /// database, or its own file next to the configured one for SQLite. Ledger databases are
+    /// prefixed with the configured one, so a ledger named like it can not share its data
+    pub fn for_ledger(&self, ledger: &LedgerName) -> Self {
+        if ledger.is_main() {
+            return self.clone();
+        }
+
+        let mut config = self.clone();
+        config.database = format!("{}__{}", self.database, ledger);
+
+        if let Some(path) = self.url.strip_prefix("sqlite://") {
+            let path = std::path::Path::new(path);
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let file_name = match path.extension() {
+                Some(extension) => format!("{stem}-{ledger}.{}", extension.to_string_lossy()),
+                None => format!("{stem}-{ledger}"),
+            };
+
+            config.url = format!("sqlite://{}", path.with_file_name(file_name).display());
+        }
+
+        config
+    }
+
+    /// Reads the config file, if any, and the environment
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = crate::shared::infrastructure::filesystem::config_path();
+        let mut config = match std::fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content).map_err(|source| ConfigError::Parse {
+                path: path.clone(),
+                source,
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(source) => return Err(ConfigError::Read { path, source }),
+        };
+
+        config.apply_env(|name| std::env::var(name).ok());
+        Ok(config)
+    }
+
+    fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str::<ConfigFile>(content).map(|file| file.database)
+    }
+
+    /// `MONEE_DB_URL`, `MONEE_DB_NAMESPACE`, `MONEE_DB_DATABASE`, `MONEE_DB_USER` and
+    /// `MONEE_DB_PASSWORD` take precedence over the file. Credentials only given in the
+    /// environment need both the user and the password
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) {
+        if let Some(url) = var("MONEE_DB_URL") {
+            self.url = url;
+        }
+        if let Some(namespace) = var("MONEE_DB_NAMESPACE") {
+            self.namespace = namespace;
+        }
+        if let Some(database) = var("MONEE_DB_DATABASE") {
+            self.database = database;
+        }
+
+        let username = var("MONEE_DB_USER");
+        let password = var("MONEE_DB_PASSWORD");
+        match (&mut self.credentials, username, password) {
+            (Some(credentials), username, password) => {
+                if let Some(username) = username {
+                    credentials.username = username;
+                }
+                if let Some(password) = password {
+                    credentials.password = password;
+                }
+            }
+            (None, Some(username), Some(password)) => {
+                self.credentials = Some(Credentials {
+                    username,
+                    password,
+                    level: AuthLevel::default(),
+                });
+            }
+            (None, _, _) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use monee_types::ledgers::ledger_name::LedgerName;
+
+    use super::{AuthLevel, DatabaseConfig};
+
+    #[test]
+    fn reads_database_table() {
+        let config = DatabaseConfig::parse(
+            r#"
+            [database]
+            url = "ws://example.com:8000"
+            database = "household"
+
+            [database.credentials]
+            username = "monee"
+            password = "secret"
+            level = "database"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.url, "ws://example.com:8000");
+        assert_eq!(config.namespace, "monee");
+        assert_eq!(config.database, "household");
+
+        let credentials = config.credentials.unwrap();
+        assert_eq!(credentials.username, "monee");
+        assert_eq!(credentials.level, AuthLevel::Database);
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let mut config = DatabaseConfig::parse(
+            r#"
+            [database.credentials]
+            username = "monee"
+            password = "secret"
+            "#,
+        )
+        .unwrap();
+
+        config.apply_env(|name| match name {
+            "MONEE_DB_URL" => Some("ws://remote:8000".to_owned()),
+            "MONEE_DB_PASSWORD" => Some("other".to_owned()),
+            _ => None,
+        });
+
+        assert_eq!(config.url, "ws://remote:8000");
+        let credentials = config.credentials.unwrap();
+        assert_eq!(credentials.username, "monee");
+        assert_eq!(credentials.password, "other");
+    }
+
+    #[test]
+    fn ledgers_get_their_own_database() {
+        let config = DatabaseConfig::from_url("ws://remote:8000");
+
+        assert_eq!(config.for_ledger(&LedgerName::main()).database, "monee");
+        assert_eq!(
+            config.for_ledger(&"household".parse().unwrap()).database,
+            "monee__household"
+        );
+    }
+
+    #[test]
+    fn ledger_named_like_the_database_does_not_share_it() {
+        let config = DatabaseConfig::from_url("ws://remote:8000");
+        let ledger = config.for_ledger(&"monee".parse().unwrap());
+
+        assert_ne!(ledger.database, config.database);
+        assert_eq!(ledger.database, "monee__monee");
+    }
+
+    #[test]
+    fn sqlite_ledgers_get_their_own_file() {
+        let config = DatabaseConfig::from_url("sqlite:///home/me/monee.db");
+        let household = config.for_ledger(&"household".parse().unwrap());
+
+        assert_eq!(household.url, "sqlite:///home/me/monee-household.db");
+    }
+
+    #[test]
+    fn env_credentials_need_user_and_password() {
+        let mut config = DatabaseConfig::from_url("ws://remote:8000");
+        config.apply_env(|name| (name == "MONEE_DB_USER").then(|| "monee".to_owned()));
+
+        assert!(config.credentials.is_none());
+    }
+}
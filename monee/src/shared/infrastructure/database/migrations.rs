@@ -0,0 +1,302 @@
+use std::{future::Future, pin::Pin};
+
+use surrealdb::{
+    method::Query,
+    sql::statements::{BeginStatement, CommitStatement},
+};
+
+use crate::shared::domain::date::Datetime;
+
+use super::{Connection, Engine, Result};
+
+/// A forward-only change to the database, applied in a transaction along with its
+/// `schema_version` record so it is either fully applied or not at all
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    up: Up,
+}
+
+type Prepared<'a> = Pin<Box<dyn Future<Output = Result<Query<'a, Engine>>> + Send + 'a>>;
+
+enum Up {
+    Statements(&'static [&'static str]),
+    /// Reads what it needs first, then adds its writes to the transaction
+    Data(for<'a> fn(&'a Connection, Query<'a, Engine>) -> Prepared<'a>),
+}
+
+/// Every migration, in the order they are applied. Versions are never reused nor reordered
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial schema",
+        up: Up::Statements(&[
+            "DEFINE TABLE event",
+            "DEFINE FIELD created_at ON event VALUE time::now()",
+            "DEFINE TABLE wallet",
+            "DEFINE FIELD name ON wallet TYPE option<string>",
+            "DEFINE FIELD description ON wallet TYPE string",
+            "DEFINE FIELD currency_id ON wallet TYPE record<currency>",
+            "DEFINE INDEX wallet_name ON wallet FIELDS name UNIQUE",
+            "DEFINE TABLE currency",
+            "DEFINE FIELD name ON currency TYPE string",
+            "DEFINE FIELD symbol ON currency TYPE string",
+            "DEFINE FIELD code ON currency TYPE string",
+            "DEFINE INDEX currency_code ON currency FIELDS code UNIQUE",
+            "DEFINE TABLE exchange_rate",
+            "DEFINE FIELD from ON exchange_rate TYPE record<currency>",
+            "DEFINE FIELD to ON exchange_rate TYPE record<currency>",
+            "DEFINE FIELD date ON exchange_rate TYPE string",
+            "DEFINE INDEX exchange_rate_pair ON exchange_rate FIELDS from, to, date UNIQUE",
+            "DEFINE TABLE actor",
+            "DEFINE FIELD name ON actor TYPE string",
+            "DEFINE FIELD type ON actor TYPE string",
+            "DEFINE FIELD alias ON actor TYPE option<string>",
+            "DEFINE INDEX actor_alias ON actor FIELDS alias UNIQUE",
+            "DEFINE TABLE item_tag",
+            "DEFINE FIELD name ON item_tag TYPE string",
+            "DEFINE INDEX item_tag_name ON item_tag FIELDS name UNIQUE",
+            "DEFINE TABLE client",
+            "DEFINE FIELD name ON host TYPE option<string>",
+        ]),
+    },
+    Migration {
+        version: 2,
+        name: "amounts with currency precision",
+        up: Up::Data(|connection, query| Box::pin(rescale_amounts(connection, query))),
+    },
+    Migration {
+        version: 3,
+        name: "wallet kinds",
+        up: Up::Statements(&["UPDATE wallet SET kind = 'debit' WHERE kind = NONE"]),
+    },
+    Migration {
+        version: 4,
+        name: "savings goals",
+        up: Up::Statements(&[
+            "DEFINE TABLE goal",
+            "DEFINE FIELD name ON goal TYPE string",
+            "DEFINE FIELD currency_id ON goal TYPE record<currency>",
+            "DEFINE FIELD deadline ON goal TYPE string",
+            "DEFINE FIELD wallets ON goal TYPE array<record<wallet>>",
+            "DEFINE INDEX goal_name ON goal FIELDS name UNIQUE",
+        ]),
+    },
+    Migration {
+        version: 5,
+        name: "snapshot checkpoints",
+        up: Up::Statements(&[
+            "DEFINE TABLE snapshot_checkpoint",
+            "DEFINE FIELD event_id ON snapshot_checkpoint TYPE record<event>",
+            "DEFINE FIELD created_at ON snapshot_checkpoint TYPE datetime",
+            "DEFINE INDEX snapshot_checkpoint_created_at ON snapshot_checkpoint FIELDS created_at",
+        ]),
+    },
+    Migration {
+        version: 6,
+        name: "event creation time kept on update",
+        up: Up::Statements(&[EVENT_CREATED_AT]),
+    },
+];
+
+/// `VALUE` is evaluated on every write, updates keep what the event had and synced events
+/// keep the time they were created at on their node
+const EVENT_CREATED_AT: &str =
+    "DEFINE FIELD created_at ON event VALUE $before OR $value OR time::now()";
+
+#[derive(Debug, serde::Deserialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub applied_at: Datetime,
+}
+
+/// Migrations already applied, oldest first
+pub(crate) async fn applied(connection: &Connection) -> Result<Vec<AppliedMigration>> {
+    let mut response = connection
+        .query(
+            "SELECT meta::id(id) AS version, name, applied_at FROM schema_version ORDER BY version",
+        )
+        .await?
+        .check()?;
+
+    response.take(0)
+}
+
+/// Applies the pending migrations in order. Databases written by a newer version are refused
+/// rather than risking to misread them
+pub(crate) async fn run(connection: &Connection) -> Result<()> {
+    let applied = applied(connection).await?;
+    let latest = MIGRATIONS.last().map_or(0, |migration| migration.version);
+    if applied.iter().any(|migration| migration.version > latest) {
+        return Err(surrealdb::Error::Api(surrealdb::error::Api::Query(
+            "database schema is newer than this version of monee".to_owned(),
+        )));
+    }
+
+    let pending = MIGRATIONS.iter().filter(|migration| {
+        !applied
+            .iter()
+            .any(|applied| applied.version == migration.version)
+    });
+
+    for migration in pending {
+        let query = connection.query(BeginStatement);
+        let query = match migration.up {
+            Up::Statements(statements) => statements
+                .iter()
+                .fold(query, |query, statement| query.query(*statement)),
+            Up::Data(prepare) => prepare(connection, query).await?,
+        };
+
+        query
+            .query("CREATE type::thing('schema_version', $version) SET name = $name, applied_at = time::now()")
+            .query(CommitStatement)
+            .bind(("version", migration.version))
+            .bind(("name", migration.name))
+            .await?
+            .check()?;
+    }
+
+    Ok(())
+}
+
+/// Decimals amounts were stored with before currencies had their own precision
+const LEGACY_AMOUNT_DECIMALS: u32 = 4;
+
+/// Rescales stored amounts to [`monee_core::Amount::DECIMALS`]. Databases that went through
+/// it before migrations were versioned keep the scale in use in `meta:amounts`. Refused when
+/// an amount would no longer fit, leaving the database as it was
+async fn rescale_amounts<'a>(
+    connection: &'a Connection,
+    query: Query<'a, Engine>,
+) -> Result<Query<'a, Engine>> {
+    let mut response = connection
+        .query("SELECT VALUE decimals FROM ONLY meta:amounts")
+        .await?
+        .check()?;
+
+    let decimals: Option<u32> = response.take(0)?;
+    let decimals = decimals.unwrap_or(LEGACY_AMOUNT_DECIMALS);
+    if decimals == monee_core::Amount::DECIMALS {
+        return Ok(query);
+    }
+
+    let factor = 10_u64.pow(monee_core::Amount::DECIMALS - decimals);
+    // Stored integers are signed, past this they would overflow once rescaled
+    let limit = i64::MAX as u64 / factor;
+    let mut response = connection
+        .query(
+            "RETURN array::len((SELECT VALUE id FROM event WHERE amount > $limit))
+            + array::len((SELECT VALUE id FROM wallet
+                WHERE kind.overdraft.credit_limit > $limit
+                OR kind.credit_card.credit_limit > $limit))",
+        )
+        .bind(("limit", limit))
+        .await?
+        .check()?;
+
+    let too_big: Option<u64> = response.take(0)?;
+    if too_big.unwrap_or_default() > 0 {
+        let message = format!(
+            "amounts over {} can not be rescaled to {} decimals",
+            limit / 10_u64.pow(decimals),
+            monee_core::Amount::DECIMALS
+        );
+        return Err(surrealdb::Error::Api(surrealdb::error::Api::Query(message)));
+    }
+
+    let snapshot = crate::backoffice::snapshot::infrastructure::snapshot_repository::read_rescaled(
+        connection, decimals,
+    )
+    .await?;
+
+    let mut query = query
+        // Legacy databases still stamp events with the time of any update
+        .query(EVENT_CREATED_AT)
+        .query("UPDATE event SET amount = amount * $factor WHERE amount != NONE")
+        .query(
+            "UPDATE wallet SET kind.overdraft.credit_limit = kind.overdraft.credit_limit * $factor
+            WHERE kind.overdraft.credit_limit != NONE",
+        )
+        .query(
+            "UPDATE wallet SET kind.credit_card.credit_limit = kind.credit_card.credit_limit * $factor
+            WHERE kind.credit_card.credit_limit != NONE",
+        );
+
+    if let Some(snapshot) = snapshot {
+        query = query
+            .query("DELETE FROM snapshot")
+            .query("CREATE snapshot CONTENT $snapshot")
+            .bind(("snapshot", snapshot));
+    }
+
+    Ok(query
+        .query("UPDATE meta:amounts SET decimals = $decimals")
+        .bind(("factor", factor))
+        .bind(("decimals", monee_core::Amount::DECIMALS)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::MIGRATIONS;
+
+    #[test]
+    fn versions_strictly_increase() {
+        assert!(MIGRATIONS
+            .windows(2)
+            .all(|pair| pair[0].version < pair[1].version));
+    }
+
+    #[cfg(feature = "db_test")]
+    #[tokio::test]
+    async fn rescaling_keeps_event_creation_time() {
+        use crate::shared::{
+            domain::date::Datetime,
+            infrastructure::database::{config::DatabaseConfig, open},
+        };
+
+        let db = open(&DatabaseConfig::from_url("mem://")).await.unwrap();
+        db.query(
+            "CREATE event:legacy SET amount = 5, created_at = <datetime> '2020-01-01T00:00:00Z'",
+        )
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+        super::run(&db).await.unwrap();
+
+        let mut response = db
+            .query("SELECT VALUE created_at FROM ONLY event:legacy")
+            .await
+            .unwrap();
+        let created_at: Option<Datetime> = response.take(0).unwrap();
+        assert_eq!(
+            created_at,
+            Some("2020-01-01T00:00:00Z".parse::<Datetime>().unwrap())
+        );
+    }
+
+    #[cfg(feature = "db_test")]
+    #[tokio::test]
+    async fn rescaling_refuses_amounts_that_would_overflow() {
+        use crate::shared::infrastructure::database::{config::DatabaseConfig, open};
+
+        let db = open(&DatabaseConfig::from_url("mem://")).await.unwrap();
+        db.query("CREATE event:legacy SET amount = 1000000000000000")
+            .await
+            .unwrap()
+            .check()
+            .unwrap();
+
+        assert!(super::run(&db).await.is_err());
+
+        let mut response = db
+            .query("SELECT VALUE amount FROM ONLY event:legacy")
+            .await
+            .unwrap();
+        let amount: Option<u64> = response.take(0).unwrap();
+        assert_eq!(amount, Some(1_000_000_000_000_000));
+    }
+}
@@ -0,0 +1,128 @@
+use cream::context::FromContext;
+use surrealdb::{
+    method::Query,
+    sql::statements::{BeginStatement, CommitStatement},
+};
+
+use crate::shared::domain::{context::DbContext, unit_of_work::UnitOfWork};
+
+use super::{Connection, Engine};
+
+type Write = Box<dyn for<'a> FnOnce(Query<'a, Engine>, usize) -> Query<'a, Engine> + Send>;
+
+/// Writes staged by repositories, sent in a single query and committed together or not at
+/// all. Nothing reaches the database before [`Transaction::commit`]
+pub struct Transaction(Writes);
+
+enum Writes {
+    Surreal {
+        connection: Connection,
+        writes: Vec<Write>,
+    },
+    #[cfg(feature = "sqlite")]
+    Sqlite {
+        connection: crate::shared::infrastructure::sqlite::Connection,
+        statements: Vec<crate::shared::infrastructure::sqlite::Statement>,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CommitError {
+    #[error(transparent)]
+    Surreal(#[from] surrealdb::Error),
+    #[cfg(feature = "sqlite")]
+    #[error(transparent)]
+    Sqlite(#[from] sqlx::Error),
+}
+
+impl Transaction {
+    pub(crate) fn new(connection: Connection) -> Self {
+        Self(Writes::Surreal {
+            connection,
+            writes: Vec::new(),
+        })
+    }
+
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn new_sqlite(
+        connection: crate::shared::infrastructure::sqlite::Connection,
+    ) -> Self {
+        Self(Writes::Sqlite {
+            connection,
+            statements: Vec::new(),
+        })
+    }
+
+    /// Stages `write`, which receives an index unique to it within the transaction to suffix
+    /// its bindings with
+    pub(crate) fn stage(
+        &mut self,
+        write: impl for<'a> FnOnce(Query<'a, Engine>, usize) -> Query<'a, Engine> + Send + 'static,
+    ) {
+        match &mut self.0 {
+            Writes::Surreal { writes, .. } => writes.push(Box::new(write)),
+            #[cfg(feature = "sqlite")]
+            Writes::Sqlite { .. } => unreachable!("SurrealDB write staged in a SQLite transaction"),
+        }
+    }
+
+    /// Stages `statement`, statements run in the order they were staged
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn stage_sqlite(
+        &mut self,
+        statement: crate::shared::infrastructure::sqlite::Statement,
+    ) {
+        match &mut self.0 {
+            Writes::Sqlite { statements, .. } => statements.push(statement),
+            Writes::Surreal { .. } => {
+                unreachable!("SQLite write staged in a SurrealDB transaction")
+            }
+        }
+    }
+
+    pub async fn commit(self) -> Result<(), CommitError> {
+        match self.0 {
+            Writes::Surreal { connection, writes } => {
+                if writes.is_empty() {
+                    return Ok(());
+                }
+
+                let query = writes
+                    .into_iter()
+                    .enumerate()
+                    .fold(connection.query(BeginStatement), |query, (i, write)| {
+                        write(query, i)
+                    });
+
+                query.query(CommitStatement).await?.check()?;
+            }
+            #[cfg(feature = "sqlite")]
+            Writes::Sqlite {
+                connection,
+                statements,
+            } => {
+                if statements.is_empty() {
+                    return Ok(());
+                }
+
+                let mut tx = connection.begin().await?;
+                for statement in statements {
+                    statement.execute(&mut *tx).await?;
+                }
+                tx.commit().await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(FromContext)]
+#[context(DbContext)]
+pub struct SurrealUnitOfWork(Connection);
+
+impl UnitOfWork for SurrealUnitOfWork {
+    fn begin(&self) -> Transaction {
+        Transaction::new(self.0.clone())
+    }
+}
@@ -0,0 +1,169 @@
+pub mod migrations;
+
+use std::borrow::Cow;
+
+use cream::context::FromContext;
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteQueryResult},
+    SqliteConnection,
+};
+
+use crate::shared::{
+    domain::{context::SqliteContext, date::Datetime, unit_of_work::UnitOfWork},
+    infrastructure::database::transaction::Transaction,
+};
+
+pub use sqlx::Result;
+
+pub type Connection = sqlx::SqlitePool;
+
+pub type Error = sqlx::Error;
+
+/// Opens the database file at `path`, creating it if missing, without applying pending
+/// migrations
+pub async fn open(path: &std::path::Path) -> Result<Connection> {
+    let options = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true);
+
+    sqlx::SqlitePool::connect_with(options).await
+}
+
+pub async fn connect(path: &std::path::Path) -> Result<Connection> {
+    let db = open(path).await?;
+    migrations::run(&db).await?;
+
+    Ok(db)
+}
+
+#[derive(FromContext)]
+#[context(SqliteContext)]
+pub struct SqliteUnitOfWork(Connection);
+
+impl UnitOfWork for SqliteUnitOfWork {
+    fn begin(&self) -> Transaction {
+        Transaction::new_sqlite(self.0.clone())
+    }
+}
+
+/// A single write, kept as plain data so it can be staged in a transaction and run later
+pub struct Statement {
+    sql: Cow<'static, str>,
+    args: Vec<Value>,
+}
+
+pub enum Value {
+    Text(String),
+    Integer(i64),
+    Null,
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_owned())
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Integer(value)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+impl Statement {
+    pub fn new(sql: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            sql: sql.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn bind(mut self, value: impl Into<Value>) -> Self {
+        self.args.push(value.into());
+        self
+    }
+
+    pub async fn execute(self, connection: &mut SqliteConnection) -> Result<SqliteQueryResult> {
+        let query = self
+            .args
+            .into_iter()
+            .fold(sqlx::query(&self.sql), |query, arg| match arg {
+                Value::Text(text) => query.bind(text),
+                Value::Integer(integer) => query.bind(integer),
+                Value::Null => query.bind(None::<String>),
+            });
+
+        query.execute(connection).await
+    }
+}
+
+/// Rows are stored as the JSON of their domain type, the columns needed to look them up or to
+/// enforce uniqueness are generated from it
+pub(crate) fn encode<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("to serialize row data")
+}
+
+pub(crate) fn decode<T: DeserializeOwned>(data: &str) -> Result<T> {
+    serde_json::from_str(data).map_err(|e| Error::Decode(Box::new(e)))
+}
+
+pub(crate) fn decode_row<K: DeserializeOwned, T: DeserializeOwned>(
+    (key, data): (String, String),
+) -> Result<(K, T)> {
+    Ok((decode_key(key)?, decode(&data)?))
+}
+
+/// Ids as they appear inside the JSON of the rows, so they can be compared with
+/// `json_extract`
+pub(crate) fn key<K: Serialize>(id: K) -> String {
+    match serde_json::to_value(id).expect("to serialize id") {
+        serde_json::Value::String(key) => key,
+        other => other.to_string(),
+    }
+}
+
+pub(crate) fn decode_key<K: DeserializeOwned>(key: String) -> Result<K> {
+    serde_json::from_value(serde_json::Value::String(key)).map_err(|e| Error::Decode(Box::new(e)))
+}
+
+/// Fixed width UTC timestamps, their text order is their chronological order
+pub(crate) fn datetime(datetime: Datetime) -> String {
+    datetime.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+pub(crate) fn decode_datetime(datetime: &str) -> Result<Datetime> {
+    chrono::DateTime::parse_from_rfc3339(datetime)
+        .map(|datetime| datetime.with_timezone(&chrono::Utc))
+        .map_err(|e| Error::Decode(Box::new(e)))
+}
+
+/// Table whose unique constraint `error` violates, if that is why it failed
+pub(crate) fn unique_violation(error: &Error) -> Option<String> {
+    let error = error.as_database_error()?;
+    if !error.is_unique_violation() {
+        return None;
+    }
+
+    // "UNIQUE constraint failed: wallet.name"
+    let table = error
+        .message()
+        .rsplit(": ")
+        .next()
+        .and_then(|columns| columns.split('.').next())
+        .unwrap_or("unknown");
+
+    Some(table.to_owned())
+}
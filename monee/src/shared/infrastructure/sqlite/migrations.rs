@@ -0,0 +1,171 @@
+use crate::shared::domain::date::{Datetime, Timezone};
+
+use super::{decode_datetime, Connection, Result};
+
+/// A forward-only change to the database, applied in a transaction along with its
+/// `schema_version` row so it is either fully applied or not at all
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    statements: &'static [&'static str],
+}
+
+/// Every migration, in the order they are applied. Versions are never reused nor reordered.
+/// They are numbered apart from the SurrealDB ones, a new SQLite file starts at the latest
+/// schema
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "initial schema",
+    statements: &[
+        "CREATE TABLE currency (
+            id TEXT PRIMARY KEY,
+            data TEXT NOT NULL,
+            code TEXT GENERATED ALWAYS AS (json_extract(data, '$.code')) VIRTUAL UNIQUE
+        )",
+        "CREATE TABLE actor (
+            id TEXT PRIMARY KEY,
+            data TEXT NOT NULL,
+            alias TEXT GENERATED ALWAYS AS (json_extract(data, '$.alias')) VIRTUAL UNIQUE
+        )",
+        "CREATE TABLE item_tag (
+            id TEXT PRIMARY KEY,
+            data TEXT NOT NULL,
+            name TEXT GENERATED ALWAYS AS (json_extract(data, '$.name')) VIRTUAL UNIQUE
+        )",
+        "CREATE TABLE contains (
+            parent_id TEXT NOT NULL,
+            child_id TEXT NOT NULL,
+            PRIMARY KEY (parent_id, child_id)
+        )",
+        "CREATE INDEX contains_child ON contains (child_id)",
+        "CREATE TABLE wallet (
+            id TEXT PRIMARY KEY,
+            data TEXT NOT NULL,
+            name TEXT GENERATED ALWAYS AS (json_extract(data, '$.name')) VIRTUAL UNIQUE
+        )",
+        "CREATE TABLE exchange_rate (
+            data TEXT NOT NULL,
+            from_id TEXT GENERATED ALWAYS AS (json_extract(data, '$.from')) VIRTUAL,
+            to_id TEXT GENERATED ALWAYS AS (json_extract(data, '$.to')) VIRTUAL,
+            date TEXT GENERATED ALWAYS AS (json_extract(data, '$.date')) VIRTUAL,
+            UNIQUE (from_id, to_id, date)
+        )",
+        "CREATE TABLE goal (
+            id TEXT PRIMARY KEY,
+            data TEXT NOT NULL,
+            name TEXT GENERATED ALWAYS AS (json_extract(data, '$.name')) VIRTUAL UNIQUE
+        )",
+        "CREATE TABLE event (
+            id TEXT PRIMARY KEY,
+            data TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        "CREATE INDEX event_created_at ON event (created_at)",
+        "CREATE TABLE snapshot (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            data TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        "CREATE TABLE snapshot_checkpoint (
+            day TEXT PRIMARY KEY,
+            data TEXT NOT NULL,
+            event_id TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        "CREATE INDEX snapshot_checkpoint_created_at ON snapshot_checkpoint (created_at)",
+        "CREATE TABLE node (id TEXT PRIMARY KEY, data TEXT NOT NULL)",
+        "CREATE TABLE client_sync (id TEXT PRIMARY KEY, data TEXT, error TEXT)",
+        "CREATE TABLE self_app (id TEXT NOT NULL)",
+        "CREATE TABLE host_binding (id INTEGER PRIMARY KEY CHECK (id = 1), data TEXT NOT NULL)",
+        "CREATE TABLE changes_record (id INTEGER PRIMARY KEY CHECK (id = 1), data TEXT NOT NULL)",
+    ],
+}];
+
+#[derive(Debug)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub applied_at: Datetime,
+}
+
+async fn ensure_schema_version(connection: &Connection) -> Result<()> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )",
+    )
+    .execute(connection)
+    .await?;
+
+    Ok(())
+}
+
+/// Migrations already applied, oldest first
+pub(crate) async fn applied(connection: &Connection) -> Result<Vec<AppliedMigration>> {
+    ensure_schema_version(connection).await?;
+
+    let rows: Vec<(i64, String, String)> =
+        sqlx::query_as("SELECT version, name, applied_at FROM schema_version ORDER BY version")
+            .fetch_all(connection)
+            .await?;
+
+    rows.into_iter()
+        .map(|(version, name, applied_at)| {
+            Ok(AppliedMigration {
+                version: version as u32,
+                name,
+                applied_at: decode_datetime(&applied_at)?,
+            })
+        })
+        .collect()
+}
+
+/// Applies the pending migrations in order. Databases written by a newer version are refused
+/// rather than risking to misread them
+pub(crate) async fn run(connection: &Connection) -> Result<()> {
+    let applied = applied(connection).await?;
+    let latest = MIGRATIONS.last().map_or(0, |migration| migration.version);
+    if applied.iter().any(|migration| migration.version > latest) {
+        return Err(sqlx::Error::Protocol(
+            "database schema is newer than this version of monee".to_owned(),
+        ));
+    }
+
+    let pending = MIGRATIONS.iter().filter(|migration| {
+        !applied
+            .iter()
+            .any(|applied| applied.version == migration.version)
+    });
+
+    for migration in pending {
+        let mut tx = connection.begin().await?;
+        for statement in migration.statements {
+            sqlx::query(*statement).execute(&mut *tx).await?;
+        }
+
+        sqlx::query("INSERT INTO schema_version (version, name, applied_at) VALUES (?, ?, ?)")
+            .bind(migration.version as i64)
+            .bind(migration.name)
+            .bind(super::datetime(Timezone::now()))
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::MIGRATIONS;
+
+    #[test]
+    fn versions_strictly_increase() {
+        assert!(MIGRATIONS
+            .windows(2)
+            .all(|pair| pair[0].version < pair[1].version));
+    }
+}
@@ -0,0 +1,451 @@
+pub mod domain {
+    pub use monee_types::vault::*;
+}
+
+pub mod application {
+    pub mod vault {
+        use std::path::{Path, PathBuf};
+
+        use crate::{
+            prelude::{AppError, InfrastructureError},
+            shared::infrastructure::database::{self, config::DatabaseConfig, Connection},
+            vault::infrastructure::{dump, sealed},
+        };
+
+        pub use crate::vault::domain::errors::VaultError as Error;
+
+        /// Keeps the embedded store encrypted while it is not in use. Locking seals every
+        /// database of the namespace into `<store>.locked` and removes the plaintext store,
+        /// unlocking brings it back. Like ledgers, it works before there is a context
+        pub struct Vault {
+            database: DatabaseConfig,
+        }
+
+        impl Vault {
+            pub fn new(database: DatabaseConfig) -> Self {
+                Self { database }
+            }
+
+            fn store(&self) -> Result<PathBuf, Error> {
+                let url = self.database.url.as_str();
+                url.strip_prefix("file://")
+                    .or_else(|| url.strip_prefix("rocksdb://"))
+                    .map(PathBuf::from)
+                    .ok_or(Error::Unsupported)
+            }
+
+            fn locked_path(store: &Path) -> PathBuf {
+                let mut path = store.as_os_str().to_owned();
+                path.push(".locked");
+                PathBuf::from(path)
+            }
+
+            pub fn is_locked(&self) -> bool {
+                self.store()
+                    .is_ok_and(|store| Self::locked_path(&store).exists())
+            }
+
+            pub async fn lock(&self, passphrase: &str) -> Result<(), AppError<Error>> {
+                let store = self.store().map_err(AppError::App)?;
+                let connection = database::open(&self.database).await?;
+                self.seal(&store, &connection, passphrase).await?;
+                drop(connection);
+
+                self.settle()?;
+                Ok(())
+            }
+
+            /// Locks a store this process already has open, which can not be removed until it
+            /// is closed. Call [`Vault::settle`] on the next start
+            pub async fn lock_open(
+                &self,
+                connection: &Connection,
+                passphrase: &str,
+            ) -> Result<(), AppError<Error>> {
+                let store = self.store().map_err(AppError::App)?;
+                self.seal(&store, connection, passphrase).await
+            }
+
+            async fn seal(
+                &self,
+                store: &Path,
+                connection: &Connection,
+                passphrase: &str,
+            ) -> Result<(), AppError<Error>> {
+                let locked = Self::locked_path(store);
+                if locked.exists() {
+                    return Err(AppError::App(Error::Locked));
+                }
+
+                let dump = dump::export(connection, &self.database.database).await?;
+                write_atomic(&locked, &sealed::seal(&dump.to_bytes()?, passphrase))?;
+
+                Ok(())
+            }
+
+            /// Removes a plaintext store left next to the locked one, a lock that could not
+            /// remove it or an unlock that did not finish
+            pub fn settle(&self) -> Result<(), InfrastructureError> {
+                let Ok(store) = self.store() else {
+                    return Ok(());
+                };
+
+                if Self::locked_path(&store).exists() {
+                    remove_store(&store)?;
+                }
+
+                Ok(())
+            }
+
+            pub async fn unlock(&self, passphrase: &str) -> Result<(), AppError<Error>> {
+                let store = self.store().map_err(AppError::App)?;
+                let locked = Self::locked_path(&store);
+                let content = match std::fs::read(&locked) {
+                    Ok(content) => content,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        return Err(AppError::App(Error::NotLocked))
+                    }
+                    Err(e) => return Err(InfrastructureError::from(e).into()),
+                };
+
+                remove_store(&store)?;
+                self.import(&store, &content, passphrase).await?;
+                std::fs::remove_file(&locked).map_err(InfrastructureError::from)?;
+
+                Ok(())
+            }
+
+            /// Only the key that wraps the data key changes, the data is not encrypted again
+            pub fn change_passphrase(
+                &self,
+                current: &str,
+                new: &str,
+            ) -> Result<(), AppError<Error>> {
+                let store = self.store().map_err(AppError::App)?;
+                let locked = Self::locked_path(&store);
+                let content = match std::fs::read(&locked) {
+                    Ok(content) => content,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        return Err(AppError::App(Error::NotLocked))
+                    }
+                    Err(e) => return Err(InfrastructureError::from(e).into()),
+                };
+
+                let content = sealed::rekey(&content, current, new).map_err(AppError::App)?;
+                write_atomic(&locked, &content)?;
+
+                Ok(())
+            }
+
+            /// An encrypted copy of the unlocked store, readable with [`Vault::restore`]
+            pub async fn backup(
+                &self,
+                target: &Path,
+                passphrase: &str,
+            ) -> Result<(), AppError<Error>> {
+                if self.is_locked() {
+                    return Err(AppError::App(Error::Locked));
+                }
+                if target.exists() {
+                    return Err(AppError::App(Error::TargetExists));
+                }
+                self.store().map_err(AppError::App)?;
+
+                let connection = database::open(&self.database).await?;
+                let dump = dump::export(&connection, &self.database.database).await?;
+                std::fs::write(target, sealed::seal(&dump.to_bytes()?, passphrase))
+                    .map_err(InfrastructureError::from)?;
+
+                Ok(())
+            }
+
+            /// Brings back a backup or a locked store taken from elsewhere, there must be no
+            /// store yet
+            pub async fn restore(
+                &self,
+                source: &Path,
+                passphrase: &str,
+            ) -> Result<(), AppError<Error>> {
+                let store = self.store().map_err(AppError::App)?;
+                if store.exists() || self.is_locked() {
+                    return Err(AppError::App(Error::StoreExists));
+                }
+
+                let content = std::fs::read(source).map_err(InfrastructureError::from)?;
+                self.import(&store, &content, passphrase).await
+            }
+
+            async fn import(
+                &self,
+                store: &Path,
+                content: &[u8],
+                passphrase: &str,
+            ) -> Result<(), AppError<Error>> {
+                let plain = sealed::open(content, passphrase).map_err(AppError::App)?;
+                let dump = dump::Dump::from_bytes(&plain)?;
+
+                let imported = async {
+                    let connection = database::open(&self.database).await?;
+                    dump::import(&connection, dump).await
+                }
+                .await;
+
+                // Do not leave a half imported store behind
+                if let Err(e) = imported {
+                    remove_store(store)?;
+                    return Err(e.into());
+                }
+
+                Ok(())
+            }
+        }
+
+        fn remove_store(store: &Path) -> Result<(), InfrastructureError> {
+            match std::fs::remove_dir_all(store) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(e.into()),
+            }
+        }
+
+        fn write_atomic(path: &Path, content: &[u8]) -> Result<(), InfrastructureError> {
+            let mut temp = path.as_os_str().to_owned();
+            temp.push(".tmp");
+
+            std::fs::write(&temp, content)?;
+            std::fs::rename(&temp, path)?;
+
+            Ok(())
+        }
+    }
+}
+
+pub mod infrastructure {
+    pub mod sealed {
+        //! `MAGIC | version | salt | key nonce | wrapped key | body nonce | body`. The body is
+        //! encrypted with a random data key, which is wrapped with a key derived from the
+        //! passphrase with Argon2id, so changing the passphrase only rewrites the header
+        use argon2::Argon2;
+        use chacha20poly1305::{
+            aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+            Key, XChaCha20Poly1305, XNonce,
+        };
+
+        use crate::vault::domain::errors::VaultError;
+
+        const MAGIC: &[u8; 8] = b"MONEEVLT";
+        const VERSION: u8 = 1;
+
+        const SALT_LEN: usize = 16;
+        const NONCE_LEN: usize = 24;
+        const WRAPPED_KEY_LEN: usize = 32 + 16;
+
+        const PREFIX_LEN: usize = MAGIC.len() + 1;
+        const HEADER_LEN: usize = PREFIX_LEN + SALT_LEN + NONCE_LEN + WRAPPED_KEY_LEN;
+
+        fn derive(passphrase: &str, salt: &[u8]) -> Key {
+            let mut key = Key::default();
+            Argon2::default()
+                .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+                .expect("salt and key lengths to be valid");
+            key
+        }
+
+        fn prefix() -> Vec<u8> {
+            let mut prefix = MAGIC.to_vec();
+            prefix.push(VERSION);
+            prefix
+        }
+
+        fn header(data_key: &Key, passphrase: &str) -> Vec<u8> {
+            let mut salt = [0; SALT_LEN];
+            OsRng.fill_bytes(&mut salt);
+
+            let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+            let wrapped = XChaCha20Poly1305::new(&derive(passphrase, &salt))
+                .encrypt(&nonce, data_key.as_slice())
+                .expect("to encrypt in memory");
+
+            let mut header = prefix();
+            header.extend_from_slice(&salt);
+            header.extend_from_slice(&nonce);
+            header.extend_from_slice(&wrapped);
+            header
+        }
+
+        fn data_key(sealed: &[u8], passphrase: &str) -> Result<Key, VaultError> {
+            if sealed.len() < HEADER_LEN + NONCE_LEN || sealed[..PREFIX_LEN] != prefix()[..] {
+                return Err(VaultError::InvalidFile);
+            }
+
+            let (salt, rest) = sealed[PREFIX_LEN..HEADER_LEN].split_at(SALT_LEN);
+            let (nonce, wrapped) = rest.split_at(NONCE_LEN);
+
+            let key = XChaCha20Poly1305::new(&derive(passphrase, salt))
+                .decrypt(XNonce::from_slice(nonce), wrapped)
+                .map_err(|_| VaultError::WrongPassphrase)?;
+
+            Ok(Key::clone_from_slice(&key))
+        }
+
+        pub fn seal(plain: &[u8], passphrase: &str) -> Vec<u8> {
+            let data_key = XChaCha20Poly1305::generate_key(&mut OsRng);
+            let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+            let aad = prefix();
+            let body = XChaCha20Poly1305::new(&data_key)
+                .encrypt(
+                    &nonce,
+                    Payload {
+                        msg: plain,
+                        aad: &aad,
+                    },
+                )
+                .expect("to encrypt in memory");
+
+            let mut sealed = header(&data_key, passphrase);
+            sealed.extend_from_slice(&nonce);
+            sealed.extend_from_slice(&body);
+            sealed
+        }
+
+        pub fn open(sealed: &[u8], passphrase: &str) -> Result<Vec<u8>, VaultError> {
+            let data_key = data_key(sealed, passphrase)?;
+            let (nonce, body) = sealed[HEADER_LEN..].split_at(NONCE_LEN);
+            let aad = prefix();
+
+            XChaCha20Poly1305::new(&data_key)
+                .decrypt(
+                    XNonce::from_slice(nonce),
+                    Payload {
+                        msg: body,
+                        aad: &aad,
+                    },
+                )
+                .map_err(|_| VaultError::InvalidFile)
+        }
+
+        pub fn rekey(sealed: &[u8], current: &str, new: &str) -> Result<Vec<u8>, VaultError> {
+            let data_key = data_key(sealed, current)?;
+
+            let mut rekeyed = header(&data_key, new);
+            rekeyed.extend_from_slice(&sealed[HEADER_LEN..]);
+            Ok(rekeyed)
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn opens_with_the_passphrase_only() {
+                let sealed = seal(b"wallet history", "correct horse");
+
+                assert_eq!(open(&sealed, "correct horse").unwrap(), b"wallet history");
+                assert!(matches!(
+                    open(&sealed, "battery staple"),
+                    Err(VaultError::WrongPassphrase)
+                ));
+                assert!(matches!(
+                    open(b"plain text", "correct horse"),
+                    Err(VaultError::InvalidFile)
+                ));
+            }
+
+            #[test]
+            fn rekey_keeps_the_body() {
+                let sealed = seal(b"wallet history", "correct horse");
+                let rekeyed = rekey(&sealed, "correct horse", "battery staple").unwrap();
+
+                assert_eq!(sealed[HEADER_LEN..], rekeyed[HEADER_LEN..]);
+                assert_eq!(open(&rekeyed, "battery staple").unwrap(), b"wallet history");
+                assert!(matches!(
+                    open(&rekeyed, "correct horse"),
+                    Err(VaultError::WrongPassphrase)
+                ));
+            }
+        }
+    }
+
+    pub mod dump {
+        use std::collections::HashMap;
+
+        use futures::StreamExt;
+
+        use crate::{
+            prelude::InfrastructureError,
+            shared::infrastructure::{database::Connection, errors::UnspecifiedError},
+        };
+
+        /// Every database of the namespace, so all ledgers kept in the store go together
+        #[derive(serde::Serialize, serde::Deserialize)]
+        pub struct Dump {
+            databases: Vec<DatabaseDump>,
+        }
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct DatabaseDump {
+            name: String,
+            /// SurrealQL statements as given by the export
+            script: String,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct NamespaceInfo {
+            databases: HashMap<String, String>,
+        }
+
+        impl Dump {
+            pub fn to_bytes(&self) -> Result<Vec<u8>, InfrastructureError> {
+                serde_json::to_vec(self)
+                    .map_err(|e| InfrastructureError::Unspecified(UnspecifiedError::new(e)))
+            }
+
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self, InfrastructureError> {
+                serde_json::from_slice(bytes)
+                    .map_err(|e| InfrastructureError::Unspecified(UnspecifiedError::new(e)))
+            }
+        }
+
+        /// Switches `connection` through every database, then back to `current`
+        pub async fn export(
+            connection: &Connection,
+            current: &str,
+        ) -> Result<Dump, InfrastructureError> {
+            let info: Option<NamespaceInfo> = connection.query("INFO FOR NS").await?.take(0)?;
+            let names = info
+                .map(|info| info.databases.into_keys().collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            let mut databases = Vec::with_capacity(names.len());
+            for name in names {
+                connection.use_db(&name).await?;
+
+                let mut backup = connection.export(()).await?;
+                let mut script = Vec::new();
+                while let Some(chunk) = backup.next().await {
+                    script.extend(chunk?);
+                }
+
+                let script = String::from_utf8(script)
+                    .map_err(|e| InfrastructureError::Unspecified(UnspecifiedError::new(e)))?;
+                databases.push(DatabaseDump { name, script });
+            }
+
+            connection.use_db(current).await?;
+
+            Ok(Dump { databases })
+        }
+
+        pub async fn import(
+            connection: &Connection,
+            dump: Dump,
+        ) -> Result<(), InfrastructureError> {
+            for database in dump.databases {
+                connection.use_db(&database.name).await?;
+                connection.query(database.script).await?.check()?;
+            }
+
+            Ok(())
+        }
+    }
+}